@@ -287,6 +287,9 @@ pub(crate) mod test {
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
+            identifier_attributes: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
@@ -298,6 +301,7 @@ pub(crate) mod test {
                 sort_facet_values_by: Setting::Set(
                     btreemap! { S("age") => FacetValuesSort::Count },
                 ),
+                hierarchies: Setting::NotSet,
             }),
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
@@ -305,6 +309,16 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            rules: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
+            synonym_sets_to_add: None,
+            stop_words_to_add: None,
+            stop_words_to_remove: None,
+            synonyms_to_add: None,
+            synonyms_to_remove: None,
+            dictionary_to_add: None,
+            dictionary_to_remove: None,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -355,6 +355,9 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             },
             stop_words: settings.stop_words.into(),
             non_separator_tokens: v6::Setting::NotSet,
+            identifier_attributes: v6::Setting::NotSet,
+            infix_attributes: v6::Setting::NotSet,
+            date_attributes: v6::Setting::NotSet,
             separator_tokens: v6::Setting::NotSet,
             dictionary: v6::Setting::NotSet,
             synonyms: settings.synonyms.into(),
@@ -374,6 +377,9 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
                     disable_on_numbers: v6::Setting::NotSet,
+                    phonetic: v6::Setting::NotSet,
+                    per_attribute: v6::Setting::NotSet,
+                    per_locale: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -382,6 +388,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Set(faceting) => v6::Setting::Set(v6::FacetingSettings {
                     max_values_per_facet: faceting.max_values_per_facet.into(),
                     sort_facet_values_by: v6::Setting::NotSet,
+                    hierarchies: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -398,6 +405,16 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            rules: v6::Setting::NotSet,
+            query_rules: v6::Setting::NotSet,
+            synonym_sets: v6::Setting::NotSet,
+            synonym_sets_to_add: None,
+            stop_words_to_add: None,
+            stop_words_to_remove: None,
+            synonyms_to_add: None,
+            synonyms_to_remove: None,
+            dictionary_to_add: None,
+            dictionary_to_remove: None,
             _kind: std::marker::PhantomData,
         }
     }
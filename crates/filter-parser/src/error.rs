@@ -59,8 +59,14 @@ pub enum ErrorKind<'a> {
     ReservedGeo(&'a str),
     GeoRadius,
     GeoBoundingBox,
+    GeoPolygon,
+    GeoContains,
+    GeoIntersects,
     MisusedGeoRadius,
     MisusedGeoBoundingBox,
+    MisusedGeoPolygon,
+    MisusedGeoContains,
+    MisusedGeoIntersects,
     InvalidPrimary,
     InvalidEscapedNumber,
     ExpectedEof,
@@ -160,8 +166,17 @@ impl Display for Error<'_> {
             ErrorKind::GeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter expects two pairs of arguments: `_geoBoundingBox([latitude, longitude], [latitude, longitude])`.")?
             }
+            ErrorKind::GeoPolygon => {
+                writeln!(f, "The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`. A polygon with holes expects rings of at least three pairs, its outer ring followed by its holes: `_geoPolygon([[latitude, longitude], ...], [[latitude, longitude], ...], ...)`.")?
+            }
+            ErrorKind::GeoContains => {
+                writeln!(f, "The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.")?
+            }
+            ErrorKind::GeoIntersects => {
+                writeln!(f, "The `_geoIntersects` filter expects two pairs of arguments: `_geoIntersects([latitude, longitude], [latitude, longitude])`.")?
+            }
             ErrorKind::ReservedGeo(name) => {
-                writeln!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.", name.escape_debug())?
+                writeln!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.", name.escape_debug())?
             }
             ErrorKind::MisusedGeoRadius => {
                 writeln!(f, "The `_geoRadius` filter is an operation and can't be used as a value.")?
@@ -169,6 +184,15 @@ impl Display for Error<'_> {
             ErrorKind::MisusedGeoBoundingBox => {
                 writeln!(f, "The `_geoBoundingBox` filter is an operation and can't be used as a value.")?
             }
+            ErrorKind::MisusedGeoPolygon => {
+                writeln!(f, "The `_geoPolygon` filter is an operation and can't be used as a value.")?
+            }
+            ErrorKind::MisusedGeoContains => {
+                writeln!(f, "The `_geoContains` filter is an operation and can't be used as a value.")?
+            }
+            ErrorKind::MisusedGeoIntersects => {
+                writeln!(f, "The `_geoIntersects` filter is an operation and can't be used as a value.")?
+            }
             ErrorKind::ReservedKeyword(word) => {
                 writeln!(f, "`{word}` is a reserved keyword and thus cannot be used as a field name unless it is put inside quotes. Use \"{word}\" or \'{word}\' instead.")?
             }
@@ -6,7 +6,7 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoPolygon | geoContains | geoIntersects | in | condition | exists | not_exists | to
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
@@ -19,6 +19,11 @@
 //! word           = (alphanumeric | _ | - | .)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoPolygon     = "_geoPolygon(" WS* (geoRing WS* ("," WS* geoRing WS*)* | geoPair WS* ("," WS* geoPair WS*){2,}) ")"
+//! geoRing        = "[" WS* geoPair WS* ("," WS* geoPair WS*){2,} "]"
+//! geoPair        = "[" WS* float WS* "," WS* float WS* "]"
+//! geoContains    = "_geoContains(" WS* float WS* "," WS* float WS* ")"
+//! geoIntersects  = "_geoIntersects([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -145,6 +150,9 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    GeoPolygon { rings: Vec<Vec<[Token<'a>; 2]>> },
+    GeoContains { point: [Token<'a>; 2] },
+    GeoIntersects { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
 }
 
 pub enum TraversedElement<'a> {
@@ -175,6 +183,9 @@ impl<'a> FilterCondition<'a> {
             }
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
+            | FilterCondition::GeoPolygon { .. }
+            | FilterCondition::GeoContains { .. }
+            | FilterCondition::GeoIntersects { .. }
             | FilterCondition::In { .. } => None,
         }
     }
@@ -199,6 +210,20 @@ impl<'a> FilterCondition<'a> {
         }
     }
 
+    /// Returns the `_geoRadius`, `_geoBoundingBox` and `_geoPolygon` filters,
+    /// the negated ones are ignored.
+    pub fn geo_shapes(&self) -> Box<dyn Iterator<Item = &FilterCondition<'a>> + '_> {
+        match self {
+            FilterCondition::GeoLowerThan { .. }
+            | FilterCondition::GeoBoundingBox { .. }
+            | FilterCondition::GeoPolygon { .. } => Box::new(std::iter::once(self)),
+            FilterCondition::And(subfilters) | FilterCondition::Or(subfilters) => {
+                Box::new(subfilters.iter().flat_map(|f| f.geo_shapes()))
+            }
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
     /// Returns the first token found at the specified depth, `None` if no token at this depth.
     pub fn token_at_depth(&self, depth: usize) -> Option<&Token> {
         match self {
@@ -422,6 +447,97 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// geoPolygon      = WS* "_geoPolygon([float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], ...)"
+/// geoPolygon      = WS* "_geoPolygon([[float WS* "," WS* float WS* "], ...], [[float WS* "," WS* float WS* "], ...], ...)"
+/// If we parse `_geoPolygon` we MUST parse the rest of the expression.
+/// The pairs can be given directly for a polygon without holes, otherwise the first ring
+/// is the outer boundary of the polygon and the other ones are its holes.
+fn parse_geo_polygon(input: Span) -> IResult<FilterCondition> {
+    let pair =
+        || ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']')));
+    // we want to allow space BEFORE the _geoPolygon but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoPolygon"))),
+        // if we were able to parse `_geoPolygon` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            char('('),
+            alt((
+                separated_list1(
+                    tag(","),
+                    ws(delimited(char('['), separated_list1(tag(","), pair()), char(']'))),
+                ),
+                map(separated_list1(tag(","), pair()), |pairs| vec![pairs]),
+            )),
+            char(')'),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+
+    let (rest, args) = parsed?;
+
+    if args.iter().any(|ring| ring.len() < 3 || ring.iter().any(|pair| pair.len() != 2)) {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+    }
+
+    let rings = args
+        .into_iter()
+        .map(|ring| ring.into_iter().map(|pair| [pair[0].into(), pair[1].into()]).collect())
+        .collect();
+    Ok((rest, FilterCondition::GeoPolygon { rings }))
+}
+
+/// geoContains      = WS* "_geoContains(float WS* "," WS* float)"
+/// If we parse `_geoContains` we MUST parse the rest of the expression.
+fn parse_geo_contains(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoContains but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoContains"))),
+        // if we were able to parse `_geoContains` and can't parse the rest of the input we return a failure
+        cut(delimited(char('('), separated_list1(tag(","), ws(recognize_float)), char(')'))),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoContains)));
+
+    let (rest, args) = parsed?;
+
+    if args.len() != 2 {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoContains)));
+    }
+
+    let res = FilterCondition::GeoContains { point: [args[0].into(), args[1].into()] };
+    Ok((rest, res))
+}
+
+/// geoIntersects      = WS* "_geoIntersects([float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "]")
+/// If we parse `_geoIntersects` we MUST parse the rest of the expression.
+fn parse_geo_intersects(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoIntersects but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoIntersects"))),
+        // if we were able to parse `_geoIntersects` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            char('('),
+            separated_list1(
+                tag(","),
+                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            ),
+            char(')'),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoIntersects)));
+
+    let (rest, args) = parsed?;
+
+    if args.len() != 2 || args[0].len() != 2 || args[1].len() != 2 {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoIntersects)));
+    }
+
+    let res = FilterCondition::GeoIntersects {
+        top_right_point: [args[0][0].into(), args[0][1].into()],
+        bottom_left_point: [args[1][0].into(), args[1][1].into()],
+    };
+    Ok((rest, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -477,7 +593,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoPolygon | geoContains | geoIntersects | condition | exists | not_exists | to
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -491,8 +607,13 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
                 Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(c.char()))
             }),
         ),
-        parse_geo_radius,
-        parse_geo_bounding_box,
+        alt((
+            parse_geo_radius,
+            parse_geo_bounding_box,
+            parse_geo_polygon,
+            parse_geo_contains,
+            parse_geo_intersects,
+        )),
         parse_in,
         parse_not_in,
         parse_condition,
@@ -573,6 +694,46 @@ impl std::fmt::Display for FilterCondition<'_> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoPolygon { rings } => {
+                let write_ring =
+                    |f: &mut std::fmt::Formatter<'_>, ring: &[[Token; 2]]| -> std::fmt::Result {
+                        for (i, [lat, lng]) in ring.iter().enumerate() {
+                            if i != 0 {
+                                write!(f, ", ")?;
+                            }
+                            write!(f, "[{lat}, {lng}]")?;
+                        }
+                        Ok(())
+                    };
+                write!(f, "_geoPolygon(")?;
+                match rings.as_slice() {
+                    [ring] => write_ring(f, ring)?,
+                    rings => {
+                        for (i, ring) in rings.iter().enumerate() {
+                            if i != 0 {
+                                write!(f, ", ")?;
+                            }
+                            write!(f, "[")?;
+                            write_ring(f, ring)?;
+                            write!(f, "]")?;
+                        }
+                    }
+                }
+                write!(f, ")")
+            }
+            FilterCondition::GeoContains { point } => {
+                write!(f, "_geoContains({}, {})", point[0], point[1])
+            }
+            FilterCondition::GeoIntersects { top_right_point, bottom_left_point } => {
+                write!(
+                    f,
+                    "_geoIntersects([{}, {}], [{}, {}])",
+                    top_right_point[0],
+                    top_right_point[1],
+                    bottom_left_point[0],
+                    bottom_left_point[1]
+                )
+            }
         }
     }
 }
@@ -674,6 +835,12 @@ pub mod tests {
         insta::assert_snapshot!(p("subscribers <= 1000"), @"{subscribers} <= {1000}");
         insta::assert_snapshot!(p("subscribers 100 TO 1000"), @"{subscribers} {100} TO {1000}");
 
+        // Test dates
+        insta::assert_snapshot!(p("published_at > 2025-01-01"), @"{published_at} > {2025-01-01}");
+        insta::assert_snapshot!(p("published_at <= 2025-01-01T10:00:00+02:00"), @"{published_at} <= {2025-01-01T10:00:00+02:00}");
+        insta::assert_snapshot!(p("published_at >= NOW-7d"), @"{published_at} >= {NOW-7d}");
+        insta::assert_snapshot!(p("published_at NOW-7d TO NOW+1d"), @"{published_at} {NOW-7d} TO {NOW+1d}");
+
         // Test NOT
         insta::assert_snapshot!(p("NOT subscribers < 1000"), @"NOT ({subscribers} < {1000})");
         insta::assert_snapshot!(p("NOT subscribers 100 TO 1000"), @"NOT ({subscribers} {100} TO {1000})");
@@ -727,6 +894,22 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test geo polygon
+        insta::assert_snapshot!(p("_geoPolygon([12, 13], [14, 15], [16, 17])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}])");
+        insta::assert_snapshot!(p("NOT _geoPolygon([12, 13], [14, 15], [16, 17])"), @"NOT (_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}]))");
+        insta::assert_snapshot!(p("_geoPolygon([12,13],[14,15],[16,17],[12,13])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}], [{12}, {13}])");
+        insta::assert_snapshot!(p("_geoPolygon([0, 0], [0, 10], [10, 10], [10, 0]) AND NOT _geoPolygon([2, 2], [2, 4], [4, 4])"), @"AND[_geoPolygon([{0}, {0}], [{0}, {10}], [{10}, {10}], [{10}, {0}]), NOT (_geoPolygon([{2}, {2}], [{2}, {4}], [{4}, {4}])), ]");
+        insta::assert_snapshot!(p("_geoPolygon([[12, 13], [14, 15], [16, 17]])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}])");
+        insta::assert_snapshot!(p("_geoPolygon( [[0, 0], [0, 10], [10, 10], [10, 0]] , [ [2, 2],[2, 4],[4, 4] ])"), @"_geoPolygon([[{0}, {0}], [{0}, {10}], [{10}, {10}], [{10}, {0}]], [[{2}, {2}], [{2}, {4}], [{4}, {4}]])");
+
+        // Test geo contains
+        insta::assert_snapshot!(p("_geoContains(12, 13)"), @"_geoContains({12}, {13})");
+        insta::assert_snapshot!(p("NOT _geoContains(12,13)"), @"NOT (_geoContains({12}, {13}))");
+
+        // Test geo intersects
+        insta::assert_snapshot!(p("_geoIntersects([12, 13], [14, 15])"), @"_geoIntersects([{12}, {13}], [{14}, {15}])");
+        insta::assert_snapshot!(p("NOT _geoIntersects([12,13],[14,15])"), @"NOT (_geoIntersects([{12}, {13}], [{14}, {15}]))");
+
         // Test OR + AND
         insta::assert_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -768,6 +951,12 @@ pub mod tests {
         17:21 channel = Ponce = 12
         "###);
 
+        // `:` and `+` are only allowed in the dates
+        insta::assert_snapshot!(p("subscribers = 1+1"), @r###"
+        Found unexpected characters at the end of the filter: `+1`. You probably forgot an `OR` or an `AND` rule.
+        16:18 subscribers = 1+1
+        "###);
+
         insta::assert_snapshot!(p("channel =    "), @r###"
         Was expecting a value but instead got nothing.
         14:14 channel =
@@ -828,33 +1017,78 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_snapshot!(p("_geoPolygon"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`. A polygon with holes expects rings of at least three pairs, its outer ring followed by its holes: `_geoPolygon([[latitude, longitude], ...], [[latitude, longitude], ...], ...)`.
+        1:12 _geoPolygon
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1, 2], [3, 4])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`. A polygon with holes expects rings of at least three pairs, its outer ring followed by its holes: `_geoPolygon([[latitude, longitude], ...], [[latitude, longitude], ...], ...)`.
+        1:28 _geoPolygon([1, 2], [3, 4])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1, 2], [3, 4], [5])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`. A polygon with holes expects rings of at least three pairs, its outer ring followed by its holes: `_geoPolygon([[latitude, longitude], ...], [[latitude, longitude], ...], ...)`.
+        1:33 _geoPolygon([1, 2], [3, 4], [5])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([[0, 0], [0, 10], [10, 10]], [[2, 2], [2, 4]])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`. A polygon with holes expects rings of at least three pairs, its outer ring followed by its holes: `_geoPolygon([[latitude, longitude], ...], [[latitude, longitude], ...], ...)`.
+        1:59 _geoPolygon([[0, 0], [0, 10], [10, 10]], [[2, 2], [2, 4]])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([[0, 0], [0, 10], [10, 10]], [2, 2])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`. A polygon with holes expects rings of at least three pairs, its outer ring followed by its holes: `_geoPolygon([[latitude, longitude], ...], [[latitude, longitude], ...], ...)`.
+        1:49 _geoPolygon([[0, 0], [0, 10], [10, 10]], [2, 2])
+        "###);
+
+        insta::assert_snapshot!(p("position = _geoPolygon([1, 2], [3, 4], [5, 6])"), @r###"
+        The `_geoPolygon` filter is an operation and can't be used as a value.
+        12:47 position = _geoPolygon([1, 2], [3, 4], [5, 6])
+        "###);
+
+        insta::assert_snapshot!(p("_geoContains(1, 2, 3)"), @r###"
+        The `_geoContains` filter expects two arguments: `_geoContains(latitude, longitude)`.
+        1:22 _geoContains(1, 2, 3)
+        "###);
+
+        insta::assert_snapshot!(p("_geoIntersects([1, 2])"), @r###"
+        The `_geoIntersects` filter expects two pairs of arguments: `_geoIntersects([latitude, longitude], [latitude, longitude])`.
+        1:23 _geoIntersects([1, 2])
+        "###);
+
+        insta::assert_snapshot!(p("shape = _geoContains(1, 2)"), @r###"
+        The `_geoContains` filter is an operation and can't be used as a value.
+        9:27 shape = _geoContains(1, 2)
+        "###);
+
         insta::assert_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         13:34 position <= _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("_geoDistance(12, 13, 14)"), @r###"
-        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         1:25 _geoDistance(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geoDistance(12, 13, 14)"), @r###"
-        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         13:37 position <= _geoDistance(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("_geo(12, 13, 14)"), @r###"
-        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         1:17 _geo(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geo(12, 13, 14)"), @r###"
-        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         13:29 position <= _geo(12, 13, 14)
         "###);
 
@@ -1037,6 +1271,35 @@ pub mod tests {
         assert_eq!(fids[0].value(), "field1");
     }
 
+    #[test]
+    fn geo_shapes() {
+        let filter = Fc::parse("field = value").unwrap().unwrap();
+        assert_eq!(filter.geo_shapes().count(), 0);
+
+        let filter =
+            Fc::parse("_geoRadius(12, 13, 14) OR (field = value AND _geoRadius(-1, 2.5, 3))")
+                .unwrap()
+                .unwrap();
+        let centers: Vec<_> = filter
+            .geo_shapes()
+            .map(|shape| match shape {
+                Fc::GeoLowerThan { point: [lat, lng], .. } => (lat.value(), lng.value()),
+                _ => panic!("unexpected shape {shape:?}"),
+            })
+            .collect();
+        assert_eq!(centers, [("12", "13"), ("-1", "2.5")]);
+
+        let filter = Fc::parse(
+            "NOT _geoRadius(12, 13, 14) AND _geoBoundingBox([1, 2], [3, 4]) AND _geoPolygon([1, 2], [3, 4], [5, 6])",
+        )
+        .unwrap()
+        .unwrap();
+        let shapes: Vec<_> = filter.geo_shapes().collect();
+        assert_eq!(shapes.len(), 2);
+        assert!(matches!(shapes[0], Fc::GeoBoundingBox { .. }));
+        assert!(matches!(shapes[1], Fc::GeoPolygon { .. }));
+    }
+
     #[test]
     fn token_from_str() {
         let s = "test string that should not be parsed";
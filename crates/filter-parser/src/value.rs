@@ -1,14 +1,15 @@
 use nom::branch::alt;
-use nom::bytes::complete::{take_till, take_while, take_while1};
+use nom::bytes::complete::{tag, take_till, take_while, take_while1, take_while_m_n};
 use nom::character::complete::{char, multispace0};
-use nom::combinator::cut;
-use nom::sequence::{delimited, terminated};
+use nom::combinator::{cut, recognize};
+use nom::sequence::{delimited, pair, terminated, tuple};
 use nom::{InputIter, InputLength, InputTake, Slice};
 
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
-    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_radius,
-    Error, ErrorKind, IResult, Span, Token,
+    parse_geo, parse_geo_bounding_box, parse_geo_contains, parse_geo_distance,
+    parse_geo_intersects, parse_geo_point, parse_geo_polygon, parse_geo_radius, Error, ErrorKind,
+    IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -64,6 +65,22 @@ pub fn word_not_keyword<'a>(input: Span<'a>) -> IResult<'a, Token<'a>> {
     Ok((input, word))
 }
 
+// date           = YYYY-MM-DD (alphanumeric | _ | - | . | : | +)* | NOW (alphanumeric | _ | - | . | +)*
+fn date_literal(input: Span) -> IResult<Token> {
+    let digits = |count| take_while_m_n(count, count, |c: char| c.is_ascii_digit());
+    let date = recognize(tuple((
+        digits(4),
+        char('-'),
+        digits(2),
+        char('-'),
+        digits(2),
+        take_while(|c| is_value_component(c) || [':', '+'].contains(&c)),
+    )));
+    let now = recognize(pair(tag("NOW"), take_while(|c| is_value_component(c) || c == '+')));
+
+    alt((date, now))(input).map(|(s, t)| (s, t.into()))
+}
+
 // word           = {tag}
 pub fn word_exact<'a, 'b: 'a>(tag: &'b str) -> impl Fn(Span<'a>) -> IResult<'a, Token<'a>> {
     move |input| {
@@ -98,34 +115,26 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         }
     }
 
-    match parse_geo_radius(input) {
-        Ok(_) => {
-            return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::MisusedGeoRadius)))
-        }
-        // if we encountered a failure it means the user badly wrote a _geoRadius filter.
-        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
-        Err(e) if e.is_failure() => {
-            return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::MisusedGeoRadius)))
-        }
-        _ => (),
-    }
-
-    match parse_geo_bounding_box(input) {
-        Ok(_) => {
-            return Err(nom::Err::Failure(Error::new_from_kind(
-                input,
-                ErrorKind::MisusedGeoBoundingBox,
-            )))
-        }
-        // if we encountered a failure it means the user badly wrote a _geoBoundingBox filter.
-        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
-        Err(e) if e.is_failure() => {
-            return Err(nom::Err::Failure(Error::new_from_kind(
-                input,
-                ErrorKind::MisusedGeoBoundingBox,
-            )))
+    // the geo operations can't be used as values either, and when we can parse one
+    // we want to tell the user so instead of returning a generic error.
+    let geo_operations = [
+        (parse_geo_radius as fn(_) -> _, ErrorKind::MisusedGeoRadius),
+        (parse_geo_bounding_box, ErrorKind::MisusedGeoBoundingBox),
+        (parse_geo_polygon, ErrorKind::MisusedGeoPolygon),
+        (parse_geo_contains, ErrorKind::MisusedGeoContains),
+        (parse_geo_intersects, ErrorKind::MisusedGeoIntersects),
+    ];
+
+    for (parser, misused_kind) in geo_operations {
+        match parser(input) {
+            Ok(_) => return Err(nom::Err::Failure(Error::new_from_kind(input, misused_kind))),
+            // if we encountered a failure it means the user badly wrote a geo filter.
+            // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+            Err(e) if e.is_failure() => {
+                return Err(nom::Err::Failure(Error::new_from_kind(input, misused_kind)))
+            }
+            _ => (),
         }
-        _ => (),
     }
 
     // this parser is only used when an error is encountered and it parse the
@@ -141,6 +150,7 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         alt((
             delimited(char('\''), cut(|input| quoted_by('\'', input)), cut(char('\''))),
             delimited(char('"'), cut(|input| quoted_by('"', input)), cut(char('"'))),
+            date_literal,
             word_not_keyword,
         )),
         multispace0,
@@ -216,6 +226,9 @@ fn is_keyword(s: &str) -> bool {
             | "WITH"
             | "_geoRadius"
             | "_geoBoundingBox"
+            | "_geoPolygon"
+            | "_geoContains"
+            | "_geoIntersects"
     )
 }
 
@@ -12,6 +12,7 @@ use crate::error::{
     ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
+use crate::milli::InvalidBoostWeight;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
 
 pub mod query_params;
@@ -197,5 +198,7 @@ merge_with_error_impl_take_error_message!(InvalidNetworkUrl);
 merge_with_error_impl_take_error_message!(InvalidNetworkSearchApiKey);
 merge_with_error_impl_take_error_message!(InvalidSearchSemanticRatio);
 merge_with_error_impl_take_error_message!(InvalidSearchRankingScoreThreshold);
+merge_with_error_impl_take_error_message!(InvalidSearchAggregations);
 merge_with_error_impl_take_error_message!(InvalidSimilarRankingScoreThreshold);
 merge_with_error_impl_take_error_message!(InvalidSimilarId);
+merge_with_error_impl_take_error_message!(InvalidBoostWeight);
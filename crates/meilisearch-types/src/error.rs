@@ -258,9 +258,11 @@ InvalidMultiSearchFederated           , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFederationOptions   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMaxValuesPerFacet   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMergeFacets         , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryAggregations   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryFacets         , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryPagination     , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryRankingRules   , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQuerySuggestQuery   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryPosition       , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchRemote              , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchWeight              , InvalidRequest       , BAD_REQUEST ;
@@ -268,7 +270,9 @@ InvalidNetworkRemotes                 , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkSelf                    , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkSearchApiKey            , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkUrl                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchAggregations             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchBoosts                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarAttributesToRetrieve    , InvalidRequest       , BAD_REQUEST ;
@@ -279,6 +283,7 @@ InvalidSimilarRankingScoreThreshold   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
@@ -297,6 +302,20 @@ InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchQuerySyntax              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSuggestQuery             , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestQ                       , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestLimit                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestFilter                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestAttributesToSearchOn    , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsEventType             , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsEventName             , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsEventIndexUid         , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsEventQueryUid         , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsEventObjectId         , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsEventPosition         , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsIndexUid              , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyticsLimit                 , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 FacetSearchDisabled                   , InvalidRequest       , BAD_REQUEST ;
@@ -323,11 +342,17 @@ InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsStopWords              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsIdentifierAttributes   , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsInfixAttributes        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDateAttributes         , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSynonymSets            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRules                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryRules             , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
@@ -415,6 +440,10 @@ impl ErrorCode for milli::Error {
                     UserError::MaxDatabaseSizeReached => Code::DatabaseSizeLimitReached,
                     UserError::AttributeLimitReached => Code::MaxFieldsLimitExceeded,
                     UserError::InvalidFilter(_) => Code::InvalidSearchFilter,
+                    UserError::InvalidBoost { .. } => Code::InvalidSearchBoosts,
+                    UserError::InvalidSearchRule { .. } => Code::InvalidSettingsRules,
+                    UserError::InvalidQueryRule { .. } => Code::InvalidSettingsQueryRules,
+                    UserError::InvalidSynonymSet { .. } => Code::InvalidSettingsSynonymSets,
                     UserError::InvalidFilterExpression(..) => Code::InvalidSearchFilter,
                     UserError::FilterOperatorNotAllowed { .. } => Code::InvalidSearchFilter,
                     UserError::MissingDocumentId { .. } => Code::MissingDocumentId,
@@ -445,6 +474,7 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetsAggregations { .. } => Code::InvalidSearchAggregations,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
@@ -570,6 +600,15 @@ impl fmt::Display for deserr_codes::InvalidSearchSemanticRatio {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchAggregations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value of `aggregations` is invalid, expected an object associating numeric attributes to their `percentiles`, floats between `0.0` and `100.0`."
+        )
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidMultiSearchWeight {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "the value of `weight` is invalid, expected a positive float (>= 0.0).")
@@ -9,6 +9,11 @@ use utoipa::ToSchema;
 pub struct LocalizedAttributesRuleView {
     pub attribute_patterns: AttributePatterns,
     pub locales: Vec<Locale>,
+    /// Index the stems of the words of the attributes in the locales, so that a query word
+    /// also matches the words sharing its stem, ranked below its exact matches.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[deserr(default)]
+    pub stemming: bool,
 }
 
 impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
@@ -16,6 +21,7 @@ impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
         Self {
             attribute_patterns: rule.attribute_patterns,
             locales: rule.locales.into_iter().map(|l| l.into()).collect(),
+            stemming: rule.stemming,
         }
     }
 }
@@ -25,6 +31,7 @@ impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
         Self {
             attribute_patterns: view.attribute_patterns,
             locales: view.locales.into_iter().map(|l| l.into()).collect(),
+            stemming: view.stemming,
         }
     }
 }
@@ -12,18 +12,20 @@ use milli::disabled_typos_terms::DisabledTyposTerms;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    AttributePatterns, AttributeTypoBudget, Criterion, CriterionError, FilterableAttributesRule,
+    Index, LocaleTypoBudget, PhoneticSettings, QueryRule, RankingExpression, SearchRule,
+    SynonymSet, SynonymSetKind, TypoBudget, TypoBudgets, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
 
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
-use crate::locales::LocalizedAttributesRuleView;
+use crate::locales::{Locale, LocalizedAttributesRuleView};
 
-/// The maximum number of results that the engine
-/// will be able to return in one search call.
-pub const DEFAULT_PAGINATION_MAX_TOTAL_HITS: usize = 1000;
+pub use milli::index::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 
 fn serialize_with_wildcard<S>(
     field: &Setting<Vec<String>>,
@@ -109,6 +111,143 @@ pub struct TypoSettings {
     #[deserr(default)]
     #[schema(value_type = Option<bool>, example = json!(true))]
     pub disable_on_numbers: Setting<bool>,
+    /// The attributes whose words also match the query words sounding alike, ranked by the
+    /// typo ranking rule after the words with two typos.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    #[schema(value_type = Option<PhoneticSettings>, example = json!({ "attributePatterns": ["lastName"], "algorithm": "doubleMetaphone" }))]
+    pub phonetic: Setting<PhoneticSettings>,
+    /// The typo budgets of the attributes matching some patterns, overriding the settings above.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    #[schema(value_type = Option<Vec<AttributeTypoBudgetView>>, example = json!([{ "attributePatterns": ["sku"], "maxTypos": 0 }]))]
+    pub per_attribute: Setting<Vec<AttributeTypoBudgetView>>,
+    /// The typo budgets of the attributes localized in some locales, overriding the settings
+    /// above when no budget of `perAttribute` matches the attribute.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    #[schema(value_type = Option<Vec<LocaleTypoBudgetView>>, example = json!([{ "locales": ["jpn"], "maxTypos": 1 }]))]
+    pub per_locale: Setting<Vec<LocaleTypoBudgetView>>,
+}
+
+fn validate_max_typos<E: DeserializeError>(
+    max_typos: Option<u8>,
+    location: ValuePointerRef,
+) -> Result<(), E> {
+    match max_typos {
+        Some(max_typos) if max_typos > 2 => Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!("`maxTypos` should be between `0` and `2` but found `{max_typos}`."),
+            },
+            location,
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn validate_attribute_typo_budget<E: DeserializeError>(
+    budget: AttributeTypoBudgetView,
+    location: ValuePointerRef,
+) -> Result<AttributeTypoBudgetView, E> {
+    validate_max_typos(budget.max_typos, location).map(|()| budget)
+}
+
+fn validate_locale_typo_budget<E: DeserializeError>(
+    budget: LocaleTypoBudgetView,
+    location: ValuePointerRef,
+) -> Result<LocaleTypoBudgetView, E> {
+    validate_max_typos(budget.max_typos, location).map(|()| budget)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsTypoTolerance>, deny_unknown_fields, rename_all = camelCase, validate = validate_attribute_typo_budget -> DeserrJsonError<InvalidSettingsTypoTolerance>)]
+pub struct AttributeTypoBudgetView {
+    #[schema(value_type = Vec<String>, example = json!(["sku"]))]
+    pub attribute_patterns: AttributePatterns,
+    /// The maximum number of typos allowed in a word, at most two.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub max_typos: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub min_word_size_for_typos: Option<MinWordSizeTyposSetting>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsTypoTolerance>, deny_unknown_fields, rename_all = camelCase, validate = validate_locale_typo_budget -> DeserrJsonError<InvalidSettingsTypoTolerance>)]
+pub struct LocaleTypoBudgetView {
+    #[schema(value_type = Vec<String>, example = json!(["jpn"]))]
+    pub locales: Vec<Locale>,
+    /// The maximum number of typos allowed in a word, at most two.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub max_typos: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub min_word_size_for_typos: Option<MinWordSizeTyposSetting>,
+}
+
+fn typo_budget(
+    max_typos: Option<u8>,
+    min_word_size_for_typos: Option<MinWordSizeTyposSetting>,
+) -> TypoBudget {
+    let (one_typo, two_typos) = match min_word_size_for_typos {
+        Some(MinWordSizeTyposSetting { one_typo, two_typos }) => (one_typo.set(), two_typos.set()),
+        None => (None, None),
+    };
+    TypoBudget { max_typos, min_word_len_one_typo: one_typo, min_word_len_two_typos: two_typos }
+}
+
+fn min_word_size_for_typos(budget: &TypoBudget) -> Option<MinWordSizeTyposSetting> {
+    let TypoBudget { max_typos: _, min_word_len_one_typo, min_word_len_two_typos } = *budget;
+    if min_word_len_one_typo.is_none() && min_word_len_two_typos.is_none() {
+        return None;
+    }
+    Some(MinWordSizeTyposSetting {
+        one_typo: min_word_len_one_typo.map_or(Setting::NotSet, Setting::Set),
+        two_typos: min_word_len_two_typos.map_or(Setting::NotSet, Setting::Set),
+    })
+}
+
+impl From<AttributeTypoBudget> for AttributeTypoBudgetView {
+    fn from(AttributeTypoBudget { attribute_patterns, budget }: AttributeTypoBudget) -> Self {
+        Self {
+            attribute_patterns,
+            max_typos: budget.max_typos,
+            min_word_size_for_typos: min_word_size_for_typos(&budget),
+        }
+    }
+}
+
+impl From<AttributeTypoBudgetView> for AttributeTypoBudget {
+    fn from(view: AttributeTypoBudgetView) -> Self {
+        let AttributeTypoBudgetView { attribute_patterns, max_typos, min_word_size_for_typos } =
+            view;
+        Self { attribute_patterns, budget: typo_budget(max_typos, min_word_size_for_typos) }
+    }
+}
+
+impl From<LocaleTypoBudget> for LocaleTypoBudgetView {
+    fn from(LocaleTypoBudget { locales, budget }: LocaleTypoBudget) -> Self {
+        Self {
+            locales: locales.into_iter().map(Into::into).collect(),
+            max_typos: budget.max_typos,
+            min_word_size_for_typos: min_word_size_for_typos(&budget),
+        }
+    }
+}
+
+impl From<LocaleTypoBudgetView> for LocaleTypoBudget {
+    fn from(view: LocaleTypoBudgetView) -> Self {
+        let LocaleTypoBudgetView { locales, max_typos, min_word_size_for_typos } = view;
+        Self {
+            locales: locales.into_iter().map(Into::into).collect(),
+            budget: typo_budget(max_typos, min_word_size_for_typos),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
@@ -123,6 +262,12 @@ pub struct FacetingSettings {
     #[deserr(default)]
     #[schema(value_type = Option<BTreeMap<String, FacetValuesSort>>, example = json!({ "genre": FacetValuesSort::Count }))]
     pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+    /// The facet hierarchies, associating their name with the attributes of their levels
+    /// from the root to the leaves, e.g. `{ "category": ["category.lvl0", "category.lvl1"] }`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<BTreeMap<String, Vec<String>>>, example = json!({ "category": ["category.lvl0", "category.lvl1"] }))]
+    pub hierarchies: Setting<BTreeMap<String, Vec<String>>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
@@ -230,6 +375,24 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsNonSeparatorTokens>)]
     #[schema(value_type = Option<Vec<String>>, example = json!([" ", "\n"]))]
     pub non_separator_tokens: Setting<BTreeSet<String>>,
+    /// Attributes holding identifiers, e.g. SKUs or part numbers, whose words are also indexed
+    /// concatenated and split between letters and digits.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsIdentifierAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["sku", "partNumber"]))]
+    pub identifier_attributes: Setting<BTreeSet<String>>,
+    /// Attributes whose words and facet values can be matched by a part of them, e.g. `mart`
+    /// matching `walmart`, in the search and the `CONTAINS` filter.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsInfixAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["brand", "title"]))]
+    pub infix_attributes: Setting<BTreeSet<String>>,
+    /// Attributes whose facet values are normalized as dates, so that the RFC 3339 dates are
+    /// sorted and compared chronologically by the sort and the filters, e.g. `published_at > NOW-7d`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDateAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["published_at"]))]
+    pub date_attributes: Setting<BTreeSet<String>>,
     /// List of characters delimiting where one term begins and ends.
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSeparatorTokens>)]
@@ -293,6 +456,49 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Merchandising rules pinning, hiding or boosting documents, associated with their id.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRules>)]
+    #[schema(value_type = Option<BTreeMap<String, SearchRule>>, example = json!({ "summer-sale": { "condition": { "query": "sandals" }, "actions": { "pin": [{ "id": "42", "position": 0 }] } } }))]
+    pub rules: Setting<BTreeMap<String, SearchRule>>,
+    /// Rules rewriting the queries before they are executed, associated with their id.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryRules>)]
+    #[schema(value_type = Option<BTreeMap<String, QueryRule>>, example = json!({ "cheap": { "condition": { "query": "cheap", "anchoring": "contains" }, "consequence": { "replaceWith": "", "filter": "price < 20" } } }))]
+    pub query_rules: Setting<BTreeMap<String, QueryRule>>,
+    /// Sets of equivalent or one-way synonyms, optionally restricted to some locales.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonymSets>)]
+    #[schema(value_type = Option<Vec<SynonymSetView>>, example = json!([{ "kind": "oneWay", "input": ["phone"], "synonyms": ["smartphone"], "locales": ["eng"] }]))]
+    pub synonym_sets: Setting<Vec<SynonymSetView>>,
+    /// The synonym sets appended to the current ones, only set by the import route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub synonym_sets_to_add: Option<Vec<SynonymSetView>>,
+    /// The stop words added to the current ones, only set by the add route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub stop_words_to_add: Option<BTreeSet<String>>,
+    /// The stop words removed from the current ones, only set by the remove route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub stop_words_to_remove: Option<BTreeSet<String>>,
+    /// The synonyms appended to the current ones, only set by the add route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub synonyms_to_add: Option<BTreeMap<String, Vec<String>>>,
+    /// The words whose synonyms are removed, only set by the remove route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub synonyms_to_remove: Option<BTreeSet<String>>,
+    /// The words added to the current dictionary, only set by the add route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub dictionary_to_add: Option<BTreeSet<String>>,
+    /// The words removed from the current dictionary, only set by the remove route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(skip)]
+    pub dictionary_to_remove: Option<BTreeSet<String>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -347,6 +553,9 @@ impl Settings<Checked> {
             stop_words: Setting::Reset,
             synonyms: Setting::Reset,
             non_separator_tokens: Setting::Reset,
+            identifier_attributes: Setting::Reset,
+            infix_attributes: Setting::Reset,
+            date_attributes: Setting::Reset,
             separator_tokens: Setting::Reset,
             dictionary: Setting::Reset,
             distinct_attribute: Setting::Reset,
@@ -359,6 +568,16 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            rules: Setting::Reset,
+            query_rules: Setting::Reset,
+            synonym_sets: Setting::Reset,
+            synonym_sets_to_add: None,
+            stop_words_to_add: None,
+            stop_words_to_remove: None,
+            synonyms_to_add: None,
+            synonyms_to_remove: None,
+            dictionary_to_add: None,
+            dictionary_to_remove: None,
             _kind: PhantomData,
         }
     }
@@ -372,6 +591,9 @@ impl Settings<Checked> {
             ranking_rules,
             stop_words,
             non_separator_tokens,
+            identifier_attributes,
+            infix_attributes,
+            date_attributes,
             separator_tokens,
             dictionary,
             synonyms,
@@ -385,6 +607,16 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            rules,
+            query_rules,
+            synonym_sets,
+            synonym_sets_to_add,
+            stop_words_to_add,
+            stop_words_to_remove,
+            synonyms_to_add,
+            synonyms_to_remove,
+            dictionary_to_add,
+            dictionary_to_remove,
             _kind,
         } = self;
 
@@ -396,6 +628,9 @@ impl Settings<Checked> {
             ranking_rules,
             stop_words,
             non_separator_tokens,
+            identifier_attributes,
+            infix_attributes,
+            date_attributes,
             separator_tokens,
             dictionary,
             synonyms,
@@ -409,6 +644,16 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            rules,
+            query_rules,
+            synonym_sets,
+            synonym_sets_to_add,
+            stop_words_to_add,
+            stop_words_to_remove,
+            synonyms_to_add,
+            synonyms_to_remove,
+            dictionary_to_add,
+            dictionary_to_remove,
             _kind: PhantomData,
         }
     }
@@ -447,6 +692,9 @@ impl Settings<Unchecked> {
             stop_words: self.stop_words,
             synonyms: self.synonyms,
             non_separator_tokens: self.non_separator_tokens,
+            identifier_attributes: self.identifier_attributes,
+            infix_attributes: self.infix_attributes,
+            date_attributes: self.date_attributes,
             separator_tokens: self.separator_tokens,
             dictionary: self.dictionary,
             distinct_attribute: self.distinct_attribute,
@@ -459,6 +707,16 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            rules: self.rules,
+            query_rules: self.query_rules,
+            synonym_sets: self.synonym_sets,
+            synonym_sets_to_add: self.synonym_sets_to_add,
+            stop_words_to_add: self.stop_words_to_add,
+            stop_words_to_remove: self.stop_words_to_remove,
+            synonyms_to_add: self.synonyms_to_add,
+            synonyms_to_remove: self.synonyms_to_remove,
+            dictionary_to_add: self.dictionary_to_add,
+            dictionary_to_remove: self.dictionary_to_remove,
             _kind: PhantomData,
         }
     }
@@ -504,6 +762,12 @@ impl Settings<Unchecked> {
                 .non_separator_tokens
                 .clone()
                 .or(self.non_separator_tokens.clone()),
+            identifier_attributes: other
+                .identifier_attributes
+                .clone()
+                .or(self.identifier_attributes.clone()),
+            infix_attributes: other.infix_attributes.clone().or(self.infix_attributes.clone()),
+            date_attributes: other.date_attributes.clone().or(self.date_attributes.clone()),
             separator_tokens: other.separator_tokens.clone().or(self.separator_tokens.clone()),
             dictionary: other.dictionary.clone().or(self.dictionary.clone()),
             synonyms: other.synonyms.clone().or(self.synonyms.clone()),
@@ -535,11 +799,107 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            rules: other.rules.clone().or(self.rules.clone()),
+            query_rules: other.query_rules.clone().or(self.query_rules.clone()),
+            // the additions are lost when all the synonym sets are replaced
+            synonym_sets_to_add: if other.synonym_sets.is_not_set() {
+                match (&self.synonym_sets_to_add, &other.synonym_sets_to_add) {
+                    (Some(left), Some(right)) => Some([left.clone(), right.clone()].concat()),
+                    (left, right) => right.clone().or(left.clone()),
+                }
+            } else {
+                other.synonym_sets_to_add.clone()
+            },
+            synonym_sets: other.synonym_sets.clone().or(self.synonym_sets.clone()),
+            // the removals are applied before the additions, and both are lost
+            // when the whole list is replaced
+            stop_words_to_add: if other.stop_words.is_not_set() {
+                merge_added_words(
+                    &self.stop_words_to_add,
+                    &other.stop_words_to_remove,
+                    &other.stop_words_to_add,
+                )
+            } else {
+                other.stop_words_to_add.clone()
+            },
+            stop_words_to_remove: if other.stop_words.is_not_set() {
+                merge_removed_words(&self.stop_words_to_remove, &other.stop_words_to_remove)
+            } else {
+                other.stop_words_to_remove.clone()
+            },
+            synonyms_to_add: if other.synonyms.is_not_set() {
+                let mut synonyms = self.synonyms_to_add.clone();
+                if let (Some(synonyms), Some(removed)) = (&mut synonyms, &other.synonyms_to_remove)
+                {
+                    synonyms.retain(|word, _| !removed.contains(word));
+                }
+                match (synonyms, &other.synonyms_to_add) {
+                    (Some(mut left), Some(right)) => {
+                        for (word, synonyms) in right {
+                            let current = left.entry(word.clone()).or_default();
+                            for synonym in synonyms {
+                                if !current.contains(synonym) {
+                                    current.push(synonym.clone());
+                                }
+                            }
+                        }
+                        Some(left)
+                    }
+                    (left, right) => right.clone().or(left),
+                }
+            } else {
+                other.synonyms_to_add.clone()
+            },
+            synonyms_to_remove: if other.synonyms.is_not_set() {
+                merge_removed_words(&self.synonyms_to_remove, &other.synonyms_to_remove)
+            } else {
+                other.synonyms_to_remove.clone()
+            },
+            dictionary_to_add: if other.dictionary.is_not_set() {
+                merge_added_words(
+                    &self.dictionary_to_add,
+                    &other.dictionary_to_remove,
+                    &other.dictionary_to_add,
+                )
+            } else {
+                other.dictionary_to_add.clone()
+            },
+            dictionary_to_remove: if other.dictionary.is_not_set() {
+                merge_removed_words(&self.dictionary_to_remove, &other.dictionary_to_remove)
+            } else {
+                other.dictionary_to_remove.clone()
+            },
             _kind: PhantomData,
         }
     }
 }
 
+/// The words added by two successive updates, without the ones removed by the second one.
+fn merge_added_words(
+    left: &Option<BTreeSet<String>>,
+    removed: &Option<BTreeSet<String>>,
+    right: &Option<BTreeSet<String>>,
+) -> Option<BTreeSet<String>> {
+    let mut left = left.clone();
+    if let (Some(left), Some(removed)) = (&mut left, removed) {
+        left.retain(|word| !removed.contains(word));
+    }
+    match (left, right) {
+        (Some(left), Some(right)) => Some(&left | right),
+        (left, right) => right.clone().or(left),
+    }
+}
+
+fn merge_removed_words(
+    left: &Option<BTreeSet<String>>,
+    right: &Option<BTreeSet<String>>,
+) -> Option<BTreeSet<String>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left | right),
+        (left, right) => right.clone().or(left.clone()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
@@ -560,6 +920,9 @@ pub fn apply_settings_to_builder(
         ranking_rules,
         stop_words,
         non_separator_tokens,
+        identifier_attributes,
+        infix_attributes,
+        date_attributes,
         separator_tokens,
         dictionary,
         synonyms,
@@ -573,6 +936,16 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        rules,
+        query_rules,
+        synonym_sets,
+        synonym_sets_to_add,
+        stop_words_to_add,
+        stop_words_to_remove,
+        synonyms_to_add,
+        synonyms_to_remove,
+        dictionary_to_add,
+        dictionary_to_remove,
         _kind,
     } = settings;
 
@@ -616,6 +989,14 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    if let Some(stop_words) = stop_words_to_remove {
+        builder.remove_stop_words(stop_words.clone());
+    }
+
+    if let Some(stop_words) = stop_words_to_add {
+        builder.add_stop_words(stop_words.clone());
+    }
+
     match non_separator_tokens {
         Setting::Set(ref non_separator_tokens) => {
             builder.set_non_separator_tokens(non_separator_tokens.clone())
@@ -624,6 +1005,28 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match identifier_attributes {
+        Setting::Set(ref identifier_attributes) => {
+            builder.set_identifier_attributes(identifier_attributes.clone())
+        }
+        Setting::Reset => builder.reset_identifier_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match infix_attributes {
+        Setting::Set(ref infix_attributes) => {
+            builder.set_infix_attributes(infix_attributes.clone())
+        }
+        Setting::Reset => builder.reset_infix_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match date_attributes {
+        Setting::Set(ref date_attributes) => builder.set_date_attributes(date_attributes.clone()),
+        Setting::Reset => builder.reset_date_attributes(),
+        Setting::NotSet => (),
+    }
+
     match separator_tokens {
         Setting::Set(ref separator_tokens) => {
             builder.set_separator_tokens(separator_tokens.clone())
@@ -638,12 +1041,28 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    if let Some(dictionary) = dictionary_to_remove {
+        builder.remove_dictionary(dictionary.clone());
+    }
+
+    if let Some(dictionary) = dictionary_to_add {
+        builder.add_dictionary(dictionary.clone());
+    }
+
     match synonyms {
         Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
         Setting::Reset => builder.reset_synonyms(),
         Setting::NotSet => (),
     }
 
+    if let Some(words) = synonyms_to_remove {
+        builder.remove_synonyms(words.clone());
+    }
+
+    if let Some(synonyms) = synonyms_to_add {
+        builder.add_synonyms(synonyms.clone());
+    }
+
     match distinct_attribute {
         Setting::Set(ref attr) => builder.set_distinct_field(attr.clone()),
         Setting::Reset => builder.reset_distinct_field(),
@@ -712,6 +1131,26 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_disable_on_numbers(),
                 Setting::NotSet => (),
             }
+
+            match value.phonetic {
+                Setting::Set(ref phonetic) => builder.set_phonetic(phonetic.clone()),
+                Setting::Reset => builder.reset_phonetic(),
+                Setting::NotSet => (),
+            }
+
+            match value.per_attribute {
+                Setting::Set(ref budgets) => builder
+                    .set_attribute_typo_budgets(budgets.iter().cloned().map(Into::into).collect()),
+                Setting::Reset => builder.reset_attribute_typo_budgets(),
+                Setting::NotSet => (),
+            }
+
+            match value.per_locale {
+                Setting::Set(ref budgets) => builder
+                    .set_locale_typo_budgets(budgets.iter().cloned().map(Into::into).collect()),
+                Setting::Reset => builder.reset_locale_typo_budgets(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -720,12 +1159,19 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_phonetic();
+            builder.reset_attribute_typo_budgets();
+            builder.reset_locale_typo_budgets();
         }
         Setting::NotSet => (),
     }
 
     match faceting {
-        Setting::Set(FacetingSettings { max_values_per_facet, sort_facet_values_by }) => {
+        Setting::Set(FacetingSettings {
+            max_values_per_facet,
+            sort_facet_values_by,
+            hierarchies,
+        }) => {
             match max_values_per_facet {
                 Setting::Set(val) => builder.set_max_values_per_facet(*val),
                 Setting::Reset => builder.reset_max_values_per_facet(),
@@ -738,10 +1184,16 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_sort_facet_values_by(),
                 Setting::NotSet => (),
             }
+            match hierarchies {
+                Setting::Set(val) => builder.set_facet_hierarchies(val.clone()),
+                Setting::Reset => builder.reset_facet_hierarchies(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             builder.reset_max_values_per_facet();
             builder.reset_sort_facet_values_by();
+            builder.reset_facet_hierarchies();
         }
         Setting::NotSet => (),
     }
@@ -783,6 +1235,30 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match rules {
+        Setting::Set(rules) => builder.set_search_rules(rules.clone()),
+        Setting::Reset => builder.reset_search_rules(),
+        Setting::NotSet => (),
+    }
+
+    match query_rules {
+        Setting::Set(rules) => builder.set_query_rules(rules.clone()),
+        Setting::Reset => builder.reset_query_rules(),
+        Setting::NotSet => (),
+    }
+
+    match synonym_sets {
+        Setting::Set(sets) => {
+            builder.set_synonym_sets(sets.iter().cloned().map(Into::into).collect())
+        }
+        Setting::Reset => builder.reset_synonym_sets(),
+        Setting::NotSet => (),
+    }
+
+    if let Some(sets) = synonym_sets_to_add {
+        builder.add_synonym_sets(sets.iter().cloned().map(Into::into).collect());
+    }
 }
 
 pub enum SecretPolicy {
@@ -817,6 +1293,9 @@ pub fn settings(
         .unwrap_or_default();
 
     let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
+    let identifier_attributes = index.identifier_attributes(rtxn)?.unwrap_or_default();
+    let infix_attributes = index.infix_attributes(rtxn)?.unwrap_or_default();
+    let date_attributes = index.date_attributes(rtxn)?.unwrap_or_default();
     let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
     let dictionary = index.dictionary(rtxn)?.unwrap_or_default();
 
@@ -838,6 +1317,8 @@ pub fn settings(
 
     let disabled_attributes = index.exact_attributes(rtxn)?.into_iter().map(String::from).collect();
     let DisabledTyposTerms { disable_on_numbers } = index.disabled_typos_terms(rtxn)?;
+    let TypoBudgets { attributes: attribute_budgets, locales: locale_budgets } =
+        index.typo_budgets(rtxn)?;
 
     let typo_tolerance = TypoSettings {
         enabled: Setting::Set(index.authorize_typos(rtxn)?),
@@ -845,6 +1326,12 @@ pub fn settings(
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
         disable_on_numbers: Setting::Set(disable_on_numbers),
+        phonetic: match index.phonetic_settings(rtxn)? {
+            Some(phonetic) => Setting::Set(phonetic),
+            None => Setting::Reset,
+        },
+        per_attribute: Setting::Set(attribute_budgets.into_iter().map(Into::into).collect()),
+        per_locale: Setting::Set(locale_budgets.into_iter().map(Into::into).collect()),
     };
 
     let faceting = FacetingSettings {
@@ -861,6 +1348,7 @@ pub fn settings(
                 .map(|(name, sort)| (name, sort.into()))
                 .collect(),
         ),
+        hierarchies: Setting::Set(index.facet_hierarchies(rtxn)?),
     };
 
     let pagination = PaginationSettings {
@@ -889,6 +1377,12 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let rules = index.search_rules(rtxn)?;
+
+    let query_rules = index.query_rules(rtxn)?;
+
+    let synonym_sets = index.synonym_sets(rtxn)?;
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -905,6 +1399,9 @@ pub fn settings(
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
         non_separator_tokens: Setting::Set(non_separator_tokens),
+        identifier_attributes: Setting::Set(identifier_attributes),
+        infix_attributes: Setting::Set(infix_attributes),
+        date_attributes: Setting::Set(date_attributes),
         separator_tokens: Setting::Set(separator_tokens),
         dictionary: Setting::Set(dictionary),
         distinct_attribute: match distinct_field {
@@ -927,6 +1424,16 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        rules: Setting::Set(rules),
+        query_rules: Setting::Set(query_rules),
+        synonym_sets: Setting::Set(synonym_sets.into_iter().map(Into::into).collect()),
+        synonym_sets_to_add: None,
+        stop_words_to_add: None,
+        stop_words_to_remove: None,
+        synonyms_to_add: None,
+        synonyms_to_remove: None,
+        dictionary_to_add: None,
+        dictionary_to_remove: None,
         _kind: PhantomData,
     };
 
@@ -958,6 +1465,10 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score computed from the numeric attributes of the documents,
+    /// e.g. `score: log1p(popularity) * 0.3 + recency_decay(published_at, 30d)`.
+    #[schema(value_type = String)]
+    Expression(RankingExpression),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1014,6 +1525,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Expression(x) => RankingRuleView::Expression(x),
         }
     }
 }
@@ -1028,6 +1540,7 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Expression(x) => Criterion::Expression(x),
         }
     }
 }
@@ -1126,6 +1639,37 @@ impl From<PrefixSearchSettings> for PrefixSearch {
     }
 }
 
+/// A set of synonyms, the `input` must only be defined by the `oneWay` sets.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsSynonymSets>, rename_all = camelCase, deny_unknown_fields)]
+pub struct SynonymSetView {
+    #[serde(default)]
+    #[deserr(default)]
+    pub kind: SynonymSetKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub input: Vec<String>,
+    pub synonyms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub locales: Vec<Locale>,
+}
+
+impl From<SynonymSet> for SynonymSetView {
+    fn from(set: SynonymSet) -> Self {
+        let SynonymSet { kind, input, synonyms, locales } = set;
+        Self { kind, input, synonyms, locales: locales.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl From<SynonymSetView> for SynonymSet {
+    fn from(view: SynonymSetView) -> Self {
+        let SynonymSetView { kind, input, synonyms, locales } = view;
+        Self { kind, input, synonyms, locales: locales.into_iter().map(Into::into).collect() }
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
@@ -1141,6 +1685,9 @@ pub(crate) mod test {
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
+            identifier_attributes: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
@@ -1154,6 +1701,16 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            rules: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
+            synonym_sets_to_add: None,
+            stop_words_to_add: None,
+            stop_words_to_remove: None,
+            synonyms_to_add: None,
+            synonyms_to_remove: None,
+            dictionary_to_add: None,
+            dictionary_to_remove: None,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1172,6 +1729,9 @@ pub(crate) mod test {
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
+            identifier_attributes: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
             synonyms: Setting::NotSet,
@@ -1185,6 +1745,16 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            rules: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            synonym_sets: Setting::NotSet,
+            synonym_sets_to_add: None,
+            stop_words_to_add: None,
+            stop_words_to_remove: None,
+            synonyms_to_add: None,
+            synonyms_to_remove: None,
+            dictionary_to_add: None,
+            dictionary_to_remove: None,
             _kind: PhantomData::<Unchecked>,
         };
 
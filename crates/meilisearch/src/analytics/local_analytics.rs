@@ -0,0 +1,922 @@
+//! The search analytics stored in the database for the operator of the instance,
+//! unlike the anonymous analytics sent to segment.
+//!
+//! They are only recorded when Meilisearch is launched with `--experimental-search-analytics`.
+//! The searches and events are sent through a channel to a thread writing them by batches
+//! in a dedicated LMDB environment, so that they never slow the searches down.
+//! The queries of each index are kept ordered by their counts, so that the top queries
+//! are read without going through the statistics of all the queries.
+
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::ffi::CStr;
+use std::path::Path;
+use std::sync::Arc;
+
+use deserr::Deserr;
+use meilisearch_auth::AuthFilter;
+use meilisearch_types::heed::types::{Bytes, SerdeJson, Str, Unit};
+use meilisearch_types::heed::{
+    self, BoxedError, BytesDecode, BytesEncode, Database, Env, EnvOpenOptions, RoTxn, RwTxn,
+    WithoutTls,
+};
+use meilisearch_types::milli::BEU64;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use tokio::sync::{mpsc, oneshot};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error::MeilisearchHttpError;
+use crate::search::{FederatedSearchResult, HitsInfo, SearchResult};
+use crate::Opt;
+
+/// The number of searches waiting to be written before new ones are dropped.
+const CHANNEL_SIZE: usize = 10_000;
+/// The maximum number of searches and events written in a single transaction.
+const MAX_BATCH_SIZE: usize = 1_000;
+/// The number of searches and events kept, the oldest ones are forgotten but not their statistics.
+const MAX_LOGGED_SEARCHES: u64 = 1_000_000;
+const MAX_LOGGED_EVENTS: u64 = 1_000_000;
+/// The number of queries whose statistics are kept, the least recently searched ones are forgotten.
+const MAX_QUERY_STATS: u64 = 100_000;
+/// The number of queries of each index kept ordered by their counts.
+const MAX_RANKED_QUERIES: u64 = 1_000;
+/// The maximum size of the keys, like in milli. The queries are truncated so that their
+/// keys in the rankings, their longest keys, fit in it.
+const MAX_LMDB_KEY_LENGTH: usize = 500;
+
+mod db_name {
+    pub const SEARCHES: &str = "searches";
+    pub const QUERY_UIDS: &str = "query-uids";
+    pub const QUERIES: &str = "queries";
+    pub const RECENT_QUERIES: &str = "recent-queries";
+    pub const TOP_QUERIES: &str = "top-queries";
+    pub const TOP_QUERIES_LENGTHS: &str = "top-queries-lengths";
+    pub const NO_RESULTS_QUERIES: &str = "no-results-queries";
+    pub const NO_RESULTS_QUERIES_LENGTHS: &str = "no-results-queries-lengths";
+    pub const EVENTS: &str = "events";
+}
+
+/// A search made on an index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoggedSearch {
+    query_uid: Uuid,
+    index_uid: String,
+    query: String,
+    hits: u64,
+    processing_time_ms: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    searched_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum AnalyticsEventType {
+    /// A document of the search results was clicked
+    Click,
+    /// A document of the search results led to a conversion, e.g. a purchase
+    Conversion,
+}
+
+/// A click or a conversion following a search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedEvent {
+    pub event_type: AnalyticsEventType,
+    pub event_name: Option<String>,
+    pub index_uid: String,
+    pub query_uid: Uuid,
+    pub object_id: String,
+    pub position: Option<usize>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub received_at: OffsetDateTime,
+}
+
+/// The statistics of a query on an index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryStats {
+    index_uid: String,
+    query: String,
+    search_count: u64,
+    no_results_count: u64,
+    total_hits: u64,
+    total_processing_time_ms: u64,
+    click_count: u64,
+    conversion_count: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    last_searched_at: OffsetDateTime,
+    /// The id of the last search of the query, its key in the recent queries.
+    last_search_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct QueryAnalytics {
+    pub index_uid: String,
+    /// The query, lowercased and with its whitespaces collapsed
+    pub query: String,
+    pub search_count: u64,
+    /// The number of searches of the query that returned no documents
+    pub no_results_count: u64,
+    pub average_hits: u64,
+    pub average_processing_time_ms: u64,
+    pub click_count: u64,
+    pub conversion_count: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schema(value_type = String)]
+    pub last_searched_at: OffsetDateTime,
+}
+
+impl From<QueryStats> for QueryAnalytics {
+    fn from(stats: QueryStats) -> Self {
+        let QueryStats {
+            index_uid,
+            query,
+            search_count,
+            no_results_count,
+            total_hits,
+            total_processing_time_ms,
+            click_count,
+            conversion_count,
+            last_searched_at,
+            last_search_id: _,
+        } = stats;
+
+        let search_count_or_one = search_count.max(1);
+        QueryAnalytics {
+            index_uid,
+            query,
+            search_count,
+            no_results_count,
+            average_hits: total_hits / search_count_or_one,
+            average_processing_time_ms: total_processing_time_ms / search_count_or_one,
+            click_count,
+            conversion_count,
+            last_searched_at,
+        }
+    }
+}
+
+enum Message {
+    Search(LoggedSearch),
+    Event(LoggedEvent),
+    /// Answered once all the messages sent before it are written.
+    Flush(oneshot::Sender<()>),
+}
+
+/// Encodes the `(index_uid, count, query)` keys of the rankings so that the queries of an index
+/// are ordered by decreasing count, index uids can't contain a `\0`.
+struct RankingKeyCodec;
+
+impl<'a> BytesEncode<'a> for RankingKeyCodec {
+    type EItem = (&'a str, u64, &'a str);
+
+    fn bytes_encode((index_uid, count, query): &Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut bytes = ranking_prefix(index_uid);
+        bytes.extend_from_slice(&(u64::MAX - count).to_be_bytes());
+        bytes.extend_from_slice(query.as_bytes());
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+impl<'a> BytesDecode<'a> for RankingKeyCodec {
+    type DItem = (&'a str, u64, &'a str);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let index_uid = CStr::from_bytes_until_nul(bytes)?.to_str()?;
+        // skip the `\0` byte following the index uid
+        let (count, query) = bytes[index_uid.len() + 1..]
+            .split_first_chunk::<8>()
+            .ok_or("The key of a ranked query is too short")?;
+        Ok((index_uid, u64::MAX - u64::from_be_bytes(*count), std::str::from_utf8(query)?))
+    }
+}
+
+fn ranking_prefix(index_uid: &str) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(index_uid.len() + 1);
+    prefix.extend_from_slice(index_uid.as_bytes());
+    prefix.push(0);
+    prefix
+}
+
+/// The queries of each index ordered by one of their counts, the greatest first.
+///
+/// Only the `max_len` greatest counts of each index are kept, [`MAX_RANKED_QUERIES`] outside
+/// of the tests, a query enters the ranking of a full index when its count becomes greater
+/// than the last one's.
+struct Ranking {
+    queries: Database<RankingKeyCodec, Unit>,
+    /// The number of queries ranked for each index.
+    lengths: Database<Str, BEU64>,
+    max_len: u64,
+}
+
+impl Ranking {
+    fn create(
+        env: &Env<WithoutTls>,
+        wtxn: &mut RwTxn,
+        queries_name: &str,
+        lengths_name: &str,
+        max_len: u64,
+    ) -> heed::Result<Self> {
+        let queries = env.create_database(wtxn, Some(queries_name))?;
+        let lengths = env.create_database(wtxn, Some(lengths_name))?;
+        Ok(Self { queries, lengths, max_len })
+    }
+
+    /// Moves the query from its previous count to its new one, a zero count is not ranked.
+    fn update(
+        &self,
+        wtxn: &mut RwTxn,
+        index_uid: &str,
+        query: &str,
+        previous_count: u64,
+        count: u64,
+    ) -> heed::Result<()> {
+        if previous_count == count {
+            return Ok(());
+        }
+        let was_ranked = self.remove(wtxn, index_uid, query, previous_count)?;
+        if count == 0 {
+            return Ok(());
+        }
+
+        let mut len = self.lengths.get(wtxn, index_uid)?.unwrap_or(0);
+        if !was_ranked && len >= self.max_len {
+            let last = self
+                .queries
+                .remap_key_type::<Bytes>()
+                .rev_prefix_iter(wtxn, &ranking_prefix(index_uid))?
+                .remap_key_type::<RankingKeyCodec>()
+                .next()
+                .transpose()?;
+            let Some(((_, last_count, last_query), ())) = last else { return Ok(()) };
+            if count <= last_count {
+                return Ok(());
+            }
+            let last_query = last_query.to_owned();
+            self.queries.delete(wtxn, &(index_uid, last_count, &last_query))?;
+            len -= 1;
+        }
+
+        self.queries.put(wtxn, &(index_uid, count, query), &())?;
+        self.lengths.put(wtxn, index_uid, &(len + 1))
+    }
+
+    /// Removes the query from the ranking, returns whether it was ranked.
+    fn remove(
+        &self,
+        wtxn: &mut RwTxn,
+        index_uid: &str,
+        query: &str,
+        count: u64,
+    ) -> heed::Result<bool> {
+        if count == 0 || !self.queries.delete(wtxn, &(index_uid, count, query))? {
+            return Ok(false);
+        }
+        let len = self.lengths.get(wtxn, index_uid)?.unwrap_or(0).saturating_sub(1);
+        if len == 0 {
+            self.lengths.delete(wtxn, index_uid)?;
+        } else {
+            self.lengths.put(wtxn, index_uid, &len)?;
+        }
+        Ok(true)
+    }
+
+    /// Returns the `limit` greatest counts of the authorized indexes along with their
+    /// index uids and queries, the greatest first.
+    fn top(
+        &self,
+        rtxn: &RoTxn,
+        index_uid: Option<&str>,
+        filters: &AuthFilter,
+        limit: usize,
+    ) -> heed::Result<Vec<(u64, String, String)>> {
+        let index_uids = match index_uid {
+            Some(index_uid) => vec![index_uid.to_string()],
+            None => self
+                .lengths
+                .iter(rtxn)?
+                .map(|result| result.map(|(index_uid, _)| index_uid.to_string()))
+                .collect::<heed::Result<_>>()?,
+        };
+
+        let mut top = Vec::new();
+        for index_uid in
+            index_uids.iter().filter(|index_uid| filters.is_index_authorized(index_uid))
+        {
+            let iter = self
+                .queries
+                .remap_key_type::<Bytes>()
+                .prefix_iter(rtxn, &ranking_prefix(index_uid))?
+                .remap_key_type::<RankingKeyCodec>();
+            for result in iter.take(limit) {
+                let ((index_uid, count, query), ()) = result?;
+                top.push((count, index_uid.to_string(), query.to_string()));
+            }
+        }
+        top.sort_by(
+            |(left_count, left_index_uid, left_query),
+             (right_count, right_index_uid, right_query)| {
+                (Reverse(left_count), left_index_uid, left_query).cmp(&(
+                    Reverse(right_count),
+                    right_index_uid,
+                    right_query,
+                ))
+            },
+        );
+        top.truncate(limit);
+        Ok(top)
+    }
+}
+
+struct AnalyticsStore {
+    env: Env<WithoutTls>,
+    searches: Database<BEU64, SerdeJson<LoggedSearch>>,
+    query_uids: Database<Bytes, BEU64>,
+    queries: Database<Str, SerdeJson<QueryStats>>,
+    /// The key of the statistics of each query by the id of its last search.
+    recent_queries: Database<BEU64, Str>,
+    top_queries: Ranking,
+    no_results_queries: Ranking,
+    events: Database<BEU64, SerdeJson<LoggedEvent>>,
+}
+
+impl AnalyticsStore {
+    fn open(path: &Path, size: usize) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(path)?;
+        let options = EnvOpenOptions::new();
+        let mut options = options.read_txn_without_tls();
+        options.map_size(size);
+        options.max_dbs(9);
+        let env = unsafe { options.open(path) }?;
+
+        let mut wtxn = env.write_txn()?;
+        let searches = env.create_database(&mut wtxn, Some(db_name::SEARCHES))?;
+        let query_uids = env.create_database(&mut wtxn, Some(db_name::QUERY_UIDS))?;
+        let queries = env.create_database(&mut wtxn, Some(db_name::QUERIES))?;
+        let recent_queries = env.create_database(&mut wtxn, Some(db_name::RECENT_QUERIES))?;
+        let top_queries = Ranking::create(
+            &env,
+            &mut wtxn,
+            db_name::TOP_QUERIES,
+            db_name::TOP_QUERIES_LENGTHS,
+            MAX_RANKED_QUERIES,
+        )?;
+        let no_results_queries = Ranking::create(
+            &env,
+            &mut wtxn,
+            db_name::NO_RESULTS_QUERIES,
+            db_name::NO_RESULTS_QUERIES_LENGTHS,
+            MAX_RANKED_QUERIES,
+        )?;
+        let events = env.create_database(&mut wtxn, Some(db_name::EVENTS))?;
+        wtxn.commit()?;
+
+        Ok(Self {
+            env,
+            searches,
+            query_uids,
+            queries,
+            recent_queries,
+            top_queries,
+            no_results_queries,
+            events,
+        })
+    }
+
+    /// Writes the searches and events of the messages in a single transaction.
+    ///
+    /// When the batch can't be written its messages are written one by one, so that a message
+    /// that can't be written is skipped without losing the other ones.
+    fn write(&self, messages: &[Message]) -> heed::Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        let error = match messages.iter().try_for_each(|message| self.put(&mut wtxn, message)) {
+            Ok(()) => match wtxn.commit() {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            },
+            Err(error) => {
+                wtxn.abort();
+                error
+            }
+        };
+        tracing::warn!(%error, "Could not write a batch of search analytics, writing its messages one by one");
+
+        for message in messages {
+            let mut wtxn = self.env.write_txn()?;
+            let result = self.put(&mut wtxn, message);
+            if let Err(error) = result.and_then(|()| wtxn.commit()) {
+                tracing::error!(%error, "Could not write a search analytics message, skipping it");
+            }
+        }
+        Ok(())
+    }
+
+    fn put(&self, wtxn: &mut RwTxn, message: &Message) -> heed::Result<()> {
+        match message {
+            Message::Search(search) => self.put_search(wtxn, search),
+            Message::Event(event) => self.put_event(wtxn, event),
+            Message::Flush(_) => Ok(()),
+        }
+    }
+
+    fn put_search(&self, wtxn: &mut RwTxn, search: &LoggedSearch) -> heed::Result<()> {
+        let id = self.searches.last(wtxn)?.map_or(0, |(id, _)| id + 1);
+        let key = query_stats_key(&search.index_uid, &search.query);
+        let mut stats = match self.queries.get(wtxn, &key)? {
+            Some(stats) => {
+                self.recent_queries.delete(wtxn, &stats.last_search_id)?;
+                stats
+            }
+            None => QueryStats {
+                index_uid: search.index_uid.clone(),
+                query: search.query.clone(),
+                search_count: 0,
+                no_results_count: 0,
+                total_hits: 0,
+                total_processing_time_ms: 0,
+                click_count: 0,
+                conversion_count: 0,
+                last_searched_at: search.searched_at,
+                last_search_id: id,
+            },
+        };
+        let previous_search_count = stats.search_count;
+        let previous_no_results_count = stats.no_results_count;
+        stats.search_count = stats.search_count.saturating_add(1);
+        if search.hits == 0 {
+            stats.no_results_count = stats.no_results_count.saturating_add(1);
+        }
+        stats.total_hits = stats.total_hits.saturating_add(search.hits);
+        stats.total_processing_time_ms =
+            stats.total_processing_time_ms.saturating_add(search.processing_time_ms);
+        stats.last_searched_at = search.searched_at;
+        stats.last_search_id = id;
+        self.top_queries.update(
+            wtxn,
+            &stats.index_uid,
+            &stats.query,
+            previous_search_count,
+            stats.search_count,
+        )?;
+        self.no_results_queries.update(
+            wtxn,
+            &stats.index_uid,
+            &stats.query,
+            previous_no_results_count,
+            stats.no_results_count,
+        )?;
+        self.queries.put(wtxn, &key, &stats)?;
+        self.recent_queries.put(wtxn, &id, &key)?;
+
+        self.query_uids.put(wtxn, search.query_uid.as_bytes(), &id)?;
+        self.searches.put(wtxn, &id, search)?;
+
+        while self.searches.len(wtxn)? > MAX_LOGGED_SEARCHES {
+            let Some((oldest_id, oldest)) = self.searches.first(wtxn)? else { break };
+            self.query_uids.delete(wtxn, oldest.query_uid.as_bytes())?;
+            self.searches.delete(wtxn, &oldest_id)?;
+        }
+
+        while self.queries.len(wtxn)? > MAX_QUERY_STATS {
+            let Some((oldest_id, oldest_key)) = self.recent_queries.first(wtxn)? else { break };
+            let oldest_key = oldest_key.to_owned();
+            self.recent_queries.delete(wtxn, &oldest_id)?;
+            if let Some(oldest) = self.queries.get(wtxn, &oldest_key)? {
+                self.top_queries.remove(
+                    wtxn,
+                    &oldest.index_uid,
+                    &oldest.query,
+                    oldest.search_count,
+                )?;
+                self.no_results_queries.remove(
+                    wtxn,
+                    &oldest.index_uid,
+                    &oldest.query,
+                    oldest.no_results_count,
+                )?;
+                self.queries.delete(wtxn, &oldest_key)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn put_event(&self, wtxn: &mut RwTxn, event: &LoggedEvent) -> heed::Result<()> {
+        // the event is attributed to the query of the search it follows
+        let search_id = self.query_uids.get(wtxn, event.query_uid.as_bytes())?;
+        let search = match search_id {
+            Some(search_id) => self.searches.get(wtxn, &search_id)?,
+            None => None,
+        };
+        if let Some(search) = search.filter(|search| search.index_uid == event.index_uid) {
+            let key = query_stats_key(&search.index_uid, &search.query);
+            if let Some(mut stats) = self.queries.get(wtxn, &key)? {
+                match event.event_type {
+                    AnalyticsEventType::Click => {
+                        stats.click_count = stats.click_count.saturating_add(1)
+                    }
+                    AnalyticsEventType::Conversion => {
+                        stats.conversion_count = stats.conversion_count.saturating_add(1)
+                    }
+                }
+                self.queries.put(wtxn, &key, &stats)?;
+            }
+        }
+
+        let id = self.events.last(wtxn)?.map_or(0, |(id, _)| id + 1);
+        self.events.put(wtxn, &id, event)?;
+
+        while self.events.len(wtxn)? > MAX_LOGGED_EVENTS {
+            let Some((oldest_id, _)) = self.events.first(wtxn)? else { break };
+            self.events.delete(wtxn, &oldest_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the statistics of the `limit` first queries of the ranking.
+    fn query_stats(
+        &self,
+        ranking: &Ranking,
+        index_uid: Option<&str>,
+        filters: &AuthFilter,
+        limit: usize,
+    ) -> heed::Result<Vec<QueryStats>> {
+        let rtxn = self.env.read_txn()?;
+        let mut stats = Vec::new();
+        for (_, index_uid, query) in ranking.top(&rtxn, index_uid, filters, limit)? {
+            if let Some(query_stats) =
+                self.queries.get(&rtxn, &query_stats_key(&index_uid, &query))?
+            {
+                stats.push(query_stats);
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// The key of the statistics of a query, index uids can't contain a `\0`.
+fn query_stats_key(index_uid: &str, query: &str) -> String {
+    format!("{index_uid}\0{query}")
+}
+
+/// Lowercases the query and collapses its whitespaces so that the same query typed
+/// differently is counted once.
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+/// Truncates the query on a char boundary so that its key in the rankings of the index,
+/// which is its longest key, fits in a LMDB key.
+fn truncate_query(index_uid: &str, query: &mut String) {
+    let max_len = MAX_LMDB_KEY_LENGTH.saturating_sub(index_uid.len() + 1 + size_of::<u64>());
+    if query.len() > max_len {
+        let mut len = max_len;
+        while !query.is_char_boundary(len) {
+            len -= 1;
+        }
+        query.truncate(len);
+        query.truncate(query.trim_end().len());
+    }
+}
+
+fn run(store: Arc<AnalyticsStore>, mut receiver: mpsc::Receiver<Message>) {
+    while let Some(message) = receiver.blocking_recv() {
+        let mut messages = vec![message];
+        while messages.len() < MAX_BATCH_SIZE {
+            match receiver.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(_) => break,
+            }
+        }
+
+        match store.write(&messages) {
+            Ok(()) => {
+                for message in messages {
+                    if let Message::Flush(flush) = message {
+                        let _ = flush.send(());
+                    }
+                }
+            }
+            // the flushes are dropped, which reports the error to the routes waiting for them
+            Err(error) => tracing::error!(%error, "Could not write the search analytics"),
+        }
+    }
+}
+
+struct Inner {
+    store: Arc<AnalyticsStore>,
+    sender: mpsc::Sender<Message>,
+}
+
+pub struct LocalAnalytics {
+    inner: Option<Inner>,
+}
+
+impl LocalAnalytics {
+    pub fn new(opt: &Opt) -> anyhow::Result<Self> {
+        if !opt.experimental_search_analytics {
+            return Ok(Self::disabled());
+        }
+
+        let store = Arc::new(AnalyticsStore::open(
+            &opt.db_path.join("analytics"),
+            opt.experimental_search_analytics_store_size.as_u64() as usize,
+        )?);
+        let (sender, receiver) = mpsc::channel(CHANNEL_SIZE);
+        let writer_store = store.clone();
+        std::thread::Builder::new()
+            .name(String::from("search-analytics"))
+            .spawn(move || run(writer_store, receiver))?;
+
+        Ok(Self { inner: Some(Inner { store, sender }) })
+    }
+
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    fn check_enabled(&self, disabled_action: &'static str) -> Result<&Inner, MeilisearchHttpError> {
+        self.inner.as_ref().ok_or(MeilisearchHttpError::SearchAnalyticsNotEnabled(disabled_action))
+    }
+
+    /// Records a search and returns the uid identifying it in the events.
+    ///
+    /// Nothing is recorded when the search analytics are disabled or when the query is empty.
+    pub fn record_search(&self, index_uid: &str, result: &SearchResult) -> Option<Uuid> {
+        let hits = match result.hits_info {
+            HitsInfo::Pagination { total_hits, .. } => total_hits,
+            HitsInfo::OffsetLimit { estimated_total_hits, .. } => estimated_total_hits,
+        };
+        self.record(index_uid, &result.query, hits as u64, result.processing_time_ms)
+    }
+
+    /// Records each query of a federated search with its own number of hits.
+    ///
+    /// The queries are given with their position in the request and the uid of their index,
+    /// the ones sent to remote instances must be left out. They can't be identified in the
+    /// events as they share their hits.
+    pub fn record_federated_search<'a>(
+        &self,
+        queries: impl IntoIterator<Item = (usize, &'a str, &'a str)>,
+        result: &FederatedSearchResult,
+    ) {
+        for (position, index_uid, query) in queries {
+            if let Some(&hits) = result.hits_by_query.get(&position) {
+                self.record(index_uid, query, hits, result.processing_time_ms);
+            }
+        }
+    }
+
+    fn record(
+        &self,
+        index_uid: &str,
+        query: &str,
+        hits: u64,
+        processing_time_ms: u128,
+    ) -> Option<Uuid> {
+        let inner = self.inner.as_ref()?;
+        let mut query = normalize_query(query);
+        truncate_query(index_uid, &mut query);
+        if query.is_empty() {
+            return None;
+        }
+
+        let query_uid = Uuid::new_v4();
+        let search = LoggedSearch {
+            query_uid,
+            index_uid: index_uid.to_string(),
+            query,
+            hits,
+            processing_time_ms: processing_time_ms as u64,
+            searched_at: OffsetDateTime::now_utc(),
+        };
+
+        // like the anonymous analytics, a search is dropped rather than waiting for the writer
+        inner.sender.try_send(Message::Search(search)).ok()?;
+        Some(query_uid)
+    }
+
+    pub async fn record_event(&self, event: LoggedEvent) -> Result<(), MeilisearchHttpError> {
+        let inner = self.check_enabled("Sending events to the `/events` route")?;
+        inner
+            .sender
+            .send(Message::Event(event))
+            .await
+            .map_err(|_| MeilisearchHttpError::SearchAnalyticsStoreIsDown)
+    }
+
+    /// Returns the `limit` most searched queries of the authorized indexes, the most searched first.
+    pub async fn top_queries(
+        &self,
+        index_uid: Option<&str>,
+        filters: &AuthFilter,
+        limit: usize,
+    ) -> Result<Vec<QueryAnalytics>, MeilisearchHttpError> {
+        let inner = self.check_enabled("Getting the `/analytics/top-queries` route")?;
+        flush(inner).await?;
+        let stats = inner.store.query_stats(&inner.store.top_queries, index_uid, filters, limit)?;
+        Ok(stats.into_iter().map(QueryAnalytics::from).collect())
+    }
+
+    /// Returns the `limit` queries of the authorized indexes that returned no documents
+    /// the most often, the most often first.
+    pub async fn no_results_queries(
+        &self,
+        index_uid: Option<&str>,
+        filters: &AuthFilter,
+        limit: usize,
+    ) -> Result<Vec<QueryAnalytics>, MeilisearchHttpError> {
+        let inner = self.check_enabled("Getting the `/analytics/no-results` route")?;
+        flush(inner).await?;
+        let stats =
+            inner.store.query_stats(&inner.store.no_results_queries, index_uid, filters, limit)?;
+        Ok(stats.into_iter().map(QueryAnalytics::from).collect())
+    }
+}
+
+/// Waits for all the searches and events received to be written.
+async fn flush(inner: &Inner) -> Result<(), MeilisearchHttpError> {
+    let (sender, receiver) = oneshot::channel();
+    inner
+        .sender
+        .send(Message::Flush(sender))
+        .await
+        .map_err(|_| MeilisearchHttpError::SearchAnalyticsStoreIsDown)?;
+    receiver.await.map_err(|_| MeilisearchHttpError::SearchAnalyticsStoreIsDown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(index_uid: &str, query: &str, hits: u64) -> LoggedSearch {
+        LoggedSearch {
+            query_uid: Uuid::new_v4(),
+            index_uid: index_uid.to_string(),
+            query: query.to_string(),
+            hits,
+            processing_time_ms: 1,
+            searched_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    fn click(index_uid: &str, query_uid: Uuid) -> LoggedEvent {
+        LoggedEvent {
+            event_type: AnalyticsEventType::Click,
+            event_name: None,
+            index_uid: index_uid.to_string(),
+            query_uid,
+            object_id: String::from("1"),
+            position: Some(0),
+            received_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    fn top(ranking: &Ranking, rtxn: &RoTxn, index_uid: Option<&str>) -> Vec<(u64, String, String)> {
+        ranking.top(rtxn, index_uid, &AuthFilter::default(), 10).unwrap()
+    }
+
+    fn stats(store: &AnalyticsStore, index_uid: &str, query: &str) -> Option<QueryStats> {
+        let rtxn = store.env.read_txn().unwrap();
+        store.queries.get(&rtxn, &query_stats_key(index_uid, query)).unwrap()
+    }
+
+    #[test]
+    fn ranking_key_codec() {
+        let bytes = RankingKeyCodec::bytes_encode(&("movies", 42, "star wars")).unwrap();
+        assert_eq!(RankingKeyCodec::bytes_decode(&bytes).unwrap(), ("movies", 42, "star wars"));
+
+        let bytes = RankingKeyCodec::bytes_encode(&("movies", 0, "")).unwrap();
+        assert_eq!(RankingKeyCodec::bytes_decode(&bytes).unwrap(), ("movies", 0, ""));
+
+        // the greatest counts come first
+        let greater = RankingKeyCodec::bytes_encode(&("movies", 43, "b")).unwrap();
+        let lower = RankingKeyCodec::bytes_encode(&("movies", 42, "a")).unwrap();
+        assert!(greater < lower);
+
+        assert!(RankingKeyCodec::bytes_decode(b"movies\0\0\0").is_err());
+    }
+
+    #[test]
+    fn ranking_eviction_and_removal() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut options = EnvOpenOptions::new().read_txn_without_tls();
+        options.map_size(10 * 1024 * 1024);
+        options.max_dbs(2);
+        let env = unsafe { options.open(dir.path()) }.unwrap();
+        let mut wtxn = env.write_txn().unwrap();
+        let ranking = Ranking::create(&env, &mut wtxn, "queries", "lengths", 2).unwrap();
+
+        ranking.update(&mut wtxn, "movies", "alien", 0, 1).unwrap();
+        ranking.update(&mut wtxn, "movies", "batman", 0, 3).unwrap();
+        // the index is full, the least searched query is evicted
+        ranking.update(&mut wtxn, "movies", "cars", 0, 2).unwrap();
+        // a query searched as much as the last one doesn't enter the ranking
+        ranking.update(&mut wtxn, "movies", "dune", 0, 2).unwrap();
+        // a ranked query is moved instead of evicting another one
+        ranking.update(&mut wtxn, "movies", "cars", 2, 4).unwrap();
+        ranking.update(&mut wtxn, "books", "alien", 0, 1).unwrap();
+
+        assert_eq!(
+            top(&ranking, &wtxn, None),
+            [
+                (4, String::from("movies"), String::from("cars")),
+                (3, String::from("movies"), String::from("batman")),
+                (1, String::from("books"), String::from("alien")),
+            ]
+        );
+        assert_eq!(ranking.lengths.get(&wtxn, "movies").unwrap(), Some(2));
+
+        // removing a query that isn't ranked doesn't change the lengths
+        assert!(!ranking.remove(&mut wtxn, "movies", "alien", 1).unwrap());
+        assert!(!ranking.remove(&mut wtxn, "movies", "cars", 3).unwrap());
+        assert_eq!(ranking.lengths.get(&wtxn, "movies").unwrap(), Some(2));
+
+        assert!(ranking.remove(&mut wtxn, "movies", "cars", 4).unwrap());
+        assert_eq!(ranking.lengths.get(&wtxn, "movies").unwrap(), Some(1));
+        // the length of an empty ranking is removed
+        assert!(ranking.remove(&mut wtxn, "movies", "batman", 3).unwrap());
+        assert_eq!(ranking.lengths.get(&wtxn, "movies").unwrap(), None);
+        assert_eq!(ranking.lengths.get(&wtxn, "books").unwrap(), Some(1));
+
+        // a free spot is taken by the next query
+        ranking.update(&mut wtxn, "movies", "dune", 0, 1).unwrap();
+        assert_eq!(
+            top(&ranking, &wtxn, Some("movies")),
+            [(1, String::from("movies"), String::from("dune"))]
+        );
+    }
+
+    #[test]
+    fn event_of_another_index_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = AnalyticsStore::open(dir.path(), 10 * 1024 * 1024).unwrap();
+
+        let search = search("movies", "star wars", 3);
+        let query_uid = search.query_uid;
+        store
+            .write(&[
+                Message::Search(search),
+                Message::Event(click("books", query_uid)),
+                Message::Event(click("movies", Uuid::new_v4())),
+            ])
+            .unwrap();
+        assert_eq!(stats(&store, "movies", "star wars").unwrap().click_count, 0);
+
+        store.write(&[Message::Event(click("movies", query_uid))]).unwrap();
+        assert_eq!(stats(&store, "movies", "star wars").unwrap().click_count, 1);
+    }
+
+    #[test]
+    fn oversized_query_does_not_lose_its_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = AnalyticsStore::open(dir.path(), 10 * 1024 * 1024).unwrap();
+
+        let oversized = "a".repeat(1_000);
+        store
+            .write(&[
+                Message::Search(search("movies", &oversized, 1)),
+                Message::Search(search("movies", "star wars", 3)),
+            ])
+            .unwrap();
+        let rtxn = store.env.read_txn().unwrap();
+        assert_eq!(
+            top(&store.top_queries, &rtxn, None),
+            [(1, String::from("movies"), String::from("star wars"))]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn oversized_query_is_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(AnalyticsStore::open(dir.path(), 10 * 1024 * 1024).unwrap());
+        let (sender, receiver) = mpsc::channel(CHANNEL_SIZE);
+        let writer_store = store.clone();
+        std::thread::spawn(move || run(writer_store, receiver));
+        let analytics = LocalAnalytics { inner: Some(Inner { store, sender }) };
+
+        let index_uid = "movies";
+        // the multi-bytes chars can't be split
+        let oversized = "é".repeat(1_000);
+        assert!(analytics.record(index_uid, &oversized, 1, 1).is_some());
+        assert!(analytics.record(index_uid, "Star  Wars", 3, 1).is_some());
+
+        let top = analytics.top_queries(None, &AuthFilter::default(), 10).await.unwrap();
+        assert_eq!(top.len(), 2);
+        let truncated = top.iter().find(|query| query.query.starts_with('é')).unwrap();
+        assert!(
+            index_uid.len() + 1 + size_of::<u64>() + truncated.query.len() <= MAX_LMDB_KEY_LENGTH
+        );
+        assert!(oversized.starts_with(&truncated.query));
+        let star_wars = top.iter().find(|query| query.query == "star wars").unwrap();
+        assert_eq!(star_wars.search_count, 1);
+    }
+}
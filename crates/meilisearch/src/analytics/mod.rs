@@ -1,3 +1,4 @@
+pub mod local_analytics;
 pub mod segment_analytics;
 
 use std::fs;
@@ -200,6 +200,8 @@ struct Infos {
     experimental_get_task_documents_route: bool,
     experimental_composite_embedders: bool,
     experimental_embedding_cache_entries: usize,
+    experimental_search_analytics: bool,
+    experimental_search_analytics_store_size: u64,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -248,6 +250,8 @@ impl Infos {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_search_analytics,
+            experimental_search_analytics_store_size,
             http_addr,
             master_key: _,
             env,
@@ -315,6 +319,9 @@ impl Infos {
             experimental_get_task_documents_route: get_task_documents_route,
             experimental_composite_embedders: composite_embedders,
             experimental_embedding_cache_entries,
+            experimental_search_analytics,
+            experimental_search_analytics_store_size: experimental_search_analytics_store_size
+                .into(),
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
@@ -35,6 +35,12 @@ pub enum MeilisearchHttpError {
     PaginationInFederatedQuery(usize, &'static str),
     #[error("Inside `.queries[{0}]`: Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{1}: {2:?}` for facets in federated search")]
     FacetsInFederatedQuery(usize, String, Vec<String>),
+    #[error("Inside `.queries[{0}]`: Using `aggregations` is not allowed in federated queries.\n - Hint: remove `aggregations` from query #{0} or remove `federation` from the request")]
+    AggregationsInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using `disjunctiveFacets` is not allowed in federated queries.\n - Hint: remove `disjunctiveFacets` from query #{0} or remove `federation` from the request")]
+    DisjunctiveFacetsInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using `suggestQuery` is not allowed in federated queries.\n - Hint: remove `suggestQuery` from query #{0} or remove `federation` from the request")]
+    SuggestQueryInFederatedQuery(usize),
     #[error("Inconsistent order for values in facet `{facet}`: index `{previous_uid}` orders {previous_facet_order}, but index `{current_uid}` orders {index_facet_order}.\n - Hint: Remove `federation.mergeFacets` or change `faceting.sortFacetValuesBy` to be consistent in settings.")]
     InconsistentFacetOrder {
         facet: String,
@@ -78,6 +84,10 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when `vector` is present.")]
     MissingSearchHybrid,
+    #[error("{0} requires enabling the `search analytics` experimental feature. Start Meilisearch with the `--experimental-search-analytics` option.")]
+    SearchAnalyticsNotEnabled(&'static str),
+    #[error("Internal error: the search analytics store is down.")]
+    SearchAnalyticsStoreIsDown,
 }
 
 impl MeilisearchHttpError {
@@ -111,6 +121,8 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::SearchAnalyticsNotEnabled(_) => Code::FeatureNotEnabled,
+            MeilisearchHttpError::SearchAnalyticsStoreIsDown => Code::Internal,
             MeilisearchHttpError::FederationOptionsInNonFederatedRequest(_) => {
                 Code::InvalidMultiSearchFederationOptions
             }
@@ -118,6 +130,15 @@ impl ErrorCode for MeilisearchHttpError {
                 Code::InvalidMultiSearchQueryPagination
             }
             MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::AggregationsInFederatedQuery(..) => {
+                Code::InvalidMultiSearchQueryAggregations
+            }
+            MeilisearchHttpError::DisjunctiveFacetsInFederatedQuery(..) => {
+                Code::InvalidMultiSearchQueryFacets
+            }
+            MeilisearchHttpError::SuggestQueryInFederatedQuery(..) => {
+                Code::InvalidMultiSearchQuerySuggestQuery
+            }
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
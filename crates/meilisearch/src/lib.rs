@@ -28,6 +28,7 @@ use actix_web::error::JsonPayloadError;
 use actix_web::http::header::{CONTENT_TYPE, USER_AGENT};
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest};
+use analytics::local_analytics::LocalAnalytics;
 use analytics::Analytics;
 use anyhow::bail;
 use error::PayloadError;
@@ -120,6 +121,7 @@ pub type LogStderrType = tracing_subscriber::filter::Filtered<
     SubscriberForSecondLayer,
 >;
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_app(
     index_scheduler: Data<IndexScheduler>,
     auth_controller: Data<AuthController>,
@@ -127,6 +129,7 @@ pub fn create_app(
     opt: Opt,
     logs: (LogRouteHandle, LogStderrHandle),
     analytics: Data<Analytics>,
+    local_analytics: Data<LocalAnalytics>,
     enable_dashboard: bool,
 ) -> actix_web::App<
     impl ServiceFactory<
@@ -147,6 +150,7 @@ pub fn create_app(
                 &opt,
                 logs,
                 analytics.clone(),
+                local_analytics.clone(),
             )
         })
         .configure(routes::configure)
@@ -589,6 +593,7 @@ fn import_dump(
     Ok(index_scheduler_dump.finish()?)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn configure_data(
     config: &mut web::ServiceConfig,
     index_scheduler: Data<IndexScheduler>,
@@ -597,6 +602,7 @@ pub fn configure_data(
     opt: &Opt,
     (logs_route, logs_stderr): (LogRouteHandle, LogStderrHandle),
     analytics: Data<Analytics>,
+    local_analytics: Data<LocalAnalytics>,
 ) {
     let http_payload_size_limit = opt.http_payload_size_limit.as_u64() as usize;
     config
@@ -604,6 +610,7 @@ pub fn configure_data(
         .app_data(auth)
         .app_data(search_queue)
         .app_data(analytics)
+        .app_data(local_analytics)
         .app_data(web::Data::new(logs_route))
         .app_data(web::Data::new(logs_stderr))
         .app_data(web::Data::new(opt.clone()))
@@ -12,6 +12,7 @@ use actix_web::web::Data;
 use actix_web::HttpServer;
 use index_scheduler::IndexScheduler;
 use is_terminal::IsTerminal;
+use meilisearch::analytics::local_analytics::LocalAnalytics;
 use meilisearch::analytics::Analytics;
 use meilisearch::option::LogMode;
 use meilisearch::search_queue::SearchQueue;
@@ -129,12 +130,22 @@ async fn try_main() -> anyhow::Result<()> {
 
     print_launch_resume(&opt, analytics.clone(), config_read_from);
 
+    let local_analytics = LocalAnalytics::new(&opt)?;
+
     tokio::spawn(async move {
         tokio::signal::ctrl_c().await.unwrap();
         std::process::exit(130);
     });
 
-    run_http(index_scheduler, auth_controller, opt, log_handle, Arc::new(analytics)).await?;
+    run_http(
+        index_scheduler,
+        auth_controller,
+        opt,
+        log_handle,
+        Arc::new(analytics),
+        Arc::new(local_analytics),
+    )
+    .await?;
 
     Ok(())
 }
@@ -145,12 +156,14 @@ async fn run_http(
     opt: Opt,
     logs: (LogRouteHandle, LogStderrHandle),
     analytics: Arc<Analytics>,
+    local_analytics: Arc<LocalAnalytics>,
 ) -> anyhow::Result<()> {
     let enable_dashboard = &opt.env == "development";
     let opt_clone = opt.clone();
     let index_scheduler = Data::from(index_scheduler);
     let auth_controller = Data::from(auth_controller);
     let analytics = Data::from(analytics);
+    let local_analytics = Data::from(local_analytics);
     let search_queue = SearchQueue::new(
         opt.experimental_search_queue_size,
         available_parallelism()
@@ -171,6 +184,7 @@ async fn run_http(
             opt.clone(),
             logs.clone(),
             analytics.clone(),
+            local_analytics.clone(),
             enable_dashboard,
         )
     })
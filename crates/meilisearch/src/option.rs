@@ -65,6 +65,9 @@ const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_SIZE";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
+const MEILI_EXPERIMENTAL_SEARCH_ANALYTICS: &str = "MEILI_EXPERIMENTAL_SEARCH_ANALYTICS";
+const MEILI_EXPERIMENTAL_SEARCH_ANALYTICS_STORE_SIZE: &str =
+    "MEILI_EXPERIMENTAL_SEARCH_ANALYTICS_STORE_SIZE";
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
@@ -85,6 +88,7 @@ const DEFAULT_LOG_EVERY_N: usize = 100_000;
 // opened simultaneously.
 pub const INDEX_SIZE: u64 = 2 * 1024 * 1024 * 1024 * 1024; // 2 TiB
 pub const TASK_DB_SIZE: u64 = 20 * 1024 * 1024 * 1024; // 20 GiB
+pub const SEARCH_ANALYTICS_STORE_SIZE: u64 = 1024 * 1024 * 1024; // 1 GiB
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -455,6 +459,21 @@ pub struct Opt {
     #[serde(default = "default_embedding_cache_entries")]
     pub experimental_embedding_cache_entries: usize,
 
+    /// Experimental search analytics feature.
+    ///
+    /// Stores the searches made on the indexes in the `analytics` directory of the database,
+    /// along with the clicks and conversions sent to the `POST /events` route. Enables the
+    /// `GET /analytics/top-queries` and `GET /analytics/no-results` routes.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_ANALYTICS)]
+    #[serde(default)]
+    pub experimental_search_analytics: bool,
+
+    /// Sets the maximum size of the search analytics store. Value must be given in bytes or explicitly
+    /// stating a base unit (for instance: 107374182400, '107.7Gb', or '107374 Mb').
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_ANALYTICS_STORE_SIZE, default_value_t = default_search_analytics_store_size())]
+    #[serde(default = "default_search_analytics_store_size")]
+    pub experimental_search_analytics_store_size: Byte,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -559,6 +578,8 @@ impl Opt {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_search_analytics,
+            experimental_search_analytics_store_size,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
@@ -655,6 +676,14 @@ impl Opt {
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_ANALYTICS,
+            experimental_search_analytics.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SEARCH_ANALYTICS_STORE_SIZE,
+            experimental_search_analytics_store_size.to_string(),
+        );
         indexer_options.export_to_env();
     }
 
@@ -976,6 +1005,10 @@ fn default_embedding_cache_entries() -> usize {
     0
 }
 
+fn default_search_analytics_store_size() -> Byte {
+    Byte::from_u64(SEARCH_ANALYTICS_STORE_SIZE)
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
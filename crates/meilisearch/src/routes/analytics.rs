@@ -0,0 +1,158 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use super::PAGINATION_DEFAULT_LIMIT;
+use crate::analytics::local_analytics::{LocalAnalytics, QueryAnalytics};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_top_queries, get_no_results_queries),
+    tags((
+        name = "Analytics",
+        description = "The analytics routes return the statistics of the searches made on the instance, they require the `search analytics` experimental feature.",
+    )),
+    components(schemas(QueryAnalytics, AnalyticsResults))
+)]
+pub struct AnalyticsApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/top-queries").route(web::get().to(SeqHandler(get_top_queries))))
+        .service(
+            web::resource("/no-results").route(web::get().to(SeqHandler(get_no_results_queries))),
+        );
+}
+
+#[derive(Deserr, Debug, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct AnalyticsQuery {
+    /// Only return the queries made on this index
+    #[param(value_type = Option<String>, example = "movies")]
+    #[deserr(default, error = DeserrQueryParamError<InvalidAnalyticsIndexUid>)]
+    pub index_uid: Option<String>,
+    /// The number of queries to return, only the first 1000 queries of each index are ranked
+    #[param(value_type = Option<usize>, default = 20, example = 10)]
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidAnalyticsLimit>)]
+    pub limit: Param<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalyticsResults {
+    pub results: Vec<QueryAnalytics>,
+}
+
+/// Get top queries
+///
+/// Get the most searched queries, the most searched first.
+#[utoipa::path(
+    get,
+    path = "/top-queries",
+    tag = "Analytics",
+    security(("Bearer" = ["analytics.get", "*"])),
+    params(AnalyticsQuery),
+    responses(
+        (status = 200, description = "The top queries are returned", body = AnalyticsResults, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "indexUid": "movies",
+                        "query": "american",
+                        "searchCount": 12,
+                        "noResultsCount": 0,
+                        "averageHits": 976,
+                        "averageProcessingTimeMs": 3,
+                        "clickCount": 4,
+                        "conversionCount": 1,
+                        "lastSearchedAt": "2024-08-12T10:00:00.000000Z"
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_top_queries(
+    local_analytics: GuardedData<ActionPolicy<{ actions::ANALYTICS_GET }>, Data<LocalAnalytics>>,
+    params: AwebQueryParameter<AnalyticsQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let AnalyticsQuery { index_uid, limit } = params.into_inner();
+
+    let results = local_analytics
+        .top_queries(index_uid.as_deref(), local_analytics.filters(), limit.0)
+        .await?;
+    let results = AnalyticsResults { results };
+
+    debug!(returns = ?results, "Get top queries");
+    Ok(HttpResponse::Ok().json(results))
+}
+
+/// Get queries without results
+///
+/// Get the queries that returned no documents, the most often empty first.
+#[utoipa::path(
+    get,
+    path = "/no-results",
+    tag = "Analytics",
+    security(("Bearer" = ["analytics.get", "*"])),
+    params(AnalyticsQuery),
+    responses(
+        (status = 200, description = "The queries without results are returned", body = AnalyticsResults, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "indexUid": "movies",
+                        "query": "amercian pie",
+                        "searchCount": 3,
+                        "noResultsCount": 3,
+                        "averageHits": 0,
+                        "averageProcessingTimeMs": 1,
+                        "clickCount": 0,
+                        "conversionCount": 0,
+                        "lastSearchedAt": "2024-08-12T10:00:00.000000Z"
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_no_results_queries(
+    local_analytics: GuardedData<ActionPolicy<{ actions::ANALYTICS_GET }>, Data<LocalAnalytics>>,
+    params: AwebQueryParameter<AnalyticsQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let AnalyticsQuery { index_uid, limit } = params.into_inner();
+
+    let results = local_analytics
+        .no_results_queries(index_uid.as_deref(), local_analytics.filters(), limit.0)
+        .await?;
+    let results = AnalyticsResults { results };
+
+    debug!(returns = ?results, "Get queries without results");
+    Ok(HttpResponse::Ok().json(results))
+}
@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use time::OffsetDateTime;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::analytics::local_analytics::{AnalyticsEventType, LocalAnalytics, LoggedEvent};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(paths(send_event), components(schemas(AnalyticsEvent, AnalyticsEventType)))]
+pub struct EventsApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(send_event))));
+}
+
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct AnalyticsEvent {
+    /// Whether a document was clicked or led to a conversion
+    #[deserr(error = DeserrJsonError<InvalidAnalyticsEventType>)]
+    event_type: AnalyticsEventType,
+    /// A name describing the event, e.g. `Product added to cart`
+    #[deserr(default, error = DeserrJsonError<InvalidAnalyticsEventName>)]
+    event_name: Option<String>,
+    /// The index of the search the event follows
+    #[schema(value_type = String, example = "movies")]
+    #[deserr(error = DeserrJsonError<InvalidAnalyticsEventIndexUid>)]
+    index_uid: IndexUid,
+    /// The `queryUid` returned by the search the event follows
+    #[schema(value_type = String, example = "b5fd0d5a-8a1b-4c3f-9e51-a5d8e5b3c1f2")]
+    #[deserr(error = DeserrJsonError<InvalidAnalyticsEventQueryUid>, try_from(&String) = Uuid::from_str -> uuid::Error)]
+    query_uid: Uuid,
+    /// The primary key of the document the event is about
+    #[schema(example = "2770")]
+    #[deserr(error = DeserrJsonError<InvalidAnalyticsEventObjectId>)]
+    object_id: String,
+    /// The position of the document in the search results, starting at 0
+    #[deserr(default, error = DeserrJsonError<InvalidAnalyticsEventPosition>)]
+    position: Option<usize>,
+}
+
+/// Send an event
+///
+/// Record a click on, or a conversion from, a document returned by a search.
+/// The event is attributed to the query of the search identified by its `queryUid`.
+/// Requires the `search analytics` experimental feature.
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "Events",
+    security(("Bearer" = ["search", "*"])),
+    request_body = AnalyticsEvent,
+    responses(
+        (status = 202, description = "The event has been received"),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn send_event(
+    local_analytics: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<LocalAnalytics>>,
+    params: AwebJson<AnalyticsEvent, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let AnalyticsEvent { event_type, event_name, index_uid, query_uid, object_id, position } =
+        params.into_inner();
+    debug!(?event_type, %index_uid, %query_uid, "Send event");
+
+    if !local_analytics.filters().is_index_authorized(&index_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let event = LoggedEvent {
+        event_type,
+        event_name,
+        index_uid: index_uid.into_inner(),
+        query_uid,
+        object_id,
+        position,
+        received_at: OffsetDateTime::now_utc(),
+    };
+    local_analytics.record_event(event).await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
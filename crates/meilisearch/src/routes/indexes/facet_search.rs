@@ -326,12 +326,17 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_ranking_score_details: false,
             filter,
             sort: None,
+            boosts: None,
             distinct: None,
             facets: None,
+            disjunctive_facets: None,
+            aggregations: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
+            query_syntax: Default::default(),
+            suggest_query: false,
             vector,
             attributes_to_search_on,
             hybrid,
@@ -14,6 +14,7 @@ use serde_json::Value;
 use tracing::debug;
 use utoipa::{IntoParams, OpenApi};
 
+use crate::analytics::local_analytics::LocalAnalytics;
 use crate::analytics::Analytics;
 use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
@@ -22,10 +23,10 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_search, FacetQuery, HybridQuery, MatchingStrategy, QuerySyntax,
+    RankingScoreThreshold, RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio,
+    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -107,6 +108,9 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     #[param(value_type = Vec<String>, explode = false)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    #[param(value_type = Vec<String>, explode = false)]
+    disjunctive_facets: Option<CS<String>>,
     #[deserr(default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     #[param(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     highlight_pre_tag: String,
@@ -118,6 +122,11 @@ pub struct SearchQueryGet {
     crop_marker: String,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchQuerySyntax>)]
+    query_syntax: QuerySyntax,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSuggestQuery>)]
+    #[param(value_type = bool)]
+    suggest_query: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSearchOn>)]
     #[param(value_type = Vec<String>, explode = false)]
     pub attributes_to_search_on: Option<CS<String>>,
@@ -217,15 +226,20 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            boosts: None,
             distinct: other.distinct,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
-            facets: other.facets.map(|o| o.into_iter().collect()),
+            facets: other.facets.map(|o| o.into_iter().map(FacetQuery::from).collect()),
+            disjunctive_facets: other.disjunctive_facets.map(|o| o.into_iter().collect()),
+            aggregations: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
+            query_syntax: other.query_syntax,
+            suggest_query: other.suggest_query.0,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
@@ -322,6 +336,7 @@ pub async fn search_with_url_query(
     params: AwebQueryParameter<SearchQueryGet, DeserrQueryParamError>,
     req: HttpRequest,
     analytics: web::Data<Analytics>,
+    local_analytics: web::Data<LocalAnalytics>,
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?params, "Search get");
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
@@ -341,9 +356,10 @@ pub async fn search_with_url_query(
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
     let permit = search_queue.try_get_search_permit().await?;
+    let search_index_uid = index_uid.to_string();
     let search_result = tokio::task::spawn_blocking(move || {
         perform_search(
-            index_uid.to_string(),
+            search_index_uid,
             &index,
             query,
             search_kind,
@@ -353,9 +369,10 @@ pub async fn search_with_url_query(
     })
     .await;
     permit.drop().await;
-    let search_result = search_result?;
-    if let Ok(ref search_result) = search_result {
+    let mut search_result = search_result?;
+    if let Ok(ref mut search_result) = search_result {
         aggregate.succeed(search_result);
+        search_result.query_uid = local_analytics.record_search(&index_uid, search_result);
     }
     analytics.publish(aggregate, &req);
 
@@ -428,6 +445,7 @@ pub async fn search_with_post(
     params: AwebJson<SearchQuery, DeserrJsonError>,
     req: HttpRequest,
     analytics: web::Data<Analytics>,
+    local_analytics: web::Data<LocalAnalytics>,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
@@ -448,9 +466,10 @@ pub async fn search_with_post(
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors);
 
     let permit = search_queue.try_get_search_permit().await?;
+    let search_index_uid = index_uid.to_string();
     let search_result = tokio::task::spawn_blocking(move || {
         perform_search(
-            index_uid.to_string(),
+            search_index_uid,
             &index,
             query,
             search_kind,
@@ -460,12 +479,13 @@ pub async fn search_with_post(
     })
     .await;
     permit.drop().await;
-    let search_result = search_result?;
-    if let Ok(ref search_result) = search_result {
+    let mut search_result = search_result?;
+    if let Ok(ref mut search_result) = search_result {
         aggregate.succeed(search_result);
         if search_result.degraded {
             MEILISEARCH_DEGRADED_SEARCH_REQUESTS.inc();
         }
+        search_result.query_uid = local_analytics.record_search(&index_uid, search_result);
     }
     analytics.publish(aggregate, &req);
 
@@ -8,7 +8,7 @@ use serde_json::{json, Value};
 use crate::aggregate_methods;
 use crate::analytics::{Aggregate, AggregateMethod};
 use crate::search::{
-    SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    QuerySyntax, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEMANTIC_RATIO,
 };
@@ -40,6 +40,7 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -53,6 +54,10 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
+    // every time a search is done using the advanced query syntax
+    advanced_query_syntax_total_number_of_uses: usize,
+    // every time a search asks for the corrections of its query
+    suggest_query_total_number_of_uses: usize,
 
     // vector
     // The maximum number of floats in a vector request
@@ -115,12 +120,17 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             show_ranking_score_details,
             filter,
             sort,
+            boosts: _,
             distinct,
             facets: _,
+            disjunctive_facets: _,
+            aggregations: _,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
+            suggest_query,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
@@ -160,6 +170,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -171,6 +182,12 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
+        if *query_syntax == QuerySyntax::Advanced {
+            ret.advanced_query_syntax_total_number_of_uses = 1;
+        }
+        if *suggest_query {
+            ret.suggest_query_total_number_of_uses = 1;
+        }
 
         if let Some(ref vector) = vector {
             ret.max_vector_size = vector.len();
@@ -221,6 +238,13 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            aggregations: _,
+            applied_rules: _,
+            applied_query_rules: _,
+            redirect: _,
+            suggested_query: _,
+            suggestions: _,
+            query_uid: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -252,11 +276,14 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             distinct,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            advanced_query_syntax_total_number_of_uses,
+            suggest_query_total_number_of_uses,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
@@ -305,6 +332,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -321,6 +349,12 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
 
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
+        self.advanced_query_syntax_total_number_of_uses = self
+            .advanced_query_syntax_total_number_of_uses
+            .saturating_add(advanced_query_syntax_total_number_of_uses);
+        self.suggest_query_total_number_of_uses = self
+            .suggest_query_total_number_of_uses
+            .saturating_add(suggest_query_total_number_of_uses);
 
         // vector
         self.max_vector_size = self.max_vector_size.max(max_vector_size);
@@ -378,11 +412,14 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             distinct,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            advanced_query_syntax_total_number_of_uses,
+            suggest_query_total_number_of_uses,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
@@ -434,6 +471,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "filter": {
                "with_geoRadius": filter_with_geo_radius,
                "with_geoBoundingBox": filter_with_geo_bounding_box,
+               "with_geoPolygon": filter_with_geo_polygon,
                "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
             },
@@ -442,6 +480,8 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             },
             "q": {
                "max_terms_number": max_terms_number,
+               "advanced_syntax_total_number_of_uses": advanced_query_syntax_total_number_of_uses,
+               "suggest_query_total_number_of_uses": suggest_query_total_number_of_uses,
             },
             "vector": {
                 "max_vector_size": max_vector_size,
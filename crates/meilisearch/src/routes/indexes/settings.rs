@@ -1,12 +1,18 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::{
+    InvalidSettingsDictionary, InvalidSettingsStopWords, InvalidSettingsSynonymSets,
+    InvalidSettingsSynonyms,
+};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::settings::{
-    settings, SecretPolicy, SettingEmbeddingSettings, Settings, Unchecked,
+    settings, SecretPolicy, SettingEmbeddingSettings, Settings, SynonymSetView, Unchecked,
 };
 use meilisearch_types::tasks::KindWithContent;
 use tracing::debug;
@@ -30,7 +36,7 @@ macro_rules! make_setting_routes {
         const _: fn(&meilisearch_types::settings::Settings<meilisearch_types::settings::Unchecked>) = |s| {
             // This pattern match will fail at compile time if any field in Settings is not listed in the macro
             match *s {
-                meilisearch_types::settings::Settings { $($attr: _,)* _kind: _ } => {}
+                meilisearch_types::settings::Settings { $($attr: _,)* synonym_sets_to_add: _, stop_words_to_add: _, stop_words_to_remove: _, synonyms_to_add: _, synonyms_to_remove: _, dictionary_to_add: _, dictionary_to_remove: _, _kind: _ } => {}
             }
         };
         $(
@@ -39,7 +45,7 @@ macro_rules! make_setting_routes {
 
         #[derive(OpenApi)]
         #[openapi(
-            paths(update_all, get_all, delete_all, $( $attr::get, $attr::update, $attr::delete,)*),
+            paths(update_all, get_all, delete_all, import_synonym_sets, stop_words_to_add::update, stop_words_to_remove::update, synonyms_to_add::update, synonyms_to_remove::update, dictionary_to_add::update, dictionary_to_remove::update, $( $attr::get, $attr::update, $attr::delete,)*),
             tags(
                 (
                     name = "Settings",
@@ -57,6 +63,15 @@ macro_rules! make_setting_routes {
                 .route(web::patch().to(SeqHandler(update_all)))
                 .route(web::get().to(SeqHandler(get_all)))
                 .route(web::delete().to(SeqHandler(delete_all))))
+                .service(
+                    web::resource("/synonym-sets/import")
+                        .route(web::post().to(SeqHandler(import_synonym_sets))))
+                .service(stop_words_to_add::resources())
+                .service(stop_words_to_remove::resources())
+                .service(synonyms_to_add::resources())
+                .service(synonyms_to_remove::resources())
+                .service(dictionary_to_add::resources())
+                .service(dictionary_to_remove::resources())
                 $(.service($attr::resources()))*;
         }
 
@@ -365,6 +380,39 @@ make_setting_routes!(
         camelcase_attr: "nonSeparatorTokens",
         analytics: NonSeparatorTokensAnalytics
     },
+    {
+        route: "/identifier-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsIdentifierAttributes,
+        >,
+        attr: identifier_attributes,
+        camelcase_attr: "identifierAttributes",
+        analytics: IdentifierAttributesAnalytics
+    },
+    {
+        route: "/infix-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsInfixAttributes,
+        >,
+        attr: infix_attributes,
+        camelcase_attr: "infixAttributes",
+        analytics: InfixAttributesAnalytics
+    },
+    {
+        route: "/date-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsDateAttributes,
+        >,
+        attr: date_attributes,
+        camelcase_attr: "dateAttributes",
+        analytics: DateAttributesAnalytics
+    },
     {
         route: "/separator-tokens",
         update_verb: put,
@@ -508,6 +556,39 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/rules",
+        update_verb: put,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::milli::SearchRule>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsRules,
+        >,
+        attr: rules,
+        camelcase_attr: "rules",
+        analytics: RulesAnalytics
+    },
+    {
+        route: "/query-rules",
+        update_verb: put,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::milli::QueryRule>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsQueryRules,
+        >,
+        attr: query_rules,
+        camelcase_attr: "queryRules",
+        analytics: QueryRulesAnalytics
+    },
+    {
+        route: "/synonym-sets",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::settings::SynonymSetView>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsSynonymSets,
+        >,
+        attr: synonym_sets,
+        camelcase_attr: "synonymSets",
+        analytics: SynonymSetsAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -595,8 +676,20 @@ pub async fn update_all(
             non_separator_tokens: NonSeparatorTokensAnalytics::new(
                 new_settings.non_separator_tokens.as_ref().set(),
             ),
+            identifier_attributes: IdentifierAttributesAnalytics::new(
+                new_settings.identifier_attributes.as_ref().set(),
+            ),
+            infix_attributes: InfixAttributesAnalytics::new(
+                new_settings.infix_attributes.as_ref().set(),
+            ),
+            date_attributes: DateAttributesAnalytics::new(
+                new_settings.date_attributes.as_ref().set(),
+            ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            rules: RulesAnalytics::new(new_settings.rules.as_ref().set()),
+            query_rules: QueryRulesAnalytics::new(new_settings.query_rules.as_ref().set()),
+            synonym_sets: SynonymSetsAnalytics::new(new_settings.synonym_sets.as_ref().set()),
         },
         &req,
     );
@@ -620,6 +713,244 @@ pub async fn update_all(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[utoipa::path(
+    post,
+    path = "{indexUid}/settings/synonym-sets/import",
+    tag = "Settings",
+    security(("Bearer" = ["settings.update", "settings.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = Vec<SynonymSetView>,
+    responses(
+        (status = 200, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "settingsUpdate",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+/// Import synonym sets
+///
+/// Append synonym sets to the ones of an index, without sending the existing synonym sets again.
+/// If the provided index does not exist, it will be created.
+pub async fn import_synonym_sets(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Vec<SynonymSetView>, DeserrJsonError<InvalidSettingsSynonymSets>>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let synonym_sets = body.into_inner();
+    debug!(parameters = ?synonym_sets, "Import synonym sets");
+
+    analytics.publish(SynonymSetsAnalytics::new(Some(&synonym_sets)).into_settings(), &req);
+
+    let new_settings = Settings { synonym_sets_to_add: Some(synonym_sets), ..Default::default() };
+    let task =
+        register_settings_delta(index_scheduler, index_uid, new_settings, &req, &opt).await?;
+
+    debug!(returns = ?task, "Import synonym sets");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// This macro generates a route applying a change to the current value of a setting,
+/// such as words to add to or remove from a list, without sending the whole setting again.
+///
+/// The generated module contains the `update` route and its `resources`.
+macro_rules! make_setting_delta_route {
+    ({route: $route:literal, value_type: $type:ty, err_type: $err_type:ty, attr: $attr:ident, operation_id: $operation_id:literal, summary: $summary:literal, description: $description:literal, analytics: $analytics:ident,}) => {
+        pub mod $attr {
+            use actix_web::web::Data;
+            use actix_web::{web, HttpRequest, HttpResponse, Resource};
+            use deserr::actix_web::AwebJson;
+            use index_scheduler::IndexScheduler;
+            use meilisearch_types::error::ResponseError;
+            use meilisearch_types::index_uid::IndexUid;
+            use meilisearch_types::settings::Settings;
+            use tracing::debug;
+            use $crate::analytics::Analytics;
+            use $crate::extractors::authentication::policies::*;
+            use $crate::extractors::authentication::GuardedData;
+            use $crate::extractors::sequential_extractor::SeqHandler;
+            use $crate::routes::SummarizedTaskView;
+            use $crate::Opt;
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[utoipa::path(
+                post,
+                path = concat!("{indexUid}/settings", $route),
+                tag = "Settings",
+                security(("Bearer" = ["settings.update", "settings.*", "*"])),
+                operation_id = $operation_id,
+                summary = $summary,
+                description = $description,
+                params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+                request_body = $type,
+                responses(
+                    (status = 200, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+                        {
+                            "taskUid": 147,
+                            "indexUid": "movies",
+                            "status": "enqueued",
+                            "type": "settingsUpdate",
+                            "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+                        }
+                    )),
+                    (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+                        {
+                            "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                            "code": "missing_authorization_header",
+                            "type": "auth",
+                            "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+                        }
+                    )),
+                )
+            )]
+            pub async fn update(
+                index_scheduler: GuardedData<
+                    ActionPolicy<{ actions::SETTINGS_UPDATE }>,
+                    Data<IndexScheduler>,
+                >,
+                index_uid: web::Path<String>,
+                body: AwebJson<$type, $err_type>,
+                req: HttpRequest,
+                opt: web::Data<Opt>,
+                analytics: web::Data<Analytics>,
+            ) -> Result<HttpResponse, ResponseError> {
+                let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+                let body = body.into_inner();
+                debug!(parameters = ?body, $summary);
+
+                // the total of the setting is only known once the task is processed
+                analytics.publish($analytics::default().into_settings(), &req);
+
+                let new_settings = Settings { $attr: Some(body), ..Default::default() };
+                let task = super::register_settings_delta(
+                    index_scheduler,
+                    index_uid,
+                    new_settings,
+                    &req,
+                    &opt,
+                )
+                .await?;
+
+                debug!(returns = ?task, $summary);
+                Ok(HttpResponse::Accepted().json(task))
+            }
+
+            pub fn resources() -> Resource {
+                Resource::new($route).route(web::post().to(SeqHandler(update)))
+            }
+        }
+    };
+}
+
+make_setting_delta_route!({
+    route: "/stop-words/add",
+    value_type: BTreeSet<String>,
+    err_type: DeserrJsonError<InvalidSettingsStopWords>,
+    attr: stop_words_to_add,
+    operation_id: "addStopWords",
+    summary: "Add stop words",
+    description: "Add words to the stop words of an index, without sending the existing stop words again. If the provided index does not exist, it will be created.",
+    analytics: StopWordsAnalytics,
+});
+
+make_setting_delta_route!({
+    route: "/stop-words/remove",
+    value_type: BTreeSet<String>,
+    err_type: DeserrJsonError<InvalidSettingsStopWords>,
+    attr: stop_words_to_remove,
+    operation_id: "removeStopWords",
+    summary: "Remove stop words",
+    description: "Remove words from the stop words of an index, without sending the remaining stop words.",
+    analytics: StopWordsAnalytics,
+});
+
+make_setting_delta_route!({
+    route: "/synonyms/add",
+    value_type: BTreeMap<String, Vec<String>>,
+    err_type: DeserrJsonError<InvalidSettingsSynonyms>,
+    attr: synonyms_to_add,
+    operation_id: "addSynonyms",
+    summary: "Add synonyms",
+    description: "Append synonyms to the ones of each word of an index, without sending the existing synonyms again. If the provided index does not exist, it will be created.",
+    analytics: SynonymsAnalytics,
+});
+
+make_setting_delta_route!({
+    route: "/synonyms/remove",
+    value_type: BTreeSet<String>,
+    err_type: DeserrJsonError<InvalidSettingsSynonyms>,
+    attr: synonyms_to_remove,
+    operation_id: "removeSynonyms",
+    summary: "Remove synonyms",
+    description: "Remove all the synonyms of the given words of an index, without sending the remaining synonyms.",
+    analytics: SynonymsAnalytics,
+});
+
+make_setting_delta_route!({
+    route: "/dictionary/add",
+    value_type: BTreeSet<String>,
+    err_type: DeserrJsonError<InvalidSettingsDictionary>,
+    attr: dictionary_to_add,
+    operation_id: "addDictionary",
+    summary: "Add dictionary words",
+    description: "Add words to the dictionary of an index, without sending the existing dictionary again. If the provided index does not exist, it will be created.",
+    analytics: DictionaryAnalytics,
+});
+
+make_setting_delta_route!({
+    route: "/dictionary/remove",
+    value_type: BTreeSet<String>,
+    err_type: DeserrJsonError<InvalidSettingsDictionary>,
+    attr: dictionary_to_remove,
+    operation_id: "removeDictionary",
+    summary: "Remove dictionary words",
+    description: "Remove words from the dictionary of an index, without sending the remaining dictionary.",
+    analytics: DictionaryAnalytics,
+});
+
+/// Registers a settings update carrying changes to apply to the current value of some settings,
+/// such as words to add to or remove from a list.
+async fn register_settings_delta(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: IndexUid,
+    new_settings: Settings<Unchecked>,
+    req: &HttpRequest,
+    opt: &Opt,
+) -> Result<SummarizedTaskView, ResponseError> {
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let task = KindWithContent::SettingsUpdate {
+        index_uid: index_uid.into_inner(),
+        new_settings: Box::new(new_settings),
+        is_deletion: false,
+        allow_index_creation,
+    };
+    let uid = get_task_id(req, opt)?;
+    let dry_run = is_dry_run(req, opt)?;
+    let task = tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+        .await??
+        .into();
+    Ok(task)
+}
+
 #[utoipa::path(
     get,
     path = "{indexUid}/settings",
@@ -8,10 +8,12 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::milli::FilterableAttributesRule;
+use meilisearch_types::milli::{
+    FilterableAttributesRule, PhoneticAlgorithm, QueryRule, SearchRule, SynonymSetKind,
+};
 use meilisearch_types::settings::{
     FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
-    RankingRuleView, SettingEmbeddingSettings, TypoSettings,
+    RankingRuleView, SettingEmbeddingSettings, SynonymSetView, TypoSettings,
 };
 use serde::Serialize;
 
@@ -37,8 +39,14 @@ pub struct SettingsAnalytics {
     pub dictionary: DictionaryAnalytics,
     pub separator_tokens: SeparatorTokensAnalytics,
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
+    pub identifier_attributes: IdentifierAttributesAnalytics,
+    pub infix_attributes: InfixAttributesAnalytics,
+    pub date_attributes: DateAttributesAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub rules: RulesAnalytics,
+    pub query_rules: QueryRulesAnalytics,
+    pub synonym_sets: SynonymSetsAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -120,6 +128,12 @@ impl Aggregate for SettingsAnalytics {
                     .typo_tolerance
                     .min_word_size_for_two_typos
                     .or(self.typo_tolerance.min_word_size_for_two_typos),
+                phonetic: new.typo_tolerance.phonetic.or(self.typo_tolerance.phonetic),
+                per_attribute: new
+                    .typo_tolerance
+                    .per_attribute
+                    .or(self.typo_tolerance.per_attribute),
+                per_locale: new.typo_tolerance.per_locale.or(self.typo_tolerance.per_locale),
             },
             faceting: FacetingAnalytics {
                 max_values_per_facet: new
@@ -134,6 +148,10 @@ impl Aggregate for SettingsAnalytics {
                     .faceting
                     .sort_facet_values_by_total
                     .or(self.faceting.sort_facet_values_by_total),
+                hierarchies_total: new
+                    .faceting
+                    .hierarchies_total
+                    .or(self.faceting.hierarchies_total),
             },
             pagination: PaginationAnalytics {
                 max_total_hits: new.pagination.max_total_hits.or(self.pagination.max_total_hits),
@@ -190,6 +208,15 @@ impl Aggregate for SettingsAnalytics {
             non_separator_tokens: NonSeparatorTokensAnalytics {
                 total: new.non_separator_tokens.total.or(self.non_separator_tokens.total),
             },
+            identifier_attributes: IdentifierAttributesAnalytics {
+                total: new.identifier_attributes.total.or(self.identifier_attributes.total),
+            },
+            infix_attributes: InfixAttributesAnalytics {
+                total: new.infix_attributes.total.or(self.infix_attributes.total),
+            },
+            date_attributes: DateAttributesAnalytics {
+                total: new.date_attributes.total.or(self.date_attributes.total),
+            },
             facet_search: FacetSearchAnalytics {
                 set: new.facet_search.set | self.facet_search.set,
                 value: new.facet_search.value.or(self.facet_search.value),
@@ -198,6 +225,36 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            rules: RulesAnalytics {
+                total: new.rules.total.or(self.rules.total),
+                with_filter_condition: new
+                    .rules
+                    .with_filter_condition
+                    .or(self.rules.with_filter_condition),
+                total_pinned_documents: new
+                    .rules
+                    .total_pinned_documents
+                    .or(self.rules.total_pinned_documents),
+                total_hidden_documents: new
+                    .rules
+                    .total_hidden_documents
+                    .or(self.rules.total_hidden_documents),
+                total_boosts: new.rules.total_boosts.or(self.rules.total_boosts),
+            },
+            query_rules: QueryRulesAnalytics {
+                total: new.query_rules.total.or(self.query_rules.total),
+                with_replacement: new
+                    .query_rules
+                    .with_replacement
+                    .or(self.query_rules.with_replacement),
+                with_filter: new.query_rules.with_filter.or(self.query_rules.with_filter),
+                with_redirect: new.query_rules.with_redirect.or(self.query_rules.with_redirect),
+            },
+            synonym_sets: SynonymSetsAnalytics {
+                total: new.synonym_sets.total.or(self.synonym_sets.total),
+                one_way: new.synonym_sets.one_way.or(self.synonym_sets.one_way),
+                localized: new.synonym_sets.localized.or(self.synonym_sets.localized),
+            },
         })
     }
 
@@ -392,6 +449,9 @@ pub struct TypoToleranceAnalytics {
     pub disable_on_words: Option<bool>,
     pub min_word_size_for_one_typo: Option<u8>,
     pub min_word_size_for_two_typos: Option<u8>,
+    pub phonetic: Option<PhoneticAlgorithm>,
+    pub per_attribute: Option<bool>,
+    pub per_locale: Option<bool>,
 }
 
 impl TypoToleranceAnalytics {
@@ -412,6 +472,15 @@ impl TypoToleranceAnalytics {
                 .as_ref()
                 .and_then(|s| s.min_word_size_for_typos.as_ref().set().map(|s| s.two_typos.set()))
                 .flatten(),
+            phonetic: setting
+                .as_ref()
+                .and_then(|s| s.phonetic.as_ref().set().map(|phonetic| phonetic.algorithm)),
+            per_attribute: setting
+                .as_ref()
+                .and_then(|s| s.per_attribute.as_ref().set().map(|b| !b.is_empty())),
+            per_locale: setting
+                .as_ref()
+                .and_then(|s| s.per_locale.as_ref().set().map(|b| !b.is_empty())),
         }
     }
     pub fn into_settings(self) -> SettingsAnalytics {
@@ -424,6 +493,7 @@ pub struct FacetingAnalytics {
     pub max_values_per_facet: Option<usize>,
     pub sort_facet_values_by_star_count: Option<bool>,
     pub sort_facet_values_by_total: Option<usize>,
+    pub hierarchies_total: Option<usize>,
 }
 
 impl FacetingAnalytics {
@@ -439,6 +509,9 @@ impl FacetingAnalytics {
             sort_facet_values_by_total: setting
                 .as_ref()
                 .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+            hierarchies_total: setting
+                .as_ref()
+                .and_then(|s| s.hierarchies.as_ref().set().map(|s| s.len())),
         }
     }
 
@@ -643,6 +716,51 @@ impl NonSeparatorTokensAnalytics {
     }
 }
 
+#[derive(Serialize, Default)]
+pub struct IdentifierAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl IdentifierAttributesAnalytics {
+    pub fn new(identifier_attributes: Option<&BTreeSet<String>>) -> Self {
+        Self { total: identifier_attributes.map(|attributes| attributes.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { identifier_attributes: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct InfixAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl InfixAttributesAnalytics {
+    pub fn new(infix_attributes: Option<&BTreeSet<String>>) -> Self {
+        Self { total: infix_attributes.map(|attributes| attributes.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { infix_attributes: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct DateAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl DateAttributesAnalytics {
+    pub fn new(date_attributes: Option<&BTreeSet<String>>) -> Self {
+        Self { total: date_attributes.map(|attributes| attributes.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { date_attributes: self, ..Default::default() }
+    }
+}
+
 #[derive(Serialize, Default)]
 pub struct FacetSearchAnalytics {
     pub set: bool,
@@ -674,3 +792,79 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct RulesAnalytics {
+    pub total: Option<usize>,
+    pub with_filter_condition: Option<usize>,
+    pub total_pinned_documents: Option<usize>,
+    pub total_hidden_documents: Option<usize>,
+    pub total_boosts: Option<usize>,
+}
+
+impl RulesAnalytics {
+    pub fn new(rules: Option<&BTreeMap<String, SearchRule>>) -> Self {
+        let count = |f: fn(&SearchRule) -> usize| rules.map(|rules| rules.values().map(f).sum());
+        Self {
+            total: rules.map(|rules| rules.len()),
+            with_filter_condition: count(|rule| rule.condition.filter.is_some() as usize),
+            total_pinned_documents: count(|rule| rule.actions.pin.len()),
+            total_hidden_documents: count(|rule| rule.actions.hide.len()),
+            total_boosts: count(|rule| rule.actions.boost.len()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { rules: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct QueryRulesAnalytics {
+    pub total: Option<usize>,
+    pub with_replacement: Option<usize>,
+    pub with_filter: Option<usize>,
+    pub with_redirect: Option<usize>,
+}
+
+impl QueryRulesAnalytics {
+    pub fn new(rules: Option<&BTreeMap<String, QueryRule>>) -> Self {
+        let count = |f: fn(&QueryRule) -> bool| {
+            rules.map(|rules| rules.values().filter(|rule| f(rule)).count())
+        };
+        Self {
+            total: rules.map(|rules| rules.len()),
+            with_replacement: count(|rule| rule.consequence.replace_with.is_some()),
+            with_filter: count(|rule| rule.consequence.filter.is_some()),
+            with_redirect: count(|rule| rule.consequence.redirect.is_some()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { query_rules: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct SynonymSetsAnalytics {
+    pub total: Option<usize>,
+    pub one_way: Option<usize>,
+    pub localized: Option<usize>,
+}
+
+impl SynonymSetsAnalytics {
+    pub fn new(sets: Option<&Vec<SynonymSetView>>) -> Self {
+        let count = |f: fn(&SynonymSetView) -> bool| {
+            sets.map(|sets| sets.iter().filter(|set| f(set)).count())
+        };
+        Self {
+            total: sets.map(|sets| sets.len()),
+            one_way: count(|set| set.kind == SynonymSetKind::OneWay),
+            localized: count(|set| !set.locales.is_empty()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { synonym_sets: self, ..Default::default() }
+    }
+}
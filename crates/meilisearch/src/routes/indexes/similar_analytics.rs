@@ -23,6 +23,7 @@ pub struct SimilarAggregator<Method: AggregateMethod> {
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -88,6 +89,7 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -125,6 +127,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             mut time_spent,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -146,6 +149,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -180,6 +184,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             time_spent,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -210,6 +215,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             "filter": {
                "with_geoRadius": filter_with_geo_radius,
                "with_geoBoundingBox": filter_with_geo_bounding_box,
+               "with_geoPolygon": filter_with_geo_polygon,
                "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
             },
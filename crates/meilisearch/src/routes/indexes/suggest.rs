@@ -0,0 +1,208 @@
+use std::collections::BinaryHeap;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use tracing::debug;
+use utoipa::OpenApi;
+
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::search::{add_search_rules, perform_suggest, SuggestQuery, SuggestResult};
+use crate::search_queue::SearchQueue;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(suggest),
+    tags(
+        (
+            name = "Suggest",
+            description = "The `/suggest` route completes a query being typed with the words and the phrases of the documents. The suggested queries are sorted by the number of documents they match, in descending order.",
+        ),
+    ),
+)]
+pub struct SuggestApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(suggest)));
+}
+
+#[derive(Default)]
+pub struct SuggestAggregator {
+    // requests
+    total_received: usize,
+    total_succeeded: usize,
+    time_spent: BinaryHeap<usize>,
+
+    // parameters
+    filter_total_number_of_uses: usize,
+    attributes_to_search_on_total_number_of_uses: usize,
+    max_limit: usize,
+}
+
+impl SuggestAggregator {
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_query(query: &SuggestQuery) -> Self {
+        let SuggestQuery { q: _, limit, filter, attributes_to_search_on } = query;
+
+        Self {
+            total_received: 1,
+            filter_total_number_of_uses: filter.is_some() as usize,
+            attributes_to_search_on_total_number_of_uses: attributes_to_search_on.is_some()
+                as usize,
+            max_limit: *limit,
+            ..Default::default()
+        }
+    }
+
+    pub fn succeed(&mut self, result: &SuggestResult) {
+        let SuggestResult { suggestions: _, query: _, processing_time_ms } = result;
+        self.total_succeeded = 1;
+        self.time_spent.push(*processing_time_ms as usize);
+    }
+}
+
+impl Aggregate for SuggestAggregator {
+    fn event_name(&self) -> &'static str {
+        "Suggest POST"
+    }
+
+    fn aggregate(mut self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        for time in new.time_spent {
+            self.time_spent.push(time);
+        }
+
+        Box::new(Self {
+            total_received: self.total_received.saturating_add(new.total_received),
+            total_succeeded: self.total_succeeded.saturating_add(new.total_succeeded),
+            time_spent: self.time_spent,
+            filter_total_number_of_uses: self
+                .filter_total_number_of_uses
+                .saturating_add(new.filter_total_number_of_uses),
+            attributes_to_search_on_total_number_of_uses: self
+                .attributes_to_search_on_total_number_of_uses
+                .saturating_add(new.attributes_to_search_on_total_number_of_uses),
+            max_limit: self.max_limit.max(new.max_limit),
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        let Self {
+            total_received,
+            total_succeeded,
+            time_spent,
+            filter_total_number_of_uses,
+            attributes_to_search_on_total_number_of_uses,
+            max_limit,
+        } = *self;
+        // the index of the 99th percentage of value
+        let percentile_99th = 0.99 * (total_succeeded as f64 - 1.) + 1.;
+        // we get all the values in a sorted manner
+        let time_spent = time_spent.into_sorted_vec();
+        // We are only interested by the slowest value of the 99th fastest results
+        let time_spent = time_spent.get(percentile_99th as usize);
+
+        serde_json::json!({
+            "requests": {
+                "99th_response_time":  time_spent.map(|t| format!("{:.2}", t)),
+                "total_succeeded": total_succeeded,
+                "total_failed": total_received.saturating_sub(total_succeeded), // just to be sure we never panics
+                "total_received": total_received,
+            },
+            "filter": {
+                "total_number_of_uses": filter_total_number_of_uses,
+            },
+            "attributes_to_search_on": {
+                "total_number_of_uses": attributes_to_search_on_total_number_of_uses,
+            },
+            "pagination": {
+                "max_limit": max_limit,
+            },
+        })
+    }
+}
+
+/// Suggest queries
+///
+/// Complete a query being typed with the words and the phrases of the documents.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/suggest",
+    tag = "Suggest",
+    security(("Bearer" = ["search", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = SuggestQuery,
+    responses(
+        (status = 200, description = "The suggested queries are returned", body = SuggestResult, content_type = "application/json", example = json!(
+            {
+              "suggestions": [
+                { "query": "american pie", "estimatedTotalHits": 4 },
+                { "query": "american", "estimatedTotalHits": 3 },
+                { "query": "american sniper", "estimatedTotalHits": 1 }
+              ],
+              "query": "americ",
+              "processingTimeMs": 2
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn suggest(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SuggestQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!(parameters = ?query, "Suggest");
+
+    let mut aggregate = SuggestAggregator::from_query(&query);
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query.filter, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let permit = search_queue.try_get_search_permit().await?;
+    let suggest_result = tokio::task::spawn_blocking(move || {
+        perform_suggest(&index, query, index_scheduler.features())
+    })
+    .await;
+    permit.drop().await;
+    let suggest_result = suggest_result?;
+
+    if let Ok(ref suggest_result) = suggest_result {
+        aggregate.succeed(suggest_result);
+    }
+    analytics.publish(aggregate, &req);
+
+    let suggest_result = suggest_result?;
+
+    debug!(returns = ?suggest_result, "Suggest");
+    Ok(HttpResponse::Ok().json(suggest_result))
+}
@@ -11,11 +11,11 @@ use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::CreateApiKey;
 use meilisearch_types::milli::{
     AttributePatterns, FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
-    FilterableAttributesRule,
+    FilterableAttributesRule, PhoneticAlgorithm, PhoneticSettings,
 };
 use meilisearch_types::settings::{
-    Checked, FacetingSettings, MinWordSizeTyposSetting, PaginationSettings, Settings, TypoSettings,
-    Unchecked,
+    AttributeTypoBudgetView, Checked, FacetingSettings, LocaleTypoBudgetView,
+    MinWordSizeTyposSetting, PaginationSettings, Settings, TypoSettings, Unchecked,
 };
 use meilisearch_types::task_view::{DetailsView, TaskView};
 use meilisearch_types::tasks::{Kind, Status, Task, TaskId};
@@ -42,7 +42,8 @@ use crate::routes::network::{Network, Remote};
 use crate::routes::swap_indexes::SwapIndexesPayload;
 use crate::search::{
     FederatedSearch, FederatedSearchResult, Federation, FederationOptions, MergeFacets,
-    SearchQueryWithIndex, SearchResultWithIndex, SimilarQuery, SimilarResult,
+    QuerySuggestion, SearchQueryWithIndex, SearchResultWithIndex, SimilarQuery, SimilarResult,
+    SuggestQuery, SuggestResult,
 };
 use crate::search_queue::SearchQueue;
 use crate::Opt;
@@ -50,9 +51,11 @@ use crate::Opt;
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 const PAGINATION_DEFAULT_LIMIT_FN: fn() -> usize = || 20;
 
+mod analytics;
 mod api_key;
 pub mod batches;
 mod dump;
+mod events;
 pub mod features;
 pub mod indexes;
 mod logs;
@@ -84,6 +87,8 @@ mod tasks_test;
         (path = "/swap-indexes", api = swap_indexes::SwapIndexesApi),
         (path = "/experimental-features", api = features::ExperimentalFeaturesApi),
         (path = "/network", api = network::NetworkApi),
+        (path = "/events", api = events::EventsApi),
+        (path = "/analytics", api = analytics::AnalyticsApi),
     ),
     paths(get_health, get_version, get_stats),
     tags(
@@ -94,7 +99,7 @@ mod tasks_test;
         url = "/",
         description = "Local server",
     )),
-    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
+    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, SuggestQuery, SuggestResult, QuerySuggestion, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, PhoneticSettings, PhoneticAlgorithm, AttributeTypoBudgetView, LocaleTypoBudgetView, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
 )]
 pub struct MeilisearchApi;
 
@@ -113,7 +118,9 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
         .service(web::scope("/experimental-features").configure(features::configure))
-        .service(web::scope("/network").configure(network::configure));
+        .service(web::scope("/network").configure(network::configure))
+        .service(web::scope("/events").configure(events::configure))
+        .service(web::scope("/analytics").configure(analytics::configure));
 
     #[cfg(feature = "swagger")]
     {
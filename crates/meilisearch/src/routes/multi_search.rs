@@ -11,6 +11,7 @@ use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
 
 use super::multi_search_analytics::MultiSearchAggregator;
+use crate::analytics::local_analytics::LocalAnalytics;
 use crate::analytics::Analytics;
 use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::ActionPolicy;
@@ -147,6 +148,7 @@ pub async fn multi_search_with_post(
     params: AwebJson<FederatedSearch, DeserrJsonError>,
     req: HttpRequest,
     analytics: web::Data<Analytics>,
+    local_analytics: web::Data<LocalAnalytics>,
 ) -> Result<HttpResponse, ResponseError> {
     // Since we don't want to process half of the search requests and then get a permit refused
     // we're going to get one permit for the whole duration of the multi-search request.
@@ -193,13 +195,30 @@ pub async fn multi_search_with_post(
                 .headers()
                 .get(PROXY_SEARCH_HEADER)
                 .is_some_and(|value| value.as_bytes() == PROXY_SEARCH_HEADER_VALUE.as_bytes());
+            // the queries sent to the remotes are recorded by them
+            let local_queries: Vec<_> = queries
+                .iter()
+                .enumerate()
+                .filter(|(_, query)| {
+                    query.federation_options.as_ref().is_none_or(|options| options.remote.is_none())
+                })
+                .filter_map(|(position, query)| {
+                    Some((position, query.index_uid.to_string(), query.q.clone()?))
+                })
+                .collect();
             let search_result =
                 perform_federated_search(&index_scheduler, queries, federation, features, is_proxy)
                     .await;
             permit.drop().await;
 
-            if search_result.is_ok() {
+            if let Ok(ref search_result) = search_result {
                 multi_aggregate.succeed();
+                local_analytics.record_federated_search(
+                    local_queries.iter().map(|(position, index_uid, q)| {
+                        (*position, index_uid.as_str(), q.as_str())
+                    }),
+                    search_result,
+                );
             }
 
             analytics.publish(multi_aggregate, &req);
@@ -263,9 +282,13 @@ pub async fn multi_search_with_post(
                     .await
                     .with_index(query_index)?;
 
+                    let mut search_result = search_result.with_index(query_index)?;
+                    search_result.query_uid =
+                        local_analytics.record_search(&index_uid, &search_result);
+
                     search_results.push(SearchResultWithIndex {
                         index_uid: index_uid.into_inner(),
-                        result: search_result.with_index(query_index)?,
+                        result: search_result,
                     });
                 }
                 Ok(search_results)
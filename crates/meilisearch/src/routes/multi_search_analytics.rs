@@ -56,12 +56,17 @@ impl MultiSearchAggregator {
             show_matches_position: _,
             filter: _,
             sort: _,
+            boosts: _,
             distinct: _,
             facets: _,
+            disjunctive_facets: _,
+            aggregations: _,
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
             matching_strategy: _,
+            query_syntax: _,
+            suggest_query: _,
             attributes_to_search_on: _,
             hybrid: _,
             ranking_score_threshold: _,
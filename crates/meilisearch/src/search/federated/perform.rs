@@ -19,8 +19,8 @@ use tokio::task::JoinHandle;
 
 use super::super::ranking_rules::{self, RankingRules};
 use super::super::{
-    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, HitMaker,
-    HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
+    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, FacetQuery,
+    HitMaker, HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
 };
 use super::proxy::{proxy_search, ProxySearchError, ProxySearchParams};
 use super::types::{
@@ -93,6 +93,7 @@ pub async fn perform_federated_search(
         mut results_by_index,
         previous_query_data: _,
         facet_order,
+        hits_by_query,
     } = search_by_index;
 
     let before_waiting_remote_results = std::time::Instant::now();
@@ -147,6 +148,7 @@ pub async fn perform_federated_search(
         facet_stats,
         facets_by_index,
         remote_errors: partitioned_queries.has_remote.then_some(remote_errors),
+        hits_by_query,
     })
 }
 
@@ -415,6 +417,7 @@ fn merge_metadata(
         degraded: degraded_for_host,
         used_negative_operator: host_used_negative_operator,
         remote_errors: _,
+        hits_by_query: _,
     } in remote_results
     {
         let this_remote_duration = Duration::from_millis(*processing_time_ms as u64);
@@ -464,8 +467,20 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.has_aggregations() {
+            return Err(MeilisearchHttpError::AggregationsInFederatedQuery(query_index).into());
+        }
+
+        if federated_query.has_disjunctive_facets() {
+            return Err(MeilisearchHttpError::DisjunctiveFacetsInFederatedQuery(query_index).into());
+        }
+
+        if federated_query.has_suggest_query() {
+            return Err(MeilisearchHttpError::SuggestQueryInFederatedQuery(query_index).into());
+        }
+
         if let Some(facets) = federated_query.has_facets() {
-            let facets = facets.to_owned();
+            let facets = facets.iter().map(|facet| facet.attribute().to_owned()).collect();
             return Err(MeilisearchHttpError::FacetsInFederatedQuery(
                 query_index,
                 federated_query.index_uid.into_inner(),
@@ -661,6 +676,8 @@ struct SearchByIndex {
     // remember the order and name of first index for each facet when merging with index settings
     // to detect if the order is inconsistent for a facet.
     facet_order: FacetOrder,
+    // the number of hits of each local query, only used for analytics purposes
+    hits_by_query: BTreeMap<usize, u64>,
 }
 
 impl SearchByIndex {
@@ -675,6 +692,7 @@ impl SearchByIndex {
             semantic_hit_count: None,
             results_by_index: Vec::with_capacity(index_count),
             previous_query_data: None,
+            hits_by_query: BTreeMap::new(),
         }
     }
 
@@ -812,6 +830,7 @@ impl SearchByIndex {
                 search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
                 search.offset(0);
                 search.limit(params.required_hit_count);
+                let geo_filter_centers = search.geo_filter_centers();
 
                 let (result, _semantic_hit_count) =
                     super::super::search_from_kind(index_uid.to_string(), search_kind, search)?;
@@ -826,6 +845,7 @@ impl SearchByIndex {
                     highlight_post_tag: query.highlight_post_tag,
                     show_matches_position: query.show_matches_position,
                     sort: query.sort,
+                    geo_filter_centers,
                     show_ranking_score: query.show_ranking_score,
                     show_ranking_score_details: query.show_ranking_score_details,
                     locales: query.locales.map(|l| l.iter().copied().map(Into::into).collect()),
@@ -838,8 +858,12 @@ impl SearchByIndex {
                     document_scores,
                     degraded: query_degraded,
                     used_negative_operator: query_used_negative_operator,
+                    disjunctive_candidates: _,
+                    applied_rules: _,
+                    query_suggestions: _,
                 } = result;
 
+                self.hits_by_query.insert(query_index, query_candidates.len());
                 candidates |= query_candidates;
                 degraded |= query_degraded;
                 used_negative_operator |= query_used_negative_operator;
@@ -918,6 +942,8 @@ impl SearchByIndex {
         let estimated_total_hits = candidates.len() as usize;
         let facets = facets_by_index
             .map(|facets_by_index| {
+                let facets_by_index: Vec<_> =
+                    facets_by_index.into_iter().map(FacetQuery::from).collect();
                 compute_facet_distribution_stats(
                     &facets_by_index,
                     &index,
@@ -983,6 +1009,7 @@ impl SearchByIndex {
             }
 
             if let Some(facets) = facets {
+                let facets: Vec<_> = facets.into_iter().map(FacetQuery::from).collect();
                 if let Err(mut error) = compute_facet_distribution_stats(
                     &facets,
                     &index,
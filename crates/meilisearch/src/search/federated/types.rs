@@ -136,6 +136,9 @@ pub struct FederatedSearchResult {
     pub degraded: bool,
     #[serde(skip)]
     pub used_negative_operator: bool,
+    /// The number of hits of each local query, by position of the query in the request.
+    #[serde(skip)]
+    pub hits_by_query: BTreeMap<usize, u64>,
 }
 
 impl fmt::Debug for FederatedSearchResult {
@@ -151,6 +154,7 @@ impl fmt::Debug for FederatedSearchResult {
             facet_stats,
             facets_by_index,
             remote_errors,
+            hits_by_query: _,
         } = self;
 
         let mut debug = f.debug_struct("SearchResult");
@@ -5,7 +5,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr};
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
@@ -26,10 +26,10 @@ use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, FacetHierarchy, FacetRanges, FacetValuesOptions, FieldId, FieldsIdsMap, Filter,
+    FormatOptions, Index, LocalizedAttributesRule, MatchBounds, MatcherBuilder, SortError,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 #[cfg(test)]
@@ -54,6 +54,9 @@ pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
 pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
+pub const DEFAULT_SUGGEST_LIMIT: fn() -> usize = || 5;
+/// Maximum number of suggestions returned by a suggest request.
+pub const MAX_SUGGEST_LIMIT: usize = 100;
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
@@ -96,10 +99,16 @@ pub struct SearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoosts>)]
+    pub boosts: Option<Vec<BoostQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
-    pub facets: Option<Vec<String>>,
+    pub facets: Option<Vec<FacetQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAggregations>)]
+    pub aggregations: Option<BTreeMap<String, AggregationQuery>>,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     #[schema(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     pub highlight_pre_tag: String,
@@ -111,6 +120,10 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>)]
+    pub query_syntax: QuerySyntax,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSuggestQuery>)]
+    pub suggest_query: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>)]
@@ -177,12 +190,17 @@ impl fmt::Debug for SearchQuery {
             show_ranking_score_details,
             filter,
             sort,
+            boosts,
             distinct,
             facets,
+            disjunctive_facets,
+            aggregations,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
+            suggest_query,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
@@ -228,13 +246,28 @@ impl fmt::Debug for SearchQuery {
         if let Some(sort) = sort {
             debug.field("sort", &sort);
         }
+        if let Some(boosts) = boosts {
+            debug.field("boosts", &boosts);
+        }
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(disjunctive_facets) = disjunctive_facets {
+            debug.field("disjunctive_facets", &disjunctive_facets);
+        }
+        if let Some(aggregations) = aggregations {
+            debug.field("aggregations", &aggregations);
+        }
         debug.field("matching_strategy", &matching_strategy);
+        if *query_syntax != QuerySyntax::default() {
+            debug.field("query_syntax", &query_syntax);
+        }
+        if *suggest_query {
+            debug.field("suggest_query", &suggest_query);
+        }
 
         // Then everything related to the formatting
         debug.field("crop_length", &crop_length);
@@ -455,10 +488,16 @@ pub struct SearchQueryWithIndex {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoosts>)]
+    pub boosts: Option<Vec<BoostQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
-    pub facets: Option<Vec<String>>,
+    pub facets: Option<Vec<FacetQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAggregations>)]
+    pub aggregations: Option<BTreeMap<String, AggregationQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -467,6 +506,10 @@ pub struct SearchQueryWithIndex {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>, default)]
+    pub query_syntax: QuerySyntax,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSuggestQuery>, default)]
+    pub suggest_query: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
@@ -493,10 +536,22 @@ impl SearchQueryWithIndex {
         }
     }
 
-    pub fn has_facets(&self) -> Option<&[String]> {
+    pub fn has_facets(&self) -> Option<&[FacetQuery]> {
         self.facets.as_deref().filter(|v| !v.is_empty())
     }
 
+    pub fn has_disjunctive_facets(&self) -> bool {
+        self.disjunctive_facets.as_ref().is_some_and(|facets| !facets.is_empty())
+    }
+
+    pub fn has_aggregations(&self) -> bool {
+        self.aggregations.as_ref().is_some_and(|aggregations| !aggregations.is_empty())
+    }
+
+    pub fn has_suggest_query(&self) -> bool {
+        self.suggest_query
+    }
+
     pub fn from_index_query_federation(
         index_uid: IndexUid,
         query: SearchQuery,
@@ -520,12 +575,17 @@ impl SearchQueryWithIndex {
             show_ranking_score_details,
             filter,
             sort,
+            boosts,
             distinct,
             facets,
+            disjunctive_facets,
+            aggregations,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
+            suggest_query,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
@@ -550,12 +610,17 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            boosts,
             distinct,
             facets,
+            disjunctive_facets,
+            aggregations,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
+            suggest_query,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
@@ -583,12 +648,17 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            boosts,
             distinct,
             facets,
+            disjunctive_facets,
+            aggregations,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
+            suggest_query,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
@@ -613,12 +683,17 @@ impl SearchQueryWithIndex {
                 show_matches_position,
                 filter,
                 sort,
+                boosts,
                 distinct,
                 facets,
+                disjunctive_facets,
+                aggregations,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
+                query_syntax,
+                suggest_query,
                 attributes_to_search_on,
                 hybrid,
                 ranking_score_threshold,
@@ -658,6 +733,23 @@ pub struct SimilarQuery {
     pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct SuggestQuery {
+    /// The query being typed, its last word is completed unless it ends with a separator
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestQ>)]
+    pub q: String,
+    /// The number of suggestions returned, at most 100
+    #[deserr(default = DEFAULT_SUGGEST_LIMIT(), error = DeserrJsonError<InvalidSuggestLimit>)]
+    pub limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestFilter>)]
+    pub filter: Option<Value>,
+    /// Restricts the words completing the query to the ones of these attributes
+    #[deserr(default, error = DeserrJsonError<InvalidSuggestAttributesToSearchOn>)]
+    pub attributes_to_search_on: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExternalDocumentId(String);
 
@@ -717,6 +809,28 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum QuerySyntax {
+    /// Only quoted phrases, `"fuzzy phrases"~N` and `-` negations are operators,
+    /// `N` being capped to 2 words
+    #[default]
+    Simple,
+    /// Also supports `+required` words, `OR` alternatives grouped in parentheses and
+    /// `attribute:word` restrictions
+    Advanced,
+}
+
+impl From<QuerySyntax> for milli::QuerySyntax {
+    fn from(other: QuerySyntax) -> Self {
+        match other {
+            QuerySyntax::Simple => Self::Simple,
+            QuerySyntax::Advanced => Self::Advanced,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum FacetValuesSort {
@@ -737,6 +851,234 @@ impl From<FacetValuesSort> for OrderBy {
     }
 }
 
+/// A facet whose distribution is requested in a search.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum FacetQuery {
+    /// The name of an attribute, the documents are counted by facet value.
+    Attribute(String),
+    /// An attribute whose documents are counted by numeric range,
+    /// e.g. `{ "attribute": "price", "ranges": [[0, 10], [10, 50]] }` or `{ "attribute": "price", "interval": 10 }`,
+    /// at most `maxValuesPerFacet` ranges are returned.
+    Ranges(FacetRangesQuery),
+    /// A hierarchy declared in the faceting settings along with the values selected at its
+    /// first levels, e.g. `{ "hierarchy": "category", "path": ["Electronics", "Electronics > Phones"] }`.
+    Hierarchy(FacetHierarchyQuery),
+    /// An attribute whose returned values are restricted,
+    /// e.g. `{ "attribute": "color", "minCount": 2, "prefix": "bl", "pinnedValues": ["red"], "maxValues": 5 }`.
+    Values(FacetValuesQuery),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetRangesQuery {
+    pub attribute: String,
+    #[serde(flatten)]
+    pub ranges: FacetRanges,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetValuesQuery {
+    pub attribute: String,
+    #[serde(flatten)]
+    pub options: FacetValuesOptions,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetHierarchyQuery {
+    pub hierarchy: String,
+    pub path: Vec<String>,
+}
+
+impl FacetQuery {
+    pub fn attribute(&self) -> &str {
+        match self {
+            FacetQuery::Attribute(attribute) => attribute,
+            FacetQuery::Ranges(FacetRangesQuery { attribute, .. }) => attribute,
+            FacetQuery::Hierarchy(FacetHierarchyQuery { hierarchy, .. }) => hierarchy,
+            FacetQuery::Values(FacetValuesQuery { attribute, .. }) => attribute,
+        }
+    }
+}
+
+impl From<String> for FacetQuery {
+    fn from(attribute: String) -> Self {
+        FacetQuery::Attribute(attribute)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for FacetQuery {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef,
+    ) -> Result<Self, E> {
+        if value.kind() != deserr::ValueKind::Map {
+            return Ok(Self::Attribute(String::deserialize_from_value(value, location)?));
+        }
+
+        let FacetQueryDeserr {
+            attribute,
+            ranges,
+            interval,
+            hierarchy,
+            path,
+            min_count,
+            prefix,
+            pinned_values,
+            max_values,
+        } = FacetQueryDeserr::deserialize_from_value(value, location)?;
+        let restricts_values = min_count.is_some()
+            || prefix.is_some()
+            || pinned_values.is_some()
+            || max_values.is_some();
+        let error = |msg: String| {
+            deserr::take_cf_content(E::error::<std::convert::Infallible>(
+                None,
+                deserr::ErrorKind::Unexpected { msg },
+                location,
+            ))
+        };
+
+        let attribute = match (attribute, hierarchy) {
+            (Some(attribute), None) if path.is_some() => {
+                return Err(error(format!(
+                    "facet `{attribute}` cannot define a `path`, only a `hierarchy` can"
+                )))
+            }
+            (Some(attribute), None) => attribute,
+            (None, Some(hierarchy)) if ranges.is_some() || interval.is_some() => {
+                return Err(error(format!(
+                    "hierarchy `{hierarchy}` cannot define `ranges` or an `interval`"
+                )))
+            }
+            (None, Some(hierarchy)) if restricts_values => {
+                return Err(error(format!(
+                    "hierarchy `{hierarchy}` cannot define `minCount`, `prefix`, `pinnedValues` or `maxValues`"
+                )))
+            }
+            (None, Some(hierarchy)) => {
+                let path = path.unwrap_or_default();
+                return Ok(Self::Hierarchy(FacetHierarchyQuery { hierarchy, path }));
+            }
+            (Some(_), Some(_)) | (None, None) => {
+                return Err(error(
+                    "a facet must define either an `attribute` or a `hierarchy`".to_string(),
+                ))
+            }
+        };
+
+        let ranges = match (ranges, interval) {
+            (Some(_), _) | (_, Some(_)) if restricts_values => {
+                return Err(error(format!(
+                    "facet `{attribute}` cannot define `minCount`, `prefix`, `pinnedValues` or `maxValues` along with `ranges` or an `interval`"
+                )))
+            }
+            (None, None) if restricts_values => {
+                let options = FacetValuesOptions {
+                    min_count,
+                    prefix,
+                    pinned_values: pinned_values.unwrap_or_default(),
+                    max_values,
+                };
+                return Ok(Self::Values(FacetValuesQuery { attribute, options }));
+            }
+            (Some(ranges), None) => {
+                let ranges = ranges
+                    .into_iter()
+                    .map(|range| match range.as_slice() {
+                        [start, end] if start <= end => Ok((*start, *end)),
+                        _ => Err(error(format!(
+                            "the ranges of facet `{attribute}` must be made of a start and an end greater than or equal to it, got `{range:?}`"
+                        ))),
+                    })
+                    .collect::<Result<_, _>>()?;
+                FacetRanges::Ranges(ranges)
+            }
+            (None, Some(interval)) if interval > 0.0 && interval.is_finite() => {
+                FacetRanges::Interval(interval)
+            }
+            (None, Some(interval)) => {
+                return Err(error(format!(
+                "the interval of facet `{attribute}` must be a positive number, got `{interval}`"
+            )))
+            }
+            (Some(_), Some(_)) | (None, None) => {
+                return Err(error(format!(
+                    "facet `{attribute}` must define either `ranges` or an `interval`"
+                )))
+            }
+        };
+
+        Ok(Self::Ranges(FacetRangesQuery { attribute, ranges }))
+    }
+}
+
+/// A filter whose matching documents are ranked before the other documents, or after them
+/// when its weight is negative, e.g. `{ "filter": "brand = acme", "weight": 2 }`.
+///
+/// The weights of the boosts matched by a document are summed up.
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchBoosts>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct BoostQuery {
+    pub filter: Value,
+    pub weight: f64,
+}
+
+/// The numeric aggregations requested for an attribute, its count, sum, average, minimum
+/// and maximum are always computed.
+#[derive(Debug, Clone, Default, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchAggregations>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationQuery {
+    /// The percentiles to compute, between `0.0` and `100.0`.
+    #[deserr(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<f64>)]
+    pub percentiles: Vec<Percentile>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidSearchAggregations)]
+pub struct Percentile(f64);
+
+impl std::convert::TryFrom<f64> for Percentile {
+    type Error = InvalidSearchAggregations;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if (0.0..=100.0).contains(&f) {
+            Ok(Percentile(f))
+        } else {
+            Err(InvalidSearchAggregations)
+        }
+    }
+}
+
+#[derive(Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+struct FacetQueryDeserr {
+    #[deserr(default)]
+    attribute: Option<String>,
+    #[deserr(default)]
+    ranges: Option<Vec<Vec<f64>>>,
+    #[deserr(default)]
+    interval: Option<f64>,
+    #[deserr(default)]
+    hierarchy: Option<String>,
+    #[deserr(default)]
+    path: Option<Vec<String>>,
+    #[deserr(default)]
+    min_count: Option<u64>,
+    #[deserr(default)]
+    prefix: Option<String>,
+    #[deserr(default)]
+    pinned_values: Option<Vec<String>>,
+    #[deserr(default)]
+    max_values: Option<usize>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct SearchHit {
     #[serde(flatten)]
@@ -767,9 +1109,31 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregations: Option<BTreeMap<String, Aggregation>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
+    /// The ids of the rules applied to the search.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub applied_rules: Vec<String>,
+    /// The ids of the query rules that rewrote the query.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub applied_query_rules: Vec<String>,
+    /// The URL the user should be redirected to, defined by a query rule.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+    /// The best correction of the query, when its words are barely found in the documents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_query: Option<String>,
+    /// The corrections of the query along with the number of documents they match, the best first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<QuerySuggestion>,
+    /// The uid identifying the search in the events sent to the `/events` route,
+    /// when the search analytics are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub query_uid: Option<uuid::Uuid>,
 
     // These fields are only used for analytics purposes
     #[serde(skip)]
@@ -787,7 +1151,14 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            aggregations,
             semantic_hit_count,
+            applied_rules,
+            applied_query_rules,
+            redirect,
+            suggested_query,
+            suggestions,
+            query_uid,
             degraded,
             used_negative_operator,
         } = self;
@@ -810,14 +1181,52 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(aggregations) = aggregations {
+            debug.field("aggregations", &aggregations);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if !applied_rules.is_empty() {
+            debug.field("applied_rules", &applied_rules);
+        }
+        if !applied_query_rules.is_empty() {
+            debug.field("applied_query_rules", &applied_query_rules);
+        }
+        if let Some(redirect) = redirect {
+            debug.field("redirect", &redirect);
+        }
+        if let Some(suggested_query) = suggested_query {
+            debug.field("suggested_query", &suggested_query);
+        }
+        if !suggestions.is_empty() {
+            debug.field("suggestions", &suggestions);
+        }
+        if let Some(query_uid) = query_uid {
+            debug.field("query_uid", &query_uid);
+        }
 
         debug.finish()
     }
 }
 
+/// A correction of the query of a search, or a completion of a query being typed.
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct QuerySuggestion {
+    pub query: String,
+    /// The number of documents containing all the words of the suggested query.
+    pub estimated_total_hits: u64,
+}
+
+impl From<milli::QuerySuggestion> for QuerySuggestion {
+    fn from(suggestion: milli::QuerySuggestion) -> Self {
+        let milli::QuerySuggestion { query, estimated_hits } = suggestion;
+        QuerySuggestion { query, estimated_total_hits: estimated_hits }
+    }
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SimilarResult {
@@ -854,6 +1263,27 @@ pub struct FacetStats {
     pub max: f64,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct Aggregation {
+    pub count: u64,
+    pub sum: f64,
+    pub avg: f64,
+    pub min: f64,
+    pub max: f64,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[schema(value_type = BTreeMap<String, f64>)]
+    pub percentiles: IndexMap<String, f64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<QuerySuggestion>,
+    pub query: String,
+    pub processing_time_ms: u128,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FacetSearchResult {
@@ -944,6 +1374,8 @@ fn prepare_search<'t>(
 
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    search.query_syntax(query.query_syntax.into());
+    search.suggest_query(query.suggest_query);
 
     let max_total_hits = index
         .pagination_max_total_hits(rtxn)
@@ -999,17 +1431,33 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
+    if let Some(ref boosts) = query.boosts {
+        let boosts = boosts
+            .iter()
+            .filter_map(|BoostQuery { filter, weight }| {
+                parse_filter(filter, Code::InvalidSearchBoosts, features)
+                    .map(|filter| filter.map(|filter| milli::Boost { filter, weight: *weight }))
+                    .transpose()
+            })
+            .collect::<Result<_, _>>()?;
+        search.boosts(boosts);
+    }
+
     if let Some(ref locales) = query.locales {
         search.locales(locales.iter().copied().map(Into::into).collect());
     }
 
+    if let Some(ref disjunctive_facets) = query.disjunctive_facets {
+        search.disjunctive_facets(disjunctive_facets.clone());
+    }
+
     Ok((search, is_finite_pagination, max_total_hits, offset))
 }
 
 pub fn perform_search(
     index_uid: String,
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     search_kind: SearchKind,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
@@ -1021,8 +1469,13 @@ pub fn perform_search(
         None => TimeBudget::default(),
     };
 
+    // the response contains the query as it was sent
+    let original_query = query.q.clone();
+    let (applied_query_rules, redirect) = apply_query_rules(index, &rtxn, &mut query)?;
+
     let (search, is_finite_pagination, max_total_hits, offset) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+    let geo_filter_centers = search.geo_filter_centers();
 
     let (
         milli::SearchResult {
@@ -1032,12 +1485,15 @@ pub fn perform_search(
             document_scores,
             degraded,
             used_negative_operator,
+            disjunctive_candidates,
+            applied_rules,
+            query_suggestions,
         },
         semantic_hit_count,
     ) = search_from_kind(index_uid, search_kind, search)?;
 
     let SearchQuery {
-        q,
+        q: _,
         limit,
         page,
         hits_per_page,
@@ -1052,6 +1508,10 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        // already used in prepare_search
+        disjunctive_facets: _,
+        boosts: _,
+        aggregations,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
@@ -1062,7 +1522,10 @@ pub fn perform_search(
         offset: _,
         ranking_score_threshold: _,
         matching_strategy: _,
+        query_syntax: _,
+        suggest_query: _,
         attributes_to_search_on: _,
+        // already used in prepare_search
         filter: _,
         distinct: _,
     } = query;
@@ -1078,6 +1541,7 @@ pub fn perform_search(
         highlight_post_tag,
         show_matches_position,
         sort,
+        geo_filter_centers,
         show_ranking_score,
         show_ranking_score_details,
         locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
@@ -1109,28 +1573,98 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = facets
-        .map(move |facets| {
-            compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
+    let aggregations = aggregations
+        .map(|aggregations| compute_aggregations(&aggregations, index, &rtxn, candidates.clone()))
+        .transpose()?;
+
+    let (mut facet_distribution, mut facet_stats): (Option<_>, Option<_>) = facets
+        .as_ref()
+        .map(|facets| {
+            compute_facet_distribution_stats(facets, index, &rtxn, candidates, Route::Search)
         })
         .transpose()?
         .map(|ComputedFacets { distribution, stats }| (distribution, stats))
         .unzip();
 
+    // the distribution of a disjunctive facet ignores the filter conditions on it
+    for (facet, candidates) in disjunctive_candidates {
+        // keep the ranges, hierarchies and value restrictions requested in the facets
+        let mut queries: Vec<_> = facets
+            .iter()
+            .flatten()
+            .filter(|query| query.attribute() == facet.as_str())
+            .cloned()
+            .collect();
+        if queries.is_empty() {
+            queries.push(FacetQuery::from(facet));
+        }
+        let ComputedFacets { distribution, stats } =
+            compute_facet_distribution_stats(&queries, index, &rtxn, candidates, Route::Search)?;
+        facet_distribution.get_or_insert_with(BTreeMap::new).extend(distribution);
+        facet_stats.get_or_insert_with(BTreeMap::new).extend(stats);
+    }
+
     let result = SearchResult {
         hits: documents,
         hits_info,
-        query: q.unwrap_or_default(),
+        query: original_query.unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        aggregations,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        applied_rules,
+        applied_query_rules,
+        redirect,
+        suggested_query: query_suggestions.first().map(|suggestion| suggestion.query.clone()),
+        suggestions: query_suggestions.into_iter().map(QuerySuggestion::from).collect(),
+        query_uid: None,
     };
     Ok(result)
 }
 
+/// Rewrites the query with the query rules of the index matching it, in the order of their ids.
+///
+/// Returns the ids of the applied rules along with the redirect of the first rule defining one.
+fn apply_query_rules(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &mut SearchQuery,
+) -> milli::Result<(Vec<String>, Option<String>)> {
+    let mut applied = Vec::new();
+    let mut redirect = None;
+    let mut text = query.q.clone().unwrap_or_default();
+
+    for (id, rule) in index.query_rules(rtxn)? {
+        let Some(rewritten) = rule.rewrite(&text) else { continue };
+        text = rewritten;
+
+        if let Some(filter) = rule.consequence.filter {
+            // the conditions of a filter array are all required
+            query.filter = Some(match query.filter.take() {
+                None => Value::String(filter),
+                Some(Value::Array(mut filters)) => {
+                    filters.push(Value::String(filter));
+                    Value::Array(filters)
+                }
+                Some(other) => Value::Array(vec![other, Value::String(filter)]),
+            });
+        }
+        if redirect.is_none() {
+            redirect = rule.consequence.redirect;
+        }
+        applied.push(id);
+    }
+
+    if query.q.is_some() || !text.is_empty() {
+        query.q = Some(text);
+    }
+
+    Ok((applied, redirect))
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct ComputedFacets {
     #[schema(value_type = BTreeMap<String, BTreeMap<String, u64>>)]
@@ -1144,8 +1678,8 @@ pub enum Route {
     Similar,
 }
 
-fn compute_facet_distribution_stats<S: AsRef<str>>(
-    facets: &[S],
+fn compute_facet_distribution_stats(
+    facets: &[FacetQuery],
     index: &Index,
     rtxn: &RoTxn,
     candidates: roaring::RoaringBitmap,
@@ -1163,13 +1697,62 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
 
     let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
 
+    let mut hierarchies = Vec::new();
+    let facet_hierarchies = index.facet_hierarchies(rtxn).map_err(milli::Error::from)?;
+    for facet in facets {
+        if let FacetQuery::Hierarchy(FacetHierarchyQuery { hierarchy, path }) = facet {
+            let Some(levels) = facet_hierarchies.get(hierarchy) else {
+                let code = match route {
+                    Route::MultiSearch => Code::InvalidMultiSearchFacets,
+                    Route::Search | Route::Similar => Code::InvalidSearchFacets,
+                };
+                let available = if facet_hierarchies.is_empty() {
+                    "This index does not have configured facet hierarchies.".to_string()
+                } else {
+                    let names: Vec<_> = facet_hierarchies.keys().map(String::as_str).collect();
+                    format!("Available hierarchies are: `{}`.", names.join(", "))
+                };
+                return Err(ResponseError::from_msg(
+                    format!(
+                        "Invalid facet distribution: Hierarchy `{hierarchy}` is not declared. {available}"
+                    ),
+                    code,
+                ));
+            };
+            hierarchies.push(FacetHierarchy { levels: levels.clone(), path: path.clone() });
+        }
+    }
+
     // add specific facet if there is no placeholder
-    if facets.iter().all(|f| f.as_ref() != "*") {
-        let fields: Vec<_> =
-            facets.iter().map(|n| (n, sort_facet_values_by.get(n.as_ref()))).collect();
+    if facets.iter().all(|f| f.attribute() != "*") {
+        let fields: Vec<_> = facets
+            .iter()
+            .filter(|f| !matches!(f, FacetQuery::Hierarchy(_)))
+            .map(FacetQuery::attribute)
+            .chain(
+                hierarchies
+                    .iter()
+                    .flat_map(|hierarchy| hierarchy.levels.iter().map(String::as_str)),
+            )
+            .map(|attribute| (attribute, sort_facet_values_by.get(attribute)))
+            .collect();
         facet_distribution.facets(fields);
     }
 
+    facet_distribution.ranges(facets.iter().filter_map(|facet| match facet {
+        FacetQuery::Attribute(_) | FacetQuery::Hierarchy(_) | FacetQuery::Values(_) => None,
+        FacetQuery::Ranges(FacetRangesQuery { attribute, ranges }) => {
+            Some((attribute, ranges.clone()))
+        }
+    }));
+    facet_distribution.values_options(facets.iter().filter_map(|facet| match facet {
+        FacetQuery::Attribute(_) | FacetQuery::Hierarchy(_) | FacetQuery::Ranges(_) => None,
+        FacetQuery::Values(FacetValuesQuery { attribute, options }) => {
+            Some((attribute, options.clone()))
+        }
+    }));
+    facet_distribution.hierarchies(hierarchies);
+
     let distribution = facet_distribution
         .candidates(candidates)
         .default_order_by(sort_facet_values_by.get("*"))
@@ -1188,6 +1771,35 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
     Ok(ComputedFacets { distribution, stats })
 }
 
+fn compute_aggregations(
+    aggregations: &BTreeMap<String, AggregationQuery>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, Aggregation>, ResponseError> {
+    let aggregations = milli::FacetAggregations::new(rtxn, index)
+        .facets(aggregations.iter().map(|(name, AggregationQuery { percentiles })| {
+            (name, percentiles.iter().map(|Percentile(percentile)| *percentile).collect())
+        }))
+        .candidates(candidates)
+        .execute()?;
+
+    let aggregations = aggregations
+        .into_iter()
+        .map(|(name, aggregation)| {
+            let avg = aggregation.avg();
+            let milli::FacetAggregation { count, sum, min, max, percentiles } = aggregation;
+            let percentiles = percentiles
+                .into_iter()
+                .map(|(percentile, value)| (percentile.to_string(), value))
+                .collect();
+            (name, Aggregation { count, sum, avg, min, max, percentiles })
+        })
+        .collect();
+
+    Ok(aggregations)
+}
+
 pub fn search_from_kind(
     index_uid: String,
     search_kind: SearchKind,
@@ -1225,6 +1837,8 @@ struct AttributesFormat {
     highlight_post_tag: String,
     show_matches_position: bool,
     sort: Option<Vec<String>>,
+    /// The `_geoDistance` of the hits is computed from these points when they are not geo sorted.
+    geo_filter_centers: Vec<[f64; 2]>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     locales: Option<Vec<Language>>,
@@ -1265,7 +1879,7 @@ struct HitMaker<'a> {
     formatted_options: BTreeMap<FieldId, FormatOptions>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
-    sort: Option<Vec<String>>,
+    geo_distance_points: Vec<[f64; 2]>,
     show_matches_position: bool,
     locales: Option<Vec<Language>>,
 }
@@ -1391,7 +2005,11 @@ impl<'a> HitMaker<'a> {
             show_ranking_score: format.show_ranking_score,
             show_ranking_score_details: format.show_ranking_score_details,
             show_matches_position: format.show_matches_position,
-            sort: format.sort,
+            geo_distance_points: format
+                .sort
+                .as_deref()
+                .and_then(sort_geo_points)
+                .unwrap_or(format.geo_filter_centers),
             locales: format.locales,
         })
     }
@@ -1461,9 +2079,7 @@ impl<'a> HitMaker<'a> {
             &localized_attributes,
         )?;
 
-        if let Some(sort) = self.sort.as_ref() {
-            insert_geo_distance(sort, &mut document);
-        }
+        insert_geo_distance_to_points(&self.geo_distance_points, &mut document);
 
         let ranking_score =
             self.show_ranking_score.then(|| ScoreDetails::global_score(score.iter()));
@@ -1640,6 +2256,9 @@ pub fn perform_similar(
         document_scores,
         degraded: _,
         used_negative_operator: _,
+        disjunctive_candidates: _,
+        applied_rules: _,
+        query_suggestions: _,
     } = similar.execute().map_err(|err| match err {
         milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
             ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
@@ -1658,6 +2277,7 @@ pub fn perform_similar(
         highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
         show_matches_position: false,
         sort: None,
+        geo_filter_centers: Vec::new(),
         show_ranking_score,
         show_ranking_score_details,
         locales: None,
@@ -1689,19 +2309,77 @@ pub fn perform_similar(
     Ok(result)
 }
 
+pub fn perform_suggest(
+    index: &Index,
+    query: SuggestQuery,
+    features: RoFeatures,
+) -> Result<SuggestResult, ResponseError> {
+    let before_suggest = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let SuggestQuery { q, limit, filter, attributes_to_search_on } = query;
+
+    if limit > MAX_SUGGEST_LIMIT {
+        return Err(ResponseError::from_msg(
+            format!("The `limit` of a suggest request must be at most {MAX_SUGGEST_LIMIT}, but `{limit}` was found."),
+            Code::InvalidSuggestLimit,
+        ));
+    }
+
+    let mut suggest = milli::Suggest::new(q.clone(), limit, index, &rtxn);
+
+    if let Some(ref filter) = filter {
+        if let Some(facets) = parse_filter(filter, Code::InvalidSuggestFilter, features)? {
+            suggest.filter(facets);
+        }
+    }
+
+    if let Some(ref attributes_to_search_on) = attributes_to_search_on {
+        suggest.searchable_attributes(attributes_to_search_on);
+    }
+
+    let suggestions = suggest.execute().map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidSuggestFilter)
+        }
+        milli::Error::UserError(milli::UserError::InvalidSearchableAttribute { .. }) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidSuggestAttributesToSearchOn)
+        }
+        err => err.into(),
+    })?;
+
+    Ok(SuggestResult {
+        suggestions: suggestions.into_iter().map(QuerySuggestion::from).collect(),
+        query: q,
+        processing_time_ms: before_suggest.elapsed().as_millis(),
+    })
+}
+
 pub fn insert_geo_distance(sorts: &[String], document: &mut Document) {
-    lazy_static::lazy_static! {
-        static ref GEO_REGEX: Regex =
-            Regex::new(r"_geoPoint\(\s*([[:digit:].\-]+)\s*,\s*([[:digit:].\-]+)\s*\)").unwrap();
-    };
-    if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
-        // TODO: TAMO: milli encountered an internal error, what do we want to do?
-        let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
-            extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+    if let Some(points) = sort_geo_points(sorts) {
+        insert_geo_distance_to_points(&points, document);
+    }
+}
+
+/// Returns the points of the first geo sort, if any.
+fn sort_geo_points(sorts: &[String]) -> Option<Vec<[f64; 2]>> {
+    sorts.iter().find_map(|sort| {
+        let asc_desc = AscDesc::from_str(sort).ok()?;
+        asc_desc.member().geo_points().map(<[_]>::to_vec)
+    })
+}
+
+/// Inserts the distance between the document and the closest of the points.
+fn insert_geo_distance_to_points(points: &[[f64; 2]], document: &mut Document) {
+    let geo_point = &document.get("_geo").unwrap_or(&json!(null));
+    if let Some((lat, lng)) =
+        extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
+    {
+        let distance = points
+            .iter()
+            .map(|point| milli::distance_between_two_points(point, &[lat, lng]))
+            .reduce(f64::min);
+        if let Some(distance) = distance {
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
         }
     }
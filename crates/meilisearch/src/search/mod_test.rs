@@ -112,3 +112,39 @@ fn test_insert_geo_distance_with_coords_as_string() {
     insert_geo_distance(sorters, &mut document);
     assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 }
+
+#[test]
+fn test_insert_geo_distance_with_multiple_points() {
+    let value: Document = serde_json::from_str(
+        r#"{
+          "_geo": {
+            "lat": 50.629973371633746,
+            "lng": 3.0569447399419567
+          },
+          "city": "Lille",
+          "id": "1"
+        }"#,
+    )
+    .unwrap();
+
+    // the distance to the closest point is used
+    let sorters =
+        &["_geoPoint([48.8566, 2.3522], [50.629973371633746, 3.0569447399419567]):asc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    let sorters = &["_geoPoint([50.629973371633746, 3.0569447399419567], [0, 0]):desc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    let sorters = &["_geoPoint(48.8566, 2.3522):asc".to_string()];
+    let mut expected = value.clone();
+    insert_geo_distance(sorters, &mut expected);
+
+    let sorters = &["_geoPoint([0, 0], [48.8566, 2.3522]):asc".to_string()];
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), expected.get("_geoDistance"));
+}
@@ -247,6 +247,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Expression(_) => canonical_criteria.push(criterion.clone()),
             }
         }
 
@@ -327,6 +328,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Expression(_) => canonical_criteria.push(criterion.clone()),
             }
         }
 
@@ -461,6 +463,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion)
                     }
                 },
+                Criterion::Expression(_) => canonical_criteria.push(criterion),
             }
         }
 
@@ -591,7 +594,8 @@ fn canonicalize_sort(
                     }
                 }
             }
-            AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => match geo_sorted {
+            AscDesc::Asc(Member::Geo(_) | Member::GeoPoints(_))
+            | AscDesc::Desc(Member::Geo(_) | Member::GeoPoints(_)) => match geo_sorted {
                 Some(earlier_sort_index) => {
                     canonicalization_actions.push(CanonicalizationAction::RemovedDuplicate {
                         earlier_occurrence: RankingRuleSource::Sort {
@@ -688,8 +692,12 @@ impl RankingRuleSource {
                         AscDesc::Desc(Member::Field(field_name)) => {
                             format!("{field_name}:desc")
                         }
-                        AscDesc::Asc(Member::Geo(_)) => "_geo(..):asc".to_string(),
-                        AscDesc::Desc(Member::Geo(_)) => "_geo(..):desc".to_string(),
+                        AscDesc::Asc(Member::Geo(_) | Member::GeoPoints(_)) => {
+                            "_geo(..):asc".to_string()
+                        }
+                        AscDesc::Desc(Member::Geo(_) | Member::GeoPoints(_)) => {
+                            "_geo(..):desc".to_string()
+                        }
                     },
                     None => "unknown".into(),
                 }
@@ -723,7 +731,8 @@ impl RankingRule {
             | Criterion::Typo
             | Criterion::Proximity
             | Criterion::Attribute
-            | Criterion::Exactness => RankingRuleKind::Relevancy,
+            | Criterion::Exactness
+            | Criterion::Expression(_) => RankingRuleKind::Relevancy,
             Criterion::Asc(s) if s == "_geo" => RankingRuleKind::AscendingGeoSort,
 
             Criterion::Asc(_) => RankingRuleKind::AscendingSort,
@@ -749,8 +758,12 @@ impl RankingRule {
         let kind = match asc_desc {
             AscDesc::Asc(Member::Field(_)) => RankingRuleKind::AscendingSort,
             AscDesc::Desc(Member::Field(_)) => RankingRuleKind::DescendingSort,
-            AscDesc::Asc(Member::Geo(_)) => RankingRuleKind::AscendingGeoSort,
-            AscDesc::Desc(Member::Geo(_)) => RankingRuleKind::DescendingGeoSort,
+            AscDesc::Asc(Member::Geo(_) | Member::GeoPoints(_)) => {
+                RankingRuleKind::AscendingGeoSort
+            }
+            AscDesc::Desc(Member::Geo(_) | Member::GeoPoints(_)) => {
+                RankingRuleKind::DescendingGeoSort
+            }
         };
         Self {
             source: RankingRuleSource::Sort {
@@ -0,0 +1,152 @@
+use meili_snap::*;
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_analytics_not_enabled() {
+    let server = Server::new_shared();
+
+    let (response, code) = server.service.get("/analytics/top-queries").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Getting the `/analytics/top-queries` route requires enabling the `search analytics` experimental feature. Start Meilisearch with the `--experimental-search-analytics` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let (response, code) = server.service.get("/analytics/no-results").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Getting the `/analytics/no-results` route requires enabling the `search analytics` experimental feature. Start Meilisearch with the `--experimental-search-analytics` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "click",
+                "indexUid": "movies",
+                "queryUid": "b5fd0d5a-8a1b-4c3f-9e51-a5d8e5b3c1f2",
+                "objectId": "299537",
+            }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Sending events to the `/events` route requires enabling the `search analytics` experimental feature. Start Meilisearch with the `--experimental-search-analytics` option.",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn events_bad_payload() {
+    let server = Server::new_shared();
+
+    let (response, code) = server.service.post("/events", json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Missing field `eventType`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "view",
+                "indexUid": "movies",
+                "queryUid": "b5fd0d5a-8a1b-4c3f-9e51-a5d8e5b3c1f2",
+                "objectId": "299537",
+            }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Unknown value `view` at `.eventType`: expected one of `click`, `conversion`",
+      "code": "invalid_analytics_event_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_analytics_event_type"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "click",
+                "indexUid": "movies",
+                "queryUid": "doggo",
+                "objectId": "299537",
+            }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.queryUid`: invalid character: expected an optional prefix of `urn:uuid:` followed by [0-9a-fA-F-], found `o` at 2",
+      "code": "invalid_analytics_event_query_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_analytics_event_query_uid"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "click",
+                "indexUid": "movies",
+                "queryUid": "b5fd0d5a-8a1b-4c3f-9e51-a5d8e5b3c1f2",
+                "objectId": "299537",
+                "position": -1,
+            }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value type at `.position`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_analytics_event_position",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_analytics_event_position"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn analytics_bad_limit() {
+    let server = Server::new_shared();
+
+    let (response, code) = server.service.get("/analytics/top-queries?limit=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value in parameter `limit`: could not parse `doggo` as a positive integer",
+      "code": "invalid_analytics_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_analytics_limit"
+    }
+    "###);
+}
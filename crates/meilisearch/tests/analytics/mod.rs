@@ -0,0 +1,349 @@
+mod errors;
+
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use once_cell::sync::Lazy;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 287947, "title": "Shazam!" },
+        { "id": 299537, "title": "Captain Marvel" },
+        { "id": 522681, "title": "Escape Room" },
+        { "id": 166428, "title": "How to Train Your Dragon: The Hidden World" },
+        { "id": 450465, "title": "Glass" },
+    ])
+});
+
+async fn server_with_search_analytics(dir: &TempDir) -> Server {
+    let options = Opt { experimental_search_analytics: true, ..default_settings(dir.path()) };
+    Server::new_with_options(options).await.unwrap()
+}
+
+#[actix_rt::test]
+async fn top_queries() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_search_analytics(&dir).await;
+    let index = server.index("movies");
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "q": "Shazam" })).await;
+    snapshot!(code, @"200 OK");
+    assert!(response["queryUid"].is_string(), "{response}");
+    // the queries are lowercased and their whitespaces collapsed
+    index.search_post(json!({ "q": "  shazam " })).await;
+    index.search_get("?q=SHAZAM").await;
+    index.search_post(json!({ "q": "captain" })).await;
+    index.search_post(json!({ "q": "xyzzy" })).await;
+    // the placeholder searches are not recorded
+    let (response, code) = index.search_post(json!({})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["queryUid"], @"null");
+
+    let (response, code) = server.service.get("/analytics/top-queries").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].averageProcessingTimeMs" => "[time]", ".results[].lastSearchedAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "indexUid": "movies",
+          "query": "shazam",
+          "searchCount": 3,
+          "noResultsCount": 0,
+          "averageHits": 1,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        },
+        {
+          "indexUid": "movies",
+          "query": "captain",
+          "searchCount": 1,
+          "noResultsCount": 0,
+          "averageHits": 1,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        },
+        {
+          "indexUid": "movies",
+          "query": "xyzzy",
+          "searchCount": 1,
+          "noResultsCount": 1,
+          "averageHits": 0,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = server.service.get("/analytics/top-queries?limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"1");
+    snapshot!(response["results"][0]["query"], @r###""shazam""###);
+
+    let (response, code) = server.service.get("/analytics/top-queries?indexUid=products").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"
+    {
+      "results": []
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn no_results_queries() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_search_analytics(&dir).await;
+    let index = server.index("movies");
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index.search_post(json!({ "q": "glass" })).await;
+    index.search_post(json!({ "q": "xyzzy" })).await;
+    index.search_post(json!({ "q": "plugh" })).await;
+    index.search_post(json!({ "q": "plugh" })).await;
+
+    let (response, code) = server.service.get("/analytics/no-results").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].averageProcessingTimeMs" => "[time]", ".results[].lastSearchedAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "indexUid": "movies",
+          "query": "plugh",
+          "searchCount": 2,
+          "noResultsCount": 2,
+          "averageHits": 0,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        },
+        {
+          "indexUid": "movies",
+          "query": "xyzzy",
+          "searchCount": 1,
+          "noResultsCount": 1,
+          "averageHits": 0,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        }
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn multi_search_queries() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_search_analytics(&dir).await;
+    let index = server.index("movies");
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+            { "indexUid": "movies", "q": "glass" },
+            { "indexUid": "movies", "q": "xyzzy" },
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert!(response["results"][0]["queryUid"].is_string(), "{response}");
+    assert!(response["results"][1]["queryUid"].is_string(), "{response}");
+
+    // the queries of a federated search are recorded with their own hits
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+            { "indexUid": "movies", "q": "glass" },
+            { "indexUid": "movies", "q": "shazam" },
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"2");
+
+    let (response, code) = server.service.get("/analytics/top-queries").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].averageProcessingTimeMs" => "[time]", ".results[].lastSearchedAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "indexUid": "movies",
+          "query": "glass",
+          "searchCount": 2,
+          "noResultsCount": 0,
+          "averageHits": 1,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        },
+        {
+          "indexUid": "movies",
+          "query": "shazam",
+          "searchCount": 1,
+          "noResultsCount": 0,
+          "averageHits": 1,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        },
+        {
+          "indexUid": "movies",
+          "query": "xyzzy",
+          "searchCount": 1,
+          "noResultsCount": 1,
+          "averageHits": 0,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 0,
+          "conversionCount": 0,
+          "lastSearchedAt": "[date]"
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = server.service.get("/analytics/top-queries?limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"].as_array().unwrap().iter().map(|query| &query["query"]).collect::<Vec<_>>()), @r###"
+    [
+      "glass"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn click_and_conversion_events() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_search_analytics(&dir).await;
+    let index = server.index("movies");
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, _code) = index.search_post(json!({ "q": "captain" })).await;
+    let query_uid = response["queryUid"].as_str().unwrap().to_string();
+
+    let (response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "click",
+                "eventName": "Movie clicked",
+                "indexUid": "movies",
+                "queryUid": query_uid,
+                "objectId": "299537",
+                "position": 0,
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response, @"null");
+
+    let (_response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "conversion",
+                "indexUid": "movies",
+                "queryUid": query_uid,
+                "objectId": "299537",
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+
+    // the events of another index or of an unknown search are not attributed to the query
+    let (_response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "click",
+                "indexUid": "products",
+                "queryUid": query_uid,
+                "objectId": "299537",
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let (_response, code) = server
+        .service
+        .post(
+            "/events",
+            json!({
+                "eventType": "click",
+                "indexUid": "movies",
+                "queryUid": "b5fd0d5a-8a1b-4c3f-9e51-a5d8e5b3c1f2",
+                "objectId": "299537",
+            }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) = server.service.get("/analytics/top-queries").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].averageProcessingTimeMs" => "[time]", ".results[].lastSearchedAt" => "[date]" }), @r###"
+    {
+      "results": [
+        {
+          "indexUid": "movies",
+          "query": "captain",
+          "searchCount": 1,
+          "noResultsCount": 0,
+          "averageHits": 1,
+          "averageProcessingTimeMs": "[time]",
+          "clickCount": 1,
+          "conversionCount": 1,
+          "lastSearchedAt": "[date]"
+        }
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_analytics_are_restricted_to_the_authorized_indexes() {
+    let dir = TempDir::new().unwrap();
+    let options = Opt { experimental_search_analytics: true, ..default_settings(dir.path()) };
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    for uid in ["movies", "products"] {
+        let index = server.index(uid);
+        let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+        index.wait_task(task.uid()).await.succeeded();
+        index.search_post(json!({ "q": "glass" })).await;
+    }
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "indexes": ["movies"],
+            "actions": ["analytics.get"],
+            "expiresAt": null,
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let (response, code) = server.service.get("/analytics/top-queries").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"].as_array().unwrap().iter().map(|query| &query["indexUid"]).collect::<Vec<_>>()), @r###"
+    [
+      "movies"
+    ]
+    "###);
+}
@@ -16,6 +16,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("POST",    "/multi-search") =>                                    hashset!{"search", "*"},
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
+            ("POST",    "/indexes/products/suggest") =>                        hashset!{"search", "*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
@@ -70,6 +71,9 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PATCH",   "/experimental-features") =>                           hashset!{"experimental.update", "*"},
             ("GET",   "/network") =>                                           hashset!{"network.get", "*"},
             ("PATCH",   "/network") =>                                         hashset!{"network.update", "*"},
+            ("POST",    "/events") =>                                          hashset!{"search", "*"},
+            ("GET",     "/analytics/top-queries") =>                           hashset!{"analytics.get", "*"},
+            ("GET",     "/analytics/no-results") =>                            hashset!{"analytics.get", "*"},
         };
 
         authorizations
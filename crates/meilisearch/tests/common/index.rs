@@ -226,6 +226,22 @@ impl<'a> Index<'a, Owned> {
         self.service.put_encoded(url, settings, self.encoder).await
     }
 
+    pub async fn import_synonym_sets(&self, synonym_sets: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/synonym-sets/import", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, synonym_sets, self.encoder).await
+    }
+
+    /// Adds the values to a list setting, `setting` being the route of the setting.
+    pub async fn add_to_setting(&self, setting: &str, values: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/{setting}/add", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, values, self.encoder).await
+    }
+
+    pub async fn remove_from_setting(&self, setting: &str, values: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/{setting}/remove", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, values, self.encoder).await
+    }
+
     pub async fn update_settings_distinct_attribute(&self, settings: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings/distinct-attribute", urlencode(self.uid.as_ref()));
         self.service.put_encoded(url, settings, self.encoder).await
@@ -546,6 +562,11 @@ impl<State> Index<'_, State> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn suggest(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/suggest", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn get_distinct_attribute(&self) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
@@ -3,6 +3,7 @@
 use std::marker::PhantomData;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use actix_http::body::MessageBody;
@@ -10,6 +11,7 @@ use actix_web::dev::ServiceResponse;
 use actix_web::http::StatusCode;
 use byte_unit::{Byte, Unit};
 use clap::Parser;
+use meilisearch::analytics::local_analytics::LocalAnalytics;
 use meilisearch::option::{IndexerOpts, MaxMemory, MaxThreads, Opt};
 use meilisearch::setup_meilisearch;
 use once_cell::sync::Lazy;
@@ -51,7 +53,8 @@ impl Server<Owned> {
         let options = default_settings(dir.path());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let local_analytics = Arc::new(LocalAnalytics::new(&options).unwrap());
+        let service = Service { index_scheduler, auth, local_analytics, options, api_key: None };
 
         Server { service, _dir: Some(dir), _marker: PhantomData }
     }
@@ -66,7 +69,8 @@ impl Server<Owned> {
         options.master_key = Some("MASTER_KEY".to_string());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let local_analytics = Arc::new(LocalAnalytics::new(&options).unwrap());
+        let service = Service { index_scheduler, auth, local_analytics, options, api_key: None };
 
         Server { service, _dir: Some(dir), _marker: PhantomData }
     }
@@ -79,7 +83,8 @@ impl Server<Owned> {
 
     pub async fn new_with_options(options: Opt) -> Result<Self, anyhow::Error> {
         let (index_scheduler, auth) = setup_meilisearch(&options)?;
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let local_analytics = Arc::new(LocalAnalytics::new(&options)?);
+        let service = Service { index_scheduler, auth, local_analytics, options, api_key: None };
 
         Ok(Server { service, _dir: None, _marker: PhantomData })
     }
@@ -199,7 +204,8 @@ impl Server<Shared> {
         let options = default_settings(dir.path());
 
         let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, api_key: None, options };
+        let local_analytics = Arc::new(LocalAnalytics::new(&options).unwrap());
+        let service = Service { index_scheduler, auth, local_analytics, api_key: None, options };
 
         Server { service, _dir: Some(dir), _marker: PhantomData }
     }
@@ -9,6 +9,7 @@ use actix_web::test;
 use actix_web::test::TestRequest;
 use actix_web::web::Data;
 use index_scheduler::IndexScheduler;
+use meilisearch::analytics::local_analytics::LocalAnalytics;
 use meilisearch::analytics::Analytics;
 use meilisearch::search_queue::SearchQueue;
 use meilisearch::{create_app, Opt, SubscriberForSecondLayer};
@@ -22,6 +23,7 @@ use crate::common::Value;
 pub struct Service {
     pub index_scheduler: Arc<IndexScheduler>,
     pub auth: Arc<AuthController>,
+    pub local_analytics: Arc<LocalAnalytics>,
     pub options: Opt,
     pub api_key: Option<String>,
 }
@@ -143,6 +145,7 @@ impl Service {
             self.options.clone(),
             (route_layer_handle, stderr_layer_handle),
             Data::new(Analytics::no_analytics()),
+            self.local_analytics.clone().into(),
             true,
         ))
         .await
@@ -75,6 +75,9 @@ async fn import_dump_v1_movie_raw() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -88,13 +91,17 @@ async fn import_dump_v1_movie_raw() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -103,7 +110,10 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -249,6 +259,9 @@ async fn import_dump_v1_movie_with_settings() {
         "the"
       ],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -262,13 +275,17 @@ async fn import_dump_v1_movie_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -277,7 +294,10 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -422,6 +442,9 @@ async fn import_dump_v1_rubygems_with_settings() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -435,13 +458,17 @@ async fn import_dump_v1_rubygems_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -450,7 +477,10 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -581,6 +611,9 @@ async fn import_dump_v2_movie_raw() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -594,13 +627,17 @@ async fn import_dump_v2_movie_raw() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -609,7 +646,10 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -752,6 +792,9 @@ async fn import_dump_v2_movie_with_settings() {
         "the"
       ],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -765,13 +808,17 @@ async fn import_dump_v2_movie_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -780,7 +827,10 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -922,6 +972,9 @@ async fn import_dump_v2_rubygems_with_settings() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -935,13 +988,17 @@ async fn import_dump_v2_rubygems_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -950,7 +1007,10 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -1081,6 +1141,9 @@ async fn import_dump_v3_movie_raw() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -1094,13 +1157,17 @@ async fn import_dump_v3_movie_raw() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1109,7 +1176,10 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -1252,6 +1322,9 @@ async fn import_dump_v3_movie_with_settings() {
         "the"
       ],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -1265,13 +1338,17 @@ async fn import_dump_v3_movie_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1280,7 +1357,10 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -1422,6 +1502,9 @@ async fn import_dump_v3_rubygems_with_settings() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -1435,13 +1518,17 @@ async fn import_dump_v3_rubygems_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1450,7 +1537,10 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -1581,6 +1671,9 @@ async fn import_dump_v4_movie_raw() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -1594,13 +1687,17 @@ async fn import_dump_v4_movie_raw() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1609,7 +1706,10 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -1752,6 +1852,9 @@ async fn import_dump_v4_movie_with_settings() {
         "the"
       ],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -1765,13 +1868,17 @@ async fn import_dump_v4_movie_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1780,7 +1887,10 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -1922,6 +2032,9 @@ async fn import_dump_v4_rubygems_with_settings() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -1935,13 +2048,17 @@ async fn import_dump_v4_rubygems_with_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1950,7 +2067,10 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###
     );
@@ -2212,6 +2332,9 @@ async fn import_dump_v6_containing_experimental_features() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -2225,13 +2348,17 @@ async fn import_dump_v6_containing_experimental_features() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -2240,7 +2367,10 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###);
 
@@ -2445,6 +2575,9 @@ async fn generate_and_import_dump_containing_vectors() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -2458,13 +2591,17 @@ async fn generate_and_import_dump_containing_vectors() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -2482,7 +2619,10 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###);
 
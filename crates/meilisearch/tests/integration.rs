@@ -1,3 +1,4 @@
+mod analytics;
 mod auth;
 mod batches;
 mod common;
@@ -56,6 +56,7 @@ async fn basic_test_log_stream_route() {
         server.service.options.clone(),
         (route_layer_handle, stderr_layer_handle),
         Data::new(Analytics::no_analytics()),
+        server.service.local_analytics.clone().into(),
         true,
     ))
     .await;
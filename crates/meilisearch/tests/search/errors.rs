@@ -386,6 +386,193 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_disjunctive_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"disjunctiveFacets": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.disjunctiveFacets`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_values_options() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .search_post(json!({"facets": [{ "attribute": "price", "interval": 10, "minCount": 2 }]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0]`: facet `price` cannot define `minCount`, `prefix`, `pinnedValues` or `maxValues` along with `ranges` or an `interval`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{ "attribute": "color", "minCount": -1 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facets[0].minCount`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_ranges() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{ "attribute": "price", "interval": 0 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0]`: the interval of facet `price` must be a positive number, got `0`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{ "attribute": "price", "ranges": [[10, 0]] }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0]`: the ranges of facet `price` must be made of a start and an end greater than or equal to it, got `[10.0, 0.0]`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"facets": [{ "attribute": "price", "ranges": [[0, 10]], "interval": 10 }]}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0]`: facet `price` must define either `ranges` or an `interval`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_hierarchy() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({"facets": [{ "attribute": "category", "path": ["Electronics"] }]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0]`: facet `category` cannot define a `path`, only a `hierarchy` can",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{ "path": ["Electronics"] }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facets[0]`: a facet must define either an `attribute` or a `hierarchy`",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facets": [{ "hierarchy": "category", "path": [] }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet distribution: Hierarchy `category` is not declared. This index does not have configured facet hierarchies.",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_aggregations() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"aggregations": { "price": { "percentiles": [101] } }})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.aggregations.price.percentiles[0]`: the value of `aggregations` is invalid, expected an object associating numeric attributes to their `percentiles`, floats between `0.0` and `100.0`.",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"aggregations": ["price"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.aggregations`: expected an object, but found an array: `[\"price\"]`",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_non_filterable_aggregations() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (response, _code) = index.update_settings(json!({"filterableAttributes": ["title"]})).await;
+    // Wait for the settings update to complete
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({"aggregations": { "doggo": {} }})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid aggregations: Attribute `doggo` is not filterable. Available filterable attributes patterns are: `title`.",
+      "code": "invalid_search_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_aggregations"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new_shared();
@@ -636,6 +823,62 @@ async fn search_bad_matching_strategy() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_query_syntax() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"querySyntax": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.querySyntax`: expected one of `simple`, `advanced`",
+      "code": "invalid_search_query_syntax",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_query_syntax"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?querySyntax=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `querySyntax`: expected one of `simple`, `advanced`",
+      "code": "invalid_search_query_syntax",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_query_syntax"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_suggest_query() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"suggestQuery": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.suggestQuery`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_suggest_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_suggest_query"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?suggestQuery=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `suggestQuery`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_suggest_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_suggest_query"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn filter_invalid_syntax_object() {
     test_settings_documents_indexing_swapping_and_search(
@@ -720,6 +963,57 @@ async fn filter_invalid_attribute_array() {
     .await;
 }
 
+#[actix_rt::test]
+async fn search_bad_boosts() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"boosts": [{ "filter": "title = Glass" }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `weight` inside `.boosts[0]`",
+      "code": "invalid_search_boosts",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boosts"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boosts": [{ "filter": "title & Glass", "weight": 1 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+      "code": "invalid_search_boosts",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boosts"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn boost_invalid_attribute() {
+    test_settings_documents_indexing_swapping_and_search(
+        &DOCUMENTS,
+        &json!({"filterableAttributes": ["title"]}),
+        &json!({"boosts": [{ "filter": "many = Glass", "weight": 1 }]}),
+        |response, code| {
+            snapshot!(response, @r###"
+            {
+              "message": "Index `[uuid]`: Invalid boost at index 0: Attribute `many` is not filterable. Available filterable attribute patterns are: `title`.\n1:5 many = Glass",
+              "code": "invalid_search_boosts",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_search_boosts"
+            }
+            "###);
+            snapshot!(code, @"400 Bad Request");
+        },
+    )
+    .await;
+}
+
 #[actix_rt::test]
 async fn filter_invalid_attribute_string() {
     test_settings_documents_indexing_swapping_and_search(
@@ -750,7 +1044,7 @@ async fn filter_reserved_geo_attribute_array() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+              "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -771,7 +1065,7 @@ async fn filter_reserved_geo_attribute_string() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+              "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -792,7 +1086,7 @@ async fn filter_reserved_attribute_array() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+              "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -813,7 +1107,7 @@ async fn filter_reserved_attribute_string() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+              "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -834,7 +1128,7 @@ async fn filter_reserved_geo_point_array() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+              "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -855,7 +1149,7 @@ async fn filter_reserved_geo_point_string() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+              "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -120,6 +120,61 @@ async fn search_with_contains_filter() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn search_with_contains_filter_on_infix_attributes() {
+    let temp = TempDir::new().unwrap();
+    let server = Server::new_with_options(Opt {
+        experimental_contains_filter: true,
+        ..default_settings(temp.path())
+    })
+    .await
+    .unwrap();
+    let index = server.index("movies");
+
+    let (task, _code) = index
+        .update_settings(json!({"filterableAttributes": ["title"], "infixAttributes": ["title"]}))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = DOCUMENTS.clone();
+    let (request, _code) = index.add_documents(documents, None).await;
+    index.wait_task(request.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "title CONTAINS cap",
+            "attributesToRetrieve": ["title"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "title": "Captain Marvel"
+      },
+      {
+        "title": "Escape Room"
+      }
+    ]
+    "###);
+
+    // the words of the infix attributes are also matched by a part of them
+    let (response, code) = index
+        .search_post(json!({
+            "q": "zam",
+            "attributesToRetrieve": ["title"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "title": "Shazam!"
+      }
+    ]
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_with_pattern_filter_settings() {
     // Check if the Equality filter works with patterns
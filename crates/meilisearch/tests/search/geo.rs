@@ -95,7 +95,8 @@ async fn geo_bounding_box_with_string_and_number() {
                       "_geo": {
                         "lat": 34.0522,
                         "lng": -118.2437
-                      }
+                      },
+                      "_geoDistance": 12574353
                     },
                     {
                       "id": 2,
@@ -106,7 +107,8 @@ async fn geo_bounding_box_with_string_and_number() {
                       "_geo": {
                         "lat": "45.4777599",
                         "lng": "9.1967508"
-                      }
+                      },
+                      "_geoDistance": 5136946
                     }
                   ],
                   "query": "",
@@ -121,6 +123,157 @@ async fn geo_bounding_box_with_string_and_number() {
         .await;
 }
 
+#[actix_rt::test]
+async fn geo_polygon_with_string_and_number() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([46, 9], [46, 10], [45, 10], [45, 9])",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+                {
+                  "hits": [
+                    {
+                      "id": 2,
+                      "name": "La Bella Italia",
+                      "address": "456 Elm Street, Townsville",
+                      "type": "Italian",
+                      "rating": 9,
+                      "_geo": {
+                        "lat": "45.4777599",
+                        "lng": "9.1967508"
+                      },
+                      "_geoDistance": 23768
+                    }
+                  ],
+                  "query": "",
+                  "processingTimeMs": "[time]",
+                  "limit": 20,
+                  "offset": 0,
+                  "estimatedTotalHits": 1
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // the polygons can cross the antimeridian
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([50, 170], [50, -110], [20, -110], [20, 170])",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_distance_from_geo_filters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await.succeeded();
+
+    // without a geo sort, the distance is computed from the center of the radius
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(45.472735, 9.184019, 10000)",
+                "attributesToRetrieve": ["id", "_geo"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geo": {
+                      "lat": "45.4777599",
+                      "lng": "9.1967508"
+                    },
+                    "_geoDistance": 1139
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // or from the center of the bounding box, even when it crosses the antimeridian
+    index
+        .search(
+            json!({
+                "filter": "_geoBoundingBox([50, -110], [20, 170])",
+                "attributesToRetrieve": ["id", "_geo"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "_geo": {
+                      "lat": 34.0522,
+                      "lng": -118.2437
+                    },
+                    "_geoDistance": 2898828
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // or from the center of the polygon
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([50, 170], [50, -110], [20, -110], [20, 170])",
+                "attributesToRetrieve": ["id", "_geo"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "_geo": {
+                      "lat": 34.0522,
+                      "lng": -118.2437
+                    },
+                    "_geoDistance": 2898828
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
 #[actix_rt::test]
 async fn bug_4640() {
     // https://github.com/meilisearch/meilisearch/issues/4640
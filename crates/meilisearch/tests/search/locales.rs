@@ -1335,3 +1335,130 @@ async fn german_search() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn synonym_sets_per_locale() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+      {"id": 0, "title": "fries"},
+      {"id": 1, "title": "frites"},
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _) = index
+        .update_settings(json!({
+            "synonymSets": [{ "synonyms": ["chips", "fries"], "locales": ["eng"] }]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the imported sets are appended to the existing ones
+    let (task, code) = index
+        .import_synonym_sets(json!([
+            { "kind": "oneWay", "input": ["chips"], "synonyms": ["frites"], "locales": ["fra"] }
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["synonymSets"]), @r###"
+    [
+      {
+        "kind": "equivalent",
+        "synonyms": [
+          "chips",
+          "fries"
+        ],
+        "locales": [
+          "eng"
+        ]
+      },
+      {
+        "kind": "oneWay",
+        "input": [
+          "chips"
+        ],
+        "synonyms": [
+          "frites"
+        ],
+        "locales": [
+          "fra"
+        ]
+      }
+    ]
+    "###);
+
+    for (locales, expected) in [
+        (json!(["eng"]), json!([{"id": 0}])),
+        (json!(["fra"]), json!([{"id": 1}])),
+        (json!([]), json!([])),
+    ] {
+        index
+            .search(
+                json!({"q": "chips", "locales": locales, "attributesToRetrieve": ["id"]}),
+                |response, code| {
+                    assert_eq!(code, 200, "{response}");
+                    assert_eq!(response["hits"], expected, "{locales}");
+                },
+            )
+            .await;
+    }
+}
+
+#[actix_rt::test]
+async fn stemming_per_localized_attribute() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                { "attributePatterns": ["title"], "locales": ["eng"], "stemming": true }
+            ]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "title": "she runs"},
+      {"id": 1, "title": "new running shoes"},
+      {"id": 2, "title": "a walk"},
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["localizedAttributes"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "title"
+        ],
+        "locales": [
+          "eng"
+        ],
+        "stemming": true
+      }
+    ]
+    "###);
+
+    index
+        .search(json!({"q": "running", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 0
+              }
+            ]
+            "###);
+        })
+        .await;
+}
@@ -126,3 +126,24 @@ async fn search_with_unknown_word() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn search_with_advanced_query_syntax() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+
+    // `captain` is never removed by the matching strategy
+    index
+        .search(json!({"q": "+captain -planet (marvel OR shazam)", "querySyntax": "advanced", "matchingStrategy": "last", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"4"},{"id":"7"}]"###);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "+captain -planet (marvel OR shazam)", "querySyntax": "advanced", "matchingStrategy": "all", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"4"}]"###);
+        })
+        .await;
+}
@@ -15,6 +15,7 @@ mod multi;
 mod pagination;
 mod restrict_searchable;
 mod search_queue;
+mod suggest;
 
 use meili_snap::{json_string, snapshot};
 
@@ -254,6 +255,87 @@ async fn negative_phrase_search() {
         .await;
 }
 
+#[actix_rt::test]
+async fn fuzzy_phrase_search() {
+    let index = shared_index_with_documents().await;
+    index
+        .search(json!({"q": "\"how train\"" }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 0);
+        })
+        .await;
+
+    // up to one word between the words of the phrase
+    index
+        .search(json!({"q": "\"how train\"~1" }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0]["id"], "166428");
+        })
+        .await;
+
+    // the slop is capped to the farthest proximity stored in the index
+    index
+        .search(json!({"q": "\"how train\"~20" }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0]["id"], "166428");
+        })
+        .await;
+
+    // the words of a fuzzy phrase tolerate typos
+    index
+        .search(
+            json!({"q": "\"trein your\"~", "attributesToHighlight": ["title"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                let hits = response["hits"].as_array().unwrap();
+                assert_eq!(hits.len(), 1);
+                assert_eq!(hits[0]["id"], "166428");
+                assert_eq!(
+                    hits[0]["_formatted"]["title"],
+                    "How to <em>Train</em> <em>Your</em> Dragon: The Hidden World"
+                );
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_suggested_query() {
+    let index = shared_index_with_documents().await;
+    index
+        .search(json!({"q": "captian marvl", "suggestQuery": true }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+            assert_eq!(response["suggestedQuery"], "captain marvel");
+            assert_eq!(
+                response["suggestions"],
+                json!([{ "query": "captain marvel", "estimatedTotalHits": 1 }])
+            );
+        })
+        .await;
+
+    // nothing is suggested when the words are found without typos
+    index
+        .search(json!({"q": "captain marvel", "suggestQuery": true }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert!(response.get("suggestedQuery").is_none(), "{response}");
+            assert!(response.get("suggestions").is_none(), "{response}");
+        })
+        .await;
+
+    // the suggestions must be requested
+    index
+        .search(json!({"q": "captian marvl" }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert!(response.get("suggestedQuery").is_none(), "{response}");
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn negative_word_search() {
     let index = shared_index_with_documents().await;
@@ -577,6 +659,494 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_facet_ranges_distribution() {
+    let index = shared_index_with_nested_documents().await;
+
+    index
+        .search(
+            json!({
+                "facets": ["father", { "attribute": "doggos.age", "ranges": [[0, 5], [5, 10], [10, 20]] }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                let dist = response["facetDistribution"].as_object().unwrap();
+                assert_eq!(dist.len(), 2, "{dist:?}");
+                assert_eq!(
+                    dist["father"],
+                    json!({ "jean": 1, "pierre": 1, "romain": 1, "jean-baptiste": 1})
+                );
+                assert_eq!(dist["doggos.age"], json!({ "0..5": 1, "5..10": 2, "10..20": 0 }));
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "facets": [{ "attribute": "doggos.age", "interval": 3 }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                let dist = response["facetDistribution"].as_object().unwrap();
+                assert_eq!(dist["doggos.age"], json!({ "0..3": 1, "3..6": 2, "6..9": 2 }));
+                assert_eq!(response["facetStats"]["doggos.age"], json!({ "min": 2.0, "max": 8.0 }));
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "doggos.age < 5",
+                "facets": [{ "attribute": "doggos.age", "interval": 3 }]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                let dist = response["facetDistribution"].as_object().unwrap();
+                assert_eq!(dist["doggos.age"], json!({ "0..3": 1, "3..6": 1 }));
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_facet_hierarchy_distribution() {
+    let documents = json!([
+        { "id": 0, "category": { "lvl0": "Electronics", "lvl1": "Electronics > Phones" } },
+        { "id": 1, "category": { "lvl0": "Electronics", "lvl1": "Electronics > Laptops" } },
+        { "id": 2, "category": { "lvl0": "Electronics", "lvl1": "Electronics > Phones" } },
+        { "id": 3, "category": { "lvl0": "Books", "lvl1": "Books > Fiction" } },
+    ]);
+    let settings = json!({
+        "filterableAttributes": ["category.lvl0", "category.lvl1", "id"],
+        "faceting": { "hierarchies": { "category": ["category.lvl0", "category.lvl1"] } }
+    });
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({
+            "facets": ["id", { "hierarchy": "category", "path": ["Electronics"] }]
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "category.lvl0": {
+                "Books": 1,
+                "Electronics": 3
+              },
+              "category.lvl1": {
+                "Electronics > Laptops": 1,
+                "Electronics > Phones": 2
+              },
+              "id": {
+                "0": 1,
+                "1": 1,
+                "2": 1,
+                "3": 1
+              }
+            }
+            "###);
+        },
+    )
+    .await;
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({
+            "filter": "id != 2",
+            "facets": [{ "hierarchy": "category" }]
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "category.lvl0": {
+                "Books": 1,
+                "Electronics": 2
+              }
+            }
+            "###);
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn search_disjunctive_facets() {
+    let documents = json!([
+        { "id": 0, "title": "phone", "color": "red", "brand": "apple", "price": 100 },
+        { "id": 1, "title": "phone", "color": "blue", "brand": "apple", "price": 300 },
+        { "id": 2, "title": "phone", "color": "red", "brand": "samsung", "price": 500 },
+        { "id": 3, "title": "phone", "color": "green", "brand": "apple", "price": 700 },
+        { "id": 4, "title": "laptop", "color": "blue", "brand": "apple", "price": 300 },
+    ]);
+    let settings = json!({ "filterableAttributes": ["color", "brand", "price"] });
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({
+            "q": "phone",
+            "filter": "color = red AND brand = apple",
+            "facets": ["brand"],
+            "disjunctiveFacets": ["color"]
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{response}");
+            // the distribution of the color ignores `color = red` but not `brand = apple`
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "brand": {
+                "apple": 1
+              },
+              "color": {
+                "blue": 1,
+                "green": 1,
+                "red": 1
+              }
+            }
+            "###);
+        },
+    )
+    .await;
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({
+            "q": "phone",
+            "filter": "color IN [red, blue]",
+            "disjunctiveFacets": ["color"]
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "color": {
+                "blue": 1,
+                "green": 1,
+                "red": 2
+              }
+            }
+            "###);
+        },
+    )
+    .await;
+
+    // the ranges requested on a disjunctive facet are kept
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({
+            "q": "phone",
+            "filter": "price >= 400",
+            "facets": [{ "attribute": "price", "ranges": [[0, 400], [400, 800]] }],
+            "disjunctiveFacets": ["price"]
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{response}");
+            let dist = response["facetDistribution"].as_object().unwrap();
+            assert_eq!(dist.len(), 1, "{dist:?}");
+            assert_eq!(dist["price"], json!({ "0..400": 2, "400..800": 2 }));
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn search_facet_values_options() {
+    let documents = json!([
+        { "id": 0, "color": "red", "brand": "Apple" },
+        { "id": 1, "color": "blue", "brand": "Apple" },
+        { "id": 2, "color": "red", "brand": "Samsung" },
+        { "id": 3, "color": "green", "brand": "Sony" },
+        { "id": 4, "color": "blue", "brand": "Sanyo" },
+    ]);
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &json!({ "filterableAttributes": ["color", "brand"] }),
+        &json!({
+            "facets": [
+                { "attribute": "color", "minCount": 2 },
+                { "attribute": "brand", "prefix": "sa", "pinnedValues": ["apple"], "maxValues": 1 }
+            ]
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "brand": {
+                "Apple": 2,
+                "Samsung": 1
+              },
+              "color": {
+                "blue": 2,
+                "red": 2
+              }
+            }
+            "###);
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn search_with_boosts() {
+    let documents = json!([
+        { "id": 0, "brand": "acme", "color": "red" },
+        { "id": 1, "brand": "other", "color": "red" },
+        { "id": 2, "brand": "acme", "color": "blue" },
+        { "id": 3, "brand": "other", "color": "blue" },
+        { "id": 4, "brand": "other", "color": "green" },
+    ]);
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &json!({ "filterableAttributes": ["brand", "color"] }),
+        &json!({
+            "boosts": [
+                { "filter": "brand = acme", "weight": 2 },
+                { "filter": ["color = blue"], "weight": -1 }
+            ],
+            "attributesToRetrieve": ["id"],
+            "showRankingScoreDetails": true
+        }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "_rankingScoreDetails": {
+                  "boost": {
+                    "order": 0,
+                    "weight": 2.0,
+                    "score": 1.0
+                  }
+                }
+              },
+              {
+                "id": 2,
+                "_rankingScoreDetails": {
+                  "boost": {
+                    "order": 0,
+                    "weight": 1.0,
+                    "score": 0.75
+                  }
+                }
+              },
+              {
+                "id": 1,
+                "_rankingScoreDetails": {
+                  "boost": {
+                    "order": 0,
+                    "weight": 0.0,
+                    "score": 0.5
+                  }
+                }
+              },
+              {
+                "id": 4,
+                "_rankingScoreDetails": {
+                  "boost": {
+                    "order": 0,
+                    "weight": 0.0,
+                    "score": 0.5
+                  }
+                }
+              },
+              {
+                "id": 3,
+                "_rankingScoreDetails": {
+                  "boost": {
+                    "order": 0,
+                    "weight": -1.0,
+                    "score": 0.25
+                  }
+                }
+              }
+            ]
+            "###);
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn search_with_rules() {
+    let documents = json!([
+        { "id": 0, "title": "shoes" },
+        { "id": 1, "title": "shoes" },
+        { "id": 2, "title": "shoes" },
+        { "id": 3, "title": "sandals" },
+        { "id": 4, "title": "hat" },
+    ]);
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &json!({
+            "rules": {
+                "summer": {
+                    "condition": { "query": "shoes" },
+                    "actions": { "pin": [{ "id": "3", "position": 1 }], "hide": ["1"] }
+                }
+            }
+        }),
+        &json!({ "q": "Shoes", "attributesToRetrieve": ["id"] }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 3
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+            snapshot!(json_string!(response["appliedRules"]), @r###"
+            [
+              "summer"
+            ]
+            "###);
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn search_with_query_rules() {
+    let documents = json!([
+        { "id": 0, "title": "red shoes", "price": 15 },
+        { "id": 1, "title": "red shoes", "price": 80 },
+        { "id": 2, "title": "blue shoes", "price": 10 },
+        { "id": 3, "title": "returns policy", "price": 0 },
+    ]);
+    let settings = json!({
+        "filterableAttributes": ["price"],
+        "queryRules": {
+            "cheap": {
+                "condition": { "query": "cheap", "anchoring": "contains" },
+                "consequence": { "replaceWith": "", "filter": "price < 20" }
+            },
+            "returns": {
+                "condition": { "query": "returns" },
+                "consequence": { "redirect": "https://example.com/returns" }
+            }
+        }
+    });
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({ "q": "Cheap red shoes", "filter": "price > 0", "attributesToRetrieve": ["id"] }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+            snapshot!(response["query"], @r###""Cheap red shoes""###);
+            snapshot!(json_string!(response["appliedQueryRules"]), @r###"
+            [
+              "cheap"
+            ]
+            "###);
+            snapshot!(response["redirect"], @"null");
+        },
+    )
+    .await;
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &settings,
+        &json!({ "q": "returns", "attributesToRetrieve": ["id"] }),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            snapshot!(response["redirect"], @r###""https://example.com/returns""###);
+        },
+    )
+    .await;
+}
+
+#[actix_rt::test]
+async fn search_aggregations() {
+    let index = shared_index_with_nested_documents().await;
+
+    index
+        .search(
+            json!({
+                "aggregations": { "doggos.age": { "percentiles": [50, 90] } }
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                snapshot!(json_string!(response["aggregations"]), @r###"
+                {
+                  "doggos.age": {
+                    "count": 5,
+                    "sum": 25.0,
+                    "avg": 5.0,
+                    "min": 2.0,
+                    "max": 8.0,
+                    "percentiles": {
+                      "50": 5.0,
+                      "90": 8.0
+                    }
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "doggos.age < 5",
+                "aggregations": { "doggos.age": {}, "father": {} }
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                snapshot!(json_string!(response["aggregations"]), @r###"
+                {
+                  "doggos.age": {
+                    "count": 2,
+                    "sum": 6.0,
+                    "avg": 3.0,
+                    "min": 2.0,
+                    "max": 4.0
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new_shared();
@@ -2054,3 +2624,42 @@ async fn test_exact_typos_terms() {
     )
     .await;
 }
+
+#[actix_rt::test]
+async fn test_phonetic_typo_tolerance() {
+    let documents = json!([
+        { "id": 0, "name": "Smith" },
+        { "id": 1, "name": "Schmidt" },
+        { "id": 2, "name": "Johnson", "note": "Smith" },
+        { "id": 3, "name": "Johnson", "note": "Schmidt" },
+    ]);
+
+    test_settings_documents_indexing_swapping_and_search(
+        &documents,
+        &json!({
+            "searchableAttributes": ["name", "note"],
+            "typoTolerance": {
+              "phonetic": { "attributePatterns": ["name"], "algorithm": "doubleMetaphone" }
+            }
+        }),
+        &json!({"q": "Smith", "attributesToRetrieve": ["id"]}),
+        |response, code| {
+            assert_eq!(code, 200, "{response}");
+            // the names sounding alike come last and the notes are not matched by their sound
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        },
+    )
+    .await;
+}
@@ -3514,6 +3514,34 @@ async fn federation_federated_contains_pagination() {
     "###);
 }
 
+#[actix_rt::test]
+async fn federation_federated_contains_suggest_query() {
+    let server = Server::new().await;
+
+    let index = server.index("fruits");
+
+    let documents = FRUITS_DOCUMENTS.clone();
+    let (value, _) = index.add_documents(documents, None).await;
+    index.wait_task(value.uid()).await.succeeded();
+
+    // fail when a federated query asks for the query suggestions
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "fruits", "q": "apple red"},
+        {"indexUid": "fruits", "q": "aple red", "suggestQuery": true},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    insta::assert_json_snapshot!(response, { ".processingTimeMs" => "[time]" }, @r###"
+    {
+      "message": "Inside `.queries[1]`: Using `suggestQuery` is not allowed in federated queries.\n - Hint: remove `suggestQuery` from query #1 or remove `federation` from the request",
+      "code": "invalid_multi_search_query_suggest_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_suggest_query"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn federation_federated_contains_facets() {
     let server = Server::new().await;
@@ -0,0 +1,195 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+    {
+        "title": "Shazam!",
+        "desc": "a Captain Marvel ersatz",
+        "universe": "dc",
+        "id": "1",
+    },
+    {
+        "title": "Captain Planet",
+        "desc": "He's not part of the Marvel Cinematic Universe",
+        "universe": "none",
+        "id": "2",
+    },
+    {
+        "title": "Captain Marvel",
+        "desc": "a Shazam ersatz",
+        "universe": "marvel",
+        "id": "3",
+    }])
+});
+
+async fn index_with_documents(server: &Server) -> Index<'_> {
+    let index = server.index("test");
+
+    let (task, _code) = index.update_settings_filterable_attributes(json!(["universe"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    index
+}
+
+#[actix_rt::test]
+async fn suggest_completes_last_word() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index.suggest(json!({"q": "capt"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["query"], @r###""capt""###);
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "captain",
+        "estimatedTotalHits": 3
+      },
+      {
+        "query": "captain marvel",
+        "estimatedTotalHits": 2
+      },
+      {
+        "query": "captain planet",
+        "estimatedTotalHits": 1
+      }
+    ]
+    "###);
+
+    // the completions must match the words already typed
+    let (response, code) = index.suggest(json!({"q": "captain m"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "captain marvel",
+        "estimatedTotalHits": 3
+      },
+      {
+        "query": "captain marvel cinematic",
+        "estimatedTotalHits": 1
+      },
+      {
+        "query": "captain marvel ersatz",
+        "estimatedTotalHits": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({"q": "capt", "limit": 1})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "captain",
+        "estimatedTotalHits": 3
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_next_words() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    // a query ending with a separator is completed with the words following its last word
+    let (response, code) = index.suggest(json!({"q": "captain "})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "captain marvel",
+        "estimatedTotalHits": 2
+      },
+      {
+        "query": "captain planet",
+        "estimatedTotalHits": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({"q": "batman"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn suggest_with_filter_and_attributes_to_search_on() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index.suggest(json!({"q": "capt", "filter": "universe = none"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "captain",
+        "estimatedTotalHits": 1
+      },
+      {
+        "query": "captain planet",
+        "estimatedTotalHits": 1
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.suggest(json!({"q": "capt", "attributesToSearchOn": ["title"]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "captain",
+        "estimatedTotalHits": 2
+      },
+      {
+        "query": "captain marvel",
+        "estimatedTotalHits": 1
+      },
+      {
+        "query": "captain planet",
+        "estimatedTotalHits": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_bad_parameters() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index.suggest(json!({"q": "capt", "limit": "five"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_limit""###);
+
+    let (response, code) = index.suggest(json!({"q": "capt", "limit": 101})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `limit` of a suggest request must be at most 100, but `101` was found.",
+      "code": "invalid_suggest_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_limit"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({"q": "capt", "filter": "title = shazam"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_filter""###);
+
+    // unknown attributes are only rejected when the searchable attributes are defined
+    let (task, _code) = index.update_settings_searchable_attributes(json!(["title", "desc"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, code) =
+        index.suggest(json!({"q": "capt", "attributesToSearchOn": ["genre"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_suggest_attributes_to_search_on""###);
+}
@@ -274,7 +274,7 @@ async fn settings_bad_typo_tolerance() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`, `disableOnNumbers`",
+      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`, `disableOnNumbers`, `phonetic`, `perAttribute`, `perLocale`",
       "code": "invalid_settings_typo_tolerance",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_typo_tolerance"
@@ -365,3 +365,98 @@ async fn settings_bad_search_cutoff_ms() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .update_settings(json!({ "rules": { "promo": { "actions": { "pin": [{ "id": "1" }] } } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `position` inside `.rules.promo.actions.pin[0]`",
+      "code": "invalid_settings_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rules"
+    }
+    "###);
+
+    let (task, code) = index
+        .update_settings(
+            json!({ "rules": { "promo": { "condition": { "filter": "title & Glass" } } } }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Index `[uuid]`: Invalid rule `promo`: Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+      "code": "invalid_settings_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_query_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "queryRules": { "cheap": { "consequence": { "filter": "price < 20" } } } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `condition` inside `.queryRules.cheap`",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+
+    let (task, code) = index
+        .update_settings(json!({ "queryRules": { "cheap": { "condition": { "query": "cheap" }, "consequence": { "filter": "price <" } } } }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Index `[uuid]`: Invalid query rule `cheap`: Was expecting a value but instead got nothing.\n8:8 price <",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_synonym_sets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .update_settings(json!({ "synonymSets": [{ "synonyms": ["tv"], "locales": ["klingon"] }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_settings_synonym_sets""###);
+
+    let (task, code) =
+        index.import_synonym_sets(json!([{ "kind": "oneWay", "synonyms": ["television"] }])).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Index `[uuid]`: Invalid synonym set at index 0: A one-way set of synonyms must define an `input`.",
+      "code": "invalid_settings_synonym_sets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_sets"
+    }
+    "###);
+}
@@ -121,6 +121,21 @@ test_setting_routes!(
         update_verb: put,
         default_value: []
     },
+    {
+        setting: identifier_attributes,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: infix_attributes,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: date_attributes,
+        update_verb: put,
+        default_value: []
+    },
     {
         setting: dictionary,
         update_verb: put,
@@ -144,7 +159,7 @@ test_setting_routes!(
     {
         setting: faceting,
         update_verb: patch,
-        default_value: {"maxValuesPerFacet": 100, "sortFacetValuesBy": {"*": "alpha"}}
+        default_value: {"maxValuesPerFacet": 100, "sortFacetValuesBy": {"*": "alpha"}, "hierarchies": {}}
     },
     {
         setting: search_cutoff_ms,
@@ -171,6 +186,21 @@ test_setting_routes!(
         update_verb: put,
         default_value: "byWord"
     },
+    {
+        setting: query_rules,
+        update_verb: put,
+        default_value: {}
+    },
+    {
+        setting: rules,
+        update_verb: put,
+        default_value: {}
+    },
+    {
+        setting: synonym_sets,
+        update_verb: put,
+        default_value: []
+    },
     {
         setting: sortable_attributes,
         update_verb: put,
@@ -179,7 +209,7 @@ test_setting_routes!(
     {
         setting: typo_tolerance,
         update_verb: patch,
-        default_value: {"enabled": true, "minWordSizeForTypos": {"oneTypo": 5, "twoTypos": 9}, "disableOnWords": [], "disableOnAttributes": [], "disableOnNumbers": false}
+        default_value: {"enabled": true, "minWordSizeForTypos": {"oneTypo": 5, "twoTypos": 9}, "disableOnWords": [], "disableOnAttributes": [], "disableOnNumbers": false, "phonetic": null, "perAttribute": [], "perLocale": []}
     },
 );
 
@@ -199,7 +229,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 26);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -211,6 +241,9 @@ async fn get_settings() {
     );
     assert_eq!(settings["stopWords"], json!([]));
     assert_eq!(settings["nonSeparatorTokens"], json!([]));
+    assert_eq!(settings["identifierAttributes"], json!([]));
+    assert_eq!(settings["infixAttributes"], json!([]));
+    assert_eq!(settings["dateAttributes"], json!([]));
     assert_eq!(settings["separatorTokens"], json!([]));
     assert_eq!(settings["dictionary"], json!([]));
     assert_eq!(
@@ -219,7 +252,8 @@ async fn get_settings() {
             "maxValuesPerFacet": 100,
             "sortFacetValuesBy": {
                 "*": "alpha"
-            }
+            },
+            "hierarchies": {}
         })
     );
     assert_eq!(
@@ -233,6 +267,9 @@ async fn get_settings() {
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
     assert_eq!(settings["facetSearch"], json!(true));
     assert_eq!(settings["embedders"], json!({}));
+    assert_eq!(settings["rules"], json!({}));
+    assert_eq!(settings["queryRules"], json!({}));
+    assert_eq!(settings["synonymSets"], json!([]));
 }
 
 #[actix_rt::test]
@@ -296,6 +333,9 @@ async fn secrets_are_hidden_in_settings() {
       ],
       "stopWords": [],
       "nonSeparatorTokens": [],
+      "identifierAttributes": [],
+      "infixAttributes": [],
+      "dateAttributes": [],
       "separatorTokens": [],
       "dictionary": [],
       "synonyms": {},
@@ -309,13 +349,17 @@ async fn secrets_are_hidden_in_settings() {
         },
         "disableOnWords": [],
         "disableOnAttributes": [],
-        "disableOnNumbers": false
+        "disableOnNumbers": false,
+        "phonetic": null,
+        "perAttribute": [],
+        "perLocale": []
       },
       "faceting": {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchies": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -336,7 +380,10 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "rules": {},
+      "queryRules": {},
+      "synonymSets": []
     }
     "###);
 
@@ -514,6 +561,89 @@ async fn set_and_reset_distinct_attribute_with_dedicated_route() {
     assert_eq!(response, json!(null));
 }
 
+#[actix_rt::test]
+async fn add_and_remove_list_settings() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _) = index
+        .update_settings(json!({
+            "stopWords": ["the", "a"],
+            "synonyms": { "phone": ["smartphone"], "tv": ["television"] },
+            "dictionary": ["J.R.R."],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    for (setting, values) in [
+        ("stop-words", json!(["an"])),
+        ("synonyms", json!({ "phone": ["mobile"], "laptop": ["notebook"] })),
+        ("dictionary", json!(["G.R.R."])),
+    ] {
+        let (task, code) = index.add_to_setting(setting, values).await;
+        snapshot!(code, @"202 Accepted");
+        index.wait_task(task.uid()).await.succeeded();
+    }
+
+    for (setting, values) in [
+        ("stop-words", json!(["a"])),
+        ("synonyms", json!(["tv"])),
+        ("dictionary", json!(["J.R.R."])),
+    ] {
+        let (task, code) = index.remove_from_setting(setting, values).await;
+        snapshot!(code, @"202 Accepted");
+        index.wait_task(task.uid()).await.succeeded();
+    }
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(json!({
+        "stopWords": response["stopWords"],
+        "synonyms": response["synonyms"],
+        "dictionary": response["dictionary"],
+    })), @r###"
+    {
+      "stopWords": [
+        "an",
+        "the"
+      ],
+      "synonyms": {
+        "laptop": [
+          "notebook"
+        ],
+        "phone": [
+          "smartphone",
+          "mobile"
+        ]
+      },
+      "dictionary": [
+        "G.R.R."
+      ]
+    }
+    "###);
+
+    // the task only carries the added values
+    let (task, _) = index.add_to_setting("stop-words", json!(["of"])).await;
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "stopWordsToAdd": [
+        "of"
+      ]
+    }
+    "###);
+
+    let (response, code) = index.add_to_setting("synonyms", json!(["phone"])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type: expected an object, but found an array: `[\"phone\"]`",
+      "code": "invalid_settings_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonyms"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn granular_filterable_attributes() {
     let server = Server::new().await;
@@ -465,3 +465,71 @@ async fn advanced_synergies() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn identifier_attributes_search() {
+    let documents = json!([
+        { "id": 1, "sku": "ABC 1234" },
+        { "id": 2, "sku": "ABC-1234" },
+        { "id": 3, "sku": "XYZ9876" },
+        { "id": 4, "sku": "XYZ 5555" },
+    ]);
+
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (add_task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(add_task.uid()).await.succeeded();
+
+    index
+        .search(json!({"q": "abc1234", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @"[]");
+        })
+        .await;
+
+    let (update_task, _code) =
+        index.update_settings(json!({ "identifierAttributes": ["sku"] })).await;
+    index.wait_task(update_task.uid()).await.succeeded();
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["identifierAttributes"]), @r###"
+    [
+      "sku"
+    ]
+    "###);
+
+    // the concatenation of the words matches the identifiers with separators
+    index
+        .search(json!({"q": "abc1234", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the digits of an identifier match on their own
+    index
+        .search(
+            json!({"q": "xyz 9876", "matchingStrategy": "all", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
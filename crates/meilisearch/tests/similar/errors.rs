@@ -536,7 +536,7 @@ async fn filter_reserved_geo_attribute_array() {
     index.wait_task(value.uid()).await.succeeded();
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
@@ -576,7 +576,7 @@ async fn filter_reserved_geo_attribute_string() {
     index.wait_task(value.uid()).await.succeeded();
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
@@ -616,7 +616,7 @@ async fn filter_reserved_attribute_array() {
     index.wait_task(value.uid()).await.succeeded();
 
     let expected_response = json!({
-        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
@@ -656,7 +656,7 @@ async fn filter_reserved_attribute_string() {
     index.wait_task(value.uid()).await.succeeded();
 
     let expected_response = json!({
-       "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+       "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
@@ -696,7 +696,7 @@ async fn filter_reserved_geo_point_array() {
     index.wait_task(value.uid()).await.succeeded();
 
     let expected_response = json!({
-        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
@@ -736,7 +736,7 @@ async fn filter_reserved_geo_point_string() {
     index.wait_task(value.uid()).await.succeeded();
 
     let expected_response = json!({
-       "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+       "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_similar_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_similar_filter"
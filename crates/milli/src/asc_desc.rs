@@ -45,6 +45,15 @@ impl From<AscDescError> for CriterionError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 CriterionError::ReservedNameForFilter { name: "_geoBoundingBox".to_string() }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoPolygon") => {
+                CriterionError::ReservedNameForFilter { name: "_geoPolygon".to_string() }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoContains") => {
+                CriterionError::ReservedNameForFilter { name: "_geoContains".to_string() }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoIntersects") => {
+                CriterionError::ReservedNameForFilter { name: "_geoIntersects".to_string() }
+            }
             AscDescError::ReservedKeyword { name } => CriterionError::ReservedName { name },
         }
     }
@@ -54,6 +63,9 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    /// Sort by the distance to the closest of several points,
+    /// written `_geoPoint([lat, lng], [lat, lng], ...)`.
+    GeoPoints(Vec<[f64; 2]>),
 }
 
 impl FromStr for Member {
@@ -61,6 +73,17 @@ impl FromStr for Member {
 
     fn from_str(text: &str) -> Result<Member, Self::Err> {
         match text.strip_prefix("_geoPoint(").and_then(|text| text.strip_suffix(')')) {
+            Some(points) if points.trim_start().starts_with('[') => {
+                let points = parse_geo_points(points)
+                    .ok_or_else(|| AscDescError::ReservedKeyword { name: text.to_string() })?;
+                for point in &points {
+                    validate_geo_point(point)?;
+                }
+                match points.as_slice() {
+                    [point] => Ok(Member::Geo(*point)),
+                    _ => Ok(Member::GeoPoints(points)),
+                }
+            }
             Some(point) => {
                 let (lat, lng) = point
                     .split_once(',')
@@ -71,17 +94,16 @@ impl FromStr for Member {
                             .and_then(|lat| lng.trim().parse().map(|lng| (lat, lng)))
                             .map_err(|_| AscDescError::ReservedKeyword { name: text.to_string() })
                     })?;
-                if !(-90.0..=90.0).contains(&lat) {
-                    return Err(BadGeoError::Lat(lat))?;
-                } else if !(-180.0..=180.0).contains(&lng) {
-                    return Err(BadGeoError::Lng(lng))?;
-                }
+                validate_geo_point(&[lat, lng])?;
                 Ok(Member::Geo([lat, lng]))
             }
             None => {
                 if is_reserved_keyword(text)
                     || text.starts_with("_geoRadius(")
                     || text.starts_with("_geoBoundingBox(")
+                    || text.starts_with("_geoPolygon(")
+                    || text.starts_with("_geoContains(")
+                    || text.starts_with("_geoIntersects(")
                     || text.starts_with("_geo(")
                     || text.starts_with("_geoDistance(")
                 {
@@ -98,6 +120,16 @@ impl fmt::Display for Member {
         match self {
             Member::Field(name) => f.write_str(name),
             Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::GeoPoints(points) => {
+                f.write_str("_geoPoint(")?;
+                for (i, [lat, lng]) in points.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "[{}, {}]", lat, lng)?;
+                }
+                f.write_str(")")
+            }
         }
     }
 }
@@ -106,18 +138,53 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::GeoPoints(_) => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
             Member::Geo(point) => Some(point),
+            Member::Field(_) | Member::GeoPoints(_) => None,
+        }
+    }
+
+    /// Returns all the points a geo sort must compute the distance to.
+    pub fn geo_points(&self) -> Option<&[[f64; 2]]> {
+        match self {
+            Member::Geo(point) => Some(std::slice::from_ref(point)),
+            Member::GeoPoints(points) => Some(points),
             Member::Field(_) => None,
         }
     }
 }
 
+/// Parses a list of points formatted like `[lat, lng], [lat, lng]`.
+fn parse_geo_points(text: &str) -> Option<Vec<[f64; 2]>> {
+    let mut points = Vec::new();
+    let mut rest = text.trim();
+    loop {
+        let (point, tail) = rest.strip_prefix('[')?.split_once(']')?;
+        let (lat, lng) = point.split_once(',')?;
+        points.push([lat.trim().parse().ok()?, lng.trim().parse().ok()?]);
+        rest = tail.trim_start();
+        if rest.is_empty() {
+            return Some(points);
+        }
+        rest = rest.strip_prefix(',')?.trim_start();
+    }
+}
+
+fn validate_geo_point(&[lat, lng]: &[f64; 2]) -> Result<(), BadGeoError> {
+    if !(-90.0..=90.0).contains(&lat) {
+        Err(BadGeoError::Lat(lat))
+    } else if !(-180.0..=180.0).contains(&lng) {
+        Err(BadGeoError::Lng(lng))
+    } else {
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum AscDesc {
     Asc(Member),
@@ -154,7 +221,7 @@ pub enum SortError {
     #[error(transparent)]
     ParseGeoError { error: BadGeoError },
     #[error("Invalid syntax for the geo parameter: expected expression formated like \
-                    `_geoPoint(latitude, longitude)` and ending by `:asc` or `:desc`, found `{name}`.")]
+                    `_geoPoint(latitude, longitude)` or `_geoPoint([latitude, longitude], ...)` and ending by `:asc` or `:desc`, found `{name}`.")]
     BadGeoPointUsage { name: String },
     #[error("Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, found `{name}`.")]
     InvalidName { name: String },
@@ -185,6 +252,15 @@ impl From<AscDescError> for SortError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 SortError::ReservedNameForFilter { name: String::from("_geoBoundingBox") }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoPolygon") => {
+                SortError::ReservedNameForFilter { name: String::from("_geoPolygon") }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoContains") => {
+                SortError::ReservedNameForFilter { name: String::from("_geoContains") }
+            }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_geoIntersects") => {
+                SortError::ReservedNameForFilter { name: String::from("_geoIntersects") }
+            }
             AscDescError::ReservedKeyword { name } => SortError::ReservedName { name },
         }
     }
@@ -223,6 +299,12 @@ mod tests {
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
+            ("_geoPoint([42, 59]):asc", Asc(Geo([42., 59.]))),
+            ("_geoPoint([42, 59], [-12.5, 3]):asc", Asc(GeoPoints(vec![[42., 59.], [-12.5, 3.]]))),
+            (
+                "_geoPoint( [ 42 , 59 ] ,[1,2],  [3, 4] ):desc",
+                Desc(GeoPoints(vec![[42., 59.], [1., 2.], [3., 4.]])),
+            ),
         ];
 
         for (req, expected) in valid_req {
@@ -268,6 +350,15 @@ mod tests {
             ("_geoPoint(0, -180.000001):desc", GeoError(BadGeoError::Lng(-180.000001))),
             ("_geoPoint(159.256, 130):asc", GeoError(BadGeoError::Lat(159.256))),
             ("_geoPoint(12, -2021):desc", GeoError(BadGeoError::Lng(-2021.))),
+            ("_geoPoint([42, 59], [200, 0]):asc", GeoError(BadGeoError::Lat(200.))),
+            ("_geoPoint([42, 59], [0, 181]):desc", GeoError(BadGeoError::Lng(181.))),
+            (
+                "_geoPoint([42, 59] [1, 2]):asc",
+                ReservedKeyword { name: S("_geoPoint([42, 59] [1, 2])") },
+            ),
+            ("_geoPoint([42, 59],):asc", ReservedKeyword { name: S("_geoPoint([42, 59],)") }),
+            ("_geoPoint([42, 59, 3]):asc", ReservedKeyword { name: S("_geoPoint([42, 59, 3])") }),
+            ("_geoPoint([42]):asc", ReservedKeyword { name: S("_geoPoint([42])") }),
             ("_geo(12, -2021):asc", ReservedKeyword { name: S("_geo(12, -2021)") }),
             ("_geo(12, -2021):desc", ReservedKeyword { name: S("_geo(12, -2021)") }),
             ("_geoDistance(12, -2021):asc", ReservedKeyword { name: S("_geoDistance(12, -2021)") }),
@@ -4,7 +4,8 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{AscDesc, Member};
+use crate::ranking_expression::RANKING_EXPRESSION_PREFIX;
+use crate::{AscDesc, Member, RankingExpression, RankingExpressionError};
 
 #[derive(Error, Debug)]
 pub enum CriterionError {
@@ -22,6 +23,8 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error("`{expression}` ranking rule is invalid: {error}.")]
+    InvalidExpression { expression: String, error: RankingExpressionError },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +48,9 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score computed by the expression from the
+    /// numeric values of the document, e.g. `score: log1p(popularity) * 0.3`.
+    Expression(RankingExpression),
 }
 
 impl Criterion {
@@ -68,10 +74,23 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            // an expression never contains a `:`, `score:asc` is a sort on the `score` field
+            text if text.starts_with(RANKING_EXPRESSION_PREFIX)
+                && !text.ends_with(":asc")
+                && !text.ends_with(":desc") =>
+            {
+                text[RANKING_EXPRESSION_PREFIX.len()..].parse().map(Criterion::Expression).map_err(
+                    |error| CriterionError::InvalidExpression {
+                        expression: text.to_string(),
+                        error,
+                    },
+                )
+            }
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
-                AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
+                AscDesc::Asc(Member::Geo(_) | Member::GeoPoints(_))
+                | AscDesc::Desc(Member::Geo(_) | Member::GeoPoints(_)) => {
                     Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
                 }
             },
@@ -103,6 +122,7 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Expression(expression) => write!(f, "{RANKING_EXPRESSION_PREFIX} {expression}"),
         }
     }
 }
@@ -130,6 +150,15 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            ("score:asc", Criterion::Asc(S("score"))),
+            ("score:popularity:desc", Criterion::Desc(S("score:popularity"))),
+            ("score: popularity", Criterion::Expression("popularity".parse().unwrap())),
+            (
+                "score:log1p(popularity) * 0.3 + recency_decay(published_at, 30d)",
+                Criterion::Expression(
+                    "log1p(popularity) * 0.3 + recency_decay(published_at, 30d)".parse().unwrap(),
+                ),
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -144,6 +173,10 @@ mod tests {
             assert_eq!(res.unwrap(), expected);
         }
 
+        let criterion: Criterion = "score:log1p( popularity )*2".parse().unwrap();
+        assert_eq!(criterion.to_string(), "score: log1p( popularity )*2");
+        assert_eq!(criterion.to_string().parse::<Criterion>().unwrap(), criterion);
+
         let invalid_criteria = [
             ("words suffix", InvalidName { name: S("words suffix") }),
             ("prefix typo", InvalidName { name: S("prefix typo") }),
@@ -165,6 +198,38 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("_geoPolygon:asc", ReservedNameForFilter { name: S("_geoPolygon") }),
+            (
+                "_geoPolygon([42, 75], [75, 59], [12, 13]):asc",
+                ReservedNameForFilter { name: S("_geoPolygon") },
+            ),
+            ("_geoContains:asc", ReservedNameForFilter { name: S("_geoContains") }),
+            ("_geoContains(42, 75):asc", ReservedNameForFilter { name: S("_geoContains") }),
+            ("_geoIntersects:asc", ReservedNameForFilter { name: S("_geoIntersects") }),
+            (
+                "_geoIntersects([42, 75], [75, 59]):asc",
+                ReservedNameForFilter { name: S("_geoIntersects") },
+            ),
+            (
+                "score:",
+                InvalidExpression { expression: S("score:"), error: RankingExpressionError::Empty },
+            ),
+            (
+                "score: popularity *",
+                InvalidExpression {
+                    expression: S("score: popularity *"),
+                    error: RankingExpressionError::UnexpectedEnd {
+                        expected: "a number, an attribute, a function or `(`",
+                    },
+                },
+            ),
+            (
+                "score: log(popularity)",
+                InvalidExpression {
+                    expression: S("score: log(popularity)"),
+                    error: RankingExpressionError::UnknownFunction { name: S("log"), position: 0 },
+                },
+            ),
         ];
 
         for (input, expected) in invalid_criteria {
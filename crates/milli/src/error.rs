@@ -13,13 +13,23 @@ use thiserror::Error;
 
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::documents::{self, DocumentsBatchCursorError};
+use crate::geojson::GeoJsonError;
 use crate::thread_pool_no_abort::PanicCatched;
 use crate::vector::settings::EmbeddingSettings;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    [RESERVED_GEO_FIELD_NAME, "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"]
-        .contains(&keyword)
+    [
+        RESERVED_GEO_FIELD_NAME,
+        "_geoDistance",
+        "_geoPoint",
+        "_geoRadius",
+        "_geoBoundingBox",
+        "_geoPolygon",
+        "_geoContains",
+        "_geoIntersects",
+    ]
+    .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -150,6 +160,23 @@ and can not be more than 511 bytes.", .document_id.to_string()
         valid_patterns: BTreeSet<String>,
         matching_rule_indices: HashMap<String, usize>,
     },
+    #[error("Invalid aggregations: {} {}",
+        if .invalid_facets_name.len() == 1 {
+            format!("Attribute `{}` is not filterable.", .invalid_facets_name.iter().next().unwrap())
+        } else {
+            format!("Attributes `{}` are not filterable.",
+                .invalid_facets_name.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "))
+        },
+        match .valid_patterns.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes patterns are: `{}`.",
+                .valid_patterns.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")),
+        }
+    )]
+    InvalidFacetsAggregations {
+        invalid_facets_name: BTreeSet<String>,
+        valid_patterns: BTreeSet<String>,
+    },
     #[error(transparent)]
     InvalidGeoField(#[from] Box<GeoError>),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
@@ -168,6 +195,14 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidVectorsEmbedderConf { document_id: String, error: String },
     #[error("{0}")]
     InvalidFilter(String),
+    #[error("Invalid boost at index {index}: {error}")]
+    InvalidBoost { index: usize, error: String },
+    #[error("Invalid rule `{id}`: {error}")]
+    InvalidSearchRule { id: String, error: String },
+    #[error("Invalid query rule `{id}`: {error}")]
+    InvalidQueryRule { id: String, error: String },
+    #[error("Invalid synonym set at index {index}: {error}")]
+    InvalidSynonymSet { index: usize, error: String },
     #[error("Invalid type for filter subexpression: expected: {}, found: {}.", .0.join(", "), .1)]
     InvalidFilterExpression(&'static [&'static str], Value),
     #[error("Filter operator `{operator}` is not allowed for the attribute `{field}`.\n  - Note: allowed operators: {}.\n  - Note: field `{field}` matched rule #{rule_index} in `filterableAttributes`\n  - Hint: enable {} in rule #{rule_index} by modifying the features.filter object\n  - Hint: prepend another rule matching `{field}` with appropriate filter features before rule #{rule_index}",
@@ -442,6 +477,8 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not a valid GeoJSON geometry: {error}.")]
+    BadGeoJson { document_id: Value, error: GeoJsonError },
 }
 
 #[allow(dead_code)]
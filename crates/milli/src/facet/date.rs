@@ -0,0 +1,152 @@
+//! Dates are stored as string facet values, they are recognized when they are formatted
+//! following [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) (`2025-01-01T10:00:00+02:00`)
+//! or as a full date (`2025-01-01`), which is interpreted as midnight UTC.
+//!
+//! To make range filters and sorting chronological, the facet value of a date is normalized
+//! into its UTC representation with a fixed width, whose lexicographic order is the
+//! chronological one.
+
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, Duration, OffsetDateTime, UtcOffset};
+
+/// The keyword representing the current date in the filters, e.g. `NOW-7d`.
+pub const NOW_KEYWORD: &str = "NOW";
+
+/// The smallest and biggest dates formatted by [`sortable_date`].
+pub const MIN_SORTABLE_DATE: &str = "0000-01-01t00:00:00.000z";
+pub const MAX_SORTABLE_DATE: &str = "9999-12-31t23:59:59.999z";
+
+/// Parses an RFC 3339 date or a full date.
+pub fn parse_date(text: &str) -> Option<OffsetDateTime> {
+    let text = text.trim();
+    // avoid trying to parse the strings that can't be dates
+    if !text.as_bytes().first().is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+
+    match OffsetDateTime::parse(text, &Rfc3339) {
+        Ok(date) => Some(date),
+        Err(_) => Date::parse(text, format_description!("[year]-[month]-[day]"))
+            .ok()
+            .map(|date| date.midnight().assume_utc()),
+    }
+}
+
+/// Parses a date used in a filter, it can either be a date or a date relative to the
+/// current one like `NOW`, `NOW-7d` or `NOW+12h`.
+///
+/// The supported units are `s`, `m`, `h`, `d` and `w`.
+pub fn parse_filter_date(text: &str, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    let Some(offset) = text.trim().strip_prefix(NOW_KEYWORD) else {
+        return parse_date(text);
+    };
+    if offset.is_empty() {
+        return Some(now);
+    }
+
+    let (sign, offset) = match offset.split_at_checked(1)? {
+        ("+", offset) => (1i64, offset),
+        ("-", offset) => (-1, offset),
+        _ => return None,
+    };
+    let (amount, unit) = offset.split_at_checked(offset.len().checked_sub(1)?)?;
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let unit_seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let seconds = amount.parse::<i64>().ok()?.checked_mul(unit_seconds)?;
+
+    now.checked_add(Duration::seconds(sign * seconds))
+}
+
+/// Formats the date in UTC with a millisecond precision, e.g. `2025-01-01t08:00:00.000z`.
+///
+/// The lexicographic order of the formatted dates is the chronological order and, like the
+/// other normalized facet values, the formatted date is lowercased.
+pub fn sortable_date(date: OffsetDateTime) -> String {
+    let format =
+        format_description!("[year]-[month]-[day]t[hour]:[minute]:[second].[subsecond digits:3]z");
+    // the format only contains components that are always available on an `OffsetDateTime`
+    date.to_offset(UtcOffset::UTC).format(format).expect("a date can always be formatted")
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(parse_date("2025-01-01"), Some(datetime!(2025-01-01 0:00 UTC)));
+        assert_eq!(parse_date(" 2025-01-01T10:00:00Z"), Some(datetime!(2025-01-01 10:00 UTC)));
+        assert_eq!(
+            parse_date("2025-01-01T10:00:00.5+02:00"),
+            Some(datetime!(2025-01-01 10:00:00.5 +2))
+        );
+
+        assert_eq!(parse_date("2025"), None);
+        assert_eq!(parse_date("2025-13-01"), None);
+        assert_eq!(parse_date("2025-01-01T10:00"), None);
+        assert_eq!(parse_date("hello"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn parse_filter_dates() {
+        let now = datetime!(2025-03-10 12:00 UTC);
+
+        assert_eq!(parse_filter_date("NOW", now), Some(now));
+        assert_eq!(parse_filter_date("NOW-7d", now), Some(datetime!(2025-03-03 12:00 UTC)));
+        assert_eq!(parse_filter_date("NOW+12h", now), Some(datetime!(2025-03-11 0:00 UTC)));
+        assert_eq!(parse_filter_date("NOW-30m", now), Some(datetime!(2025-03-10 11:30 UTC)));
+        assert_eq!(parse_filter_date("NOW-15s", now), Some(datetime!(2025-03-10 11:59:45 UTC)));
+        assert_eq!(parse_filter_date("NOW-2w", now), Some(datetime!(2025-02-24 12:00 UTC)));
+        assert_eq!(parse_filter_date("2025-01-01", now), Some(datetime!(2025-01-01 0:00 UTC)));
+
+        assert_eq!(parse_filter_date("NOW-", now), None);
+        assert_eq!(parse_filter_date("NOW-d", now), None);
+        assert_eq!(parse_filter_date("NOW-7", now), None);
+        assert_eq!(parse_filter_date("NOW-7y", now), None);
+        assert_eq!(parse_filter_date("NOW--7d", now), None);
+        assert_eq!(parse_filter_date("NOW7d", now), None);
+        assert_eq!(parse_filter_date("now", now), None);
+        assert_eq!(parse_filter_date("NOW-99999999999999999999d", now), None);
+        assert_eq!(parse_filter_date("NOW-999999999999999d", now), None);
+    }
+
+    #[test]
+    fn sortable_dates() {
+        assert_eq!(sortable_date(datetime!(2025-01-01 0:00 UTC)), "2025-01-01t00:00:00.000z");
+        assert_eq!(
+            sortable_date(datetime!(2025-01-01 10:00:00.123456 +2)),
+            "2025-01-01t08:00:00.123z"
+        );
+
+        let mut dates = [
+            "2025-01-01T10:00:00.5Z",
+            "2025-01-01T10:00:00+01:00",
+            "2024-12-31",
+            "2025-01-01T10:00:00Z",
+        ]
+        .map(|date| sortable_date(parse_date(date).unwrap()));
+        dates.sort();
+        assert_eq!(
+            dates,
+            [
+                "2024-12-31t00:00:00.000z",
+                "2025-01-01t09:00:00.000z",
+                "2025-01-01t10:00:00.000z",
+                "2025-01-01t10:00:00.500z",
+            ]
+        );
+    }
+}
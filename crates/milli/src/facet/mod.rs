@@ -1,3 +1,4 @@
+pub mod date;
 mod facet_type;
 mod facet_value;
 pub mod value_encoding;
@@ -5,7 +5,7 @@ use charabia::Language;
 use heed::RoTxn;
 
 use super::FieldsIdsMap;
-use crate::attribute_patterns::{match_field_legacy, PatternMatch};
+use crate::attribute_patterns::{match_field_legacy, AttributePatterns, PatternMatch};
 use crate::constants::{RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
 use crate::{
     is_faceted_by, FieldId, FilterableAttributesFeatures, FilterableAttributesRule, Index,
@@ -28,6 +28,8 @@ pub struct Metadata {
     pub localized_attributes_rule_id: Option<NonZeroU16>,
     /// The id of the filterable attributes rule if the field is filterable.
     pub filterable_attributes_rule_id: Option<NonZeroU16>,
+    /// The field is part of the date attributes, its facet values are normalized as dates.
+    pub date: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -116,10 +118,17 @@ impl Metadata {
         &self,
         rules: &'rules [LocalizedAttributesRule],
     ) -> Option<&'rules [Language]> {
+        self.localized_attributes_rule(rules).map(LocalizedAttributesRule::locales)
+    }
+
+    pub fn localized_attributes_rule<'rules>(
+        &self,
+        rules: &'rules [LocalizedAttributesRule],
+    ) -> Option<&'rules LocalizedAttributesRule> {
         let localized_attributes_rule_id = self.localized_attributes_rule_id?.get();
         // - 1: `localized_attributes_rule_id` is NonZero
         let rule = rules.get((localized_attributes_rule_id - 1) as usize).unwrap();
-        Some(rule.locales())
+        Some(rule)
     }
 
     pub fn filterable_attributes<'rules>(
@@ -181,6 +190,19 @@ impl Metadata {
         self.geo
     }
 
+    pub fn is_date(&self) -> bool {
+        self.date
+    }
+
+    /// Normalizes a string facet value of the field, as a date if the field is a date attribute.
+    pub fn normalize_facet(&self, original: &str) -> String {
+        if self.date {
+            crate::normalize_date_facet(original)
+        } else {
+            crate::normalize_facet(original)
+        }
+    }
+
     /// Returns `true` if the field is part of the facet databases. (sortable, distinct, asc_desc, filterable or facet searchable)
     pub fn is_faceted(&self, rules: &[FilterableAttributesRule]) -> bool {
         if self.is_distinct() || self.is_sortable() || self.is_asc_desc() {
@@ -210,6 +232,7 @@ pub struct MetadataBuilder {
     localized_attributes: Option<Vec<LocalizedAttributesRule>>,
     distinct_attribute: Option<String>,
     asc_desc_attributes: HashSet<String>,
+    date_attributes: Option<AttributePatterns>,
 }
 
 impl MetadataBuilder {
@@ -222,6 +245,8 @@ impl MetadataBuilder {
         let localized_attributes = index.localized_attributes_rules(rtxn)?;
         let distinct_attribute = index.distinct_field(rtxn)?.map(|s| s.to_string());
         let asc_desc_attributes = index.asc_desc_fields(rtxn)?;
+        let date_attributes =
+            index.date_attributes(rtxn)?.map(|fields| fields.into_iter().collect());
 
         Ok(Self::new(
             searchable_attributes,
@@ -230,6 +255,7 @@ impl MetadataBuilder {
             localized_attributes,
             distinct_attribute,
             asc_desc_attributes,
+            date_attributes,
         ))
     }
 
@@ -243,6 +269,7 @@ impl MetadataBuilder {
         localized_attributes: Option<Vec<LocalizedAttributesRule>>,
         distinct_attribute: Option<String>,
         asc_desc_attributes: HashSet<String>,
+        date_attributes: Option<Vec<String>>,
     ) -> Self {
         let searchable_attributes = match searchable_attributes {
             Some(fields) if fields.iter().any(|f| f == "*") => None,
@@ -257,6 +284,7 @@ impl MetadataBuilder {
             localized_attributes,
             distinct_attribute,
             asc_desc_attributes,
+            date_attributes: date_attributes.map(AttributePatterns::from),
         }
     }
 
@@ -271,6 +299,7 @@ impl MetadataBuilder {
                 geo: false,
                 localized_attributes_rule_id: None,
                 filterable_attributes_rule_id: None,
+                date: false,
             };
         }
 
@@ -297,6 +326,7 @@ impl MetadataBuilder {
                 geo: true,
                 localized_attributes_rule_id: None,
                 filterable_attributes_rule_id,
+                date: false,
             };
         }
 
@@ -322,6 +352,11 @@ impl MetadataBuilder {
             // saturating_add(1): make `id` `NonZero`
             .map(|id| NonZeroU16::new(id.saturating_add(1).try_into().unwrap()).unwrap());
 
+        let date = self
+            .date_attributes
+            .as_ref()
+            .is_some_and(|patterns| patterns.match_str(field) == PatternMatch::Match);
+
         Metadata {
             searchable,
             sortable,
@@ -330,6 +365,7 @@ impl MetadataBuilder {
             geo: false,
             localized_attributes_rule_id,
             filterable_attributes_rule_id,
+            date,
         }
     }
 
@@ -348,4 +384,8 @@ impl MetadataBuilder {
     pub fn localized_attributes_rules(&self) -> Option<&[LocalizedAttributesRule]> {
         self.localized_attributes.as_deref()
     }
+
+    pub fn date_attributes(&self) -> Option<&AttributePatterns> {
+        self.date_attributes.as_ref()
+    }
 }
@@ -5,7 +5,7 @@ use utoipa::ToSchema;
 
 use crate::{
     attribute_patterns::{match_distinct_field, match_field_legacy, PatternMatch},
-    constants::RESERVED_GEO_FIELD_NAME,
+    constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME},
     AttributePatterns,
 };
 
@@ -35,6 +35,13 @@ impl FilterableAttributesRule {
         matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEO_FIELD_NAME)
     }
 
+    /// Check if the rule is a geojson field.
+    ///
+    /// prefer using `index.is_geojson_filtering_enabled` to check if the geojson feature is enabled.
+    pub fn has_geojson(&self) -> bool {
+        matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEOJSON_FIELD_NAME)
+    }
+
     /// Get the features of the rule.
     pub fn features(&self) -> FilterableAttributesFeatures {
         match self {
@@ -0,0 +1,443 @@
+//! Support for the GeoJSON geometries that documents can store under the `_geojson` field.
+//!
+//! The geometries are stored in the same `[latitude, longitude]` order as the `_geo` points,
+//! even though GeoJSON expresses its positions as `[longitude, latitude]`. The spatial predicates
+//! consider the shapes to be drawn on a plane where the latitude is the y axis and the longitude
+//! the x axis, this is why the geometries crossing the antimeridian are rejected: as recommended
+//! by the GeoJSON specification, they must be split in two on each side of the antimeridian.
+
+use rstar::primitives::{GeomWithData, Rectangle};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{DocumentId, GeoJsonShape};
+
+/// A GeoJSON geometry with all of its positions expressed as `[latitude, longitude]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Geometry {
+    Point([f64; 2]),
+    MultiPoint(Vec<[f64; 2]>),
+    LineString(Vec<[f64; 2]>),
+    MultiLineString(Vec<Vec<[f64; 2]>>),
+    /// The first ring is the exterior of the polygon, the following ones are its holes.
+    Polygon(Vec<Vec<[f64; 2]>>),
+    MultiPolygon(Vec<Vec<Vec<[f64; 2]>>>),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GeoJsonError {
+    #[error("was expecting an object but instead got `{0}`")]
+    NotAnObject(Value),
+    #[error("the `type` field is missing")]
+    MissingType,
+    #[error("the `{0}` type is not supported, it must be one of `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, `MultiPolygon` or `Feature`")]
+    UnsupportedType(String),
+    #[error("the `coordinates` field is missing")]
+    MissingCoordinates,
+    #[error("the `coordinates` field doesn't match the structure expected by the `{0}` type")]
+    MalformedCoordinates(String),
+    #[error("the position `[{lng}, {lat}]` is invalid, the longitude must be between -180 and 180 and the latitude between -90 and 90")]
+    BadPosition { lat: f64, lng: f64 },
+    #[error("a line must contain at least two positions")]
+    LineTooShort,
+    #[error("a polygon ring must contain at least four positions and its first and last positions must be the same")]
+    BadLinearRing,
+    #[error("the segment from `[{}, {}]` to `[{}, {}]` crosses the antimeridian, the geometry must be split in two on each side of the antimeridian", .from[1], .from[0], .to[1], .to[0])]
+    CrossesAntimeridian { from: [f64; 2], to: [f64; 2] },
+}
+
+impl Geometry {
+    /// Parses a GeoJSON geometry, or the geometry of a GeoJSON `Feature`.
+    ///
+    /// Returns `None` if the value or the geometry of the feature is `null`.
+    pub fn from_json(value: Value) -> Result<Option<Self>, GeoJsonError> {
+        let mut object = match value {
+            Value::Null => return Ok(None),
+            Value::Object(object) => object,
+            value => return Err(GeoJsonError::NotAnObject(value)),
+        };
+
+        let kind = match object.remove("type") {
+            Some(Value::String(kind)) => kind,
+            Some(value) => return Err(GeoJsonError::UnsupportedType(value.to_string())),
+            None => return Err(GeoJsonError::MissingType),
+        };
+
+        if kind == "Feature" {
+            return match object.remove("geometry") {
+                Some(geometry) => Self::from_json(geometry),
+                None => Ok(None),
+            };
+        }
+
+        let coordinates = object.remove("coordinates").ok_or(GeoJsonError::MissingCoordinates)?;
+        let geometry = match kind.as_str() {
+            "Point" => parse_position(&coordinates).map(Geometry::Point),
+            "MultiPoint" => parse_positions(&coordinates).map(Geometry::MultiPoint),
+            "LineString" => parse_line(&coordinates).map(Geometry::LineString),
+            "MultiLineString" => {
+                parse_array(&coordinates, parse_line).map(Geometry::MultiLineString)
+            }
+            "Polygon" => parse_polygon(&coordinates).map(Geometry::Polygon),
+            "MultiPolygon" => parse_array(&coordinates, parse_polygon).map(Geometry::MultiPolygon),
+            _ => return Err(GeoJsonError::UnsupportedType(kind)),
+        };
+
+        match geometry {
+            Err(GeoJsonError::MalformedCoordinates(_)) => {
+                Err(GeoJsonError::MalformedCoordinates(kind))
+            }
+            otherwise => otherwise.map(Some),
+        }
+    }
+
+    /// Returns the bounding box of the geometry associated with the document
+    /// so it can be stored in the shapes rtree.
+    pub fn to_shape(&self, docid: DocumentId) -> GeoJsonShape {
+        GeomWithData::new(self.bounding_box(), docid)
+    }
+
+    /// Returns the smallest rectangle containing every position of the geometry.
+    pub fn bounding_box(&self) -> Rectangle<[f64; 2]> {
+        let (min, max) = self.positions().fold(
+            ([f64::MAX, f64::MAX], [f64::MIN, f64::MIN]),
+            |(min, max), [lat, lng]| {
+                ([min[0].min(*lat), min[1].min(*lng)], [max[0].max(*lat), max[1].max(*lng)])
+            },
+        );
+        Rectangle::from_corners(min, max)
+    }
+
+    /// Returns `true` if the point is inside the geometry or on one of its lines.
+    pub fn contains(&self, point: &[f64; 2]) -> bool {
+        match self {
+            Geometry::Point(position) => position == point,
+            Geometry::MultiPoint(positions) => positions.contains(point),
+            Geometry::LineString(_) | Geometry::MultiLineString(_) => {
+                self.segments().any(|segment| point_on_segment(point, segment))
+            }
+            Geometry::Polygon(rings) => polygon_contains(rings, point),
+            Geometry::MultiPolygon(polygons) => {
+                polygons.iter().any(|rings| polygon_contains(rings, point))
+            }
+        }
+    }
+
+    /// Returns `true` if the geometry and the box share at least one point.
+    ///
+    /// The box must not cross the antimeridian, its bottom left corner must be lower than its top right one.
+    pub fn intersects_box(&self, bottom_left: &[f64; 2], top_right: &[f64; 2]) -> bool {
+        let [bottom, left] = *bottom_left;
+        let [top, right] = *top_right;
+
+        // a part of the geometry is inside the box
+        if self
+            .positions()
+            .any(|[lat, lng]| (bottom..=top).contains(lat) && (left..=right).contains(lng))
+        {
+            return true;
+        }
+
+        // a line or the border of a polygon crosses the box
+        let corners = [[bottom, left], [bottom, right], [top, right], [top, left]];
+        let edges = [
+            (&corners[0], &corners[1]),
+            (&corners[1], &corners[2]),
+            (&corners[2], &corners[3]),
+            (&corners[3], &corners[0]),
+        ];
+        if self
+            .segments()
+            .any(|segment| edges.iter().any(|&edge| segments_intersect(segment, edge)))
+        {
+            return true;
+        }
+
+        // the box is entirely inside a polygon
+        self.contains(bottom_left)
+    }
+
+    fn positions(&self) -> Box<dyn Iterator<Item = &[f64; 2]> + '_> {
+        match self {
+            Geometry::Point(position) => Box::new(std::iter::once(position)),
+            Geometry::MultiPoint(positions) | Geometry::LineString(positions) => {
+                Box::new(positions.iter())
+            }
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => {
+                Box::new(lines.iter().flatten())
+            }
+            Geometry::MultiPolygon(polygons) => Box::new(polygons.iter().flatten().flatten()),
+        }
+    }
+
+    fn segments(&self) -> Box<dyn Iterator<Item = (&[f64; 2], &[f64; 2])> + '_> {
+        fn line_segments(line: &[[f64; 2]]) -> impl Iterator<Item = (&[f64; 2], &[f64; 2])> {
+            line.windows(2).map(|window| (&window[0], &window[1]))
+        }
+
+        match self {
+            Geometry::Point(_) | Geometry::MultiPoint(_) => Box::new(std::iter::empty()),
+            Geometry::LineString(line) => Box::new(line_segments(line)),
+            Geometry::MultiLineString(lines) | Geometry::Polygon(lines) => {
+                Box::new(lines.iter().flat_map(|line| line_segments(line)))
+            }
+            Geometry::MultiPolygon(polygons) => {
+                Box::new(polygons.iter().flatten().flat_map(|line| line_segments(line)))
+            }
+        }
+    }
+}
+
+fn parse_array<T>(
+    value: &Value,
+    parse: impl Fn(&Value) -> Result<T, GeoJsonError>,
+) -> Result<Vec<T>, GeoJsonError> {
+    match value {
+        Value::Array(values) => values.iter().map(parse).collect(),
+        _ => Err(GeoJsonError::MalformedCoordinates(String::new())),
+    }
+}
+
+/// Parses a `[longitude, latitude]` position and ignores the altitude if there is one.
+fn parse_position(value: &Value) -> Result<[f64; 2], GeoJsonError> {
+    let (lng, lat) = match value.as_array().map(Vec::as_slice) {
+        Some([lng, lat, ..]) => match (lng.as_f64(), lat.as_f64()) {
+            (Some(lng), Some(lat)) => (lng, lat),
+            _ => return Err(GeoJsonError::MalformedCoordinates(String::new())),
+        },
+        _ => return Err(GeoJsonError::MalformedCoordinates(String::new())),
+    };
+
+    if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) {
+        Ok([lat, lng])
+    } else {
+        Err(GeoJsonError::BadPosition { lat, lng })
+    }
+}
+
+fn parse_positions(value: &Value) -> Result<Vec<[f64; 2]>, GeoJsonError> {
+    parse_array(value, parse_position)
+}
+
+fn parse_line(value: &Value) -> Result<Vec<[f64; 2]>, GeoJsonError> {
+    let line = parse_positions(value)?;
+    if line.len() >= 2 {
+        check_antimeridian(&line)?;
+        Ok(line)
+    } else {
+        Err(GeoJsonError::LineTooShort)
+    }
+}
+
+fn parse_polygon(value: &Value) -> Result<Vec<Vec<[f64; 2]>>, GeoJsonError> {
+    let rings = parse_array(value, |ring| {
+        let ring = parse_positions(ring)?;
+        if ring.len() >= 4 && ring.first() == ring.last() {
+            check_antimeridian(&ring)?;
+            Ok(ring)
+        } else {
+            Err(GeoJsonError::BadLinearRing)
+        }
+    })?;
+
+    if rings.is_empty() {
+        Err(GeoJsonError::MalformedCoordinates(String::new()))
+    } else {
+        Ok(rings)
+    }
+}
+
+/// Rejects the lines with a segment spanning more than 180° of longitude, which is
+/// understood as crossing the antimeridian like in the `_geoPolygon` filter.
+fn check_antimeridian(line: &[[f64; 2]]) -> Result<(), GeoJsonError> {
+    match line.windows(2).find(|window| (window[0][1] - window[1][1]).abs() > 180.0) {
+        Some(window) => Err(GeoJsonError::CrossesAntimeridian { from: window[0], to: window[1] }),
+        None => Ok(()),
+    }
+}
+
+/// Returns `true` if the point is inside the polygon using the even-odd rule.
+///
+/// The polygon is considered to be drawn on a plane where the latitude is the y axis and the
+/// longitude the x axis, it doesn't matter whether it is closed (last point equal to the first one)
+/// or not.
+pub(crate) fn point_in_polygon(point: &[f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let [y, x] = *point;
+    let mut inside = false;
+    let Some(mut previous) = polygon.last() else { return false };
+    for current in polygon {
+        let (&[yi, xi], &[yj, xj]) = (current, previous);
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        previous = current;
+    }
+    inside
+}
+
+/// Returns `true` if the point is on the border or inside the exterior ring
+/// of the polygon without being strictly inside one of its holes.
+fn polygon_contains(rings: &[Vec<[f64; 2]>], point: &[f64; 2]) -> bool {
+    let on_border = rings.iter().any(|ring| {
+        ring.windows(2).any(|window| point_on_segment(point, (&window[0], &window[1])))
+    });
+    match rings.split_first() {
+        Some(_) if on_border => true,
+        Some((exterior, holes)) => {
+            point_in_polygon(point, exterior)
+                && !holes.iter().any(|hole| point_in_polygon(point, hole))
+        }
+        None => false,
+    }
+}
+
+/// The sign of the cross product tells on which side of the `a -> b` line the point `c` is.
+fn orientation(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn point_on_segment(point: &[f64; 2], (a, b): (&[f64; 2], &[f64; 2])) -> bool {
+    orientation(a, b, point).abs() <= f64::EPSILON
+        && (a[0].min(b[0])..=a[0].max(b[0])).contains(&point[0])
+        && (a[1].min(b[1])..=a[1].max(b[1])).contains(&point[1])
+}
+
+fn segments_intersect((a, b): (&[f64; 2], &[f64; 2]), (c, d): (&[f64; 2], &[f64; 2])) -> bool {
+    let (abc, abd) = (orientation(a, b, c), orientation(a, b, d));
+    let (cda, cdb) = (orientation(c, d, a), orientation(c, d, b));
+
+    if abc * abd < 0.0 && cda * cdb < 0.0 {
+        return true;
+    }
+
+    // the segments are touching or colinear
+    point_on_segment(c, (a, b))
+        || point_on_segment(d, (a, b))
+        || point_on_segment(a, (c, d))
+        || point_on_segment(b, (c, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_geometries() {
+        let point = Geometry::from_json(json!({ "type": "Point", "coordinates": [2.35, 48.85] }));
+        assert_eq!(point.unwrap(), Some(Geometry::Point([48.85, 2.35])));
+
+        let feature = Geometry::from_json(json!({
+            "type": "Feature",
+            "properties": { "name": "Paris" },
+            "geometry": { "type": "LineString", "coordinates": [[2.35, 48.85, 35.0], [2.29, 48.86]] },
+        }));
+        assert_eq!(
+            feature.unwrap(),
+            Some(Geometry::LineString(vec![[48.85, 2.35], [48.86, 2.29]]))
+        );
+
+        let empty_feature = Geometry::from_json(json!({ "type": "Feature", "geometry": null }));
+        assert_eq!(empty_feature.unwrap(), None);
+        assert_eq!(Geometry::from_json(Value::Null).unwrap(), None);
+
+        let error = Geometry::from_json(json!([1, 2])).unwrap_err();
+        insta::assert_snapshot!(error, @"was expecting an object but instead got `[1,2]`");
+        let error = Geometry::from_json(json!({ "coordinates": [1, 2] })).unwrap_err();
+        insta::assert_snapshot!(error, @"the `type` field is missing");
+        let error =
+            Geometry::from_json(json!({ "type": "Circle", "coordinates": [1, 2] })).unwrap_err();
+        insta::assert_snapshot!(error, @"the `Circle` type is not supported, it must be one of `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, `MultiPolygon` or `Feature`");
+        let error = Geometry::from_json(json!({ "type": "Point" })).unwrap_err();
+        insta::assert_snapshot!(error, @"the `coordinates` field is missing");
+        let error =
+            Geometry::from_json(json!({ "type": "Polygon", "coordinates": [[1, 2], [3, 4]] }))
+                .unwrap_err();
+        insta::assert_snapshot!(error, @"the `coordinates` field doesn't match the structure expected by the `Polygon` type");
+        let error =
+            Geometry::from_json(json!({ "type": "Point", "coordinates": [2, 91] })).unwrap_err();
+        insta::assert_snapshot!(error, @"the position `[2, 91]` is invalid, the longitude must be between -180 and 180 and the latitude between -90 and 90");
+        let error = Geometry::from_json(json!({ "type": "LineString", "coordinates": [[1, 2]] }))
+            .unwrap_err();
+        insta::assert_snapshot!(error, @"a line must contain at least two positions");
+        let error = Geometry::from_json(
+            json!({ "type": "Polygon", "coordinates": [[[0, 0], [0, 1], [1, 1], [1, 0]]] }),
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"a polygon ring must contain at least four positions and its first and last positions must be the same");
+        let error = Geometry::from_json(
+            json!({ "type": "LineString", "coordinates": [[170, 10], [-170, 10], [-160, 20]] }),
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"the segment from `[170, 10]` to `[-170, 10]` crosses the antimeridian, the geometry must be split in two on each side of the antimeridian");
+        let error = Geometry::from_json(json!({
+            "type": "Polygon",
+            "coordinates": [[[170, -10], [-170, -10], [-170, 10], [170, 10], [170, -10]]],
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"the segment from `[170, -10]` to `[-170, -10]` crosses the antimeridian, the geometry must be split in two on each side of the antimeridian");
+
+        // the same polygon split on each side of the antimeridian
+        let polygon = Geometry::from_json(json!({
+            "type": "MultiPolygon",
+            "coordinates": [
+                [[[170, -10], [180, -10], [180, 10], [170, 10], [170, -10]]],
+                [[[-180, -10], [-170, -10], [-170, 10], [-180, 10], [-180, -10]]],
+            ],
+        }))
+        .unwrap()
+        .unwrap();
+        assert!(polygon.contains(&[0.0, 175.0]));
+        assert!(polygon.contains(&[0.0, -175.0]));
+        assert!(!polygon.contains(&[0.0, 0.0]));
+        assert!(polygon.intersects_box(&[-1.0, -179.0], &[1.0, -178.0]));
+    }
+
+    #[test]
+    fn spatial_predicates() {
+        // a square from 0 to 10 with a hole from 4 to 6
+        let polygon = Geometry::from_json(json!({
+            "type": "Polygon",
+            "coordinates": [
+                [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]],
+            ],
+        }))
+        .unwrap()
+        .unwrap();
+
+        assert!(polygon.contains(&[1.0, 1.0]));
+        assert!(polygon.contains(&[0.0, 5.0]));
+        assert!(polygon.contains(&[4.0, 5.0]));
+        assert!(!polygon.contains(&[5.0, 5.0]));
+        assert!(!polygon.contains(&[11.0, 5.0]));
+
+        // the box is entirely inside the polygon
+        assert!(polygon.intersects_box(&[1.0, 1.0], &[2.0, 2.0]));
+        // the box contains the polygon
+        assert!(polygon.intersects_box(&[-1.0, -1.0], &[11.0, 11.0]));
+        // the box crosses the border of the polygon without containing any of its positions
+        assert!(polygon.intersects_box(&[-1.0, 1.0], &[1.0, 2.0]));
+        // the box is inside the hole
+        assert!(!polygon.intersects_box(&[4.5, 4.5], &[5.5, 5.5]));
+        // the box is outside of the polygon
+        assert!(!polygon.intersects_box(&[20.0, 20.0], &[30.0, 30.0]));
+
+        let line = Geometry::from_json(json!({
+            "type": "LineString",
+            "coordinates": [[0, 0], [10, 10]],
+        }))
+        .unwrap()
+        .unwrap();
+
+        assert!(line.contains(&[5.0, 5.0]));
+        assert!(!line.contains(&[5.0, 6.0]));
+        assert!(line.intersects_box(&[4.0, 1.0], &[6.0, 9.0]));
+        assert!(!line.intersects_box(&[6.0, 1.0], &[9.0, 4.0]));
+
+        let bounding_box = polygon.bounding_box();
+        assert_eq!(bounding_box.lower(), [0.0, 0.0]);
+        assert_eq!(bounding_box.upper(), [10.0, 10.0]);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::MAX_WORD_LENGTH;
+
+/// The maximum number of consecutive words concatenated into a variant, besides the
+/// concatenation of all the words of a run.
+const MAX_NGRAM_LEN: usize = 3;
+
+/// Returns the variants of the words of a value of an identifier attribute, e.g. a SKU or
+/// a part number, to index along the words at the position of their first word.
+///
+/// The words only separated by soft separators, i.e. at consecutive positions, form a run:
+/// - the n-grams of the runs and the runs themselves are concatenated, so that `abc1234`
+///   matches `ABC-1234` and `ABC 1234`,
+/// - the words mixing letters and digits are split between them, so that `1234` matches
+///   `ABC1234`.
+pub fn identifier_variants<'a>(
+    words: impl IntoIterator<Item = (u16, &'a str)>,
+) -> Vec<(u16, String)> {
+    let words: Vec<_> = words.into_iter().collect();
+    let mut variants = Vec::new();
+
+    let mut start = 0;
+    while start < words.len() {
+        let mut end = start + 1;
+        while end < words.len() && words[end - 1].0.checked_add(1) == Some(words[end].0) {
+            end += 1;
+        }
+
+        let run = &words[start..end];
+        for i in 0..run.len() {
+            for j in i + 2..=run.len() {
+                if j - i > MAX_NGRAM_LEN && (i, j) != (0, run.len()) {
+                    continue;
+                }
+                let variant: String = run[i..j].iter().map(|(_, word)| *word).collect();
+                if variant.len() <= MAX_WORD_LENGTH {
+                    variants.push((run[i].0, variant));
+                }
+            }
+        }
+
+        start = end;
+    }
+
+    for &(position, word) in &words {
+        let parts = letters_and_digits(word);
+        if parts.len() > 1 {
+            variants.extend(parts.into_iter().map(|part| (position, part.to_string())));
+        }
+    }
+
+    variants
+}
+
+/// Splits the alphanumeric word between its letters and its digits.
+fn letters_and_digits(word: &str) -> Vec<&str> {
+    if !word.chars().all(char::is_alphanumeric) {
+        return Vec::new();
+    }
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut previous_is_numeric = None;
+    for (index, c) in word.char_indices() {
+        let is_numeric = c.is_numeric();
+        if previous_is_numeric.is_some_and(|previous| previous != is_numeric) {
+            parts.push(&word[start..index]);
+            start = index;
+        }
+        previous_is_numeric = Some(is_numeric);
+    }
+    parts.push(&word[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(words: &[(u16, &str)]) -> Vec<(u16, String)> {
+        identifier_variants(words.iter().copied())
+    }
+
+    #[test]
+    fn concatenates_the_runs() {
+        insta::assert_debug_snapshot!(variants(&[(0, "abc"), (1, "1234")]), @r###"
+        [
+            (
+                0,
+                "abc1234",
+            ),
+        ]
+        "###);
+
+        // the words separated by a hard separator are not concatenated
+        let variants = variants(&[(0, "ab"), (1, "12"), (2, "cd"), (3, "34"), (11, "x")]);
+        let variants: Vec<_> = variants.iter().map(|(p, v)| format!("{p}:{v}")).collect();
+        insta::assert_snapshot!(variants.join(" "), @"0:ab12 0:ab12cd 0:ab12cd34 1:12cd 1:12cd34 2:cd34");
+    }
+
+    #[test]
+    fn splits_letters_and_digits() {
+        let variants = variants(&[(0, "abc1234x"), (8, "1234"), (16, "v2.0")]);
+        let variants: Vec<_> = variants.iter().map(|(p, v)| format!("{p}:{v}")).collect();
+        insta::assert_snapshot!(variants.join(" "), @"0:abc 0:1234 0:x");
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
 
+use charabia::Language;
 use heed::types::*;
 use heed::{CompactionOption, Database, DatabaseStat, RoTxn, RwTxn, Unspecified, WithoutTls};
 use indexmap::IndexMap;
@@ -16,6 +17,7 @@ use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::fields_ids_map::FieldsIdsMap;
+use crate::geojson::Geometry;
 use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
     FieldIdCodec, OrderedF64Codec,
@@ -26,16 +28,21 @@ use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
 use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
+    default_criteria, AttributePatterns, CboRoaringBitmapCodec, Criterion, DocumentId,
+    ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry,
+    FieldIdWordCountCodec, FieldidsWeightsMap, FilterableAttributesRule, GeoJsonShape, GeoPoint,
+    LocalizedAttributesRule, ObkvCodec, PatternMatch, QueryRule, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, SearchRule, SynonymSet, U8StrStrCodec, Weight, BEU16, BEU32,
     BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
+/// The maximum number of results that the engine
+/// will be able to return in one search call.
+pub const DEFAULT_PAGINATION_MAX_TOTAL_HITS: usize = 1000;
+
 pub mod main_key {
     pub const VERSION_KEY: &str = "version";
     pub const CRITERIA_KEY: &str = "criteria";
@@ -50,17 +57,25 @@ pub mod main_key {
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
     pub const STOP_WORDS_KEY: &str = "stop-words";
     pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
+    pub const IDENTIFIER_ATTRIBUTES_KEY: &str = "identifier-attributes";
+    pub const INFIX_ATTRIBUTES_KEY: &str = "infix-attributes";
+    pub const DATE_ATTRIBUTES_KEY: &str = "date-attributes";
     pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const DICTIONARY_KEY: &str = "dictionary";
     pub const SYNONYMS_KEY: &str = "synonyms";
     pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
+    pub const SYNONYM_SETS_KEY: &str = "synonym-sets";
+    pub const LOCALIZED_SYNONYMS_KEY: &str = "localized-synonyms";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
+    pub const WORDS_TRIGRAMS_FST_KEY: &str = "words-trigrams-fst";
+    pub const FACET_STRINGS_TRIGRAMS_FST_KEY: &str = "facet-strings-trigrams-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
     pub const UPDATED_AT_KEY: &str = "updated-at";
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
@@ -70,6 +85,9 @@ pub mod main_key {
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const FACET_HIERARCHIES: &str = "facet-hierarchies";
+    pub const SEARCH_RULES: &str = "search-rules";
+    pub const QUERY_RULES: &str = "query-rules";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
@@ -79,6 +97,8 @@ pub mod main_key {
     pub const PREFIX_SEARCH: &str = "prefix_search";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
     pub const DISABLED_TYPOS_TERMS: &str = "disabled_typos_terms";
+    pub const PHONETIC_SETTINGS: &str = "phonetic-settings";
+    pub const TYPO_BUDGETS: &str = "typo-budgets";
 }
 
 pub mod db_name {
@@ -107,8 +127,9 @@ pub mod db_name {
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
     pub const DOCUMENTS: &str = "documents";
+    pub const GEOJSON_GEOMETRIES: &str = "geojson-geometries";
 }
-const NUMBER_OF_DBS: u32 = 25;
+const NUMBER_OF_DBS: u32 = 26;
 
 #[derive(Clone)]
 pub struct Index {
@@ -176,6 +197,10 @@ pub struct Index {
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
+
+    /// Maps the document id to the GeoJSON geometry of its `_geojson` field, the geometries
+    /// are indexed by their bounding box in the geojson rtree.
+    pub geojson_geometries: Database<BEU32, SerdeBincode<Geometry>>,
 }
 
 impl Index {
@@ -232,6 +257,7 @@ impl Index {
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
+        let geojson_geometries = env.create_database(&mut wtxn, Some(GEOJSON_GEOMETRIES))?;
 
         let this = Index {
             env: env.clone(),
@@ -259,6 +285,7 @@ impl Index {
             vector_arroy,
             embedder_category_id,
             documents,
+            geojson_geometries,
         };
         if this.get_version(&wtxn)?.is_none() && creation {
             this.put_version(
@@ -667,6 +694,38 @@ impl Index {
         }
     }
 
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the bounding boxes of geojson shapes to documents ids.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rtree: &RTree<GeoJsonShape>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>().put(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the bounding boxes of geojson shapes to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the bounding boxes of geojson shapes to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn<'_>) -> Result<Option<RTree<GeoJsonShape>>> {
+        match self
+            .main
+            .remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>()
+            .get(rtxn, main_key::GEOJSON_RTREE_KEY)?
+        {
+            Some(rtree) => Ok(Some(rtree)),
+            None => Ok(None),
+        }
+    }
+
     /* geo faceted */
 
     /// Writes the documents ids that are faceted with a _geo field.
@@ -1002,6 +1061,13 @@ impl Index {
         Ok(geo_filter || geo_sortable)
     }
 
+    /// Returns true if the geojson filtering feature is enabled.
+    pub fn is_geojson_filtering_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let geojson_filter =
+            self.filterable_attributes_rules(rtxn)?.iter().any(|field| field.has_geojson());
+        Ok(geojson_filter)
+    }
+
     /// Returns true if the geo sorting feature is enabled.
     pub fn is_geo_sorting_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
         let geo_sortable = self.sortable_fields(rtxn)?.contains(RESERVED_GEO_FIELD_NAME);
@@ -1019,9 +1085,13 @@ impl Index {
         let asc_desc_fields = self
             .criteria(rtxn)?
             .into_iter()
-            .filter_map(|criterion| match criterion {
-                Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
-                _otherwise => None,
+            .flat_map(|criterion| match criterion {
+                Criterion::Asc(field) | Criterion::Desc(field) => vec![field],
+                // the attributes used by an expression must be faceted to compute its value
+                Criterion::Expression(expression) => {
+                    expression.attributes().into_iter().map(String::from).collect()
+                }
+                _otherwise => Vec::new(),
             })
             .collect();
 
@@ -1186,6 +1256,68 @@ impl Index {
             .get(rtxn, main_key::NON_SEPARATOR_TOKENS_KEY)?)
     }
 
+    /* identifier attributes */
+
+    pub(crate) fn put_identifier_attributes(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(
+            wtxn,
+            main_key::IDENTIFIER_ATTRIBUTES_KEY,
+            set,
+        )
+    }
+
+    pub(crate) fn delete_identifier_attributes(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::IDENTIFIER_ATTRIBUTES_KEY)
+    }
+
+    /// The patterns of the attributes holding identifiers, e.g. SKUs or part numbers,
+    /// whose words are also indexed concatenated and split between letters and digits.
+    pub fn identifier_attributes(&self, rtxn: &RoTxn<'_>) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<BTreeSet<String>>>()
+            .get(rtxn, main_key::IDENTIFIER_ATTRIBUTES_KEY)?)
+    }
+
+    /* date attributes */
+
+    pub(crate) fn put_date_attributes(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(
+            wtxn,
+            main_key::DATE_ATTRIBUTES_KEY,
+            set,
+        )
+    }
+
+    pub(crate) fn delete_date_attributes(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::DATE_ATTRIBUTES_KEY)
+    }
+
+    /// The patterns of the attributes holding dates, whose facet values are normalized
+    /// so that their order is chronological.
+    pub fn date_attributes(&self, rtxn: &RoTxn<'_>) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<BTreeSet<String>>>()
+            .get(rtxn, main_key::DATE_ATTRIBUTES_KEY)?)
+    }
+
+    /// Returns `true` if the field matches the patterns of the date attributes.
+    pub fn is_date_attribute(&self, rtxn: &RoTxn<'_>, field: &str) -> Result<bool> {
+        Ok(self.date_attributes(rtxn)?.is_some_and(|attributes| {
+            let patterns = AttributePatterns::from(attributes.into_iter().collect::<Vec<_>>());
+            patterns.match_str(field) == PatternMatch::Match
+        }))
+    }
+
     /* separator tokens */
 
     pub(crate) fn put_separator_tokens(
@@ -1299,6 +1431,51 @@ impl Index {
             .unwrap_or_default())
     }
 
+    pub(crate) fn put_synonym_sets(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        synonym_sets: &[SynonymSet],
+        localized_synonyms: &BTreeMap<Language, HashMap<Vec<String>, Vec<Vec<String>>>>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::SYNONYM_SETS_KEY,
+            &synonym_sets,
+        )?;
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(
+            wtxn,
+            main_key::LOCALIZED_SYNONYMS_KEY,
+            localized_synonyms,
+        )
+    }
+
+    pub(crate) fn delete_synonym_sets(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::LOCALIZED_SYNONYMS_KEY)?;
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::SYNONYM_SETS_KEY)
+    }
+
+    /// Returns the synonym sets as they were defined by the user.
+    pub fn synonym_sets(&self, rtxn: &RoTxn<'_>) -> heed::Result<Vec<SynonymSet>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::SYNONYM_SETS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the normalized synonyms of the synonym sets restricted to some locales,
+    /// grouped by locale.
+    pub fn localized_synonyms(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<Language, HashMap<Vec<String>, Vec<Vec<String>>>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<_>>()
+            .get(rtxn, main_key::LOCALIZED_SYNONYMS_KEY)?
+            .unwrap_or_default())
+    }
+
     pub fn words_synonyms<S: AsRef<str>>(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1615,6 +1792,76 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::SORT_FACET_VALUES_BY)
     }
 
+    /// Returns the facet hierarchies, associating their name with the attributes of
+    /// their levels, from the root to the leaves.
+    pub fn facet_hierarchies(
+        &self,
+        txn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<String, Vec<String>>> {
+        let hierarchies = self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, Vec<String>>>>()
+            .get(txn, main_key::FACET_HIERARCHIES)?
+            .unwrap_or_default();
+        Ok(hierarchies)
+    }
+
+    pub(crate) fn put_facet_hierarchies(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, Vec<String>>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::FACET_HIERARCHIES, &val)
+    }
+
+    pub(crate) fn delete_facet_hierarchies(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FACET_HIERARCHIES)
+    }
+
+    /// Returns the merchandising rules associated with their id.
+    pub fn search_rules(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, SearchRule>> {
+        let rules = self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, SearchRule>>>()
+            .get(txn, main_key::SEARCH_RULES)?
+            .unwrap_or_default();
+        Ok(rules)
+    }
+
+    pub(crate) fn put_search_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, SearchRule>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::SEARCH_RULES, &val)
+    }
+
+    pub(crate) fn delete_search_rules(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SEARCH_RULES)
+    }
+
+    /// Returns the rules rewriting the queries associated with their id.
+    pub fn query_rules(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, QueryRule>> {
+        let rules = self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, QueryRule>>>()
+            .get(txn, main_key::QUERY_RULES)?
+            .unwrap_or_default();
+        Ok(rules)
+    }
+
+    pub(crate) fn put_query_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, QueryRule>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::QUERY_RULES, &val)
+    }
+
+    pub(crate) fn delete_query_rules(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_RULES)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1834,6 +2081,7 @@ impl Index {
             vector_arroy,
             embedder_category_id,
             documents,
+            geojson_geometries,
         } = self;
 
         fn compute_size(stats: DatabaseStat) -> usize {
@@ -1905,6 +2153,7 @@ impl Index {
         sizes.insert("vector_arroy", vector_arroy.stat(rtxn).map(compute_size)?);
         sizes.insert("embedder_category_id", embedder_category_id.stat(rtxn).map(compute_size)?);
         sizes.insert("documents", documents.stat(rtxn).map(compute_size)?);
+        sizes.insert("geojson_geometries", geojson_geometries.stat(rtxn).map(compute_size)?);
 
         Ok(sizes)
     }
@@ -18,15 +18,24 @@ mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 mod filterable_attributes_rules;
+pub mod geojson;
 pub mod heed_codec;
+pub mod identifier_attributes;
 pub mod index;
 mod localized_attributes_rules;
 pub mod order_by_map;
+pub mod phonetic;
 pub mod prompt;
 pub mod proximity;
+mod ranking_expression;
 pub mod score_details;
 mod search;
+mod search_rules;
+pub mod stemming;
+mod synonym_sets;
 mod thread_pool_no_abort;
+pub mod trigrams;
+pub mod typo_budgets;
 pub mod update;
 pub mod vector;
 
@@ -47,8 +56,8 @@ pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    execute_search, filtered_universe, Boost, DefaultSearchLogger, GeoSortStrategy,
+    QuerySuggestion, SearchContext, SearchLogger, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
@@ -68,6 +77,7 @@ pub use self::filterable_attributes_rules::{
     FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
     FilterableAttributesRule,
 };
+pub use self::geojson::Geometry;
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
@@ -76,12 +86,23 @@ pub use self::heed_codec::{
 };
 pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::phonetic::{PhoneticAlgorithm, PhoneticSettings};
+pub use self::ranking_expression::{RankingExpression, RankingExpressionError};
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
 pub use self::search::similar::Similar;
+pub use self::search::suggest::Suggest;
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    FacetAggregation, FacetAggregations, FacetDistribution, FacetHierarchy, FacetRanges,
+    FacetValuesOptions, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
+    QuerySyntax, Search, SearchResult, SemanticSearch, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
+pub use self::search_rules::{
+    BoostWeight, InvalidBoostWeight, PinnedDocument, QueryAnchoring, QueryRule, QueryRuleCondition,
+    QueryRuleConsequence, SearchRule, SearchRuleActions, SearchRuleBoost, SearchRuleCondition,
+};
+pub use self::synonym_sets::{SynonymSet, SynonymSetKind};
+pub use self::typo_budgets::{AttributeTypoBudget, LocaleTypoBudget, TypoBudget, TypoBudgets};
 pub use self::update::ChannelCongestion;
 
 pub use arroy;
@@ -112,6 +133,12 @@ pub type SmallVec8<T> = smallvec::SmallVec<[T; 8]>;
 /// expressed in term of latitude and longitude.
 pub type GeoPoint = rstar::primitives::GeomWithData<[f64; 3], (DocumentId, [f64; 2])>;
 
+/// A GeoJsonShape is the bounding box of a GeoJSON geometry expressed in term of latitude
+/// and longitude. Its metadata is the DocumentId of the associated document, the geometry
+/// itself is stored in the `geojson_geometries` database.
+pub type GeoJsonShape =
+    rstar::primitives::GeomWithData<rstar::primitives::Rectangle<[f64; 2]>, DocumentId>;
+
 /// The maximum length a LMDB key can be.
 ///
 /// Note that the actual allowed length is a little bit higher, but
@@ -380,6 +407,15 @@ pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Normalizes a facet value of a date attribute, dates are normalized so that their order
+/// is chronological and the other values like any facet value.
+pub fn normalize_date_facet(original: &str) -> String {
+    match facet::date::parse_date(original) {
+        Some(date) => facet::date::sortable_date(date),
+        None => normalize_facet(original),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -16,16 +16,25 @@ use crate::{AttributePatterns, FieldId};
 /// The pattern `attribute_name*` matches any attribute name that starts with `attribute_name`.
 /// The pattern `*attribute_name` matches any attribute name that ends with `attribute_name`.
 /// The pattern `*attribute_name*` matches any attribute name that contains `attribute_name`.
+///
+/// When `stemming` is enabled, the stems of the words of the matching attributes in the locales
+/// of the rule are indexed along the words.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct LocalizedAttributesRule {
     pub attribute_patterns: AttributePatterns,
     #[schema(value_type = Vec<String>)]
     pub locales: Vec<Language>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stemming: bool,
 }
 
 impl LocalizedAttributesRule {
     pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
-        Self { attribute_patterns: AttributePatterns::from(attribute_patterns), locales }
+        Self {
+            attribute_patterns: AttributePatterns::from(attribute_patterns),
+            locales,
+            stemming: false,
+        }
     }
 
     pub fn match_str(&self, str: &str) -> PatternMatch {
@@ -35,6 +44,15 @@ impl LocalizedAttributesRule {
     pub fn locales(&self) -> &[Language] {
         &self.locales
     }
+
+    /// Returns the stems of the word to index along it, if the rule enables stemming.
+    pub fn stems(&self, word: &str) -> Vec<String> {
+        if self.stemming {
+            crate::stemming::stems(&self.locales, word)
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
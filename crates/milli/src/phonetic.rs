@@ -0,0 +1,128 @@
+use deserr::Deserr;
+use heed::types::{SerdeJson, Str};
+use heed::{RoTxn, RwTxn};
+use rphonetic::{DoubleMetaphone, Encoder, Soundex};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::attribute_patterns::PatternMatch;
+use crate::index::main_key;
+use crate::{AttributePatterns, Index};
+
+/// The character prefixing the phonetic codes in the word databases.
+///
+/// It can't be part of a token and the codes are uppercase, so that they never collide
+/// with the words of the documents nor with their typo derivations.
+pub const PHONETIC_CODE_PREFIX: char = '\u{1}';
+
+/// The phonetic codes of the words of some attributes, indexed along the words so that
+/// spelling variants sounding the same match each other, e.g. `Schmidt` and `Smith`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase)]
+pub struct PhoneticSettings {
+    #[schema(value_type = Vec<String>, example = json!(["firstName", "lastName"]))]
+    pub attribute_patterns: AttributePatterns,
+    #[serde(default)]
+    #[deserr(default)]
+    pub algorithm: PhoneticAlgorithm,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum PhoneticAlgorithm {
+    Soundex,
+    #[default]
+    DoubleMetaphone,
+}
+
+impl PhoneticSettings {
+    pub fn match_str(&self, str: &str) -> PatternMatch {
+        self.attribute_patterns.match_str(str)
+    }
+
+    /// Returns the distinct phonetic codes of the word, prefixed by [`PHONETIC_CODE_PREFIX`].
+    ///
+    /// Only the words made of ASCII letters are encoded, the algorithms being designed
+    /// for the latin alphabet.
+    pub fn codes(&self, word: &str) -> Vec<String> {
+        let mut codes = Vec::new();
+        if word.is_empty() || !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return codes;
+        }
+
+        let mut push = |code: String| {
+            if !code.is_empty() {
+                let code = format!("{PHONETIC_CODE_PREFIX}{code}");
+                if !codes.contains(&code) {
+                    codes.push(code);
+                }
+            }
+        };
+
+        match self.algorithm {
+            PhoneticAlgorithm::Soundex => push(Soundex::default().encode(word)),
+            PhoneticAlgorithm::DoubleMetaphone => {
+                let encoder = DoubleMetaphone::default();
+                push(encoder.encode(word));
+                push(encoder.encode_alternate(word));
+            }
+        }
+
+        codes
+    }
+}
+
+/// Returns `true` if the word of the word databases is a phonetic code.
+pub fn is_phonetic_code(word: &str) -> bool {
+    word.starts_with(PHONETIC_CODE_PREFIX)
+}
+
+impl Index {
+    pub fn phonetic_settings(&self, txn: &RoTxn<'_>) -> heed::Result<Option<PhoneticSettings>> {
+        self.main
+            .remap_types::<Str, SerdeJson<PhoneticSettings>>()
+            .get(txn, main_key::PHONETIC_SETTINGS)
+    }
+
+    pub(crate) fn put_phonetic_settings(
+        &self,
+        txn: &mut RwTxn<'_>,
+        phonetic: &PhoneticSettings,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<PhoneticSettings>>().put(
+            txn,
+            main_key::PHONETIC_SETTINGS,
+            phonetic,
+        )
+    }
+
+    pub(crate) fn delete_phonetic_settings(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::PHONETIC_SETTINGS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(algorithm: PhoneticAlgorithm) -> PhoneticSettings {
+        PhoneticSettings { attribute_patterns: vec!["name".to_string()].into(), algorithm }
+    }
+
+    #[test]
+    fn sounding_alike_words_share_a_code() {
+        for algorithm in [PhoneticAlgorithm::Soundex, PhoneticAlgorithm::DoubleMetaphone] {
+            let settings = settings(algorithm);
+            let schmidt = settings.codes("schmidt");
+            let smith = settings.codes("smith");
+            assert!(schmidt.iter().any(|code| smith.contains(code)), "{algorithm:?}");
+            assert!(schmidt.iter().all(|code| is_phonetic_code(code)));
+        }
+
+        let settings = settings(PhoneticAlgorithm::DoubleMetaphone);
+        let smith = settings.codes("smith");
+        assert!(!settings.codes("johnson").iter().any(|code| smith.contains(code)));
+    }
+}
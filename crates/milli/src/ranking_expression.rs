@@ -0,0 +1,708 @@
+//! This module provides the `RankingExpression` type used by the `score: <expression>` ranking
+//! rule and defines all the errors related to this type.
+//!
+//! An expression combines the numeric values of the attributes of a document, e.g.
+//! `log1p(popularity) * 0.3 + recency_decay(published_at, 30d)`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The prefix that introduces a ranking expression in the ranking rules.
+pub const RANKING_EXPRESSION_PREFIX: &str = "score:";
+
+/// The maximum depth of the tree of an expression, past it the expression is rejected so that
+/// parsing, evaluating and dropping it can't overflow the stack.
+const MAX_EXPRESSION_DEPTH: usize = 200;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RankingExpressionError {
+    #[error("the expression is empty")]
+    Empty,
+    #[error("unexpected character `{character}` at position {position}")]
+    UnexpectedCharacter { character: char, position: usize },
+    #[error("unexpected `{found}` at position {position}, expected {expected}")]
+    UnexpectedToken { found: String, position: usize, expected: &'static str },
+    #[error("unexpected end of the expression, expected {expected}")]
+    UnexpectedEnd { expected: &'static str },
+    #[error("invalid number `{number}` at position {position}")]
+    InvalidNumber { number: String, position: usize },
+    #[error(
+        "unknown function `{name}` at position {position}, available functions are {}",
+        Function::ALL.iter().map(|f| format!("`{}`", f.name())).collect::<Vec<_>>().join(", ")
+    )]
+    UnknownFunction { name: String, position: usize },
+    #[error("the `{function}` function takes {expected} argument(s) but {found} were given")]
+    InvalidArgumentCount { function: &'static str, expected: usize, found: usize },
+    #[error("the first argument of the `recency_decay` function must be an attribute")]
+    RecencyDecayAttribute,
+    #[error(
+        "the second argument of the `recency_decay` function must be a positive duration like `30d`, \
+        durations are expressed with the `s`, `m`, `h`, `d` and `w` units"
+    )]
+    RecencyDecayScale,
+    #[error("the duration at position {position} can only be used as the scale of the `recency_decay` function")]
+    MisplacedDuration { position: usize },
+    #[error(
+        "the expression is nested too deeply at position {position}, \
+        expressions can't be more than {MAX_EXPRESSION_DEPTH} levels deep"
+    )]
+    TooDeep { position: usize },
+}
+
+/// A ranking expression, it is displayed and stored as it was written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RankingExpression {
+    source: String,
+    root: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Attribute(String),
+    Negate(Box<Expr>),
+    Binary(BinaryOperator, Box<Expr>, Box<Expr>),
+    Function(Function, Vec<Expr>),
+    RecencyDecay { attribute: String, scale: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Log1p,
+    Ln,
+    Log10,
+    Sqrt,
+    Abs,
+    Exp,
+    Min,
+    Max,
+    Pow,
+    Coalesce,
+    RecencyDecay,
+}
+
+impl Function {
+    const ALL: [Function; 11] = [
+        Function::Log1p,
+        Function::Ln,
+        Function::Log10,
+        Function::Sqrt,
+        Function::Abs,
+        Function::Exp,
+        Function::Min,
+        Function::Max,
+        Function::Pow,
+        Function::Coalesce,
+        Function::RecencyDecay,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Function::Log1p => "log1p",
+            Function::Ln => "ln",
+            Function::Log10 => "log10",
+            Function::Sqrt => "sqrt",
+            Function::Abs => "abs",
+            Function::Exp => "exp",
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::Pow => "pow",
+            Function::Coalesce => "coalesce",
+            Function::RecencyDecay => "recency_decay",
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Function::Log1p
+            | Function::Ln
+            | Function::Log10
+            | Function::Sqrt
+            | Function::Abs
+            | Function::Exp => 1,
+            Function::Min
+            | Function::Max
+            | Function::Pow
+            | Function::Coalesce
+            | Function::RecencyDecay => 2,
+        }
+    }
+}
+
+impl RankingExpression {
+    /// Returns the attributes whose values are used by the expression.
+    pub fn attributes(&self) -> Vec<&str> {
+        let mut attributes = Vec::new();
+        self.root.attributes(&mut attributes);
+        attributes.sort_unstable();
+        attributes.dedup();
+        attributes
+    }
+
+    /// Computes the score of a document.
+    ///
+    /// The `attribute_value` function returns the numeric value of an attribute of the document,
+    /// `now` is the current unix timestamp in seconds used by the `recency_decay` function.
+    /// `None` is returned when an attribute is missing or when the result is not a finite number,
+    /// unless the missing value is replaced by the `coalesce` function.
+    pub fn evaluate<E>(
+        &self,
+        attribute_value: &mut impl FnMut(&str) -> Result<Option<f64>, E>,
+        now: f64,
+    ) -> Result<Option<f64>, E> {
+        self.root.evaluate(attribute_value, now)
+    }
+}
+
+impl Expr {
+    fn attributes<'a>(&'a self, attributes: &mut Vec<&'a str>) {
+        match self {
+            Expr::Number(_) => (),
+            Expr::Attribute(attribute) | Expr::RecencyDecay { attribute, .. } => {
+                attributes.push(attribute)
+            }
+            Expr::Negate(expr) => expr.attributes(attributes),
+            Expr::Binary(_, left, right) => {
+                left.attributes(attributes);
+                right.attributes(attributes);
+            }
+            Expr::Function(_, arguments) => {
+                arguments.iter().for_each(|argument| argument.attributes(attributes))
+            }
+        }
+    }
+
+    fn evaluate<E>(
+        &self,
+        attribute_value: &mut impl FnMut(&str) -> Result<Option<f64>, E>,
+        now: f64,
+    ) -> Result<Option<f64>, E> {
+        let value = match self {
+            Expr::Number(number) => Some(*number),
+            Expr::Attribute(attribute) => attribute_value(attribute)?,
+            Expr::Negate(expr) => expr.evaluate(attribute_value, now)?.map(|value| -value),
+            Expr::Binary(operator, left, right) => {
+                let left = left.evaluate(attribute_value, now)?;
+                let right = right.evaluate(attribute_value, now)?;
+                left.zip(right).map(|(left, right)| match operator {
+                    BinaryOperator::Add => left + right,
+                    BinaryOperator::Subtract => left - right,
+                    BinaryOperator::Multiply => left * right,
+                    BinaryOperator::Divide => left / right,
+                })
+            }
+            Expr::Function(Function::Coalesce, arguments) => {
+                match arguments[0].evaluate(attribute_value, now)? {
+                    Some(value) => Some(value),
+                    None => arguments[1].evaluate(attribute_value, now)?,
+                }
+            }
+            Expr::Function(function, arguments) => {
+                let mut values = Vec::with_capacity(arguments.len());
+                for argument in arguments {
+                    match argument.evaluate(attribute_value, now)? {
+                        Some(value) => values.push(value),
+                        None => return Ok(None),
+                    }
+                }
+                Some(match (function, values.as_slice()) {
+                    (Function::Log1p, [x]) => x.ln_1p(),
+                    (Function::Ln, [x]) => x.ln(),
+                    (Function::Log10, [x]) => x.log10(),
+                    (Function::Sqrt, [x]) => x.sqrt(),
+                    (Function::Abs, [x]) => x.abs(),
+                    (Function::Exp, [x]) => x.exp(),
+                    (Function::Min, [x, y]) => x.min(*y),
+                    (Function::Max, [x, y]) => x.max(*y),
+                    (Function::Pow, [x, y]) => x.powf(*y),
+                    _ => unreachable!("the arity of the functions is checked at parse time"),
+                })
+            }
+            Expr::RecencyDecay { attribute, scale } => attribute_value(attribute)?.map(|time| {
+                // the score is halved every `scale` seconds, documents from the future are fresh
+                let age = (now - time).max(0.0);
+                0.5f64.powf(age / scale)
+            }),
+        };
+
+        Ok(value.filter(|value| value.is_finite()))
+    }
+}
+
+impl FromStr for RankingExpression {
+    type Err = RankingExpressionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let source = text.trim();
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err(RankingExpressionError::Empty);
+        }
+        let mut parser = Parser { tokens, next: 0 };
+        let root = parser.expression(0)?;
+        match parser.peek() {
+            None => Ok(RankingExpression { source: source.to_string(), root }),
+            Some((token, position)) => Err(RankingExpressionError::UnexpectedToken {
+                found: token.to_string(),
+                position,
+                expected: "an operator or the end of the expression",
+            }),
+        }
+    }
+}
+
+impl TryFrom<String> for RankingExpression {
+    type Error = RankingExpressionError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<RankingExpression> for String {
+    fn from(expression: RankingExpression) -> Self {
+        expression.source
+    }
+}
+
+impl fmt::Display for RankingExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Two expressions are equal when they are written the same way.
+impl PartialEq for RankingExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for RankingExpression {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    /// A duration expressed in seconds.
+    Duration(f64),
+    Identifier(String),
+    LeftParenthesis,
+    RightParenthesis,
+    Comma,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Duration(seconds) => write!(f, "{seconds}s"),
+            Token::Identifier(identifier) => f.write_str(identifier),
+            Token::LeftParenthesis => f.write_str("("),
+            Token::RightParenthesis => f.write_str(")"),
+            Token::Comma => f.write_str(","),
+            Token::Plus => f.write_str("+"),
+            Token::Minus => f.write_str("-"),
+            Token::Star => f.write_str("*"),
+            Token::Slash => f.write_str("/"),
+        }
+    }
+}
+
+/// Splits the expression in tokens associated with their position in characters.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, RankingExpressionError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while let Some(&character) = chars.get(position) {
+        let start = position;
+        let token = match character {
+            c if c.is_whitespace() => {
+                position += 1;
+                continue;
+            }
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            ',' => Token::Comma,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            c if c.is_ascii_digit() || c == '.' => {
+                while chars.get(position).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+                    position += 1;
+                }
+                let number: String = chars[start..position].iter().collect();
+                let value: f64 =
+                    number.parse().map_err(|_| RankingExpressionError::InvalidNumber {
+                        number: number.clone(),
+                        position: start,
+                    })?;
+                let unit = match chars.get(position) {
+                    Some('s') => Some(1.),
+                    Some('m') => Some(60.),
+                    Some('h') => Some(60. * 60.),
+                    Some('d') => Some(24. * 60. * 60.),
+                    Some('w') => Some(7. * 24. * 60. * 60.),
+                    _ => None,
+                };
+                let token = match unit {
+                    Some(unit) => {
+                        position += 1;
+                        Token::Duration(value * unit)
+                    }
+                    None => Token::Number(value),
+                };
+                if chars.get(position).is_some_and(|c| c.is_alphanumeric() || *c == '_') {
+                    let number: String = chars[start..=position].iter().collect();
+                    return Err(RankingExpressionError::InvalidNumber { number, position: start });
+                }
+                tokens.push((token, start));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while chars
+                    .get(position)
+                    .is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    position += 1;
+                }
+                tokens.push((Token::Identifier(chars[start..position].iter().collect()), start));
+                continue;
+            }
+            character => {
+                return Err(RankingExpressionError::UnexpectedCharacter { character, position })
+            }
+        };
+        tokens.push((token, start));
+        position += 1;
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser following this grammar:
+///
+/// ```text
+/// expression = term (("+" | "-") term)*
+/// term       = factor (("*" | "/") factor)*
+/// factor     = "-" factor | primary
+/// primary    = number | attribute | function "(" expression ("," expression)* ")" | "(" expression ")"
+/// ```
+///
+/// Every rule takes the depth of the node it parses in the expression tree and
+/// rejects the expression once it goes past [`MAX_EXPRESSION_DEPTH`].
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(&Token, usize)> {
+        self.tokens.get(self.next).map(|(token, position)| (token, *position))
+    }
+
+    fn advance(
+        &mut self,
+        expected: &'static str,
+    ) -> Result<(Token, usize), RankingExpressionError> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token.ok_or(RankingExpressionError::UnexpectedEnd { expected })
+    }
+
+    fn expect(
+        &mut self,
+        expected_token: Token,
+        expected: &'static str,
+    ) -> Result<(), RankingExpressionError> {
+        match self.advance(expected)? {
+            (token, _) if token == expected_token => Ok(()),
+            (token, position) => Err(RankingExpressionError::UnexpectedToken {
+                found: token.to_string(),
+                position,
+                expected,
+            }),
+        }
+    }
+
+    /// Returns the depth of the children of a node at `depth`, the node starting at `position`.
+    fn nested(depth: usize, position: usize) -> Result<usize, RankingExpressionError> {
+        if depth >= MAX_EXPRESSION_DEPTH {
+            return Err(RankingExpressionError::TooDeep { position });
+        }
+        Ok(depth + 1)
+    }
+
+    fn expression(&mut self, depth: usize) -> Result<Expr, RankingExpressionError> {
+        let mut expr = self.term(depth)?;
+        // the left operand of a chain of operators is nested a bit deeper at each operator
+        let mut depth = depth;
+        loop {
+            let (operator, position) = match self.peek() {
+                Some((Token::Plus, position)) => (BinaryOperator::Add, position),
+                Some((Token::Minus, position)) => (BinaryOperator::Subtract, position),
+                _ => return Ok(expr),
+            };
+            self.next += 1;
+            depth = Self::nested(depth, position)?;
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(self.term(depth)?));
+        }
+    }
+
+    fn term(&mut self, depth: usize) -> Result<Expr, RankingExpressionError> {
+        let mut expr = self.factor(depth)?;
+        let mut depth = depth;
+        loop {
+            let (operator, position) = match self.peek() {
+                Some((Token::Star, position)) => (BinaryOperator::Multiply, position),
+                Some((Token::Slash, position)) => (BinaryOperator::Divide, position),
+                _ => return Ok(expr),
+            };
+            self.next += 1;
+            depth = Self::nested(depth, position)?;
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(self.factor(depth)?));
+        }
+    }
+
+    fn factor(&mut self, depth: usize) -> Result<Expr, RankingExpressionError> {
+        if let Some((Token::Minus, position)) = self.peek() {
+            self.next += 1;
+            let depth = Self::nested(depth, position)?;
+            return Ok(Expr::Negate(Box::new(self.factor(depth)?)));
+        }
+        self.primary(depth)
+    }
+
+    fn primary(&mut self, depth: usize) -> Result<Expr, RankingExpressionError> {
+        const EXPECTED: &str = "a number, an attribute, a function or `(`";
+        match self.advance(EXPECTED)? {
+            (Token::Number(number), _) => Ok(Expr::Number(number)),
+            (Token::Duration(_), position) => {
+                Err(RankingExpressionError::MisplacedDuration { position })
+            }
+            (Token::LeftParenthesis, position) => {
+                let expr = self.expression(Self::nested(depth, position)?)?;
+                self.expect(Token::RightParenthesis, "`)`")?;
+                Ok(expr)
+            }
+            (Token::Identifier(name), position) => {
+                if let Some((Token::LeftParenthesis, _)) = self.peek() {
+                    self.next += 1;
+                    self.function(name, position, Self::nested(depth, position)?)
+                } else {
+                    Ok(Expr::Attribute(name))
+                }
+            }
+            (token, position) => Err(RankingExpressionError::UnexpectedToken {
+                found: token.to_string(),
+                position,
+                expected: EXPECTED,
+            }),
+        }
+    }
+
+    /// Parses the arguments of a function, the opening parenthesis is already consumed.
+    fn function(
+        &mut self,
+        name: String,
+        position: usize,
+        depth: usize,
+    ) -> Result<Expr, RankingExpressionError> {
+        let function = Function::ALL
+            .into_iter()
+            .find(|function| function.name() == name)
+            .ok_or(RankingExpressionError::UnknownFunction { name, position })?;
+
+        if function == Function::RecencyDecay {
+            return self.recency_decay();
+        }
+
+        let mut arguments = vec![self.expression(depth)?];
+        loop {
+            match self.advance("`,` or `)`")? {
+                (Token::Comma, _) => arguments.push(self.expression(depth)?),
+                (Token::RightParenthesis, _) => break,
+                (token, position) => {
+                    return Err(RankingExpressionError::UnexpectedToken {
+                        found: token.to_string(),
+                        position,
+                        expected: "`,` or `)`",
+                    })
+                }
+            }
+        }
+
+        if arguments.len() != function.arity() {
+            return Err(RankingExpressionError::InvalidArgumentCount {
+                function: function.name(),
+                expected: function.arity(),
+                found: arguments.len(),
+            });
+        }
+
+        Ok(Expr::Function(function, arguments))
+    }
+
+    fn recency_decay(&mut self) -> Result<Expr, RankingExpressionError> {
+        let attribute = match self.advance("an attribute")? {
+            (Token::Identifier(attribute), _) => attribute,
+            _ => return Err(RankingExpressionError::RecencyDecayAttribute),
+        };
+        self.expect(Token::Comma, "`,`")?;
+        let scale = match self.advance("a duration")? {
+            (Token::Duration(scale), _) if scale > 0. => scale,
+            _ => return Err(RankingExpressionError::RecencyDecayScale),
+        };
+        self.expect(Token::RightParenthesis, "`)`")?;
+        Ok(Expr::RecencyDecay { attribute, scale })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str, values: &[(&str, f64)], now: f64) -> Option<f64> {
+        let expression: RankingExpression = expression.parse().unwrap();
+        let mut attribute_value = |name: &str| -> Result<_, ()> {
+            Ok(values.iter().find(|(attribute, _)| *attribute == name).map(|(_, value)| *value))
+        };
+        expression.evaluate(&mut attribute_value, now).unwrap()
+    }
+
+    #[test]
+    fn parse_and_evaluate() {
+        let values = [("popularity", 99.), ("price", 4.), ("stats.rating", 3.5)];
+
+        assert_eq!(evaluate("1 + 2 * 3", &values, 0.), Some(7.));
+        assert_eq!(evaluate("(1 + 2) * 3", &values, 0.), Some(9.));
+        assert_eq!(evaluate("10 - 4 - 3", &values, 0.), Some(3.));
+        assert_eq!(evaluate("12 / 3 / 2", &values, 0.), Some(2.));
+        assert_eq!(evaluate("-price + --2", &values, 0.), Some(-2.));
+        assert_eq!(evaluate("log10(popularity + 1) * 0.5", &values, 0.), Some(1.));
+        assert_eq!(evaluate("sqrt(price) + pow(2, 3)", &values, 0.), Some(10.));
+        assert_eq!(evaluate("min(price, stats.rating) + max(1, abs(-2))", &values, 0.), Some(5.5));
+        assert_eq!(evaluate("ln(exp(2))", &values, 0.), Some(2.));
+        assert_eq!(evaluate("log1p(0)", &values, 0.), Some(0.));
+
+        // missing attributes and non-finite results have no value
+        assert_eq!(evaluate("missing * 2", &values, 0.), None);
+        assert_eq!(evaluate("coalesce(missing, 0) * 2 + 1", &values, 0.), Some(1.));
+        assert_eq!(evaluate("coalesce(price, 0)", &values, 0.), Some(4.));
+        assert_eq!(evaluate("price / 0", &values, 0.), None);
+        assert_eq!(evaluate("ln(-1)", &values, 0.), None);
+    }
+
+    #[test]
+    fn recency_decay() {
+        let day = 24. * 60. * 60.;
+        let values = [("published_at", 100. * day)];
+
+        assert_eq!(evaluate("recency_decay(published_at, 30d)", &values, 100. * day), Some(1.));
+        assert_eq!(evaluate("recency_decay(published_at, 30d)", &values, 130. * day), Some(0.5));
+        assert_eq!(evaluate("recency_decay(published_at, 15d)", &values, 130. * day), Some(0.25));
+        assert_eq!(evaluate("recency_decay(published_at, 720h)", &values, 130. * day), Some(0.5));
+        // documents from the future are considered fresh
+        assert_eq!(evaluate("recency_decay(published_at, 1w)", &values, 50. * day), Some(1.));
+        assert_eq!(evaluate("recency_decay(missing, 1w)", &values, 50. * day), None);
+    }
+
+    #[test]
+    fn attributes() {
+        let expression: RankingExpression =
+            "log1p(popularity) * 0.3 + recency_decay(published_at, 30d) + popularity"
+                .parse()
+                .unwrap();
+        assert_eq!(expression.attributes(), ["popularity", "published_at"]);
+        assert_eq!(
+            expression.to_string(),
+            "log1p(popularity) * 0.3 + recency_decay(published_at, 30d) + popularity"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let errors = [
+            ("", "the expression is empty"),
+            ("   ", "the expression is empty"),
+            ("popularity $ 2", "unexpected character `$` at position 11"),
+            ("popularity 2", "unexpected `2` at position 11, expected an operator or the end of the expression"),
+            ("popularity *", "unexpected end of the expression, expected a number, an attribute, a function or `(`"),
+            ("(popularity", "unexpected end of the expression, expected `)`"),
+            ("popularity)", "unexpected `)` at position 10, expected an operator or the end of the expression"),
+            ("* 2", "unexpected `*` at position 0, expected a number, an attribute, a function or `(`"),
+            ("1.2.3", "invalid number `1.2.3` at position 0"),
+            ("12abc", "invalid number `12a` at position 0"),
+            ("log(popularity)", "unknown function `log` at position 0, available functions are `log1p`, `ln`, `log10`, `sqrt`, `abs`, `exp`, `min`, `max`, `pow`, `coalesce`, `recency_decay`"),
+            ("log1p(popularity, 2)", "the `log1p` function takes 1 argument(s) but 2 were given"),
+            ("max(popularity)", "the `max` function takes 2 argument(s) but 1 were given"),
+            ("max(popularity 2)", "unexpected `2` at position 15, expected `,` or `)`"),
+            ("recency_decay(2, 30d)", "the first argument of the `recency_decay` function must be an attribute"),
+            ("recency_decay(published_at, 30)", "the second argument of the `recency_decay` function must be a positive duration like `30d`, durations are expressed with the `s`, `m`, `h`, `d` and `w` units"),
+            ("recency_decay(published_at, 0d)", "the second argument of the `recency_decay` function must be a positive duration like `30d`, durations are expressed with the `s`, `m`, `h`, `d` and `w` units"),
+            ("popularity * 30d", "the duration at position 13 can only be used as the scale of the `recency_decay` function"),
+        ];
+
+        for (input, expected) in errors {
+            let error = input.parse::<RankingExpression>().unwrap_err();
+            assert_eq!(error.to_string(), expected, "bad error for input `{input}`");
+        }
+    }
+
+    #[test]
+    fn too_deep() {
+        let negations = format!("{}1", "-".repeat(500_000));
+        assert_eq!(
+            negations.parse::<RankingExpression>().unwrap_err(),
+            RankingExpressionError::TooDeep { position: MAX_EXPRESSION_DEPTH }
+        );
+
+        let parentheses = format!("{}1{}", "(".repeat(500_000), ")".repeat(500_000));
+        assert_eq!(
+            parentheses.parse::<RankingExpression>().unwrap_err(),
+            RankingExpressionError::TooDeep { position: MAX_EXPRESSION_DEPTH }
+        );
+
+        let functions = format!("{}1{}", "abs(".repeat(500_000), ")".repeat(500_000));
+        assert!(matches!(
+            functions.parse::<RankingExpression>().unwrap_err(),
+            RankingExpressionError::TooDeep { .. }
+        ));
+
+        let additions = format!("1{}", " + 1".repeat(500_000));
+        assert!(matches!(
+            additions.parse::<RankingExpression>().unwrap_err(),
+            RankingExpressionError::TooDeep { .. }
+        ));
+
+        // right below the limit the expression is still accepted
+        let depth = MAX_EXPRESSION_DEPTH / 2;
+        let nested = format!("{}-1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(evaluate(&nested, &[], 0.), Some(-1.));
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let expression: RankingExpression = "log1p(popularity) * 2".parse().unwrap();
+        let json = serde_json::to_string(&expression).unwrap();
+        assert_eq!(json, r#""log1p(popularity) * 2""#);
+        let deserialized: RankingExpression = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, expression);
+        assert!(serde_json::from_str::<RankingExpression>(r#""log1p(""#).is_err());
+    }
+}
@@ -17,6 +17,11 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Expression(Expression),
+    Boost(Boost),
+    /// Returned for the documents pinned by a search rule, they are placed at their position
+    /// regardless of the ranking rules.
+    Pinned(Pinned),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -59,6 +64,9 @@ impl ScoreDetails {
             ScoreDetails::Position(details) => Some(*details),
             ScoreDetails::ExactAttribute(details) => Some(details.rank()),
             ScoreDetails::ExactWords(details) => Some(details.rank()),
+            ScoreDetails::Expression(details) => Some(details.rank),
+            ScoreDetails::Boost(details) => Some(details.rank),
+            ScoreDetails::Pinned(_) => Some(Rank { rank: 1, max_rank: 1 }),
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
@@ -129,6 +137,9 @@ impl ScoreDetails {
             ScoreDetails::Position(p) => RankOrValue::Rank(*p),
             ScoreDetails::ExactAttribute(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::ExactWords(e) => RankOrValue::Rank(e.rank()),
+            ScoreDetails::Expression(e) => RankOrValue::Rank(e.rank),
+            ScoreDetails::Boost(b) => RankOrValue::Rank(b.rank),
+            ScoreDetails::Pinned(_) => RankOrValue::Rank(Rank { rank: 1, max_rank: 1 }),
             ScoreDetails::Sort(sort) => RankOrValue::Sort(sort),
             ScoreDetails::GeoSort(geosort) => RankOrValue::GeoSort(geosort),
             ScoreDetails::Vector(vector) => {
@@ -281,6 +292,42 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::Expression(details) => {
+                    let expression = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        format!("score: {}", details.expression)
+                    };
+                    let value = if details.redacted {
+                        "<hidden>".into()
+                    } else {
+                        serde_json::json!(details.value)
+                    };
+                    let expression_details = serde_json::json!({
+                        "order": order,
+                        "value": value,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(expression, expression_details);
+                    order += 1;
+                }
+                ScoreDetails::Boost(details) => {
+                    let boost_details = serde_json::json!({
+                        "order": order,
+                        "weight": details.weight,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert("boost".into(), boost_details);
+                    order += 1;
+                }
+                ScoreDetails::Pinned(details) => {
+                    let pinned_details = serde_json::json!({
+                        "order": order,
+                        "position": details.position,
+                    });
+                    details_map.insert("pinned".into(), pinned_details);
+                    order += 1;
+                }
                 ScoreDetails::Vector(s) => {
                     let similarity = s.similarity.as_ref();
 
@@ -511,6 +558,33 @@ impl PartialOrd for Sort {
     }
 }
 
+/// The score of a document computed by a ranking expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub expression: String,
+    pub redacted: bool,
+    /// The value computed by the expression, `None` if it couldn't be computed.
+    pub value: Option<f64>,
+    /// The value normalized between the smallest and the biggest values of the candidates.
+    pub rank: Rank,
+}
+
+/// The score of a document computed from the boosts it matches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Boost {
+    /// The sum of the weights of the boosts matched by the document.
+    pub weight: f64,
+    /// The rank of the total weight among the distinct total weights of the candidates.
+    pub rank: Rank,
+}
+
+/// The position at which a search rule pinned a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pinned {
+    /// The position of the document in the results, starting from the first page.
+    pub position: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct GeoSort {
     pub target_point: [f64; 2],
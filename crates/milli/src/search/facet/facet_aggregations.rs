@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::{fmt, mem};
+
+use heed::types::Bytes;
+use heed::BytesDecode;
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+
+use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::heed_codec::facet::{
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec, OrderedF64Codec,
+};
+use crate::heed_codec::BytesRefCodec;
+use crate::{Error, FieldId, Index, Result, UserError};
+
+/// Threshold on the number of candidates under which the values are read
+/// from the documents instead of the facet database.
+const CANDIDATES_THRESHOLD: u64 = 3000;
+
+/// The aggregations of the numeric values of a facet.
+///
+/// Like the facet distribution, the aggregations are computed over the values and not the
+/// documents: a document with several values is taken into account once for each of its values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetAggregation {
+    /// The number of values.
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    /// The requested percentiles associated with their value, in the requested order.
+    pub percentiles: Vec<(f64, f64)>,
+}
+
+impl FacetAggregation {
+    pub fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+pub struct FacetAggregations<'a> {
+    facets: BTreeMap<String, Vec<f64>>,
+    candidates: Option<RoaringBitmap>,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> FacetAggregations<'a> {
+    pub fn new(rtxn: &'a heed::RoTxn<'a>, index: &'a Index) -> FacetAggregations<'a> {
+        FacetAggregations { facets: BTreeMap::new(), candidates: None, rtxn, index }
+    }
+
+    /// The facets to aggregate along with the percentiles, between `0` and `100`,
+    /// to compute for each of them.
+    pub fn facets<I: IntoIterator<Item = (A, Vec<f64>)>, A: AsRef<str>>(
+        &mut self,
+        names_percentiles: I,
+    ) -> &mut Self {
+        self.facets = names_percentiles
+            .into_iter()
+            .map(|(name, percentiles)| (name.as_ref().to_string(), percentiles))
+            .collect();
+        self
+    }
+
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// Returns the aggregations of the facets, the facets without any numeric value
+    /// in the candidates are omitted.
+    pub fn execute(&self) -> Result<BTreeMap<String, FacetAggregation>> {
+        self.check_faceted_fields()?;
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let mut aggregations = BTreeMap::new();
+        for (name, percentiles) in &self.facets {
+            let Some(field_id) = fields_ids_map.id(name) else { continue };
+            let values = if candidates.len() <= CANDIDATES_THRESHOLD {
+                self.values_from_documents(field_id, candidates)?
+            } else {
+                self.values_from_facet_database(field_id, candidates)?
+            };
+            if let Some(aggregation) = aggregate(&values, percentiles) {
+                aggregations.insert(name.clone(), aggregation);
+            }
+        }
+
+        Ok(aggregations)
+    }
+
+    /// There is a small amount of candidates, we read the values of each one of them
+    /// and count them by value.
+    fn values_from_documents(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+    ) -> heed::Result<Vec<(f64, u64)>> {
+        let mut values = BTreeMap::new();
+        let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
+
+        let db = self.index.field_id_docid_facet_f64s;
+        for docid in candidates {
+            key_buffer.truncate(mem::size_of::<FieldId>());
+            key_buffer.extend_from_slice(&docid.to_be_bytes());
+            let iter = db
+                .remap_key_type::<Bytes>()
+                .prefix_iter(self.rtxn, &key_buffer)?
+                .remap_key_type::<FieldDocIdFacetF64Codec>();
+
+            for result in iter {
+                let ((_, _, value), ()) = result?;
+                *values.entry(OrderedFloat(value)).or_insert(0) += 1;
+            }
+        }
+
+        Ok(values.into_iter().map(|(OrderedFloat(value), count)| (value, count)).collect())
+    }
+
+    /// There are too many candidates, we iterate over the values of the facet database
+    /// in ascending order and count the candidates associated with each of them.
+    fn values_from_facet_database(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+    ) -> heed::Result<Vec<(f64, u64)>> {
+        let db =
+            self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let leaf_prefix: FacetGroupKey<&[u8]> =
+            FacetGroupKey { field_id, level: 0, left_bound: &[] };
+
+        let mut values = Vec::new();
+        for result in db.prefix_iter(self.rtxn, &leaf_prefix)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let count = bitmap.intersection_len(candidates);
+            if count != 0 {
+                let value =
+                    OrderedF64Codec::bytes_decode(left_bound).map_err(heed::Error::Decoding)?;
+                values.push((value, count));
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Check that the facets to aggregate are filterable.
+    fn check_faceted_fields(&self) -> Result<()> {
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+
+        let invalid_facets: BTreeSet<_> = self
+            .facets
+            .keys()
+            .filter(|field| {
+                !matching_features(field, &filterable_attributes_rules)
+                    .is_some_and(|(_, features)| features.is_filterable())
+            })
+            .cloned()
+            .collect();
+
+        if !invalid_facets.is_empty() {
+            let valid_patterns =
+                filtered_matching_patterns(&filterable_attributes_rules, &|features| {
+                    features.is_filterable()
+                })
+                .into_iter()
+                .map(String::from)
+                .collect();
+            return Err(Error::UserError(UserError::InvalidFacetsAggregations {
+                invalid_facets_name: invalid_facets,
+                valid_patterns,
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FacetAggregations<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FacetAggregations { facets, candidates, rtxn: _, index: _ } = self;
+
+        f.debug_struct("FacetAggregations")
+            .field("facets", facets)
+            .field("candidates", candidates)
+            .finish()
+    }
+}
+
+/// Computes the aggregation of the values sorted in ascending order along with their number
+/// of occurrences.
+///
+/// The percentiles are computed with the nearest-rank method, they are always one of the values.
+fn aggregate(values: &[(f64, u64)], percentiles: &[f64]) -> Option<FacetAggregation> {
+    let (min, _) = values.first()?;
+    let (max, _) = values.last()?;
+    let count: u64 = values.iter().map(|(_, count)| count).sum();
+    let sum = values.iter().map(|(value, count)| value * *count as f64).sum();
+
+    let percentiles = percentiles
+        .iter()
+        .map(|&percentile| {
+            let rank = ((percentile / 100.0 * count as f64).ceil() as u64).clamp(1, count);
+            let mut seen = 0;
+            let value = values
+                .iter()
+                .find(|(_, count)| {
+                    seen += count;
+                    seen >= rank
+                })
+                .map_or(*max, |(value, _)| *value);
+            (percentile, value)
+        })
+        .collect();
+
+    Some(FacetAggregation { count, sum, min: *min, max: *max, percentiles })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use big_s::S;
+
+    use super::aggregate;
+    use crate::documents::mmap_from_objects;
+    use crate::index::tests::TempIndex;
+    use crate::{milli_snap, FacetAggregations, FilterableAttributesRule};
+
+    #[test]
+    fn aggregate_values() {
+        assert_eq!(aggregate(&[], &[50.]), None);
+
+        let values = [(1., 1), (2., 2), (10., 1)];
+        let aggregation = aggregate(&values, &[0., 25., 50., 75., 100.]).unwrap();
+        milli_snap!(format!("{aggregation:?}"), @"FacetAggregation { count: 4, sum: 15.0, min: 1.0, max: 10.0, percentiles: [(0.0, 1.0), (25.0, 1.0), (50.0, 2.0), (75.0, 2.0), (100.0, 10.0)] }");
+        assert_eq!(aggregation.avg(), 3.75);
+    }
+
+    #[test]
+    fn facet_aggregations() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("price")),
+                    FilterableAttributesRule::Field(S("colour")),
+                ])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..10_000 {
+            let document = if i % 10 == 0 {
+                serde_json::json!({ "id": i, "price": [i % 100, 1000], "colour": "blue" })
+            } else {
+                serde_json::json!({ "id": i, "price": i % 100, "colour": "red" })
+            };
+            documents.push(document.as_object().unwrap().clone());
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetAggregations::new(&txn, &index)
+            .facets([("price", vec![50., 99.]), ("colour", vec![])])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": FacetAggregation { count: 11000, sum: 1495000.0, min: 0.0, max: 1000.0, percentiles: [(50.0, 54.0), (99.0, 1000.0)] }}"###);
+
+        // the few candidates are read from the documents
+        let map = FacetAggregations::new(&txn, &index)
+            .facets(iter::once(("price", vec![50.])))
+            .candidates((0..20).collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": FacetAggregation { count: 22, sum: 2190.0, min: 0.0, max: 1000.0, percentiles: [(50.0, 10.0)] }}"###);
+
+        let map = FacetAggregations::new(&txn, &index)
+            .facets(iter::once(("price", vec![50.])))
+            .candidates(Default::default())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @"{}");
+
+        let error = FacetAggregations::new(&txn, &index)
+            .facets(iter::once(("id", vec![])))
+            .execute()
+            .unwrap_err();
+
+        milli_snap!(format!("{error}"), @"Invalid aggregations: Attribute `id` is not filterable. Available filterable attributes patterns are: `colour, price`.");
+    }
+}
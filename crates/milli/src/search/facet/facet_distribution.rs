@@ -1,6 +1,7 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Display;
-use std::ops::ControlFlow;
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::Bytes;
@@ -8,18 +9,24 @@ use heed::BytesDecode;
 use indexmap::IndexMap;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::attribute_patterns::match_field_legacy;
 use crate::facet::FacetType;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
-use crate::{Error, FieldId, FilterableAttributesRule, Index, PatternMatch, Result, UserError};
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::search::facet::{facet_max_value, facet_min_value};
+use crate::{
+    Error, FieldId, FieldsIdsMap, FilterableAttributesRule, Index, PatternMatch, Result, UserError,
+};
 
 /// The default number of values by facets that will
 /// be fetched from the key-value store.
@@ -48,8 +55,96 @@ impl Display for OrderBy {
     }
 }
 
+/// The numeric ranges over which the distribution of a facet is computed.
+///
+/// Each range includes its start and excludes its end, the documents are counted in every
+/// range containing one of their values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum FacetRanges {
+    /// Explicit `[start, end)` ranges, returned in the given order.
+    ///
+    /// Like the ranges of an interval, only the first `max_values_per_facet` ranges are returned.
+    Ranges(Vec<(f64, f64)>),
+    /// Consecutive ranges of the given width, aligned on its multiples and covering all the
+    /// values of the candidates.
+    Interval(f64),
+}
+
+impl FacetRanges {
+    /// The key of a range in the facet distribution, e.g. `10..50`.
+    pub fn range_key(start: f64, end: f64) -> String {
+        format!("{start}..{end}")
+    }
+}
+
+/// A hierarchy of facets, e.g. `category.lvl0`, `category.lvl1`, ..., along with the
+/// values selected at its first levels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetHierarchy {
+    /// The attributes of the levels, from the root to the leaves.
+    pub levels: Vec<String>,
+    /// The selected value of each one of the first levels.
+    pub path: Vec<String>,
+}
+
+/// Restrictions on the values returned in the distribution of a facet.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetValuesOptions {
+    /// The minimum number of candidates a value must be associated with to be returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_count: Option<u64>,
+    /// Only returns the values starting with this prefix, the comparison is made on the
+    /// normalized values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// The values that are always returned first, in the given order, even if they are not part
+    /// of the top values or do not match the other restrictions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_values: Vec<String>,
+    /// Lowers the maximum number of values returned for this facet, it is capped to the maximum
+    /// number of values per facet and the pinned values are not taken into account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_values: Option<usize>,
+}
+
+/// The values of a facet to keep while iterating over its distribution,
+/// built from its [`FacetValuesOptions`].
+struct ValuesSelection {
+    limit: usize,
+    min_count: u64,
+    normalized_prefix: Option<String>,
+    /// The normalized pinned values, they have already been inserted in the distribution.
+    normalized_pinned: HashSet<String>,
+}
+
+impl ValuesSelection {
+    fn accepts(&self, normalized_value: &str, count: u64) -> bool {
+        count >= self.min_count
+            && self
+                .normalized_prefix
+                .as_ref()
+                .is_none_or(|prefix| normalized_value.starts_with(prefix.as_str()))
+            && !self.normalized_pinned.contains(normalized_value)
+    }
+
+    /// Whether the distribution, which starts with the pinned values, contains enough values.
+    fn is_full(&self, distribution: &IndexMap<String, u64>) -> bool {
+        distribution.len() >= self.normalized_pinned.len() + self.limit
+    }
+
+    /// The number of values that can still be inserted in the distribution.
+    fn remaining(&self, distribution: &IndexMap<String, u64>) -> usize {
+        (self.normalized_pinned.len() + self.limit).saturating_sub(distribution.len())
+    }
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
+    ranges: HashMap<String, FacetRanges>,
+    values_options: HashMap<String, FacetValuesOptions>,
+    hierarchies: Vec<FacetHierarchy>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
@@ -61,6 +156,9 @@ impl<'a> FacetDistribution<'a> {
     pub fn new(rtxn: &'a heed::RoTxn<'a>, index: &'a Index) -> FacetDistribution<'a> {
         FacetDistribution {
             facets: None,
+            ranges: HashMap::new(),
+            values_options: HashMap::new(),
+            hierarchies: Vec::new(),
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
@@ -82,6 +180,39 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Computes the distribution of the given facets over numeric ranges instead of
+    /// their distinct values.
+    pub fn ranges<I: IntoIterator<Item = (A, FacetRanges)>, A: AsRef<str>>(
+        &mut self,
+        ranges: I,
+    ) -> &mut Self {
+        self.ranges =
+            ranges.into_iter().map(|(name, ranges)| (name.as_ref().to_string(), ranges)).collect();
+        self
+    }
+
+    /// Restricts the values returned in the distribution of the given facets.
+    pub fn values_options<I: IntoIterator<Item = (A, FacetValuesOptions)>, A: AsRef<str>>(
+        &mut self,
+        options: I,
+    ) -> &mut Self {
+        self.values_options = options
+            .into_iter()
+            .map(|(name, options)| (name.as_ref().to_string(), options))
+            .collect();
+        self
+    }
+
+    /// Computes the distribution of the levels of the given hierarchies restricted to their
+    /// selected path.
+    ///
+    /// The distribution of a level is computed over the candidates having the selected values
+    /// of all the previous levels, the levels following the first unselected one are skipped.
+    pub fn hierarchies(&mut self, hierarchies: Vec<FacetHierarchy>) -> &mut Self {
+        self.hierarchies = hierarchies;
+        self
+    }
+
     pub fn max_values_per_facet(&mut self, max: usize) -> &mut Self {
         self.max_values_per_facet = max;
         self
@@ -104,6 +235,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         facet_type: FacetType,
         candidates: &RoaringBitmap,
+        selection: &ValuesSelection,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
         match facet_type {
@@ -126,10 +258,12 @@ impl<'a> FacetDistribution<'a> {
                     }
                 }
 
+                let remaining = selection.remaining(distribution);
                 distribution.extend(
                     lexicographic_distribution
                         .into_iter()
-                        .take(self.max_values_per_facet.saturating_sub(distribution.len())),
+                        .filter(|(value, count)| selection.accepts(value, *count))
+                        .take(remaining),
                 );
             }
             FacetType::String => {
@@ -157,9 +291,13 @@ impl<'a> FacetDistribution<'a> {
                     }
                 }
 
+                let remaining = selection.remaining(distribution);
                 let iter = normalized_distribution
                     .into_iter()
-                    .take(self.max_values_per_facet.saturating_sub(distribution.len()))
+                    .filter(|(normalized, (_original, count))| {
+                        selection.accepts(normalized, *count)
+                    })
+                    .take(remaining)
                     .map(|(_normalized, (original, count))| (original.to_string(), count));
                 distribution.extend(iter);
             }
@@ -175,6 +313,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         candidates: &RoaringBitmap,
         order_by: OrderBy,
+        selection: &ValuesSelection,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
         let search_function = match order_by {
@@ -188,9 +327,11 @@ impl<'a> FacetDistribution<'a> {
             field_id,
             candidates,
             |facet_key, nbr_docids, _| {
-                let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
-                distribution.insert(facet_key.to_string(), nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap().to_string();
+                if selection.accepts(&facet_key, nbr_docids) {
+                    distribution.insert(facet_key, nbr_docids);
+                }
+                if selection.is_full(distribution) {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -204,6 +345,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         candidates: &RoaringBitmap,
         order_by: OrderBy,
+        selection: &ValuesSelection,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
         let search_function = match order_by {
@@ -218,6 +360,11 @@ impl<'a> FacetDistribution<'a> {
             candidates,
             |facet_key, nbr_docids, any_docid| {
                 let facet_key = StrRefCodec::bytes_decode(facet_key).unwrap();
+                if selection.is_full(distribution) {
+                    return Ok(ControlFlow::Break(()));
+                } else if !selection.accepts(facet_key, nbr_docids) {
+                    return Ok(ControlFlow::Continue(()));
+                }
 
                 let key: (FieldId, _, &str) = (field_id, any_docid, facet_key);
                 let optional_original_string =
@@ -235,7 +382,7 @@ impl<'a> FacetDistribution<'a> {
                 };
 
                 distribution.insert(original_string, nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if selection.is_full(distribution) {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -244,24 +391,146 @@ impl<'a> FacetDistribution<'a> {
         )
     }
 
+    /// The values are restricted to a prefix, we seek straight to the facet strings starting
+    /// with it instead of moving through all the facet values.
+    fn facet_strings_distribution_from_prefix(
+        &self,
+        field_id: FieldId,
+        prefix: &str,
+        candidates: &RoaringBitmap,
+        order_by: OrderBy,
+        selection: &ValuesSelection,
+        distribution: &mut IndexMap<String, u64>,
+    ) -> heed::Result<()> {
+        let mut prefix_key = field_id.to_be_bytes().to_vec();
+        prefix_key.push(0); // level 0
+        prefix_key.extend_from_slice(prefix.as_bytes());
+
+        let iter = self
+            .index
+            .facet_id_string_docids
+            .remap_types::<Bytes, FacetGroupValueCodec>()
+            .prefix_iter(self.rtxn, &prefix_key)?
+            .remap_key_type::<FacetGroupKeyCodec<StrRefCodec>>();
+
+        let remaining = selection.remaining(distribution);
+        let mut values = Vec::new();
+        for result in iter {
+            let (key, value) = result?;
+            let docids = value.bitmap & candidates;
+            let count = docids.len();
+            if count == 0 || !selection.accepts(key.left_bound, count) {
+                continue;
+            }
+
+            values.push((key.left_bound, count, docids.min()));
+            // the values are iterated in lexicographic order, we can stop once we have enough
+            if order_by == OrderBy::Lexicographic && values.len() >= remaining {
+                break;
+            }
+        }
+
+        if order_by == OrderBy::Count {
+            // the sort is stable, the values with the same count stay in lexicographic order
+            values.sort_by_key(|(_, count, _)| Reverse(*count));
+        }
+
+        for (normalized, count, any_docid) in values.into_iter().take(remaining) {
+            let optional_original_string = match any_docid {
+                Some(docid) => self
+                    .index
+                    .field_id_docid_facet_strings
+                    .get(self.rtxn, &(field_id, docid, normalized))?,
+                None => None,
+            };
+
+            let original_string = match optional_original_string {
+                Some(original_string) => original_string.to_owned(),
+                None => {
+                    tracing::error!(
+                        "Missing original facet string. Using the normalized facet {} instead",
+                        normalized
+                    );
+                    normalized.to_string()
+                }
+            };
+
+            distribution.insert(original_string, count);
+        }
+
+        Ok(())
+    }
+
+    /// The options of a facet, those of the facet itself or of its closest parent.
+    fn facet_values_options(&self, name: &str) -> Option<&FacetValuesOptions> {
+        self.values_options.get(name).or_else(|| {
+            self.values_options
+                .iter()
+                .filter(|(key, _)| match_field_legacy(key, name) == PatternMatch::Match)
+                .max_by_key(|(key, _)| key.len())
+                .map(|(_, options)| options)
+        })
+    }
+
     fn facet_values(
         &self,
         field_id: FieldId,
+        name: &str,
         order_by: OrderBy,
-    ) -> heed::Result<IndexMap<String, u64>> {
+        candidates: Option<&RoaringBitmap>,
+    ) -> Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
+        let options = self.facet_values_options(name);
+        let is_date = self.index.is_date_attribute(self.rtxn, name)?;
         let mut distribution = IndexMap::new();
-        match (order_by, &self.candidates) {
-            (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
+        let mut normalized_pinned = HashSet::new();
+        for value in options.iter().flat_map(|options| &options.pinned_values) {
+            if let Some((normalized, original, count)) =
+                self.pinned_facet_value(field_id, value, candidates, is_date)?
+            {
+                if normalized_pinned.insert(normalized) {
+                    distribution.insert(original, count);
+                }
+            }
+        }
+
+        let selection = ValuesSelection {
+            limit: options
+                .and_then(|options| options.max_values)
+                .unwrap_or(self.max_values_per_facet)
+                .min(self.max_values_per_facet),
+            min_count: options.and_then(|options| options.min_count).unwrap_or(0),
+            normalized_prefix: options
+                .and_then(|options| options.prefix.as_deref())
+                .map(crate::normalize_facet),
+            normalized_pinned,
+        };
+
+        match (order_by, candidates) {
+            (OrderBy::Lexicographic, Some(cnd))
+                if cnd.len() <= CANDIDATES_THRESHOLD && selection.normalized_prefix.is_none() =>
+            {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    Number,
+                    cnd,
+                    &selection,
+                    &mut distribution,
+                )?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    String,
+                    cnd,
+                    &selection,
+                    &mut distribution,
+                )?;
             }
             _ => {
                 let universe;
-                let candidates = match &self.candidates {
+                let candidates = match candidates {
                     Some(cnd) => cnd,
                     None => {
                         universe = self.index.documents_ids(self.rtxn)?;
@@ -273,20 +542,182 @@ impl<'a> FacetDistribution<'a> {
                     field_id,
                     candidates,
                     order_by,
+                    &selection,
                     &mut distribution,
                 )?;
-                self.facet_strings_distribution_from_facet_levels(
+                match selection.normalized_prefix.as_deref() {
+                    Some(prefix) => self.facet_strings_distribution_from_prefix(
+                        field_id,
+                        prefix,
+                        candidates,
+                        order_by,
+                        &selection,
+                        &mut distribution,
+                    )?,
+                    None => self.facet_strings_distribution_from_facet_levels(
+                        field_id,
+                        candidates,
+                        order_by,
+                        &selection,
+                        &mut distribution,
+                    )?,
+                }
+            }
+        };
+
+        Ok(distribution)
+    }
+
+    /// Returns the normalized and original forms of a pinned value along with its number
+    /// of candidates, or `None` if the value is neither a number nor a string of the facet.
+    ///
+    /// A value that isn't associated with any candidate is returned with a count of zero.
+    fn pinned_facet_value(
+        &self,
+        field_id: FieldId,
+        value: &str,
+        candidates: Option<&RoaringBitmap>,
+        is_date: bool,
+    ) -> heed::Result<Option<(String, String, u64)>> {
+        let count = |docids: &RoaringBitmap| match candidates {
+            Some(candidates) => docids.intersection_len(candidates),
+            None => docids.len(),
+        };
+
+        if let Ok(number) = value.trim().parse::<f64>() {
+            let key = FacetGroupKey { field_id, level: 0, left_bound: number };
+            if let Some(value) = self.index.facet_id_f64_docids.get(self.rtxn, &key)? {
+                let number = number.to_string();
+                return Ok(Some((number.clone(), number, count(&value.bitmap))));
+            }
+        }
+
+        let normalized = if is_date {
+            crate::normalize_date_facet(value)
+        } else {
+            crate::normalize_facet(value)
+        };
+        let key = FacetGroupKey { field_id, level: 0, left_bound: normalized.as_str() };
+        let Some(facet_value) = self.index.facet_id_string_docids.get(self.rtxn, &key)? else {
+            return Ok(None);
+        };
+        // prefer the original string of a candidate, as the facet distribution does
+        let any_docid = candidates
+            .and_then(|candidates| (&facet_value.bitmap & candidates).min())
+            .or_else(|| facet_value.bitmap.min());
+        let original = match any_docid {
+            Some(docid) => self
+                .index
+                .field_id_docid_facet_strings
+                .get(self.rtxn, &(field_id, docid, normalized.as_str()))?
+                .map_or_else(|| value.to_string(), str::to_string),
+            None => value.to_string(),
+        };
+        let count = count(&facet_value.bitmap);
+
+        Ok(Some((normalized, original, count)))
+    }
+
+    /// Counts the candidates having a numeric value within each range, using the facet
+    /// levels of the numbers database.
+    fn facet_ranges_distribution(
+        &self,
+        field_id: FieldId,
+        ranges: &FacetRanges,
+    ) -> Result<IndexMap<String, u64>> {
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let ranges = match ranges {
+            FacetRanges::Ranges(ranges) => {
+                ranges.iter().copied().take(self.max_values_per_facet).collect()
+            }
+            FacetRanges::Interval(interval) => {
+                let min = facet_min_value(self.index, self.rtxn, field_id, candidates.clone())?;
+                let max = facet_max_value(self.index, self.rtxn, field_id, candidates.clone())?;
+                match min.zip(max) {
+                    Some((min, max)) => {
+                        let first = (min / interval).floor();
+                        (0..)
+                            .map(|i| (first + i as f64) * interval)
+                            .take_while(|start| *start <= max)
+                            .take(self.max_values_per_facet)
+                            .map(|start| (start, start + interval))
+                            .collect()
+                    }
+                    None => Vec::new(),
+                }
+            }
+        };
+
+        let mut distribution = IndexMap::new();
+        for (start, end) in ranges {
+            let mut docids = RoaringBitmap::new();
+            if start < end {
+                find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                    self.rtxn,
+                    self.index.facet_id_f64_docids,
                     field_id,
-                    candidates,
-                    order_by,
-                    &mut distribution,
+                    &Bound::Included(start),
+                    &Bound::Excluded(end),
+                    Some(candidates),
+                    &mut docids,
                 )?;
             }
-        };
+            distribution.insert(FacetRanges::range_key(start, end), docids.len());
+        }
 
         Ok(distribution)
     }
 
+    /// Computes the distribution of each level of the hierarchy that is either selected or
+    /// directly follows the selected path, narrowing the candidates down level by level.
+    fn facet_hierarchy_distribution(
+        &self,
+        hierarchy: &FacetHierarchy,
+        fields_ids_map: &FieldsIdsMap,
+        distribution: &mut BTreeMap<String, IndexMap<String, u64>>,
+    ) -> Result<()> {
+        let mut candidates = self.candidates.clone();
+        for (depth, level) in hierarchy.levels.iter().enumerate().take(hierarchy.path.len() + 1) {
+            let Some(field_id) = fields_ids_map.id(level) else { break };
+            let order_by = self
+                .facets
+                .as_ref()
+                .and_then(|facets| facets.get(level).copied())
+                .unwrap_or(self.default_order_by);
+            let values = self.facet_values(field_id, level, order_by, candidates.as_ref())?;
+            distribution.insert(level.to_string(), values);
+
+            if let Some(selected) = hierarchy.path.get(depth) {
+                let selected = if self.index.is_date_attribute(self.rtxn, level)? {
+                    crate::normalize_date_facet(selected)
+                } else {
+                    crate::normalize_facet(selected)
+                };
+                let key = FacetGroupKey { field_id, level: 0, left_bound: selected.as_str() };
+                let selected_docids = self
+                    .index
+                    .facet_id_string_docids
+                    .get(self.rtxn, &key)?
+                    .map(|v| v.bitmap)
+                    .unwrap_or_default();
+                candidates = Some(match candidates {
+                    Some(candidates) => candidates & selected_docids,
+                    None => selected_docids,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
         let candidates = if let Some(candidates) = self.candidates.clone() {
             candidates
@@ -335,14 +766,28 @@ impl<'a> FacetDistribution<'a> {
         self.check_faceted_fields(&filterable_attributes_rules)?;
 
         let mut distribution = BTreeMap::new();
+        for hierarchy in &self.hierarchies {
+            self.facet_hierarchy_distribution(hierarchy, &fields_ids_map, &mut distribution)?;
+        }
+
         for (fid, name) in fields_ids_map.iter() {
+            // the levels of the hierarchies have already been computed
+            if distribution.contains_key(name) {
+                continue;
+            }
             if self.select_field(name, &filterable_attributes_rules) {
+                if let Some(ranges) = self.ranges.get(name) {
+                    let values = self.facet_ranges_distribution(fid, ranges)?;
+                    distribution.insert(name.to_string(), values);
+                    continue;
+                }
+
                 let order_by = self
                     .facets
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let values = self.facet_values(fid, name, order_by, self.candidates.as_ref())?;
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -380,19 +825,23 @@ impl<'a> FacetDistribution<'a> {
         let mut invalid_facets = BTreeSet::new();
         let mut matching_rule_indices = HashMap::new();
 
-        if let Some(facets) = &self.facets {
-            for field in facets.keys() {
-                let matched_rule = matching_features(field, filterable_attributes_rules);
-                let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
-
-                if !is_filterable {
-                    invalid_facets.insert(field.to_string());
-
-                    // If the field matched a rule but that rule doesn't enable filtering,
-                    // store the rule index for better error messages
-                    if let Some((rule_index, _)) = matched_rule {
-                        matching_rule_indices.insert(field.to_string(), rule_index);
-                    }
+        let fields = self
+            .facets
+            .iter()
+            .flat_map(|facets| facets.keys())
+            .chain(self.ranges.keys())
+            .chain(self.hierarchies.iter().flat_map(|hierarchy| &hierarchy.levels));
+        for field in fields {
+            let matched_rule = matching_features(field, filterable_attributes_rules);
+            let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
+
+            if !is_filterable {
+                invalid_facets.insert(field.to_string());
+
+                // If the field matched a rule but that rule doesn't enable filtering,
+                // store the rule index for better error messages
+                if let Some((rule_index, _)) = matched_rule {
+                    matching_rule_indices.insert(field.to_string(), rule_index);
                 }
             }
         }
@@ -420,6 +869,9 @@ impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FacetDistribution {
             facets,
+            ranges,
+            values_options,
+            hierarchies,
             candidates,
             max_values_per_facet,
             default_order_by,
@@ -429,6 +881,9 @@ impl fmt::Debug for FacetDistribution<'_> {
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
+            .field("ranges", ranges)
+            .field("values_options", values_options)
+            .field("hierarchies", hierarchies)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
@@ -444,7 +899,10 @@ mod tests {
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, FilterableAttributesRule, OrderBy};
+    use crate::{
+        milli_snap, FacetDistribution, FacetHierarchy, FacetRanges, FacetValuesOptions,
+        FilterableAttributesRule, OrderBy,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -886,4 +1344,245 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn facet_ranges() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("price"))])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({
+                "id": i,
+                "price": i % 100,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let ranges = FacetRanges::Ranges(vec![(0., 10.), (10., 50.), (50., 50.), (90., 200.)]);
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", ranges.clone())))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"0..10": 100, "10..50": 400, "50..50": 0, "90..200": 100}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", ranges.clone())))
+            .candidates((0..500).collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"0..10": 50, "10..50": 200, "50..50": 0, "90..200": 50}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", ranges)))
+            .max_values_per_facet(2)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"0..10": 100, "10..50": 400}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", FacetRanges::Interval(25.))))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"0..25": 250, "25..50": 250, "50..75": 250, "75..100": 250}}"###);
+
+        // the ranges are aligned on the multiples of the interval
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", FacetRanges::Interval(30.))))
+            .candidates((40..100).collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"30..60": 20, "60..90": 30, "90..120": 10}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", FacetRanges::Interval(25.))))
+            .max_values_per_facet(2)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {"0..25": 250, "25..50": 250}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("price", OrderBy::default())))
+            .ranges(iter::once(("price", FacetRanges::Interval(25.))))
+            .candidates(Default::default())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"price": {}}"###);
+    }
+
+    #[test]
+    fn facet_values_options() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("colour"))])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "colour": "Blue" },
+            { "id": 1, "colour": "Black" },
+            { "id": 2, "colour": "Black" },
+            { "id": 3, "colour": "Red" },
+            { "id": 4, "colour": "Brown" },
+            { "id": 5, "colour": "Black" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let options =
+            FacetValuesOptions { min_count: Some(2), prefix: Some(S("b")), ..Default::default() };
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .values_options(iter::once(("colour", options)))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Black": 3}}"###);
+
+        // the pinned values come first and aren't taken into account by the limit
+        let options = FacetValuesOptions {
+            pinned_values: vec![S("red"), S("purple")],
+            max_values: Some(1),
+            ..Default::default()
+        };
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::Count)))
+            .values_options(iter::once(("colour", options)))
+            .candidates((0..6).collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Red": 1, "Black": 3}}"###);
+
+        // a pinned value is returned even if none of the candidates has it
+        let options = FacetValuesOptions {
+            prefix: Some(S("B")),
+            pinned_values: vec![S("Brown")],
+            ..Default::default()
+        };
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .values_options(iter::once(("colour", options)))
+            .candidates([0, 1, 3].into_iter().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Brown": 0, "Black": 1, "Blue": 1}}"###);
+
+        // the limit of a facet is capped to the maximum number of values per facet
+        let options =
+            FacetValuesOptions { prefix: Some(S("b")), max_values: Some(10), ..Default::default() };
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::Count)))
+            .values_options(iter::once(("colour", options)))
+            .max_values_per_facet(2)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Black": 3, "Blue": 1}}"###);
+    }
+
+    #[test]
+    fn facet_hierarchies() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("category.lvl0")),
+                    FilterableAttributesRule::Field(S("category.lvl1")),
+                    FilterableAttributesRule::Field(S("category.lvl2")),
+                ])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "category": { "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > Android" } },
+            { "id": 1, "category": { "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > iOS" } },
+            { "id": 2, "category": { "lvl0": "Electronics", "lvl1": "Electronics > Laptops" } },
+            { "id": 3, "category": { "lvl0": "Books", "lvl1": "Books > Fiction" } },
+            { "id": 4, "category": { "lvl0": "Books" } }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let hierarchy = |path: &[&str]| FacetHierarchy {
+            levels: vec![S("category.lvl0"), S("category.lvl1"), S("category.lvl2")],
+            path: path.iter().map(|value| value.to_string()).collect(),
+        };
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, _)>())
+            .hierarchies(vec![hierarchy(&[])])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"category.lvl0": {"Books": 2, "Electronics": 3}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, _)>())
+            .hierarchies(vec![hierarchy(&["electronics"])])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"category.lvl0": {"Books": 2, "Electronics": 3}, "category.lvl1": {"Electronics > Laptops": 1, "Electronics > Phones": 2}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, _)>())
+            .hierarchies(vec![hierarchy(&["Electronics", "Electronics > Phones"])])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"category.lvl0": {"Books": 2, "Electronics": 3}, "category.lvl1": {"Electronics > Laptops": 1, "Electronics > Phones": 2}, "category.lvl2": {"Electronics > Phones > Android": 1, "Electronics > Phones > iOS": 1}}"###);
+
+        // the levels are restricted to the candidates
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, _)>())
+            .hierarchies(vec![hierarchy(&["Electronics"])])
+            .candidates([1, 3].into_iter().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"category.lvl0": {"Books": 1, "Electronics": 1}, "category.lvl1": {"Electronics > Phones": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, _)>())
+            .hierarchies(vec![hierarchy(&["Toys"])])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"category.lvl0": {"Books": 2, "Electronics": 3}, "category.lvl1": {}}"###);
+    }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 use std::fmt::{Debug, Display};
 use std::ops::Bound::{self, Excluded, Included, Unbounded};
@@ -8,16 +9,21 @@ use heed::types::LazyDecode;
 use heed::BytesEncode;
 use memchr::memmem::Finder;
 use roaring::{MultiOps, RoaringBitmap};
+use rstar::AABB;
 use serde_json::Value;
+use time::OffsetDateTime;
 
 use super::facet_range_search;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::{Error, UserError};
+use crate::facet::date;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::geojson::point_in_polygon;
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec,
 };
 use crate::index::db_name::FACET_ID_STRING_DOCIDS;
+use crate::trigrams;
 use crate::{
     distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap,
     FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError, Result,
@@ -228,6 +234,92 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Returns the centers of the `_geoRadius`, `_geoBoundingBox` and `_geoPolygon` filters
+    /// that are not negated, the center of a box or a polygon is the center of its bounding box.
+    ///
+    /// It is used to compute the `_geoDistance` of the documents when no geo sort is specified.
+    pub fn geo_centers(&self) -> Vec<[f64; 2]> {
+        let point = |[lat, lng]: &[Token; 2]| -> Option<[f64; 2]> {
+            Some([lat.parse_finite_float().ok()?, lng.parse_finite_float().ok()?])
+        };
+
+        self.condition
+            .geo_shapes()
+            .filter_map(|shape| match shape {
+                FilterCondition::GeoLowerThan { point: center, .. } => point(center),
+                FilterCondition::GeoBoundingBox { top_right_point, bottom_left_point } => {
+                    Some(bounding_box_center(point(bottom_left_point)?, point(top_right_point)?))
+                }
+                FilterCondition::GeoPolygon { rings } => {
+                    let polygon = rings[0].iter().map(point).collect::<Option<Vec<_>>>()?;
+                    let (bottom_left, top_right) =
+                        polygon_bounding_box(&unwrap_antimeridian(&polygon));
+                    Some(bounding_box_center(bottom_left, top_right))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the filter without its top-level conditions that only apply to the given
+    /// attribute, `None` when there is no condition left.
+    ///
+    /// It is used to compute the distribution of a disjunctive facet, which must
+    /// ignore the conditions on the facet itself.
+    pub fn without_attribute(&self, attribute: &str) -> Option<Filter<'a>> {
+        match &self.condition {
+            FilterCondition::And(conditions) => {
+                let mut conditions: Vec<_> = conditions
+                    .iter()
+                    .filter(|condition| !only_applies_to(condition, attribute))
+                    .cloned()
+                    .collect();
+                match conditions.len() {
+                    0 => None,
+                    1 => conditions.pop().map(Filter::from),
+                    _ => Some(Filter::from(FilterCondition::And(conditions))),
+                }
+            }
+            condition if only_applies_to(condition, attribute) => None,
+            _ => Some(self.clone()),
+        }
+    }
+
+    /// Whether all the top-level conditions of the other filter are part of this filter,
+    /// which is then at least as restrictive as the other one.
+    pub fn includes(&self, other: &Filter<'_>) -> bool {
+        let conditions = top_level_conditions(&self.condition);
+        top_level_conditions(&other.condition)
+            .iter()
+            .all(|condition| conditions.iter().any(|c| c == condition))
+    }
+}
+
+fn top_level_conditions<'c, 'a>(condition: &'c FilterCondition<'a>) -> &'c [FilterCondition<'a>] {
+    match condition {
+        FilterCondition::And(conditions) => conditions,
+        condition => std::slice::from_ref(condition),
+    }
+}
+
+/// Whether all the conditions of this filter apply to the given attribute.
+fn only_applies_to(condition: &FilterCondition<'_>, attribute: &str) -> bool {
+    match condition {
+        FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+            fid.value() == attribute
+        }
+        FilterCondition::Not(condition) => only_applies_to(condition, attribute),
+        FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+            !conditions.is_empty()
+                && conditions.iter().all(|condition| only_applies_to(condition, attribute))
+        }
+        FilterCondition::GeoLowerThan { .. }
+        | FilterCondition::GeoBoundingBox { .. }
+        | FilterCondition::GeoPolygon { .. }
+        | FilterCondition::GeoContains { .. }
+        | FilterCondition::GeoIntersects { .. } => false,
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -256,6 +348,7 @@ impl<'a> Filter<'a> {
         self.inner_evaluate(rtxn, index, &fields_ids_map, &filterable_attributes_rules, None)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn evaluate_operator(
         rtxn: &heed::RoTxn<'_>,
         index: &Index,
@@ -264,6 +357,7 @@ impl<'a> Filter<'a> {
         operator: &Condition<'a>,
         features: &FilterableAttributesFeatures,
         rule_index: usize,
+        is_date: bool,
     ) -> Result<RoaringBitmap> {
         let numbers_db = index.facet_id_f64_docids;
         let strings_db = index.facet_id_string_docids;
@@ -308,25 +402,25 @@ impl<'a> Filter<'a> {
             Condition::GreaterThan(val) => {
                 let number = val.parse_finite_float().ok();
                 let number_bounds = number.map(|number| (Excluded(number), Included(f64::MAX)));
-                let str_bounds = (Excluded(val.value()), Unbounded);
+                let str_bounds = string_bounds(Excluded(val), Unbounded, is_date);
                 (number_bounds, str_bounds)
             }
             Condition::GreaterThanOrEqual(val) => {
                 let number = val.parse_finite_float().ok();
                 let number_bounds = number.map(|number| (Included(number), Included(f64::MAX)));
-                let str_bounds = (Included(val.value()), Unbounded);
+                let str_bounds = string_bounds(Included(val), Unbounded, is_date);
                 (number_bounds, str_bounds)
             }
             Condition::LowerThan(val) => {
                 let number = val.parse_finite_float().ok();
                 let number_bounds = number.map(|number| (Included(f64::MIN), Excluded(number)));
-                let str_bounds = (Unbounded, Excluded(val.value()));
+                let str_bounds = string_bounds(Unbounded, Excluded(val), is_date);
                 (number_bounds, str_bounds)
             }
             Condition::LowerThanOrEqual(val) => {
                 let number = val.parse_finite_float().ok();
                 let number_bounds = number.map(|number| (Included(f64::MIN), Included(number)));
-                let str_bounds = (Unbounded, Included(val.value()));
+                let str_bounds = string_bounds(Unbounded, Included(val), is_date);
                 (number_bounds, str_bounds)
             }
             Condition::Between { from, to } => {
@@ -335,7 +429,7 @@ impl<'a> Filter<'a> {
 
                 let number_bounds =
                     from_number.zip(to_number).map(|(from, to)| (Included(from), Included(to)));
-                let str_bounds = (Included(from.value()), Included(to.value()));
+                let str_bounds = string_bounds(Included(from), Included(to), is_date);
                 (number_bounds, str_bounds)
             }
             Condition::Null => {
@@ -357,7 +451,11 @@ impl<'a> Filter<'a> {
                         &FacetGroupKey {
                             field_id,
                             level: 0,
-                            left_bound: &crate::normalize_facet(val.value()),
+                            left_bound: &if is_date {
+                                crate::normalize_date_facet(val.value())
+                            } else {
+                                crate::normalize_facet(val.value())
+                            },
                         },
                     )?
                     .map(|v| v.bitmap)
@@ -375,13 +473,17 @@ impl<'a> Filter<'a> {
             Condition::NotEqual(val) => {
                 let operator = Condition::Equal(val.clone());
                 let docids = Self::evaluate_operator(
-                    rtxn, index, field_id, None, &operator, features, rule_index,
+                    rtxn, index, field_id, None, &operator, features, rule_index, is_date,
                 )?;
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
             Condition::Contains { keyword: _, word } => {
                 let value = crate::normalize_facet(word.value());
+                // the facet strings of the infix attributes are indexed by their trigrams
+                if let Some(docids) = Self::infix_facet_docids(rtxn, index, field_id, &value)? {
+                    return Ok(docids);
+                }
                 let finder = Finder::new(&value);
                 let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
                 let docids = strings_db
@@ -449,6 +551,8 @@ impl<'a> Filter<'a> {
             )?;
         }
 
+        let left_str = left_str.as_ref().map(|s| &**s);
+        let right_str = right_str.as_ref().map(|s| &**s);
         Self::explore_facet_levels(
             rtxn,
             strings_db,
@@ -492,6 +596,40 @@ impl<'a> Filter<'a> {
         Ok(())
     }
 
+    /// Returns the documents ids of the facet strings containing the normalized value, using
+    /// the trigrams of the facet strings of the infix attributes.
+    ///
+    /// Returns `None` if the field is not an infix attribute or the value is shorter than
+    /// a trigram, the facet strings must then be scanned.
+    fn infix_facet_docids(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        value: &str,
+    ) -> Result<Option<RoaringBitmap>> {
+        if !index.infix_fields_ids(rtxn)?.contains(&field_id) {
+            return Ok(None);
+        }
+        let Some(fst) = index.facet_strings_trigrams_fst(rtxn)? else {
+            return Ok(None);
+        };
+        let prefix = field_id.to_be_bytes();
+        let Some(values) = trigrams::values_containing(&fst, &prefix, value, usize::MAX) else {
+            return Ok(None);
+        };
+
+        let mut docids = RoaringBitmap::new();
+        for left_bound in &values {
+            let key = FacetGroupKey { field_id, level: 0, left_bound: left_bound.as_str() };
+            if let Some(FacetGroupValue { bitmap, .. }) =
+                index.facet_id_string_docids.get(rtxn, &key)?
+            {
+                docids |= bitmap;
+            }
+        }
+        Ok(Some(docids))
+    }
+
     fn inner_evaluate(
         &self,
         rtxn: &heed::RoTxn<'_>,
@@ -531,12 +669,13 @@ impl<'a> Filter<'a> {
                 else {
                     return Ok(RoaringBitmap::new());
                 };
+                let is_date = index.is_date_attribute(rtxn, fid.value())?;
 
                 els.iter()
                     .map(|el| Condition::Equal(el.clone()))
                     .map(|op| {
                         Self::evaluate_operator(
-                            rtxn, index, field_id, universe, &op, &features, rule_index,
+                            rtxn, index, field_id, universe, &op, &features, rule_index, is_date,
                         )
                     })
                     .union()
@@ -550,8 +689,11 @@ impl<'a> Filter<'a> {
                 else {
                     return Ok(RoaringBitmap::new());
                 };
+                let is_date = index.is_date_attribute(rtxn, fid.value())?;
 
-                Self::evaluate_operator(rtxn, index, field_id, universe, op, &features, rule_index)
+                Self::evaluate_operator(
+                    rtxn, index, field_id, universe, op, &features, rule_index, is_date,
+                )
             }
             FilterCondition::Or(subfilters) => subfilters
                 .iter()
@@ -778,10 +920,309 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { rings } => {
+                if index.is_geo_filtering_enabled(rtxn)? {
+                    let mut polygon = Vec::with_capacity(rings.len());
+                    for ring in rings {
+                        let mut points = Vec::with_capacity(ring.len());
+                        for [lat, lng] in ring {
+                            let point = [lat.parse_finite_float()?, lng.parse_finite_float()?];
+                            if !(-90.0..=90.0).contains(&point[0]) {
+                                return Err(lat.as_external_error(BadGeoError::Lat(point[0])))?;
+                            }
+                            if !(-180.0..=180.0).contains(&point[1]) {
+                                return Err(lng.as_external_error(BadGeoError::Lng(point[1])))?;
+                            }
+                            points.push(point);
+                        }
+                        polygon.push(points);
+                    }
+
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    // Only the points of the rtree lying in the envelope of the outer ring of
+                    // the polygon are retrieved to do the precise containment check.
+                    let polygon = unwrap_polygon(&polygon);
+                    let result = rtree
+                        .locate_in_envelope_intersecting(&polygon_envelope(&polygon[0]))
+                        .filter(|point| {
+                            universe.is_none_or(|universe| universe.contains(point.data.0))
+                        })
+                        .filter(|point| {
+                            let [lat, lng] = point.data.1;
+                            // the unwrapped polygon can go past the antimeridian
+                            [lng, lng + 360.0, lng - 360.0]
+                                .iter()
+                                .any(|lng| point_in_rings(&[lat, *lng], &polygon))
+                        })
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(result)
+                } else {
+                    Err(rings[0][0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEO_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
+            FilterCondition::GeoContains { point } => {
+                if index.is_geojson_filtering_enabled(rtxn)? {
+                    let base_point: [f64; 2] =
+                        [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
+                    if !(-90.0..=90.0).contains(&base_point[0]) {
+                        return Err(point[0].as_external_error(BadGeoError::Lat(base_point[0])))?;
+                    }
+                    if !(-180.0..=180.0).contains(&base_point[1]) {
+                        return Err(point[1].as_external_error(BadGeoError::Lng(base_point[1])))?;
+                    }
+
+                    let rtree = match index.geojson_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    let mut result = RoaringBitmap::new();
+                    for shape in
+                        rtree.locate_in_envelope_intersecting(&AABB::from_point(base_point)).filter(
+                            |shape| universe.is_none_or(|universe| universe.contains(shape.data)),
+                        )
+                    {
+                        let docid = shape.data;
+                        if let Some(geometry) = index.geojson_geometries.get(rtxn, &docid)? {
+                            if geometry.contains(&base_point) {
+                                result.insert(docid);
+                            }
+                        }
+                    }
+
+                    Ok(result)
+                } else {
+                    Err(point[0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEOJSON_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
+            FilterCondition::GeoIntersects { top_right_point, bottom_left_point } => {
+                if index.is_geojson_filtering_enabled(rtxn)? {
+                    let top_right: [f64; 2] = [
+                        top_right_point[0].parse_finite_float()?,
+                        top_right_point[1].parse_finite_float()?,
+                    ];
+                    let bottom_left: [f64; 2] = [
+                        bottom_left_point[0].parse_finite_float()?,
+                        bottom_left_point[1].parse_finite_float()?,
+                    ];
+                    if !(-90.0..=90.0).contains(&top_right[0]) {
+                        return Err(
+                            top_right_point[0].as_external_error(BadGeoError::Lat(top_right[0]))
+                        )?;
+                    }
+                    if !(-180.0..=180.0).contains(&top_right[1]) {
+                        return Err(
+                            top_right_point[1].as_external_error(BadGeoError::Lng(top_right[1]))
+                        )?;
+                    }
+                    if !(-90.0..=90.0).contains(&bottom_left[0]) {
+                        return Err(bottom_left_point[0]
+                            .as_external_error(BadGeoError::Lat(bottom_left[0])))?;
+                    }
+                    if !(-180.0..=180.0).contains(&bottom_left[1]) {
+                        return Err(bottom_left_point[1]
+                            .as_external_error(BadGeoError::Lng(bottom_left[1])))?;
+                    }
+                    if top_right[0] < bottom_left[0] {
+                        return Err(bottom_left_point[1].as_external_error(
+                            BadGeoError::BoundingBoxTopIsBelowBottom(top_right[0], bottom_left[0]),
+                        ))?;
+                    }
+
+                    let rtree = match index.geojson_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    // When the box is wrapping around the earth (going from 180 to -180)
+                    // we split it in two boxes, one on each side of the antimeridian.
+                    let boxes = if top_right[1] < bottom_left[1] {
+                        vec![
+                            (bottom_left, [top_right[0], 180.0]),
+                            ([bottom_left[0], -180.0], top_right),
+                        ]
+                    } else {
+                        vec![(bottom_left, top_right)]
+                    };
+
+                    let mut result = RoaringBitmap::new();
+                    for (bottom_left, top_right) in boxes {
+                        let envelope = AABB::from_corners(bottom_left, top_right);
+                        for shape in
+                            rtree.locate_in_envelope_intersecting(&envelope).filter(|shape| {
+                                universe.is_none_or(|universe| universe.contains(shape.data))
+                            })
+                        {
+                            let docid = shape.data;
+                            if result.contains(docid) {
+                                continue;
+                            }
+                            if let Some(geometry) = index.geojson_geometries.get(rtxn, &docid)? {
+                                if geometry.intersects_box(&bottom_left, &top_right) {
+                                    result.insert(docid);
+                                }
+                            }
+                        }
+                    }
+
+                    Ok(result)
+                } else {
+                    Err(top_right_point[0].as_external_error(
+                        FilterError::AttributeNotFilterable {
+                            attribute: RESERVED_GEOJSON_FIELD_NAME,
+                            filterable_patterns: filtered_matching_patterns(
+                                filterable_attribute_rules,
+                                &|features| features.is_filterable(),
+                            ),
+                        },
+                    ))?
+                }
+            }
         }
     }
 }
 
+/// Shifts the longitudes of the polygon so that none of its edges is longer than 180°,
+/// an edge going from `170` to `-170` is understood as crossing the antimeridian
+/// and becomes an edge going from `170` to `190`.
+fn unwrap_antimeridian(polygon: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut unwrapped: Vec<[f64; 2]> = Vec::with_capacity(polygon.len());
+    for &[lat, lng] in polygon {
+        let lng = match unwrapped.last() {
+            Some(&[_, previous]) => {
+                let mut lng = lng;
+                while lng - previous > 180.0 {
+                    lng -= 360.0;
+                }
+                while previous - lng > 180.0 {
+                    lng += 360.0;
+                }
+                lng
+            }
+            None => lng,
+        };
+        unwrapped.push([lat, lng]);
+    }
+    unwrapped
+}
+
+/// Unwraps the rings of the polygon with [`unwrap_antimeridian`], its holes are shifted
+/// by whole turns to start in the longitudes covered by its outer ring.
+fn unwrap_polygon(rings: &[Vec<[f64; 2]>]) -> Vec<Vec<[f64; 2]>> {
+    let mut rings = rings.iter().map(|ring| unwrap_antimeridian(ring));
+    let Some(outer) = rings.next() else { return Vec::new() };
+    let min_lng = outer.iter().fold(f64::MAX, |min, [_, lng]| min.min(*lng));
+    let holes = rings.map(|mut hole| {
+        let shift = (hole[0][1] - min_lng).div_euclid(360.0) * 360.0;
+        hole.iter_mut().for_each(|[_, lng]| *lng -= shift);
+        hole
+    });
+    std::iter::once(outer).chain(holes).collect()
+}
+
+/// Whether the point is in the polygon following the even-odd rule over all its rings,
+/// a point in one of its holes is outside of the polygon.
+fn point_in_rings(point: &[f64; 2], rings: &[Vec<[f64; 2]>]) -> bool {
+    rings.iter().filter(|ring| point_in_polygon(point, ring)).count() % 2 == 1
+}
+
+/// Returns the bottom left and top right corners of the box containing the polygon,
+/// the longitude of the top right corner is lower than the bottom left one when the
+/// box is crossing the antimeridian, like in the `_geoBoundingBox` filter.
+fn polygon_bounding_box(polygon: &[[f64; 2]]) -> ([f64; 2], [f64; 2]) {
+    let (min, max) = polygon.iter().fold(
+        ([f64::MAX, f64::MAX], [f64::MIN, f64::MIN]),
+        |(min, max), [lat, lng]| {
+            ([min[0].min(*lat), min[1].min(*lng)], [max[0].max(*lat), max[1].max(*lng)])
+        },
+    );
+
+    if max[1] - min[1] >= 360.0 {
+        return ([min[0], -180.0], [max[0], 180.0]);
+    }
+    let wrap = |lng: f64| (lng + 180.0).rem_euclid(360.0) - 180.0;
+    let (min_lng, max_lng) =
+        if (-180.0..=180.0).contains(&min[1]) && (-180.0..=180.0).contains(&max[1]) {
+            (min[1], max[1])
+        } else {
+            (wrap(min[1]), wrap(max[1]))
+        };
+    ([min[0], min_lng], [max[0], max_lng])
+}
+
+/// Returns the envelope, in the cartesian coordinates of the geo rtree, of the part of the
+/// sphere between the minimum and maximum latitudes and longitudes of the unwrapped polygon.
+fn polygon_envelope(polygon: &[[f64; 2]]) -> AABB<[f64; 3]> {
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    let (min, max) = polygon.iter().fold(
+        ([f64::MAX, f64::MAX], [f64::MIN, f64::MIN]),
+        |(min, max), [lat, lng]| {
+            ([min[0].min(*lat), min[1].min(*lng)], [max[0].max(*lat), max[1].max(*lng)])
+        },
+    );
+    let [min_lat, min_lng] = min.map(f64::to_radians);
+    let [max_lat, max_lng] = max.map(f64::to_radians);
+
+    // The range of a sine or cosine over `[min, max]`, `f` reaching 1 at `top` and -1 at
+    // `top + π` modulo 2π. The longitudes can cover more than a turn or go past ±π.
+    let range = |min: f64, max: f64, f: fn(f64) -> f64, top: f64| {
+        let reaches = |angle: f64| min + (angle - min).rem_euclid(TAU) <= max;
+        let (a, b) = (f(min), f(max));
+        let low = if reaches(top + PI) { -1.0 } else { a.min(b) };
+        let high = if reaches(top) { 1.0 } else { a.max(b) };
+        (low, high)
+    };
+    let (cos_lat_low, cos_lat_high) = range(min_lat, max_lat, f64::cos, 0.0);
+    let (cos_lng_low, cos_lng_high) = range(min_lng, max_lng, f64::cos, 0.0);
+    let (sin_lng_low, sin_lng_high) = range(min_lng, max_lng, f64::sin, FRAC_PI_2);
+
+    // x and y are the product of the positive cosine of the latitude with the
+    // cosine and sine of the longitude, see `lat_lng_to_xyz`.
+    let product = |low: f64, high: f64| {
+        ((cos_lat_low * low).min(cos_lat_high * low), (cos_lat_low * high).max(cos_lat_high * high))
+    };
+    let (x_low, x_high) = product(cos_lng_low, cos_lng_high);
+    let (y_low, y_high) = product(sin_lng_low, sin_lng_high);
+
+    // the points on the edges must not be lost to rounding errors
+    let margin = 1e-9;
+    AABB::from_corners(
+        [x_low - margin, y_low - margin, min_lat.sin() - margin],
+        [x_high + margin, y_high + margin, max_lat.sin() + margin],
+    )
+}
+
+/// Returns the center of the box, which is crossing the antimeridian when the longitude
+/// of its top right corner is lower than the bottom left one.
+fn bounding_box_center(bottom_left: [f64; 2], top_right: [f64; 2]) -> [f64; 2] {
+    let [bottom, left] = bottom_left;
+    let [top, mut right] = top_right;
+    if right < left {
+        right += 360.0;
+    }
+    let lng = (left + right) / 2.0;
+    [(bottom + top) / 2.0, (lng + 180.0).rem_euclid(360.0) - 180.0]
+}
+
 fn generate_filter_error(
     rtxn: &heed::RoTxn<'_>,
     index: &Index,
@@ -804,6 +1245,41 @@ fn generate_filter_error(
     }
 }
 
+/// Returns the bounds used to compare the filter values with the string facet values.
+///
+/// When the field is a date attribute and the values are dates, including the ones relative
+/// to the current date like `NOW-7d`, they are normalized like the date facet values so that
+/// they are compared chronologically and the unbounded side of the range only covers the dates.
+fn string_bounds<'t>(
+    left: Bound<&'t Token<'_>>,
+    right: Bound<&'t Token<'_>>,
+    is_date: bool,
+) -> (Bound<Cow<'t, str>>, Bound<Cow<'t, str>>) {
+    let as_text = || {
+        (
+            left.map(|token| Cow::Borrowed(token.value())),
+            right.map(|token| Cow::Borrowed(token.value())),
+        )
+    };
+    if !is_date {
+        return as_text();
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let as_date = |bound: Bound<&Token>, unbounded: &'static str| match bound {
+        Included(token) => date::parse_filter_date(token.value(), now)
+            .map(|date| Included(Cow::Owned(date::sortable_date(date)))),
+        Excluded(token) => date::parse_filter_date(token.value(), now)
+            .map(|date| Excluded(Cow::Owned(date::sortable_date(date)))),
+        Unbounded => Some(Included(Cow::Borrowed(unbounded))),
+    };
+
+    match (as_date(left, date::MIN_SORTABLE_DATE), as_date(right, date::MAX_SORTABLE_DATE)) {
+        (Some(left), Some(right)) => (left, right),
+        _ => as_text(),
+    }
+}
+
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     fn from(fc: FilterCondition<'a>) -> Self {
         Self { condition: fc }
@@ -812,6 +1288,7 @@ impl<'a> From<FilterCondition<'a>> for Filter<'a> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
     use std::fmt::Write;
     use std::iter::FromIterator;
 
@@ -820,9 +1297,9 @@ mod tests {
     use meili_snap::snapshot;
     use roaring::RoaringBitmap;
 
-    use crate::constants::RESERVED_GEO_FIELD_NAME;
+    use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
     use crate::index::tests::TempIndex;
-    use crate::{Filter, FilterableAttributesRule};
+    use crate::{Filter, FilterCondition, FilterableAttributesRule};
 
     #[test]
     fn empty_db() {
@@ -928,6 +1405,28 @@ mod tests {
         18:20 _geoBoundingBox([42, 150], [30, 10])
         "###);
 
+        let filter =
+            Filter::from_str("_geoPolygon([42, 150], [30, 10], [12, 13])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        snapshot!(error.to_string(), @r###"
+        Attribute `_geo` is not filterable. This index does not have configured filterable attributes.
+        14:16 _geoPolygon([42, 150], [30, 10], [12, 13])
+        "###);
+
+        let filter = Filter::from_str("_geoContains(42, 150)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        snapshot!(error.to_string(), @r###"
+        Attribute `_geojson` is not filterable. This index does not have configured filterable attributes.
+        14:16 _geoContains(42, 150)
+        "###);
+
+        let filter = Filter::from_str("_geoIntersects([42, 150], [30, 10])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        snapshot!(error.to_string(), @r###"
+        Attribute `_geojson` is not filterable. This index does not have configured filterable attributes.
+        17:19 _geoIntersects([42, 150], [30, 10])
+        "###);
+
         let filter = Filter::from_str("dog = \"bernese mountain\"").unwrap().unwrap();
         let error = filter.evaluate(&rtxn, &index).unwrap_err();
         snapshot!(error.to_string(), @r###"
@@ -1088,6 +1587,211 @@ mod tests {
         assert_eq!(documents_ids, vec![0]);
     }
 
+    #[test]
+    fn geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEO_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 1, "lng": 1 } },
+              { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 3, "lng": 3 } },
+              { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": 8, "lng": 2 } },
+              { "id": 3, RESERVED_GEO_FIELD_NAME: { "lat": 20, "lng": 20 } },
+              { "id": 4, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 179 } },
+              { "id": 5, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": -179 } },
+              { "id": 6, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": -160 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("_geoPolygon([0, 0], [10, 0], [0, 10])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 1]);
+
+        // the rings after the first one are the holes of the polygon
+        let filter = Filter::from_str(
+            "_geoPolygon([[0, 0], [10, 0], [10, 10], [0, 10]], [[2, 2], [4, 2], [4, 4], [2, 4]])",
+        )
+        .unwrap()
+        .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 2]);
+
+        // a hole can also be punched in the polygon by excluding another one
+        let filter = Filter::from_str(
+            "_geoPolygon([0, 0], [10, 0], [10, 10], [0, 10]) AND NOT _geoPolygon([2, 2], [4, 2], [4, 4], [2, 4])",
+        )
+        .unwrap()
+        .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 2]);
+
+        // a polygon crossing the antimeridian
+        let filter =
+            Filter::from_str("_geoPolygon([-10, 170], [-10, -170], [10, -170], [10, 170])")
+                .unwrap()
+                .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![4, 5]);
+
+        // with a hole on the other side of the antimeridian
+        let filter = Filter::from_str(
+            "_geoPolygon([[-10, 170], [-10, -170], [10, -170], [10, 170]], [[-1, -179.5], [-1, -178], [1, -178], [1, -179.5]])",
+        )
+        .unwrap()
+        .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![4]);
+
+        let filter = Filter::from_str("_geoPolygon([0, 0], [10, 0], [0, 200])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Bad longitude `200`. Longitude must be contained between -180 and 180 degrees."
+            ),
+            "{}",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn geo_polygon_envelope() {
+        use rstar::Envelope;
+
+        let polygons: [&[[f64; 2]]; 4] = [
+            &[[0., 0.], [10., 0.], [0., 10.]],
+            // crossing the antimeridian once unwrapped
+            &[[-10., 170.], [-10., 190.], [10., 190.], [10., 170.]],
+            // going through the north pole and the greenwich meridian
+            &[[60., -100.], [90., -100.], [90., 100.], [60., 100.]],
+            &[[-80., -45.], [-20., -45.], [-20., 135.], [-80., 135.]],
+        ];
+
+        for polygon in polygons {
+            let envelope = super::polygon_envelope(polygon);
+            let (min, max) = polygon.iter().fold(
+                ([f64::MAX, f64::MAX], [f64::MIN, f64::MIN]),
+                |(min, max), [lat, lng]| {
+                    ([min[0].min(*lat), min[1].min(*lng)], [max[0].max(*lat), max[1].max(*lng)])
+                },
+            );
+            for i in 0..=20 {
+                for j in 0..=20 {
+                    let lat = min[0] + (max[0] - min[0]) * i as f64 / 20.;
+                    let lng = min[1] + (max[1] - min[1]) * j as f64 / 20.;
+                    let point = crate::lat_lng_to_xyz(&[lat, lng]);
+                    assert!(
+                        envelope.contains_point(&point),
+                        "{lat}, {lng} is outside of the envelope of {polygon:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn geojson_shapes() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+              // a square with a hole in its center
+              { "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [
+                  [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                  [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]],
+              ] } },
+              { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[20, 20], [30, 30]] } },
+              { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [-170, 5] } },
+              { "id": 3 },
+            ]))
+            .unwrap();
+
+        // the shapes are indexed when the field becomes filterable
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEOJSON_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.geojson_geometries.len(&rtxn).unwrap(), 3);
+
+        let filter = Filter::from_str("_geoContains(1, 1)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0]);
+
+        let filter = Filter::from_str("_geoContains(5, 5)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(documents_ids.is_empty());
+
+        let filter = Filter::from_str("_geoContains(25, 25)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![1]);
+
+        let filter = Filter::from_str("_geoIntersects([12, 12], [-1, -1])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0]);
+
+        // the box is inside the hole of the square
+        let filter = Filter::from_str("_geoIntersects([5.5, 5.5], [4.5, 4.5])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(documents_ids.is_empty());
+
+        // the box crosses the line without containing any of its ends
+        let filter = Filter::from_str("_geoIntersects([26, 29], [24, 21])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![1]);
+
+        // the box is wrapping around the earth
+        let filter = Filter::from_str("_geoIntersects([10, -160], [0, 170])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![2]);
+
+        let filter = Filter::from_str("_geoIntersects([0, 10], [10, 0])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("The top latitude `0` is below the bottom latitude `10`."),
+            "{}",
+            error.to_string()
+        );
+        drop(rtxn);
+
+        // the shapes are updated with the documents
+        index
+            .add_documents(documents!([
+              { "id": 0, RESERVED_GEOJSON_FIELD_NAME: null },
+              { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Feature", "properties": {}, "geometry": {
+                  "type": "Polygon", "coordinates": [[[40, 40], [50, 40], [50, 50], [40, 50], [40, 40]]]
+              } } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.geojson_geometries.get(&rtxn, &0).unwrap().is_none());
+
+        let filter = Filter::from_str("_geoContains(1, 1)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(documents_ids.is_empty());
+
+        let filter =
+            Filter::from_str("_geoContains(45, 45) OR _geoContains(25, 25)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![1, 4]);
+    }
+
     #[test]
     fn geo_radius_error() {
         let index = TempIndex::new();
@@ -1248,6 +1952,25 @@ mod tests {
         assert!(filter.is_some());
     }
 
+    #[test]
+    fn filter_without_attribute() {
+        let without = |filter: &str, attribute: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            match filter.without_attribute(attribute) {
+                Some(filter) => FilterCondition::from(filter).to_string(),
+                None => S("None"),
+            }
+        };
+
+        snapshot!(without("color = red AND (brand = apple OR brand = samsung)", "brand"), @"{color} = {red}");
+        snapshot!(without("color = red AND brand = apple AND price > 10", "brand"), @"AND[{color} = {red}, {price} > {10}, ]");
+        snapshot!(without("brand = apple AND _geoRadius(45, 7, 100)", "brand"), @"_geoRadius({45}, {7}, {100})");
+        snapshot!(without("brand IN [apple, samsung]", "brand"), @"None");
+        // the conditions also applying to other attributes are kept
+        snapshot!(without("color = red OR brand = apple", "brand"), @"OR[{color} = {red}, {brand} = {apple}, ]");
+        snapshot!(without("color = red AND brand = apple", "price"), @"AND[{color} = {red}, {brand} = {apple}, ]");
+    }
+
     #[test]
     fn empty_filter() {
         let option = Filter::from_str("     ").unwrap();
@@ -1392,4 +2115,120 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_date() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                    "published_at".to_string(),
+                )]);
+                settings.set_date_attributes(BTreeSet::from([S("published_at")]));
+            })
+            .unwrap();
+
+        let now = time::OffsetDateTime::now_utc();
+        let format = time::format_description::well_known::Rfc3339;
+        let two_days_ago = (now - time::Duration::days(2)).format(&format).unwrap();
+        let ten_days_ago = (now - time::Duration::days(10)).format(&format).unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "published_at": "2024-12-31" },
+                { "id": 1, "published_at": "2025-01-01T00:00:00Z" },
+                { "id": 2, "published_at": "2025-01-01T10:00:00+02:00" },
+                { "id": 3, "published_at": "2025-01-01T09:00:00Z" },
+                { "id": 4, "published_at": "2025-02-01T00:00:00.250Z" },
+                { "id": 5, "published_at": two_days_ago },
+                { "id": 6, "published_at": ten_days_ago },
+                { "id": 7, "published_at": "9999-01-01" },
+                { "id": 8, "published_at": "not a date" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        // the dates are compared chronologically, whatever their offset
+        snapshot!(format!("{:?}", filter("published_at = 2025-01-01T08:00:00Z")), @"[2]");
+        snapshot!(format!("{:?}", filter("published_at = 2025-01-01")), @"[1]");
+        snapshot!(format!("{:?}", filter("published_at > 2025-01-01")), @"[2, 3, 4, 5, 6, 7]");
+        snapshot!(format!("{:?}", filter("published_at <= 2025-01-01")), @"[0, 1]");
+        snapshot!(format!("{:?}", filter("published_at 2024-12-31 TO 2025-01-01T09:00:00+01:00")), @"[0, 1, 2]");
+        snapshot!(format!("{:?}", filter("published_at < 2025-01-01T09:00:00.001Z")), @"[0, 1, 2, 3]");
+
+        // the dates can be relative to the current date
+        snapshot!(format!("{:?}", filter("published_at >= NOW-7d")), @"[5, 7]");
+        snapshot!(format!("{:?}", filter("published_at NOW-14d TO NOW")), @"[5, 6]");
+        snapshot!(format!("{:?}", filter("published_at 2025-02-01 TO NOW-7d")), @"[4, 6]");
+
+        // the strings that are not dates are still compared lexicographically
+        snapshot!(format!("{:?}", filter("published_at >= not")), @"[8]");
+        drop(rtxn);
+
+        // the dates of the attributes that are not date attributes are compared as strings
+        index.update_settings(|settings| settings.reset_date_attributes()).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        snapshot!(format!("{:?}", filter("published_at = 2025-01-01T08:00:00Z")), @"[]");
+        snapshot!(format!("{:?}", filter("published_at = 2025-01-01T00:00:00Z")), @"[1]");
+    }
+
+    #[test]
+    fn filter_contains_infix() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                    "brand".to_string(),
+                )]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "Walmart" },
+                { "id": 1, "brand": "Kmart" },
+                { "id": 2, "brand": "Martini" },
+                { "id": 3, "brand": "Target" },
+                { "id": 4, "brand": "Smart & Final" },
+            ]))
+            .unwrap();
+
+        let filter = |filter: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        snapshot!(format!("{:?}", filter("brand CONTAINS mart")), @"[0, 1, 2, 4]");
+
+        index
+            .update_settings(|settings| settings.set_infix_attributes(["brand".to_string()].into()))
+            .unwrap();
+
+        // the indexed facet strings give the same results as the scan
+        snapshot!(format!("{:?}", filter("brand CONTAINS mart")), @"[0, 1, 2, 4]");
+        snapshot!(format!("{:?}", filter("brand CONTAINS \"t & f\"")), @"[4]");
+        snapshot!(format!("{:?}", filter("brand CONTAINS rat")), @"[]");
+        // the values shorter than a trigram are still scanned
+        snapshot!(format!("{:?}", filter("brand CONTAINS ar")), @"[0, 1, 2, 3, 4]");
+
+        // the trigrams follow the documents updates
+        index.add_documents(documents!([{ "id": 5, "brand": "Martha" }])).unwrap();
+        snapshot!(format!("{:?}", filter("brand CONTAINS mart")), @"[0, 1, 2, 4, 5]");
+    }
 }
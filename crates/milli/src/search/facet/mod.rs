@@ -4,13 +4,18 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_aggregations::{FacetAggregation, FacetAggregations};
+pub use self::facet_distribution::{
+    FacetDistribution, FacetHierarchy, FacetRanges, FacetValuesOptions, OrderBy,
+    DEFAULT_VALUES_PER_FACET,
+};
 pub use self::filter::{BadGeoError, Filter};
 pub use self::search::{FacetValueHit, SearchForFacetValues};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
 use crate::heed_codec::BytesRefCodec;
 use crate::{Index, Result};
 
+mod facet_aggregations;
 mod facet_distribution;
 mod facet_distribution_iter;
 mod facet_range_search;
@@ -1,11 +1,13 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 use itertools::Itertools;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
 use crate::search::SemanticSearch;
-use crate::{MatchingWords, Result, Search, SearchResult};
+use crate::search_rules::MatchedRules;
+use crate::{MatchingWords, QuerySuggestion, Result, Search, SearchResult};
 
 struct ScoreWithRatioResult {
     matching_words: MatchingWords,
@@ -13,6 +15,9 @@ struct ScoreWithRatioResult {
     document_scores: Vec<(u32, ScoreWithRatio)>,
     degraded: bool,
     used_negative_operator: bool,
+    disjunctive_candidates: BTreeMap<String, RoaringBitmap>,
+    applied_rules: Vec<String>,
+    query_suggestions: Vec<QuerySuggestion>,
 }
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
@@ -82,6 +87,9 @@ impl ScoreWithRatioResult {
             document_scores,
             degraded: results.degraded,
             used_negative_operator: results.used_negative_operator,
+            disjunctive_candidates: results.disjunctive_candidates,
+            applied_rules: results.applied_rules,
+            query_suggestions: results.query_suggestions,
         }
     }
 
@@ -136,6 +144,11 @@ impl ScoreWithRatioResult {
             document_scores.push(main_score);
         }
 
+        let mut disjunctive_candidates = keyword_results.disjunctive_candidates;
+        for (facet, candidates) in vector_results.disjunctive_candidates {
+            *disjunctive_candidates.entry(facet).or_default() |= candidates;
+        }
+
         (
             SearchResult {
                 matching_words: keyword_results.matching_words,
@@ -145,6 +158,11 @@ impl ScoreWithRatioResult {
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
+                disjunctive_candidates,
+                // both searches apply the same rules
+                applied_rules: keyword_results.applied_rules,
+                // only the keyword search has a query to correct
+                query_suggestions: keyword_results.query_suggestions,
             },
             semantic_hit_count,
         )
@@ -162,10 +180,13 @@ impl Search<'_> {
             offset: 0,
             limit: self.limit + self.offset,
             sort_criteria: self.sort_criteria.clone(),
+            boosts: self.boosts.clone(),
             distinct: self.distinct.clone(),
             searchable_attributes: self.searchable_attributes,
             geo_param: self.geo_param,
             terms_matching_strategy: self.terms_matching_strategy,
+            query_syntax: self.query_syntax,
+            suggest_query: self.suggest_query,
             scoring_strategy: ScoringStrategy::Detailed,
             words_limit: self.words_limit,
             exhaustive_number_hits: self.exhaustive_number_hits,
@@ -175,23 +196,28 @@ impl Search<'_> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            disjunctive_facets: self.disjunctive_facets.clone(),
         };
 
+        // the pinned documents are inserted once the results of both searches are merged
+        let rules = self.index.search_rules(self.rtxn)?;
+        let rules = self.matched_rules(&rules)?;
+
         let semantic = search.semantic.take();
-        let keyword_results = search.execute()?;
+        let keyword_results = search.execute_with_rules(&rules, search.offset, search.limit)?;
 
         // completely skip semantic search if the results of the keyword search are good enough
         if self.results_good_enough(&keyword_results, semantic_ratio) {
-            return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
+            return Ok(return_keyword_results(&rules, self.limit, self.offset, keyword_results));
         }
 
         // no vector search against placeholder search
         let Some(query) = search.query.take() else {
-            return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
+            return Ok(return_keyword_results(&rules, self.limit, self.offset, keyword_results));
         };
         // no embedder, no semantic search
         let Some(SemanticSearch { vector, embedder_name, embedder, quantized }) = semantic else {
-            return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
+            return Ok(return_keyword_results(&rules, self.limit, self.offset, keyword_results));
         };
 
         let vector_query = match vector {
@@ -208,6 +234,7 @@ impl Search<'_> {
                     Err(error) => {
                         tracing::error!(error=%error, "Embedding failed");
                         return Ok(return_keyword_results(
+                            &rules,
                             self.limit,
                             self.offset,
                             keyword_results,
@@ -221,13 +248,19 @@ impl Search<'_> {
             Some(SemanticSearch { vector: Some(vector_query), embedder_name, embedder, quantized });
 
         // TODO: would be better to have two distinct functions at this point
-        let vector_results = search.execute()?;
+        let vector_results = search.execute_with_rules(&rules, search.offset, search.limit)?;
 
         let keyword_results = ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
         let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
 
-        let (merge_results, semantic_hit_count) =
-            ScoreWithRatioResult::merge(vector_results, keyword_results, self.offset, self.limit);
+        let (merge_results, semantic_hit_count) = if rules.pinned.is_empty() {
+            ScoreWithRatioResult::merge(vector_results, keyword_results, self.offset, self.limit)
+        } else {
+            // the semantic hits are counted from the first result
+            let (merge_results, semantic_hit_count) =
+                ScoreWithRatioResult::merge(vector_results, keyword_results, 0, search.limit);
+            (rules.paginate(merge_results, self.offset, self.limit), semantic_hit_count)
+        };
         assert!(merge_results.documents_ids.len() <= self.limit);
         Ok((merge_results, Some(semantic_hit_count)))
     }
@@ -254,17 +287,26 @@ impl Search<'_> {
 }
 
 fn return_keyword_results(
+    rules: &MatchedRules<'_>,
     limit: usize,
     offset: usize,
-    SearchResult {
+    results: SearchResult,
+) -> (SearchResult, Option<u32>) {
+    if !rules.pinned.is_empty() {
+        return (rules.paginate(results, offset, limit), Some(0));
+    }
+
+    let SearchResult {
         matching_words,
         candidates,
         mut documents_ids,
         mut document_scores,
         degraded,
         used_negative_operator,
-    }: SearchResult,
-) -> (SearchResult, Option<u32>) {
+        disjunctive_candidates,
+        applied_rules,
+        query_suggestions,
+    } = results;
     let (documents_ids, document_scores) = if offset >= documents_ids.len() ||
     // technically redudant because documents_ids.len() == document_scores.len(),
     // defensive programming
@@ -289,6 +331,9 @@ fn return_keyword_results(
             document_scores,
             degraded,
             used_negative_operator,
+            disjunctive_candidates,
+            applied_rules,
+            query_suggestions,
         },
         Some(0),
     )
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
@@ -6,15 +7,23 @@ use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    FacetAggregation, FacetAggregations, FacetDistribution, FacetHierarchy, FacetRanges,
+    FacetValuesOptions, Filter, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
+use self::new::{
+    compute_query_suggestions, distinct_single_docid, execute_vector_search, PartialSearchResult,
+    QuerySuggestion, VectorStoreStats,
+};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::index::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search_rules::MatchedRules;
 use crate::vector::Embedder;
 use crate::{
     execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    Result, SearchContext, SearchRule, TimeBudget, UserError,
 };
 
 // Building these factories is not free.
@@ -27,6 +36,7 @@ mod fst_utils;
 pub mod hybrid;
 pub mod new;
 pub mod similar;
+pub mod suggest;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
@@ -43,10 +53,13 @@ pub struct Search<'a> {
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    boosts: Vec<new::Boost<'a>>,
     distinct: Option<String>,
     searchable_attributes: Option<&'a [String]>,
     geo_param: new::GeoSortParameter,
     terms_matching_strategy: TermsMatchingStrategy,
+    query_syntax: QuerySyntax,
+    suggest_query: bool,
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
@@ -56,6 +69,7 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    disjunctive_facets: Vec<String>,
 }
 
 impl<'a> Search<'a> {
@@ -66,10 +80,13 @@ impl<'a> Search<'a> {
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            boosts: Vec::new(),
             distinct: None,
             searchable_attributes: None,
             geo_param: new::GeoSortParameter::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
+            query_syntax: QuerySyntax::default(),
+            suggest_query: false,
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
             words_limit: 10,
@@ -79,6 +96,7 @@ impl<'a> Search<'a> {
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            disjunctive_facets: Vec::new(),
         }
     }
 
@@ -113,6 +131,13 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Ranks the documents matching the filters of the boosts before, or after when their
+    /// weight is negative, the other documents.
+    pub fn boosts(&mut self, boosts: Vec<new::Boost<'a>>) -> &mut Search<'a> {
+        self.boosts = boosts;
+        self
+    }
+
     pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
         self.distinct = Some(distinct);
         self
@@ -128,6 +153,18 @@ impl<'a> Search<'a> {
         self
     }
 
+    pub fn query_syntax(&mut self, value: QuerySyntax) -> &mut Search<'a> {
+        self.query_syntax = value;
+        self
+    }
+
+    /// Computes corrections of the query when its words are barely found in the documents,
+    /// returned in [`SearchResult::query_suggestions`].
+    pub fn suggest_query(&mut self, value: bool) -> &mut Search<'a> {
+        self.suggest_query = value;
+        self
+    }
+
     pub fn scoring_strategy(&mut self, value: ScoringStrategy) -> &mut Search<'a> {
         self.scoring_strategy = value;
         self
@@ -143,6 +180,11 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// The centers of the geo shapes of the filter, see [`Filter::geo_centers`].
+    pub fn geo_filter_centers(&self) -> Vec<[f64; 2]> {
+        self.filter.as_ref().map(Filter::geo_centers).unwrap_or_default()
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_param.strategy = strategy;
@@ -177,17 +219,69 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// The facets whose distribution must ignore the filter conditions applying to them,
+    /// the candidates to compute their distribution on are returned in
+    /// [`SearchResult::disjunctive_candidates`].
+    pub fn disjunctive_facets(&mut self, facets: Vec<String>) -> &mut Search<'a> {
+        self.disjunctive_facets = facets;
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
-            filtered_universe(ctx.index, ctx.txn, &self.filter)
+            let rules = self.index.search_rules(self.rtxn)?;
+            let rules = self.matched_rules(&rules)?;
+            Ok(filtered_universe(ctx.index, ctx.txn, &self.filter)? - rules.hidden)
         } else {
             Ok(self.execute()?.candidates)
         }
     }
 
+    /// Executes the search.
+    ///
+    /// When search rules pin documents, the pinned documents move the other results,
+    /// which are then retrieved from the start: the cost of the search grows with its offset,
+    /// which is bounded by the max total hits of the index.
     pub fn execute(&self) -> Result<SearchResult> {
+        let rules = self.index.search_rules(self.rtxn)?;
+        let rules = self.matched_rules(&rules)?;
+        if rules.pinned.is_empty() {
+            return self.execute_with_rules(&rules, self.offset, self.limit);
+        }
+
+        let max_total_hits = self
+            .index
+            .pagination_max_total_hits(self.rtxn)?
+            .map(|hits| hits as usize)
+            .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+        let length = self.offset.saturating_add(self.limit).min(max_total_hits);
+        let limit = length.saturating_sub(self.offset);
+        let result = self.execute_with_rules(&rules, 0, length)?;
+        Ok(rules.paginate(result, self.offset, limit))
+    }
+
+    /// The rules whose condition is matched by the query and the filter of this search.
+    fn matched_rules<'r>(
+        &self,
+        rules: &'r BTreeMap<String, SearchRule>,
+    ) -> Result<MatchedRules<'r>> {
+        MatchedRules::new(self.index, self.rtxn, rules, self.query.as_deref(), self.filter.as_ref())
+    }
+
+    /// Executes the search without the hidden and pinned documents of the rules,
+    /// the pinned documents are only added to the candidates.
+    fn execute_with_rules(
+        &self,
+        rules: &MatchedRules<'_>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchResult> {
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+        ctx.query_syntax = self.query_syntax;
+        if self.suggest_query {
+            ctx.query_words = Some(Vec::new());
+        }
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.attributes_to_search_on(searchable_attributes)?;
@@ -221,14 +315,62 @@ impl<'a> Search<'a> {
             }
         }
 
-        let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        let mut universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        universe -= &rules.hidden;
+        let mut pinned = &universe & rules.pinned_docids();
+        universe -= &pinned;
+
+        // the pinned documents are always returned, so they exclude the other documents of their
+        // distinct group, and a pinned document is dropped if a previous one is of the same group
+        if !pinned.is_empty() {
+            let distinct_field = match self.distinct.as_deref() {
+                Some(distinct) => Some(distinct),
+                None => ctx.index.distinct_field(ctx.txn)?,
+            };
+            let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+            if let Some(distinct_fid) = distinct_field.and_then(|f| fields_ids_map.id(f)) {
+                let mut excluded = RoaringBitmap::new();
+                for &(_, docid) in &rules.pinned {
+                    if !pinned.contains(docid) {
+                        continue;
+                    }
+                    if excluded.contains(docid) {
+                        pinned.remove(docid);
+                    } else {
+                        distinct_single_docid(
+                            ctx.index,
+                            ctx.txn,
+                            distinct_fid,
+                            docid,
+                            &mut excluded,
+                        )?;
+                    }
+                }
+                universe -= excluded;
+            }
+        }
+
+        let mut boosts = self.boosts.clone();
+        boosts.extend(rules.boosts().iter().cloned());
+
+        let suggestions_universe = self.suggest_query.then(|| universe.clone());
+
+        // the documents matching the query regardless of the filter are only needed
+        // to compute the candidates of the disjunctive facets
+        let unfiltered_universe = match &self.filter {
+            Some(_) if !self.disjunctive_facets.is_empty() => {
+                Some(ctx.index.documents_ids(ctx.txn)? - &rules.hidden)
+            }
+            _ => None,
+        };
         let PartialSearchResult {
             located_query_terms,
-            candidates,
+            mut candidates,
             documents_ids,
             document_scores,
             degraded,
             used_negative_operator,
+            query_candidates,
         } = match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder, quantized }) => {
                 execute_vector_search(
@@ -237,16 +379,21 @@ impl<'a> Search<'a> {
                     self.scoring_strategy,
                     universe,
                     &self.sort_criteria,
+                    &boosts,
                     &self.distinct,
                     self.geo_param,
-                    self.offset,
-                    self.limit,
+                    offset,
+                    limit,
                     embedder_name,
                     embedder,
                     *quantized,
                     self.time_budget.clone(),
                     self.ranking_score_threshold,
-                )?
+                )
+                .map(|result| {
+                    // all the documents are candidates of a vector search
+                    PartialSearchResult { query_candidates: unfiltered_universe, ..result }
+                })?
             }
             _ => execute_search(
                 &mut ctx,
@@ -255,11 +402,13 @@ impl<'a> Search<'a> {
                 self.scoring_strategy,
                 self.exhaustive_number_hits,
                 universe,
+                unfiltered_universe,
                 &self.sort_criteria,
+                &boosts,
                 &self.distinct,
                 self.geo_param,
-                self.offset,
-                self.limit,
+                offset,
+                limit,
                 Some(self.words_limit),
                 &mut DefaultSearchLogger,
                 &mut DefaultSearchLogger,
@@ -275,6 +424,37 @@ impl<'a> Search<'a> {
             tracing::debug!("Vector store stats: total_time={total_time:.02?}, total_queries={total_queries}, total_results={total_results}");
         }
 
+        // the pinned documents are part of the results even when they don't match the query
+        candidates |= pinned;
+
+        let disjunctive_candidates = match (&self.filter, query_candidates) {
+            (Some(filter), Some(query_candidates)) => self
+                .disjunctive_facets
+                .iter()
+                .map(|facet| {
+                    let docids = match filter.without_attribute(facet) {
+                        Some(filter) => filter.evaluate(ctx.txn, ctx.index)? & &query_candidates,
+                        None => query_candidates.clone(),
+                    };
+                    Ok((facet.clone(), docids))
+                })
+                .collect::<Result<_>>()?,
+            // without filter, the disjunctive facets are computed on the candidates
+            _ => self
+                .disjunctive_facets
+                .iter()
+                .map(|facet| (facet.clone(), candidates.clone()))
+                .collect(),
+        };
+
+        let query_suggestions =
+            match (self.query.as_deref(), &located_query_terms, &suggestions_universe) {
+                (Some(query), Some(located_query_terms), Some(universe)) => {
+                    compute_query_suggestions(&mut ctx, query, located_query_terms, universe)?
+                }
+                _ => Vec::new(),
+            };
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
             Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
@@ -288,6 +468,9 @@ impl<'a> Search<'a> {
             documents_ids,
             degraded,
             used_negative_operator,
+            disjunctive_candidates,
+            applied_rules: rules.ids.clone(),
+            query_suggestions,
         })
     }
 }
@@ -300,10 +483,13 @@ impl fmt::Debug for Search<'_> {
             offset,
             limit,
             sort_criteria,
+            boosts,
             distinct,
             searchable_attributes,
             geo_param: _,
             terms_matching_strategy,
+            query_syntax,
+            suggest_query,
             scoring_strategy,
             words_limit,
             exhaustive_number_hits,
@@ -313,6 +499,7 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            disjunctive_facets,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -321,9 +508,12 @@ impl fmt::Debug for Search<'_> {
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("boosts", boosts)
             .field("distinct", distinct)
             .field("searchable_attributes", searchable_attributes)
             .field("terms_matching_strategy", terms_matching_strategy)
+            .field("query_syntax", query_syntax)
+            .field("suggest_query", suggest_query)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
@@ -334,6 +524,7 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("disjunctive_facets", disjunctive_facets)
             .finish()
     }
 }
@@ -346,6 +537,12 @@ pub struct SearchResult {
     pub document_scores: Vec<Vec<ScoreDetails>>,
     pub degraded: bool,
     pub used_negative_operator: bool,
+    /// The candidates to compute the distribution of each disjunctive facet on.
+    pub disjunctive_candidates: BTreeMap<String, RoaringBitmap>,
+    /// The ids of the rules applied to the search.
+    pub applied_rules: Vec<String>,
+    /// The corrections of the query, the best one first.
+    pub query_suggestions: Vec<QuerySuggestion>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -364,6 +561,20 @@ impl Default for TermsMatchingStrategy {
     }
 }
 
+/// How the query is interpreted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum QuerySyntax {
+    /// Only quoted phrases, `"fuzzy phrases"~N` and `-` negations are operators.
+    ///
+    /// The slop `N` of a fuzzy phrase is capped to 2, the farthest proximity stored in the
+    /// index, so a larger slop matches the same documents as `~2`.
+    #[default]
+    Simple,
+    /// The `+` required and `-` excluded clauses, the `OR` alternatives, the parentheses
+    /// grouping them and the `attribute:` restrictions are operators too.
+    Advanced,
+}
+
 fn get_first(s: &str) -> &str {
     match s.chars().next() {
         Some(c) => &s[..c.len_utf8()],
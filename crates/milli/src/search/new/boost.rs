@@ -0,0 +1,141 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::{Error, Filter, Result, UserError};
+
+/// A filter whose matching documents are ranked before the other documents when its weight is
+/// positive, or after them when it is negative.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boost<'a> {
+    pub filter: Filter<'a>,
+    pub weight: f64,
+}
+
+/// Sorts the documents by the decreasing sum of the weights of the boosts they match.
+///
+/// The documents matching no boost have a total weight of zero, they are returned after the
+/// boosted documents and before the buried ones.
+pub struct BoostSort<Query> {
+    /// The documents matching each boost along with its weight.
+    boosts: Vec<(RoaringBitmap, f64)>,
+    original_query: Option<Query>,
+    /// The buckets of documents sharing the same total weight, the best bucket is the last one.
+    buckets: Vec<(f64, RoaringBitmap)>,
+    /// The number of non-empty buckets when the iteration started, used to compute the ranks.
+    max_rank: u32,
+}
+
+impl<Query> BoostSort<Query> {
+    /// Evaluates the filters of the boosts once, the documents they match are intersected
+    /// with the candidates of each iteration.
+    pub fn new(ctx: &SearchContext<'_>, boosts: &[Boost<'_>]) -> Result<Self> {
+        let boosts = boosts
+            .iter()
+            .enumerate()
+            .map(|(index, Boost { filter, weight })| {
+                let docids = filter.evaluate(ctx.txn, ctx.index).map_err(|error| match error {
+                    Error::UserError(error) => Error::UserError(UserError::InvalidBoost {
+                        index,
+                        error: error.to_string(),
+                    }),
+                    error => error,
+                })?;
+                Ok((docids, *weight))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { boosts, original_query: None, buckets: Vec::new(), max_rank: 0 })
+    }
+
+    fn score_details(&self, weight: f64, rank: u32) -> ScoreDetails {
+        ScoreDetails::Boost(score_details::Boost {
+            weight,
+            rank: Rank { rank, max_rank: self.max_rank.max(rank) },
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for BoostSort<Query> {
+    fn id(&self) -> String {
+        "boost".to_string()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        // split the candidates on each boost, merging the groups reaching the same total weight
+        let mut buckets = vec![(0.0, parent_candidates.clone())];
+        for (docids, weight) in &self.boosts {
+            let mut split = Vec::with_capacity(buckets.len() * 2);
+            for (total, candidates) in buckets {
+                let matching = &candidates & docids;
+                if !matching.is_empty() {
+                    split.push((total + weight, matching));
+                }
+                let remaining = candidates - docids;
+                if !remaining.is_empty() {
+                    split.push((total, remaining));
+                }
+            }
+
+            split.sort_unstable_by(|(left, _), (right, _)| left.total_cmp(right));
+            buckets = Vec::with_capacity(split.len());
+            for (total, candidates) in split {
+                match buckets.last_mut() {
+                    Some((last, bucket)) if *last == total => *bucket |= candidates,
+                    _ => buckets.push((total, candidates)),
+                }
+            }
+        }
+
+        self.max_rank = buckets.len() as u32;
+        self.buckets = buckets;
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        let output = match self.buckets.pop() {
+            Some((weight, docids)) => {
+                // the remaining buckets all have a lower total weight
+                let rank = self.buckets.len() as u32 + 1;
+                RankingRuleOutput {
+                    query,
+                    candidates: docids & universe,
+                    score: self.score_details(weight, rank),
+                }
+            }
+            None => RankingRuleOutput {
+                query,
+                candidates: universe.clone(),
+                score: self.score_details(0.0, 1),
+            },
+        };
+        Ok(Some(output))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+        self.max_rank = 0;
+    }
+}
@@ -14,7 +14,7 @@ use crate::heed_codec::{BytesDecodeOwned, StrBEU16Codec};
 use crate::proximity::ProximityPrecision;
 use crate::update::MergeCboRoaringBitmaps;
 use crate::{
-    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Result, SearchContext, U8StrStrCodec,
+    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldId, Result, SearchContext, U8StrStrCodec,
 };
 
 /// A cache storing pointers to values in the LMDB databases.
@@ -204,6 +204,22 @@ impl<'ctx> SearchContext<'ctx> {
         }
     }
 
+    /// Retrieve the documents containing the word in any of the given fields.
+    pub fn word_docids_within_field_ids(
+        &mut self,
+        universe: Option<&RoaringBitmap>,
+        word: Interned<String>,
+        fids: &[FieldId],
+    ) -> Result<Option<RoaringBitmap>> {
+        let mut docids: Option<RoaringBitmap> = None;
+        for &fid in fids {
+            if let Some(word_fid_docids) = self.get_db_word_fid_docids(universe, word, fid)? {
+                *docids.get_or_insert_with(RoaringBitmap::new) |= word_fid_docids;
+            }
+        }
+        Ok(docids)
+    }
+
     /// Retrieve or insert the given value in the `word_docids` database.
     fn get_db_word_docids(
         &mut self,
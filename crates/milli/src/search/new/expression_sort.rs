@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+
+use super::geo_sort::facet_number_values;
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::{FieldId, Index, RankingExpression, Result, TimeBudget};
+
+/// The number of distinct ranks the normalized values of an expression are mapped to.
+const MAX_RANK: u32 = 20;
+
+/// Sorts the documents by the decreasing value of a [`RankingExpression`].
+///
+/// The values are computed from the numeric facet values of the documents when the first
+/// bucket is requested, and the buckets are then taken one by one from the best one.
+/// Documents for which the expression can't be computed, or that were not evaluated
+/// before the time budget was exceeded, are returned last.
+pub struct ExpressionSort<Query> {
+    expression: RankingExpression,
+    field_ids: HashMap<String, Option<FieldId>>,
+    must_redact: bool,
+    /// The current unix timestamp in seconds, used by `recency_decay`.
+    now: f64,
+    time_budget: TimeBudget,
+    original_query: Option<Query>,
+    /// The documents sharing the same value, `None` until the first bucket is requested.
+    /// The documents without a value are returned once all the buckets have been.
+    buckets: Option<BTreeMap<OrderedFloat<f64>, RoaringBitmap>>,
+    /// The smallest and biggest values of the candidates, used to compute the ranks.
+    bounds: Option<(f64, f64)>,
+}
+
+impl<Query> ExpressionSort<Query> {
+    pub fn new(
+        index: &Index,
+        rtxn: &heed::RoTxn<'_>,
+        expression: RankingExpression,
+        time_budget: TimeBudget,
+    ) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_ids: HashMap<_, _> = expression
+            .attributes()
+            .into_iter()
+            .map(|attribute| (attribute.to_string(), fields_ids_map.id(attribute)))
+            .collect();
+
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => field_ids
+                .keys()
+                .any(|attribute| !displayed_fields.iter().any(|&field| field == attribute)),
+            None => false,
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_secs_f64());
+
+        Ok(Self {
+            expression,
+            field_ids,
+            must_redact,
+            now,
+            time_budget,
+            original_query: None,
+            buckets: None,
+            bounds: None,
+        })
+    }
+
+    /// Computes the value of the expression for the candidates and groups them by value.
+    ///
+    /// The evaluation stops as soon as the time budget is exceeded, the remaining candidates
+    /// are then considered as having no value.
+    fn evaluate(&mut self, ctx: &mut SearchContext<'_>, candidates: &RoaringBitmap) -> Result<()> {
+        let mut buckets = BTreeMap::new();
+
+        for docid in candidates {
+            if self.time_budget.exceeded() {
+                break;
+            }
+
+            // when an attribute has multiple values, the smallest one is used
+            let mut attribute_value = |attribute: &str| -> Result<Option<f64>> {
+                let Some(&Some(field_id)) = self.field_ids.get(attribute) else {
+                    return Ok(None);
+                };
+                match facet_number_values(docid, field_id, ctx.index, ctx.txn)?.next() {
+                    Some(Ok(((_, _, value), ()))) => Ok(Some(value)),
+                    Some(Err(e)) => Err(e.into()),
+                    None => Ok(None),
+                }
+            };
+
+            if let Some(value) = self.expression.evaluate(&mut attribute_value, self.now)? {
+                buckets.entry(OrderedFloat(value)).or_insert_with(RoaringBitmap::new).insert(docid);
+            }
+        }
+
+        self.bounds = buckets
+            .first_key_value()
+            .zip(buckets.last_key_value())
+            .map(|((min, _), (max, _))| (min.0, max.0));
+        self.buckets = Some(buckets);
+        Ok(())
+    }
+
+    fn score_details(&self, value: Option<f64>) -> ScoreDetails {
+        let rank = match (value, self.bounds) {
+            (None, _) | (_, None) => 1,
+            (Some(_), Some((min, max))) if min == max => MAX_RANK,
+            // the best rank is kept for the biggest value and the worst one for missing values
+            (Some(value), Some((min, max))) => {
+                let normalized = (value - min) / (max - min);
+                2 + (normalized * (MAX_RANK - 2) as f64).round() as u32
+            }
+        };
+
+        ScoreDetails::Expression(score_details::Expression {
+            expression: self.expression.to_string(),
+            redacted: self.must_redact,
+            value,
+            rank: Rank { rank, max_rank: MAX_RANK },
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for ExpressionSort<Query> {
+    fn id(&self) -> String {
+        format!("score: {}", self.expression)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::expression_sort")]
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        _parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        // the candidates are only evaluated when the first bucket is requested
+        self.buckets = None;
+        self.bounds = None;
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::expression_sort")]
+    fn next_bucket(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        if self.buckets.is_none() {
+            self.evaluate(ctx, universe)?;
+        }
+
+        let query = self.original_query.as_ref().unwrap().clone();
+        let bucket = self.buckets.as_mut().and_then(|buckets| buckets.pop_last());
+        let output = match bucket {
+            Some((value, docids)) => RankingRuleOutput {
+                query,
+                candidates: docids & universe,
+                score: self.score_details(Some(value.0)),
+            },
+            None => RankingRuleOutput {
+                query,
+                candidates: universe.clone(),
+                score: self.score_details(None),
+            },
+        };
+        Ok(Some(output))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::expression_sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = None;
+        self.bounds = None;
+    }
+}
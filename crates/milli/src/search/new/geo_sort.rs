@@ -22,7 +22,7 @@ fn facet_values_prefix_key(distinct: u16, id: u32) -> [u8; FID_SIZE + DOCID_SIZE
 }
 
 /// Return an iterator over each number value in the given field of the given document.
-fn facet_number_values<'a>(
+pub(super) fn facet_number_values<'a>(
     docid: u32,
     field_id: u16,
     index: &Index,
@@ -91,7 +91,8 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
 
     strategy: Strategy,
     ascending: bool,
-    point: [f64; 2],
+    /// The documents are sorted by their distance to the closest of these points.
+    points: Vec<[f64; 2]>,
     field_ids: Option<[u16; 2]>,
     rtree: Option<RTree<GeoPoint>>,
 
@@ -108,15 +109,16 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
     pub fn new(
         parameter: Parameter,
         geo_faceted_docids: RoaringBitmap,
-        point: [f64; 2],
+        points: Vec<[f64; 2]>,
         ascending: bool,
     ) -> Result<Self> {
+        debug_assert!(!points.is_empty(), "a geo sort requires at least one point");
         let Parameter { strategy, max_bucket_size, distance_error_margin } = parameter;
         Ok(Self {
             query: None,
             strategy,
             ascending,
-            point,
+            points,
             geo_candidates: geo_faceted_docids,
             field_ids: None,
             rtree: None,
@@ -136,8 +138,12 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         debug_assert!(self.field_ids.is_some(), "fill_buffer can't be called without the lat&lng");
         debug_assert!(self.cached_sorted_docids.is_empty());
 
+        // The rtree can't find the points that are the farthest from several points at once,
+        // a descending sort on multiple points always uses the iterative strategy.
+        let rtree_compatible = self.ascending || self.points.len() == 1;
+
         // lazily initialize the rtree if needed by the strategy, and cache it in `self.rtree`
-        let rtree = if self.strategy.use_rtree(geo_candidates.len() as usize) {
+        let rtree = if rtree_compatible && self.strategy.use_rtree(geo_candidates.len() as usize) {
             if let Some(rtree) = self.rtree.as_ref() {
                 // get rtree from cache
                 Some(rtree)
@@ -154,9 +160,23 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         let cache_size = self.strategy.cache_size();
         if let Some(rtree) = rtree {
             if self.ascending {
-                let point = lat_lng_to_xyz(&self.point);
-                for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
+                // we merge the nearest neighbors of every point by always taking the closest one,
+                // a document can be reached from several points so we skip the ones already seen
+                let points: Vec<_> = self.points.iter().map(lat_lng_to_xyz).collect();
+                let mut neighbors: Vec<_> = points
+                    .iter()
+                    .map(|point| rtree.nearest_neighbor_iter_with_distance_2(point).peekable())
+                    .collect();
+                let mut seen = RoaringBitmap::new();
+                loop {
+                    let closest = neighbors
+                        .iter_mut()
+                        .enumerate()
+                        .filter_map(|(i, iter)| iter.peek().map(|(_, distance)| (i, *distance)))
+                        .min_by(|(_, left), (_, right)| left.total_cmp(right));
+                    let Some((i, _)) = closest else { break };
+                    let (point, _) = neighbors[i].next().unwrap();
+                    if geo_candidates.contains(point.data.0) && seen.insert(point.data.0) {
                         self.cached_sorted_docids.push_back(point.data);
                         if self.cached_sorted_docids.len() >= cache_size {
                             break;
@@ -166,7 +186,7 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             } else {
                 // in the case of the desc geo sort we look for the closest point to the opposite of the queried point
                 // and we insert the points in reverse order they get reversed when emptying the cache later on
-                let point = lat_lng_to_xyz(&opposite_of(self.point));
+                let point = lat_lng_to_xyz(&opposite_of(self.points[0]));
                 for point in rtree.nearest_neighbor_iter(&point) {
                     if geo_candidates.contains(point.data.0) {
                         self.cached_sorted_docids.push_front(point.data);
@@ -185,13 +205,35 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
                 .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
                 .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
             // computing the distance between two points is expensive thus we cache the result
-            documents
-                .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
+            documents.sort_by_cached_key(|(_, p)| self.closest_point(p).1 as usize);
             self.cached_sorted_docids.extend(documents);
         };
 
         Ok(())
     }
+
+    /// Returns the point of the sort that is the closest to the given point and its distance.
+    fn closest_point(&self, point: &[f64; 2]) -> ([f64; 2], f64) {
+        self.points
+            .iter()
+            .map(|origin| (*origin, distance_between_two_points(origin, point)))
+            .min_by(|(_, left), (_, right)| left.total_cmp(right))
+            .expect("a geo sort requires at least one point")
+    }
+
+    /// Returns the score details of a bucket, the target point being the
+    /// point of the sort that is the closest to the bucket value.
+    fn score_details(&self, value: Option<[f64; 2]>) -> ScoreDetails {
+        let target_point = match value {
+            Some(value) => self.closest_point(&value).0,
+            None => self.points[0],
+        };
+        ScoreDetails::GeoSort(score_details::GeoSort {
+            target_point,
+            ascending: self.ascending,
+            value,
+        })
+    }
 }
 
 /// Extracts the lat and long values from a single document.
@@ -272,11 +314,7 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::GeoSort(score_details::GeoSort {
-                    target_point: self.point,
-                    ascending: self.ascending,
-                    value: None,
-                }),
+                score: self.score_details(None),
             }));
         }
 
@@ -303,7 +341,7 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
             // The loop will only exit when we have found all points with equal distance or have exhausted the candidates.
             if let Some((id, point)) = next(&mut self.cached_sorted_docids) {
                 if geo_candidates.contains(id) {
-                    let distance = distance_between_two_points(&self.point, &point);
+                    let (_, distance) = self.closest_point(&point);
                     if let Some((point0, bucket_distance)) = current_distance.as_ref() {
                         if (bucket_distance - distance).abs() > self.distance_error_margin {
                             // different distance, point belongs to next bucket
@@ -311,11 +349,7 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                             return Ok(Some(RankingRuleOutput {
                                 query,
                                 candidates: current_bucket,
-                                score: ScoreDetails::GeoSort(score_details::GeoSort {
-                                    target_point: self.point,
-                                    ascending: self.ascending,
-                                    value: Some(point0.to_owned()),
-                                }),
+                                score: self.score_details(Some(point0.to_owned())),
                             }));
                         } else {
                             // same distance, point belongs to current bucket
@@ -327,11 +361,7 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                                 return Ok(Some(RankingRuleOutput {
                                     query,
                                     candidates: current_bucket,
-                                    score: ScoreDetails::GeoSort(score_details::GeoSort {
-                                        target_point: self.point,
-                                        ascending: self.ascending,
-                                        value: Some(point0.to_owned()),
-                                    }),
+                                    score: self.score_details(Some(point0.to_owned())),
                                 }));
                             }
                         }
@@ -345,11 +375,7 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                             return Ok(Some(RankingRuleOutput {
                                 query,
                                 candidates: current_bucket,
-                                score: ScoreDetails::GeoSort(score_details::GeoSort {
-                                    target_point: self.point,
-                                    ascending: self.ascending,
-                                    value: Some(point.to_owned()),
-                                }),
+                                score: self.score_details(Some(point.to_owned())),
                             }));
                         }
                     }
@@ -364,21 +390,13 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                         return Ok(Some(RankingRuleOutput {
                             query,
                             candidates: current_bucket,
-                            score: ScoreDetails::GeoSort(score_details::GeoSort {
-                                target_point: self.point,
-                                ascending: self.ascending,
-                                value: Some(point0.to_owned()),
-                            }),
+                            score: self.score_details(Some(point0.to_owned())),
                         }));
                     } else {
                         return Ok(Some(RankingRuleOutput {
                             query,
                             candidates: universe.clone(),
-                            score: ScoreDetails::GeoSort(score_details::GeoSort {
-                                target_point: self.point,
-                                ascending: self.ascending,
-                                value: None,
-                            }),
+                            score: self.score_details(None),
                         }));
                     }
                 }
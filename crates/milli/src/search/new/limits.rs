@@ -15,3 +15,18 @@ pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
 ///
 /// This limit is meant to gracefully handle the case where a word would have very long phrases as synonyms.
 pub const MAX_SYNONYM_WORD_COUNT: usize = 100;
+
+/// Maximum number of words allowed between two consecutive words of a fuzzy phrase.
+///
+/// The farthest proximity between two words stored in the index is `MAX_DISTANCE - 1`.
+pub const MAX_PHRASE_SLOP: u8 = (crate::proximity::MAX_DISTANCE - 2) as u8;
+/// Maximum number of words that can be derived with typos from a single word of a fuzzy phrase.
+pub const MAX_PHRASE_TYPO_COUNT: usize = 10;
+
+/// Maximum number of corrections of the query returned by a search.
+pub const MAX_QUERY_SUGGESTION_COUNT: usize = 3;
+/// Maximum number of corrections considered for a single word of the query.
+pub const MAX_WORD_CORRECTION_COUNT: usize = 3;
+/// How many times more documents a typo derivation of a word must be found in
+/// than the word itself to be suggested as its correction.
+pub const MIN_CORRECTION_FREQUENCY_RATIO: u64 = 10;
@@ -43,10 +43,24 @@ impl MatchingWords {
             let (matching_words, matching_phrases) = term.all_computed_derivations();
 
             for matching_phrase in matching_phrases {
-                phrases.push(LocatedMatchingPhrase {
-                    value: matching_phrase,
-                    positions: located_term.positions.clone(),
-                });
+                let phrase = ctx.phrase_interner.get(matching_phrase);
+                if phrase.is_exact() {
+                    phrases.push(LocatedMatchingPhrase {
+                        value: matching_phrase,
+                        positions: located_term.positions.clone(),
+                    });
+                    continue;
+                }
+                // the words of a fuzzy phrase may be apart or misspelled, match them one by one
+                for derivations in phrase.derivations().into_iter().flatten() {
+                    let original_char_count = ctx.word_interner.get(derivations[0]).chars().count();
+                    words.push(LocatedMatchingWords {
+                        value: derivations,
+                        positions: located_term.positions.clone(),
+                        is_prefix: false,
+                        original_char_count,
+                    });
+                }
             }
 
             words.push(LocatedMatchingWords {
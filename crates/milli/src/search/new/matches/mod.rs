@@ -511,7 +511,9 @@ mod tests {
                 crate::score_details::ScoringStrategy::Skip,
                 false,
                 universe,
+                None,
                 &None,
+                &[],
                 &None,
                 crate::search::new::GeoSortParameter::default(),
                 0,
@@ -8,21 +8,25 @@ mod limits;
 mod logger;
 pub mod matches;
 mod query_graph;
+mod query_suggestions;
 mod query_term;
 mod ranking_rule_graph;
 mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
 
+mod boost;
 mod exact_attribute;
+mod expression_sort;
 mod sort;
 mod vector_sort;
 
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
-use std::ops::AddAssign;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::{AddAssign, Range};
 use std::time::Duration;
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
@@ -36,21 +40,30 @@ pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
 use query_term::{
-    located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
+    located_query_clauses_from_advanced_query, located_query_terms_from_tokens, ExtractedClauses,
+    ExtractedTokens, LocatedQueryTerm, Phrase, QueryClause, QueryTerm, QueryTermSubset,
 };
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
-use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
+use resolve_query_graph::{
+    compute_query_graph_docids, compute_query_term_subset_docids, PhraseDocIdsCache,
+};
 use roaring::RoaringBitmap;
 use sort::Sort;
 
+pub use self::boost::Boost;
+use self::boost::BoostSort;
+pub(crate) use self::distinct::distinct_single_docid;
 use self::distinct::facet_string_values;
+use self::expression_sort::ExpressionSort;
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Parameter as GeoSortParameter;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
+pub(crate) use self::query_suggestions::compute_query_suggestions;
+pub use self::query_suggestions::QuerySuggestion;
 use self::vector_sort::VectorSort;
 use crate::attribute_patterns::{match_pattern, PatternMatch};
 use crate::constants::RESERVED_GEO_FIELD_NAME;
@@ -58,10 +71,11 @@ use crate::index::PrefixSearch;
 use crate::localized_attributes_rules::LocalizedFieldIds;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
+use crate::typo_budgets::FieldsTypoBudgets;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, DocumentId, FieldId, Filter, Index, Member, PhoneticSettings, QuerySyntax, Result,
+    TermsMatchingStrategy, TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -76,6 +90,28 @@ pub struct SearchContext<'ctx> {
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
     pub vector_store_stats: Option<VectorStoreStats>,
+    /// The locales of the search, selecting the synonym sets restricted to some locales
+    /// and the stemmed locales.
+    pub locales: Vec<Language>,
+    /// The synonyms applied to the search, loaded on first use.
+    synonyms: Option<HashMap<Vec<String>, Vec<Vec<String>>>>,
+    /// The locales in which the query words are stemmed, loaded on first use.
+    stemming_locales: Option<Vec<Language>>,
+    /// The phonetic settings of the index, if the words of some attributes are indexed
+    /// by their phonetic codes.
+    phonetic: Option<PhoneticSettings>,
+    /// The typo budgets of the typo tolerant fields, if some fields override the global
+    /// typo tolerance settings.
+    pub(crate) typo_budgets: Option<FieldsTypoBudgets>,
+    /// The fields of the infix attributes, in which the words containing a query word match it.
+    pub(crate) infix_fids: Vec<FieldId>,
+    /// The words of the infix attributes indexed by their trigrams.
+    words_trigrams_fst: Option<fst::Set<&'ctx [u8]>>,
+    /// How the query is interpreted.
+    pub query_syntax: QuerySyntax,
+    /// The words of the query along with their byte ranges, as tokenized by the search,
+    /// only recorded when `Some` to locate the corrected words of the query suggestions.
+    pub query_words: Option<Vec<(String, Range<usize>)>>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -83,6 +119,15 @@ impl<'ctx> SearchContext<'ctx> {
         let searchable_fids = index.searchable_fields_and_weights(txn)?;
         let exact_attributes_ids = index.exact_attributes_ids(txn)?;
 
+        let typo_budgets = FieldsTypoBudgets::new(
+            index,
+            txn,
+            searchable_fids
+                .iter()
+                .filter(|(_, fid, _)| !exact_attributes_ids.contains(fid))
+                .map(|(name, fid, _)| (name.as_ref(), *fid)),
+        )?;
+
         let mut exact = Vec::new();
         let mut tolerant = Vec::new();
         for (_name, fid, weight) in searchable_fids {
@@ -94,6 +139,10 @@ impl<'ctx> SearchContext<'ctx> {
         }
 
         let prefix_search = index.prefix_search(txn)?.unwrap_or_default();
+        let phonetic = index.phonetic_settings(txn)?;
+        let infix_fids = index.infix_fields_ids(txn)?;
+        let words_trigrams_fst =
+            if infix_fids.is_empty() { None } else { index.words_trigrams_fst(txn)? };
 
         Ok(Self {
             index,
@@ -106,6 +155,15 @@ impl<'ctx> SearchContext<'ctx> {
             restricted_fids: None,
             prefix_search,
             vector_store_stats: None,
+            locales: Vec::new(),
+            synonyms: None,
+            stemming_locales: None,
+            phonetic,
+            typo_budgets,
+            infix_fids,
+            words_trigrams_fst,
+            query_syntax: QuerySyntax::default(),
+            query_words: None,
         })
     }
 
@@ -113,6 +171,68 @@ impl<'ctx> SearchContext<'ctx> {
         self.prefix_search != PrefixSearch::Disabled
     }
 
+    /// Returns the synonyms of the words, along with the ones of the synonym sets
+    /// restricted to the locales of the search.
+    pub fn synonyms(&mut self, words: &[String]) -> Result<Vec<Vec<String>>> {
+        if self.synonyms.is_none() {
+            let mut synonyms = self.index.synonyms(self.txn)?;
+            if !self.locales.is_empty() {
+                for (locale, localized) in self.index.localized_synonyms(self.txn)? {
+                    if !self.locales.contains(&locale) {
+                        continue;
+                    }
+                    for (words, localized) in localized {
+                        let entry = synonyms.entry(words).or_default();
+                        entry.extend(localized);
+                        entry.sort_unstable();
+                        entry.dedup();
+                    }
+                }
+            }
+            self.synonyms = Some(synonyms);
+        }
+
+        let synonyms = self.synonyms.as_ref().and_then(|synonyms| synonyms.get(words));
+        Ok(synonyms.cloned().unwrap_or_default())
+    }
+
+    /// Returns the stems of the word in the locales of the search that are stemmed by
+    /// a localized attributes rule, or in all the stemmed locales if the search has no locales.
+    pub fn stems(&mut self, word: &str) -> Result<Vec<String>> {
+        if self.stemming_locales.is_none() {
+            let rules = self.index.localized_attributes_rules(self.txn)?.unwrap_or_default();
+            let mut locales: Vec<_> = rules
+                .into_iter()
+                .filter(|rule| rule.stemming)
+                .flat_map(|rule| rule.locales)
+                .filter(|locale| self.locales.is_empty() || self.locales.contains(locale))
+                .collect();
+            locales.sort_unstable();
+            locales.dedup();
+            self.stemming_locales = Some(locales);
+        }
+
+        match self.stemming_locales.as_deref() {
+            Some([]) | None => Ok(Vec::new()),
+            Some(locales) => Ok(crate::stemming::stems(locales, word)),
+        }
+    }
+
+    /// Returns the phonetic codes of the word, if the words of some attributes are indexed
+    /// by their phonetic codes.
+    pub fn phonetic_codes(&self, word: &str) -> Vec<String> {
+        self.phonetic.as_ref().map_or_else(Vec::new, |phonetic| phonetic.codes(word))
+    }
+
+    /// Returns the words of the infix attributes containing the word, if it is at least
+    /// as long as a trigram.
+    pub fn infix_words(&self, word: &str) -> Vec<String> {
+        self.words_trigrams_fst.as_ref().map_or_else(Vec::new, |fst| {
+            crate::trigrams::values_containing(fst, &[], word, limits::MAX_PREFIX_COUNT)
+                .unwrap_or_default()
+        })
+    }
+
     pub fn attributes_to_search_on(
         &mut self,
         attributes_to_search_on: &'ctx [String],
@@ -159,18 +279,9 @@ impl<'ctx> SearchContext<'ctx> {
                 None if user_defined_searchable.is_none() => continue,
                 // The field is not searchable => User error
                 None => {
-                    let (valid_fields, hidden_fields) = self.index.remove_hidden_fields(
-                        self.txn,
-                        searchable_fields_weights.iter().map(|(name, _, _)| name),
-                    )?;
-
-                    let field = field_name.to_string();
-                    return Err(UserError::InvalidSearchableAttribute {
-                        field,
-                        valid_fields,
-                        hidden_fields,
-                    }
-                    .into());
+                    let error =
+                        self.invalid_searchable_attribute(field_name, &searchable_fields_weights)?;
+                    return Err(error);
                 }
             };
 
@@ -189,6 +300,42 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    /// Returns the searchable fields of the attribute of an `attribute:` clause of an advanced
+    /// query, among the attributes to search on.
+    pub fn fields_ids_of_attribute(&self, attribute: &str) -> Result<Vec<FieldId>> {
+        let searchable_fields_weights = self.index.searchable_fields_and_weights(self.txn)?;
+        let fids: Vec<_> = searchable_fields_weights
+            .iter()
+            .filter(|(name, _, _)| {
+                match_pattern(attribute, name) == PatternMatch::Match
+                    || crate::is_faceted_by(name, attribute)
+            })
+            .map(|(_, fid, _)| *fid)
+            .filter(|fid| self.restricted_fids.as_ref().is_none_or(|fids| fids.contains(fid)))
+            .collect();
+
+        // like an attribute to search on, an unknown attribute is only rejected when the
+        // searchable attributes are defined by the user
+        if fids.is_empty() && self.index.user_defined_searchable_fields(self.txn)?.is_some() {
+            return Err(self.invalid_searchable_attribute(attribute, &searchable_fields_weights)?);
+        }
+        Ok(fids)
+    }
+
+    fn invalid_searchable_attribute(
+        &self,
+        field_name: &str,
+        searchable_fields_weights: &[(Cow<'_, str>, FieldId, Weight)],
+    ) -> Result<crate::Error> {
+        let (valid_fields, hidden_fields) = self.index.remove_hidden_fields(
+            self.txn,
+            searchable_fields_weights.iter().map(|(name, _, _)| name),
+        )?;
+
+        let field = field_name.to_string();
+        Ok(UserError::InvalidSearchableAttribute { field, valid_fields, hidden_fields }.into())
+    }
 }
 
 #[derive(Debug, Default)]
@@ -309,7 +456,13 @@ fn resolve_negative_phrases(
     for term in negative_phrases {
         let query_term = ctx.term_interner.get(term.value);
         if let Some(phrase) = query_term.original_phrase() {
-            negative_bitmap |= ctx.get_phrase_docids(phrase)?;
+            // an excluded phrase scoped to some attributes is only excluded from their fields
+            if query_term.restricted_fids().is_some() {
+                let term = QueryTermSubset::full(term.value);
+                negative_bitmap |= compute_query_term_subset_docids(ctx, None, &term)?;
+            } else {
+                negative_bitmap |= ctx.get_phrase_docids(phrase)?;
+            }
         }
     }
     Ok(negative_bitmap)
@@ -319,12 +472,18 @@ fn resolve_negative_phrases(
 fn get_ranking_rules_for_placeholder_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: &[Boost<'_>],
     geo_param: geo_sort::Parameter,
+    time_budget: &TimeBudget,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     let mut sort = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<'ctx, PlaceholderQuery>> = vec![];
+    // the boosts are applied before any other ranking rule
+    if !boosts.is_empty() {
+        ranking_rules.push(Box::new(BoostSort::new(ctx, boosts)?));
+    }
     let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
     for rr in settings_ranking_rules {
         match rr {
@@ -362,6 +521,14 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Expression(expression) => {
+                ranking_rules.push(Box::new(ExpressionSort::new(
+                    ctx.index,
+                    ctx.txn,
+                    expression,
+                    time_budget.clone(),
+                )?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -371,12 +538,14 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
 fn get_ranking_rules_for_vector<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: &[Boost<'_>],
     geo_param: geo_sort::Parameter,
     limit_plus_offset: usize,
     target: &[f32],
     embedder_name: &str,
     embedder: &Embedder,
     quantized: bool,
+    time_budget: &TimeBudget,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     // query graph search
 
@@ -386,6 +555,10 @@ fn get_ranking_rules_for_vector<'ctx>(
 
     let mut vector = false;
     let mut ranking_rules: Vec<BoxRankingRule<'ctx, PlaceholderQuery>> = vec![];
+    // the boosts are applied before any other ranking rule
+    if !boosts.is_empty() {
+        ranking_rules.push(Box::new(BoostSort::new(ctx, boosts)?));
+    }
 
     let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
     for rr in settings_ranking_rules {
@@ -438,6 +611,14 @@ fn get_ranking_rules_for_vector<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Expression(expression) => {
+                ranking_rules.push(Box::new(ExpressionSort::new(
+                    ctx.index,
+                    ctx.txn,
+                    expression,
+                    time_budget.clone(),
+                )?));
+            }
         }
     }
 
@@ -448,8 +629,10 @@ fn get_ranking_rules_for_vector<'ctx>(
 fn get_ranking_rules_for_query_graph_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: &[Boost<'_>],
     geo_param: geo_sort::Parameter,
     terms_matching_strategy: TermsMatchingStrategy,
+    time_budget: &TimeBudget,
 ) -> Result<Vec<BoxRankingRule<'ctx, QueryGraph>>> {
     // query graph search
     let mut words = false;
@@ -460,6 +643,8 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     let mut exactness = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
+    // the boosts are applied right after the `words` ranking rule, or first without it
+    let mut boost_position = 0;
 
     // Don't add the `words` ranking rule if the term matching strategy is `All`
     if matches!(terms_matching_strategy, TermsMatchingStrategy::All) {
@@ -477,6 +662,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
             | crate::Criterion::Exactness => {
                 if !words {
                     ranking_rules.push(Box::new(Words::new(terms_matching_strategy)));
+                    boost_position = ranking_rules.len();
                     words = true;
                 }
             }
@@ -488,6 +674,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                     continue;
                 }
                 ranking_rules.push(Box::new(Words::new(terms_matching_strategy)));
+                boost_position = ranking_rules.len();
                 words = true;
             }
             crate::Criterion::Typo => {
@@ -548,8 +735,20 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Expression(expression) => {
+                ranking_rules.push(Box::new(ExpressionSort::new(
+                    ctx.index,
+                    ctx.txn,
+                    expression,
+                    time_budget.clone(),
+                )?));
+            }
         }
     }
+
+    if !boosts.is_empty() {
+        ranking_rules.insert(boost_position, Box::new(BoostSort::new(ctx, boosts)?));
+    }
     Ok(ranking_rules)
 }
 
@@ -579,27 +778,29 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
-            AscDesc::Asc(Member::Geo(point)) => {
+            AscDesc::Asc(member @ (Member::Geo(_) | Member::GeoPoints(_))) => {
                 if *geo_sorted {
                     continue;
                 }
                 let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
+                let points = member.geo_points().unwrap_or_default().to_vec();
                 ranking_rules.push(Box::new(GeoSort::new(
                     geo_param,
                     geo_faceted_docids,
-                    point,
+                    points,
                     true,
                 )?));
             }
-            AscDesc::Desc(Member::Geo(point)) => {
+            AscDesc::Desc(member @ (Member::Geo(_) | Member::GeoPoints(_))) => {
                 if *geo_sorted {
                     continue;
                 }
                 let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
+                let points = member.geo_points().unwrap_or_default().to_vec();
                 ranking_rules.push(Box::new(GeoSort::new(
                     geo_param,
                     geo_faceted_docids,
-                    point,
+                    points,
                     false,
                 )?));
             }
@@ -628,6 +829,7 @@ pub fn execute_vector_search(
     scoring_strategy: ScoringStrategy,
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: &[Boost<'_>],
     distinct: &Option<String>,
     geo_param: geo_sort::Parameter,
     from: usize,
@@ -645,12 +847,14 @@ pub fn execute_vector_search(
     let ranking_rules = get_ranking_rules_for_vector(
         ctx,
         sort_criteria,
+        boosts,
         geo_param,
         from + length,
         vector,
         embedder_name,
         embedder,
         quantized,
+        &time_budget,
     )?;
 
     let mut placeholder_search_logger = logger::DefaultSearchLogger;
@@ -678,6 +882,7 @@ pub fn execute_vector_search(
         located_query_terms: None,
         degraded,
         used_negative_operator: false,
+        query_candidates: None,
     })
 }
 
@@ -690,7 +895,9 @@ pub fn execute_search(
     scoring_strategy: ScoringStrategy,
     exhaustive_number_hits: bool,
     mut universe: RoaringBitmap,
+    mut unfiltered_universe: Option<RoaringBitmap>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: &[Boost<'_>],
     distinct: &Option<String>,
     geo_param: geo_sort::Parameter,
     from: usize,
@@ -706,7 +913,7 @@ pub fn execute_search(
 
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
-    let query_terms = if let Some(query) = query {
+    let query_clauses = if let Some(query) = query {
         let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
         let entered = span.enter();
 
@@ -735,6 +942,7 @@ pub fn execute_search(
         let db_locales;
         match locales {
             Some(locales) => {
+                ctx.locales = locales.clone();
                 if !locales.is_empty() {
                     tokbuilder.allow_list(locales);
                 }
@@ -765,6 +973,7 @@ pub fn execute_search(
                 };
 
                 db_locales = localized_fields.all_locales();
+                ctx.locales = db_locales.clone();
                 if !db_locales.is_empty() {
                     tokbuilder.allow_list(&db_locales);
                 }
@@ -774,44 +983,92 @@ pub fn execute_search(
         let tokenizer = tokbuilder.build();
         drop(entered);
 
-        let span = tracing::trace_span!(target: "search::tokens", "tokenize");
-        let entered = span.enter();
-        let tokens = tokenizer.tokenize(query);
-        drop(entered);
+        if let Some(query_words) = &mut ctx.query_words {
+            let words = tokenizer.tokenize(query).filter(|token| token.is_word());
+            query_words.extend(
+                words.map(|token| (token.lemma().to_owned(), token.byte_start..token.byte_end)),
+            );
+        }
 
-        let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+        let (query_clauses, negative_words, negative_phrases) = match ctx.query_syntax {
+            QuerySyntax::Simple => {
+                let span = tracing::trace_span!(target: "search::tokens", "tokenize");
+                let entered = span.enter();
+                let tokens = tokenizer.tokenize(query);
+                drop(entered);
+
+                let ExtractedTokens { query_terms, negative_words, negative_phrases } =
+                    located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+                let query_clauses: Vec<_> =
+                    query_terms.into_iter().map(QueryClause::optional).collect();
+                (query_clauses, negative_words, negative_phrases)
+            }
+            QuerySyntax::Advanced => {
+                let ExtractedClauses { clauses, negative_words, negative_phrases } =
+                    located_query_clauses_from_advanced_query(ctx, &tokenizer, query, words_limit)?;
+                (clauses, negative_words, negative_phrases)
+            }
+        };
         used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
 
-        let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
+        let negative_universe = unfiltered_universe.as_ref().unwrap_or(&universe);
+        let ignored_documents =
+            resolve_negative_words(ctx, Some(negative_universe), &negative_words)?;
         let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
 
-        universe -= ignored_documents;
-        universe -= ignored_phrases;
+        universe -= &ignored_documents;
+        universe -= &ignored_phrases;
+        if let Some(unfiltered_universe) = &mut unfiltered_universe {
+            *unfiltered_universe -= ignored_documents;
+            *unfiltered_universe -= ignored_phrases;
+        }
 
-        if query_terms.is_empty() {
+        if query_clauses.is_empty() {
             // Do a placeholder search instead
             None
         } else {
-            Some(query_terms)
+            Some(query_clauses)
         }
     } else {
         None
     };
 
-    let bucket_sort_output = if let Some(query_terms) = query_terms {
-        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
+    let bucket_sort_output = if let Some(query_clauses) = query_clauses {
+        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_clauses)?;
         located_query_terms = Some(new_located_query_terms);
 
         let ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
             sort_criteria,
+            boosts,
             geo_param,
             terms_matching_strategy,
+            &time_budget,
         )?;
 
-        universe &=
-            resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
+        // when the documents matching the query regardless of the filter are requested, the
+        // query graph is resolved once on all of them and the filtered universe deduced from it
+        match &mut unfiltered_universe {
+            Some(unfiltered_universe) => {
+                *unfiltered_universe &= resolve_universe(
+                    ctx,
+                    unfiltered_universe,
+                    &graph,
+                    terms_matching_strategy,
+                    query_graph_logger,
+                )?;
+                universe &= &*unfiltered_universe;
+            }
+            None => {
+                universe &= resolve_universe(
+                    ctx,
+                    &universe,
+                    &graph,
+                    terms_matching_strategy,
+                    query_graph_logger,
+                )?;
+            }
+        }
 
         bucket_sort(
             ctx,
@@ -827,8 +1084,13 @@ pub fn execute_search(
             ranking_score_threshold,
         )?
     } else {
-        let ranking_rules =
-            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_param)?;
+        let ranking_rules = get_ranking_rules_for_placeholder_search(
+            ctx,
+            sort_criteria,
+            boosts,
+            geo_param,
+            &time_budget,
+        )?;
         bucket_sort(
             ctx,
             ranking_rules,
@@ -869,6 +1131,7 @@ pub fn execute_search(
         located_query_terms,
         degraded,
         used_negative_operator,
+        query_candidates: unfiltered_universe,
     })
 }
 
@@ -909,7 +1172,9 @@ fn check_sort_criteria(
                 }
                 .into());
             }
-            Member::Geo(_) if !sortable_fields.contains(RESERVED_GEO_FIELD_NAME) => {
+            Member::Geo(_) | Member::GeoPoints(_)
+                if !sortable_fields.contains(RESERVED_GEO_FIELD_NAME) =>
+            {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
 
@@ -935,4 +1200,7 @@ pub struct PartialSearchResult {
 
     pub degraded: bool,
     pub used_negative_operator: bool,
+    /// The documents matching the query regardless of the filter,
+    /// only computed when an unfiltered universe is given.
+    pub query_candidates: Option<RoaringBitmap>,
 }
@@ -7,7 +7,8 @@ use roaring::RoaringBitmap;
 
 use super::interner::{FixedSizeInterner, Interned};
 use super::query_term::{
-    self, number_of_typos_allowed, LocatedQueryTerm, LocatedQueryTermSubset, QueryTermSubset,
+    self, number_of_typos_allowed, LocatedQueryTerm, LocatedQueryTermSubset, QueryClause,
+    QueryTermSubset,
 };
 use super::small_bitmap::SmallBitmap;
 use super::SearchContext;
@@ -92,12 +93,16 @@ pub struct QueryGraph {
 impl QueryGraph {
     /// Build the query graph from the parsed user search query, return an updated list of the located query terms
     /// which contains ngrams.
+    ///
+    /// The alternatives of a clause are parallel nodes sharing its term id, and the nodes of
+    /// a required clause are never removed by the terms matching strategy.
     pub fn from_query(
         ctx: &mut SearchContext<'_>,
-        // The terms here must be consecutive
-        terms: &[LocatedQueryTerm],
+        // The clauses here must be consecutive
+        clauses: &[QueryClause],
     ) -> Result<(QueryGraph, Vec<LocatedQueryTerm>)> {
-        let mut new_located_query_terms = terms.to_vec();
+        let mut new_located_query_terms: Vec<_> =
+            clauses.iter().flat_map(|clause| clause.alternatives.iter().cloned()).collect();
 
         let nbr_typos = number_of_typos_allowed(ctx)?;
 
@@ -109,29 +114,40 @@ impl QueryGraph {
         let (mut prev2, mut prev1, mut prev0): (Vec<u16>, Vec<u16>, Vec<u16>) =
             (vec![], vec![], vec![root_node]);
 
-        let original_terms_len = terms.len();
-        for term_idx in 0..original_terms_len {
+        let original_clauses_len = clauses.len();
+        for term_idx in 0..original_clauses_len {
             let mut new_nodes = vec![];
 
-            let new_node_idx = add_node(
-                &mut nodes_data,
-                QueryNodeData::Term(LocatedQueryTermSubset {
-                    term_subset: QueryTermSubset::full(terms[term_idx].value),
-                    positions: terms[term_idx].positions.clone(),
-                    term_ids: term_idx as u8..=term_idx as u8,
-                }),
-            );
-            new_nodes.push(new_node_idx);
+            let clause = &clauses[term_idx];
+            for term in &clause.alternatives {
+                let mut term_subset = QueryTermSubset::full(term.value);
+                if clause.required {
+                    term_subset.make_mandatory();
+                }
+                let new_node_idx = add_node(
+                    &mut nodes_data,
+                    QueryNodeData::Term(LocatedQueryTermSubset {
+                        term_subset,
+                        positions: term.positions.clone(),
+                        term_ids: term_idx as u8..=term_idx as u8,
+                    }),
+                );
+                new_nodes.push(new_node_idx);
+            }
 
             if !prev1.is_empty() {
-                if let Some(ngram) =
-                    query_term::make_ngram(ctx, &terms[term_idx - 1..=term_idx], &nbr_typos)?
+                if let Some((ngram, mandatory)) =
+                    make_clauses_ngram(ctx, &clauses[term_idx - 1..=term_idx], &nbr_typos)?
                 {
                     new_located_query_terms.push(ngram.clone());
+                    let mut term_subset = QueryTermSubset::full(ngram.value);
+                    if mandatory {
+                        term_subset.make_mandatory();
+                    }
                     let ngram_idx = add_node(
                         &mut nodes_data,
                         QueryNodeData::Term(LocatedQueryTermSubset {
-                            term_subset: QueryTermSubset::full(ngram.value),
+                            term_subset,
                             positions: ngram.positions,
                             term_ids: term_idx as u8 - 1..=term_idx as u8,
                         }),
@@ -140,14 +156,18 @@ impl QueryGraph {
                 }
             }
             if !prev2.is_empty() {
-                if let Some(ngram) =
-                    query_term::make_ngram(ctx, &terms[term_idx - 2..=term_idx], &nbr_typos)?
+                if let Some((ngram, mandatory)) =
+                    make_clauses_ngram(ctx, &clauses[term_idx - 2..=term_idx], &nbr_typos)?
                 {
                     new_located_query_terms.push(ngram.clone());
+                    let mut term_subset = QueryTermSubset::full(ngram.value);
+                    if mandatory {
+                        term_subset.make_mandatory();
+                    }
                     let ngram_idx = add_node(
                         &mut nodes_data,
                         QueryNodeData::Term(LocatedQueryTermSubset {
-                            term_subset: QueryTermSubset::full(ngram.value),
+                            term_subset,
                             positions: ngram.positions,
                             term_ids: term_idx as u8 - 2..=term_idx as u8,
                         }),
@@ -414,6 +434,27 @@ impl QueryGraph {
     }
 }
 
+/// Returns the ngram of the clauses if they are each made of a single term searched in all
+/// the fields, along with whether it is mandatory, i.e. all the clauses are required.
+fn make_clauses_ngram(
+    ctx: &mut SearchContext<'_>,
+    clauses: &[QueryClause],
+    number_of_typos_allowed: &impl Fn(&str) -> u8,
+) -> Result<Option<(LocatedQueryTerm, bool)>> {
+    let mut terms = Vec::with_capacity(clauses.len());
+    for clause in clauses {
+        match clause.alternatives.as_slice() {
+            [term] if ctx.term_interner.get(term.value).restricted_fids().is_none() => {
+                terms.push(term.clone())
+            }
+            _ => return Ok(None),
+        }
+    }
+    let mandatory = clauses.iter().all(|clause| clause.required);
+    let ngram = query_term::make_ngram(ctx, &terms, number_of_typos_allowed)?;
+    Ok(ngram.map(|ngram| (ngram, mandatory)))
+}
+
 fn add_node(nodes_data: &mut Vec<QueryNodeData>, node_data: QueryNodeData) -> u16 {
     let new_node_idx = nodes_data.len() as u16;
     nodes_data.push(node_data);
@@ -458,8 +499,15 @@ impl QueryGraph {
                 if let Some(prev_dest_term) = prev_dest_term.take() {
                     if let Some(mut start_term) = start_term {
                         if start_term.term_ids == prev_dest_term.term_ids {
-                            start_term.term_subset.intersect(&prev_dest_term.term_subset);
-                            processed_path.push(start_term);
+                            if start_term.term_subset.has_same_original(&prev_dest_term.term_subset)
+                            {
+                                start_term.term_subset.intersect(&prev_dest_term.term_subset);
+                                processed_path.push(start_term);
+                            } else {
+                                // The fuzzy phrase was matched with a smaller slop, which is
+                                // a subset of the phrase matched with its own slop.
+                                processed_path.push(prev_dest_term);
+                            }
                         } else {
                             processed_path.push(prev_dest_term);
                             processed_path.push(start_term);
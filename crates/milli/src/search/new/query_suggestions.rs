@@ -0,0 +1,159 @@
+use std::cmp::Reverse;
+
+use roaring::RoaringBitmap;
+
+use super::interner::Interned;
+use super::query_term::{LocatedQueryTerm, QueryTerm, QueryTermSubset};
+use super::resolve_query_graph::compute_query_term_subset_docids;
+use super::{limits, SearchContext, Word};
+use crate::phonetic::is_phonetic_code;
+use crate::stemming::is_stem;
+use crate::Result;
+
+/// A correction of the query of a search, in which the words barely found in the index
+/// are replaced by words a few typos away from them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuerySuggestion {
+    pub query: String,
+    /// The number of documents containing all the words of the suggested query.
+    pub estimated_hits: u64,
+}
+
+/// A word of the query along with the words it could be corrected to.
+struct CorrectedWord {
+    original: String,
+    /// The corrections along with the documents containing them, the best first.
+    corrections: Vec<(Interned<String>, RoaringBitmap)>,
+}
+
+/// Computes the suggested corrections of the query, the best one first.
+///
+/// A word of the query is corrected when it is not found in the documents of the universe,
+/// or when one of its typo derivations is found in many more of them. There are no
+/// suggestions when all the words are matched well enough without typos.
+pub fn compute_query_suggestions(
+    ctx: &mut SearchContext<'_>,
+    query: &str,
+    located_query_terms: &[LocatedQueryTerm],
+    universe: &RoaringBitmap,
+) -> Result<Vec<QuerySuggestion>> {
+    let mut corrected_words = Vec::new();
+    // the documents containing all the words which are not corrected
+    let mut kept_docids = universe.clone();
+
+    for located_term in located_query_terms {
+        // the ngrams are alternatives to the words composing them
+        if located_term.positions.start() != located_term.positions.end() {
+            continue;
+        }
+
+        let mut exact_subset = QueryTermSubset::full(located_term.value);
+        exact_subset.clear_one_typo_subset();
+        exact_subset.clear_two_typo_subset();
+        exact_subset.clear_stem_subset();
+        exact_subset.clear_phonetic_subset();
+        exact_subset.clear_infix_subset();
+        let exact_docids = compute_query_term_subset_docids(ctx, Some(universe), &exact_subset)?;
+
+        let term = ctx.term_interner.get(located_term.value);
+        let corrections = if term.original_phrase().is_some() {
+            Vec::new()
+        } else {
+            word_corrections(ctx, located_term.value, universe, exact_docids.len())?
+        };
+
+        if !corrections.is_empty() {
+            let original = ctx.term_interner.get(located_term.value).original_word(ctx);
+            corrected_words.push(CorrectedWord { original, corrections });
+        } else if !exact_docids.is_empty() {
+            // a word matching no document would be removed by the terms matching strategy
+            kept_docids &= exact_docids;
+        }
+    }
+
+    if corrected_words.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // the best correction of every word, then each other correction of a word in turn
+    let mut choices = vec![vec![0; corrected_words.len()]];
+    for (index, word) in corrected_words.iter().enumerate() {
+        for rank in 1..word.corrections.len() {
+            let mut choice = vec![0; corrected_words.len()];
+            choice[index] = rank;
+            choices.push(choice);
+        }
+    }
+
+    // the words are located with the tokenizer of the search, to match the query terms
+    let query_words = ctx.query_words.take().unwrap_or_default();
+
+    let mut suggestions: Vec<QuerySuggestion> = Vec::new();
+    for choice in choices {
+        let mut docids = kept_docids.clone();
+        for (word, &rank) in corrected_words.iter().zip(&choice) {
+            docids &= &word.corrections[rank].1;
+        }
+        if docids.is_empty() {
+            continue;
+        }
+
+        // replace the corrected words in the query to keep its operators and separators
+        let mut suggested_query = String::with_capacity(query.len());
+        let mut last_byte = 0;
+        for (lemma, bytes) in &query_words {
+            let Some((word, &rank)) =
+                corrected_words.iter().zip(&choice).find(|(word, _)| word.original == *lemma)
+            else {
+                continue;
+            };
+            suggested_query.push_str(&query[last_byte..bytes.start]);
+            suggested_query.push_str(ctx.word_interner.get(word.corrections[rank].0));
+            last_byte = bytes.end;
+        }
+        suggested_query.push_str(&query[last_byte..]);
+
+        if suggested_query != query && suggestions.iter().all(|s| s.query != suggested_query) {
+            suggestions
+                .push(QuerySuggestion { query: suggested_query, estimated_hits: docids.len() });
+        }
+    }
+
+    // the alternatives are ranked by the number of documents they would match
+    if let Some((_best, alternatives)) = suggestions.split_first_mut() {
+        alternatives.sort_by_key(|suggestion| Reverse(suggestion.estimated_hits));
+    }
+    suggestions.truncate(limits::MAX_QUERY_SUGGESTION_COUNT);
+
+    Ok(suggestions)
+}
+
+/// Returns the typo derivations of the term found in many more documents than the term
+/// itself, along with these documents, the ones with the least typos and the most documents first.
+fn word_corrections(
+    ctx: &mut SearchContext<'_>,
+    term: Interned<QueryTerm>,
+    universe: &RoaringBitmap,
+    exact_docids_len: u64,
+) -> Result<Vec<(Interned<String>, RoaringBitmap)>> {
+    let min_docids_len = exact_docids_len.saturating_mul(limits::MIN_CORRECTION_FREQUENCY_RATIO);
+
+    let mut corrections = Vec::new();
+    for (word, nbr_typos) in QueryTermSubset::full(term).typo_derivations(ctx)? {
+        let word_str = ctx.word_interner.get(word);
+        if is_stem(word_str) || is_phonetic_code(word_str) {
+            continue;
+        }
+        let Some(docids) = ctx.word_docids(Some(universe), Word::Derived(word))? else {
+            continue;
+        };
+        if !docids.is_empty() && docids.len() > min_docids_len {
+            corrections.push((nbr_typos, word, docids));
+        }
+    }
+
+    corrections.sort_by_key(|(nbr_typos, _, docids)| (*nbr_typos, Reverse(docids.len())));
+    corrections.truncate(limits::MAX_WORD_CORRECTION_COUNT);
+
+    Ok(corrections.into_iter().map(|(_, word, docids)| (word, docids)).collect())
+}
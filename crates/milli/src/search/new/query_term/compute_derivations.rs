@@ -9,11 +9,13 @@ use heed::types::DecodeIgnore;
 use itertools::{merge_join_by, EitherOrBoth};
 
 use super::{OneTypoTerm, Phrase, QueryTerm, ZeroTypoTerm};
+use crate::phonetic::is_phonetic_code;
 use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
+use crate::stemming::is_stem;
 use crate::{Result, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -131,6 +133,11 @@ fn find_one_two_typo_derivations(
 
     while let Some((derived_word, state)) = stream.next() {
         let derived_word = std::str::from_utf8(derived_word)?;
+        // the stems and phonetic codes are only one typo away from the words they are derived
+        // from, because of their prefix, but they are matched on their own
+        if is_stem(derived_word) || is_phonetic_code(derived_word) {
+            continue;
+        }
         let derived_word_interned = word_interner.insert(derived_word.to_owned());
         // in the case the typo is on the first letter, we know the number of typo
         // is two
@@ -164,6 +171,56 @@ fn find_one_two_typo_derivations(
     Ok(())
 }
 
+/// Returns the words derived with up to `max_typo` typos from a word of a fuzzy phrase,
+/// the ones with a single typo first.
+pub fn phrase_word_typo_derivations(
+    ctx: &mut SearchContext<'_>,
+    word: Interned<String>,
+    max_typo: u8,
+) -> Result<Vec<Interned<String>>> {
+    let mut one_typo = Vec::new();
+    let mut two_typos = Vec::new();
+
+    match max_typo {
+        0 => (),
+        1 => find_one_typo_derivations(ctx, word, false, |derived_word| {
+            one_typo.push(derived_word);
+            if one_typo.len() < limits::MAX_PHRASE_TYPO_COUNT {
+                Ok(ControlFlow::Continue(()))
+            } else {
+                Ok(ControlFlow::Break(()))
+            }
+        })?,
+        _ => {
+            let fst = ctx.get_words_fst()?;
+            find_one_two_typo_derivations(
+                word,
+                false,
+                fst,
+                &mut ctx.word_interner,
+                |derived_word, nbr_typos| {
+                    match nbr_typos {
+                        NumberOfTypos::One => one_typo.push(derived_word),
+                        NumberOfTypos::Two if two_typos.len() < limits::MAX_PHRASE_TYPO_COUNT => {
+                            two_typos.push(derived_word)
+                        }
+                        NumberOfTypos::Two => (),
+                    }
+                    if one_typo.len() < limits::MAX_PHRASE_TYPO_COUNT {
+                        Ok(ControlFlow::Continue(()))
+                    } else {
+                        Ok(ControlFlow::Break(()))
+                    }
+                },
+            )?
+        }
+    }
+
+    one_typo.extend(two_typos);
+    one_typo.truncate(limits::MAX_PHRASE_TYPO_COUNT);
+    Ok(one_typo)
+}
+
 pub fn partially_initialized_term_from_word(
     ctx: &mut SearchContext<'_>,
     word: &str,
@@ -183,6 +240,10 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                stems: BTreeSet::new(),
+                phonetic: BTreeSet::new(),
+                infix_of: BTreeSet::new(),
+                restricted_fids: None,
             }
         });
     }
@@ -220,12 +281,33 @@ pub fn partially_initialized_term_from_word(
             }
         })?;
     }
-    let synonyms = ctx.index.synonyms(ctx.txn)?;
+    // the stems, phonetic codes and trigrams are only indexed for the words of the documents,
+    // not their concatenations
+    let mut stems = BTreeSet::new();
+    let mut phonetic = BTreeSet::new();
+    let mut infix_of = BTreeSet::new();
+    if !is_ngram {
+        for stem in ctx.stems(word)? {
+            if ctx.index.contains_word(ctx.txn, &stem)? {
+                stems.insert(ctx.word_interner.insert(stem));
+            }
+        }
+        for code in ctx.phonetic_codes(word) {
+            if ctx.index.contains_word(ctx.txn, &code)? {
+                phonetic.insert(ctx.word_interner.insert(code));
+            }
+        }
+        // the words matching the original word or starting with it are already derived
+        for infix_word in ctx.infix_words(word) {
+            if infix_word != word && !(is_prefix && infix_word.starts_with(word)) {
+                infix_of.insert(ctx.word_interner.insert(infix_word));
+            }
+        }
+    }
+
+    let synonyms = ctx.synonyms(&[word.to_owned()])?;
     let mut synonym_word_count = 0;
     let synonyms = synonyms
-        .get(&vec![word.to_owned()])
-        .cloned()
-        .unwrap_or_default()
         .into_iter()
         .take(limits::MAX_SYNONYM_PHRASE_COUNT)
         .filter_map(|words| {
@@ -234,7 +316,7 @@ pub fn partially_initialized_term_from_word(
             }
             synonym_word_count += words.len();
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
+            Some(ctx.phrase_interner.insert(Phrase { words, ..Default::default() }))
         })
         .collect();
     let zero_typo =
@@ -248,12 +330,17 @@ pub fn partially_initialized_term_from_word(
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        stems,
+        phonetic,
+        infix_of,
+        restricted_fids: None,
     })
 }
 
 fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
-        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
+        let words = vec![Some(l), Some(r)];
+        Ok(Some(ctx.phrase_interner.insert(Phrase { words, ..Default::default() })))
     } else {
         Ok(None)
     }
@@ -306,7 +393,7 @@ impl Interned<QueryTerm> {
         let split_words = if let Some((ngram_words, split_words)) =
             self_mut.ngram_words.as_ref().zip(split_words.as_ref())
         {
-            let Phrase { words } = ctx.phrase_interner.get(*split_words);
+            let Phrase { words, .. } = ctx.phrase_interner.get(*split_words);
             if ngram_words.iter().ne(words.iter().flatten()) {
                 Some(*split_words)
             } else {
@@ -1,14 +1,18 @@
 mod compute_derivations;
 mod ntypo_subset;
+mod parse_advanced_query;
 mod parse_query;
 mod phrase;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter::FromIterator;
 use std::ops::RangeInclusive;
 
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
+pub use parse_advanced_query::{
+    located_query_clauses_from_advanced_query, ExtractedClauses, QueryClause,
+};
 pub use parse_query::{
     located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
 };
@@ -16,7 +20,7 @@ pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
-use crate::Result;
+use crate::{FieldId, Result};
 
 /// A set of word derivations attached to a location in the search query.
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -32,6 +36,9 @@ pub struct QueryTermSubset {
     zero_typo_subset: NTypoTermSubset,
     one_typo_subset: NTypoTermSubset,
     two_typo_subset: NTypoTermSubset,
+    stem_subset: NTypoTermSubset,
+    phonetic_subset: NTypoTermSubset,
+    infix_subset: NTypoTermSubset,
     /// `true` if the term cannot be deleted through the term matching strategy
     ///
     /// Note that there are other reasons for which a term cannot be deleted, such as
@@ -51,6 +58,15 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// The stems of the original word found in the stemmed attributes
+    stems: BTreeSet<Interned<String>>,
+    /// The phonetic codes of the original word found in the phonetic attributes
+    phonetic: BTreeSet<Interned<String>>,
+    /// The words of the infix attributes containing the original word, other than the
+    /// original word itself and the words it is a prefix of
+    infix_of: BTreeSet<Interned<String>>,
+    /// The fields the term is restricted to by an `attribute:` clause of an advanced query
+    restricted_fids: Option<Vec<FieldId>>,
 }
 
 // SubTerms will be in a dedup interner
@@ -148,6 +164,9 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::Nothing,
             one_typo_subset: NTypoTermSubset::Nothing,
             two_typo_subset: NTypoTermSubset::Nothing,
+            stem_subset: NTypoTermSubset::Nothing,
+            phonetic_subset: NTypoTermSubset::Nothing,
+            infix_subset: NTypoTermSubset::Nothing,
             mandatory: false,
         }
     }
@@ -157,6 +176,9 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::All,
             one_typo_subset: NTypoTermSubset::All,
             two_typo_subset: NTypoTermSubset::All,
+            stem_subset: NTypoTermSubset::All,
+            phonetic_subset: NTypoTermSubset::All,
+            infix_subset: NTypoTermSubset::All,
             mandatory: false,
         }
     }
@@ -166,12 +188,23 @@ impl QueryTermSubset {
         self.zero_typo_subset.union(&other.zero_typo_subset);
         self.one_typo_subset.union(&other.one_typo_subset);
         self.two_typo_subset.union(&other.two_typo_subset);
+        self.stem_subset.union(&other.stem_subset);
+        self.phonetic_subset.union(&other.phonetic_subset);
+        self.infix_subset.union(&other.infix_subset);
+    }
+    /// Returns `true` if both subsets are subsets of the same term, which isn't the case of
+    /// the subsets of a fuzzy phrase with a smaller slop, see [`Self::phrase_slop_subsets`].
+    pub fn has_same_original(&self, other: &Self) -> bool {
+        self.original == other.original
     }
     pub fn intersect(&mut self, other: &Self) {
         assert!(self.original == other.original);
         self.zero_typo_subset.intersect(&other.zero_typo_subset);
         self.one_typo_subset.intersect(&other.one_typo_subset);
         self.two_typo_subset.intersect(&other.two_typo_subset);
+        self.stem_subset.intersect(&other.stem_subset);
+        self.phonetic_subset.intersect(&other.phonetic_subset);
+        self.infix_subset.intersect(&other.infix_subset);
     }
 
     pub fn use_prefix_db(&self, ctx: &SearchContext<'_>) -> Option<Word> {
@@ -288,6 +321,62 @@ impl QueryTermSubset {
             NTypoTermSubset::Nothing => {}
         };
 
+        match &self.stem_subset {
+            NTypoTermSubset::All => {
+                result.extend(original.stems.iter().copied().map(Word::Derived));
+            }
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                result.extend(original.stems.intersection(words).copied().map(Word::Derived));
+            }
+            NTypoTermSubset::Nothing => {}
+        };
+
+        match &self.phonetic_subset {
+            NTypoTermSubset::All => {
+                result.extend(original.phonetic.iter().copied().map(Word::Derived));
+            }
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                result.extend(original.phonetic.intersection(words).copied().map(Word::Derived));
+            }
+            NTypoTermSubset::Nothing => {}
+        };
+
+        result.extend(self.infix_words(ctx).into_iter().map(Word::Derived));
+
+        Ok(result)
+    }
+    /// Returns the words of the subset derived from the original word with one or two typos,
+    /// along with their number of typos, except the words also matching it without typos.
+    pub fn typo_derivations(
+        &self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<BTreeMap<Interned<String>, u8>> {
+        let mut result = BTreeMap::new();
+        if self.one_typo_subset.is_empty() && self.two_typo_subset.is_empty() {
+            return Ok(result);
+        }
+        self.original.compute_fully_if_needed(ctx)?;
+
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo else {
+            panic!()
+        };
+        let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo else { panic!() };
+        // a word derived with one and two typos costs one typo
+        for (nbr_typos, subset, words) in
+            [(2, &self.two_typo_subset, two_typos), (1, &self.one_typo_subset, one_typo)]
+        {
+            let words = words.iter().copied().filter(|word| subset.contains_word(*word));
+            result.extend(words.map(|word| (word, nbr_typos)));
+        }
+
+        let ZeroTypoTerm { exact, prefix_of, .. } = &original.zero_typo;
+        for word in exact.iter().chain(prefix_of) {
+            if self.zero_typo_subset.contains_word(*word) {
+                result.remove(word);
+            }
+        }
+
         Ok(result)
     }
     pub fn all_phrases(&self, ctx: &mut SearchContext<'_>) -> Result<BTreeSet<Interned<Phrase>>> {
@@ -337,6 +426,25 @@ impl QueryTermSubset {
         }
         None
     }
+    /// Returns the subsets matching the phrase of a fuzzy phrase term with each slop up to
+    /// its own, the tightest first, or only this subset if the term isn't such a phrase.
+    pub fn phrase_slop_subsets(&self, ctx: &mut SearchContext<'_>) -> Vec<QueryTermSubset> {
+        let Some(phrase) = self.original_phrase(ctx) else {
+            return vec![self.clone()];
+        };
+        let phrase = ctx.phrase_interner.get(phrase).clone();
+        let mut subsets = Vec::with_capacity(phrase.slop as usize + 1);
+        for slop in 0..phrase.slop {
+            let tighter_phrase = ctx.phrase_interner.insert(Phrase { slop, ..phrase.clone() });
+            let mut term = ctx.term_interner.get(self.original).clone();
+            term.zero_typo.phrase = Some(tighter_phrase);
+            let term = ctx.term_interner.push(term);
+            subsets
+                .push(QueryTermSubset { mandatory: self.mandatory, ..QueryTermSubset::full(term) });
+        }
+        subsets.push(self.clone());
+        subsets
+    }
     pub fn max_typo_cost(&self, ctx: &SearchContext<'_>) -> u8 {
         let t = ctx.term_interner.get(self.original);
         match t.max_levenshtein_distance {
@@ -368,6 +476,43 @@ impl QueryTermSubset {
             _ => panic!(),
         }
     }
+    /// Returns `true` if the subset contains stems of the original word.
+    pub fn has_stems(&self, ctx: &SearchContext<'_>) -> bool {
+        let t = ctx.term_interner.get(self.original);
+        match &self.stem_subset {
+            NTypoTermSubset::All => !t.stems.is_empty(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                t.stems.intersection(words).next().is_some()
+            }
+            NTypoTermSubset::Nothing => false,
+        }
+    }
+    /// Returns `true` if the subset contains phonetic codes of the original word.
+    pub fn has_phonetic_codes(&self, ctx: &SearchContext<'_>) -> bool {
+        let t = ctx.term_interner.get(self.original);
+        match &self.phonetic_subset {
+            NTypoTermSubset::All => !t.phonetic.is_empty(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                t.phonetic.intersection(words).next().is_some()
+            }
+            NTypoTermSubset::Nothing => false,
+        }
+    }
+    /// Returns the fields the term is restricted to, if it only matches in some fields.
+    pub fn restricted_fids(&self, ctx: &SearchContext<'_>) -> Option<Vec<FieldId>> {
+        ctx.term_interner.get(self.original).restricted_fids.clone()
+    }
+    /// Returns the words of the infix attributes of the subset containing the original word.
+    pub fn infix_words(&self, ctx: &SearchContext<'_>) -> BTreeSet<Interned<String>> {
+        let t = ctx.term_interner.get(self.original);
+        match &self.infix_subset {
+            NTypoTermSubset::All => t.infix_of.clone(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                t.infix_of.intersection(words).copied().collect()
+            }
+            NTypoTermSubset::Nothing => BTreeSet::new(),
+        }
+    }
     pub fn keep_only_exact_term(&mut self, ctx: &SearchContext<'_>) {
         if let Some(term) = self.exact_term(ctx) {
             match term {
@@ -378,6 +523,9 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_stem_subset();
+                    self.clear_phonetic_subset();
+                    self.clear_infix_subset();
                 }
                 ExactTerm::Word(w) => {
                     self.zero_typo_subset = NTypoTermSubset::Subset {
@@ -386,6 +534,9 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_stem_subset();
+                    self.clear_phonetic_subset();
+                    self.clear_infix_subset();
                 }
             }
         }
@@ -399,6 +550,15 @@ impl QueryTermSubset {
     pub fn clear_two_typo_subset(&mut self) {
         self.two_typo_subset = NTypoTermSubset::Nothing;
     }
+    pub fn clear_stem_subset(&mut self) {
+        self.stem_subset = NTypoTermSubset::Nothing;
+    }
+    pub fn clear_phonetic_subset(&mut self) {
+        self.phonetic_subset = NTypoTermSubset::Nothing;
+    }
+    pub fn clear_infix_subset(&mut self) {
+        self.infix_subset = NTypoTermSubset::Nothing;
+    }
     pub fn description(&self, ctx: &SearchContext<'_>) -> String {
         let t = ctx.term_interner.get(self.original);
         ctx.word_interner.get(t.original).to_owned()
@@ -437,7 +597,12 @@ impl QueryTerm {
             return false;
         };
 
-        self.zero_typo.is_empty() && one_typo.is_empty() && two_typo.is_empty()
+        self.zero_typo.is_empty()
+            && one_typo.is_empty()
+            && two_typo.is_empty()
+            && self.stems.is_empty()
+            && self.phonetic.is_empty()
+            && self.infix_of.is_empty()
     }
     fn allows_split_words(&self) -> bool {
         self.zero_typo.phrase.is_none()
@@ -477,6 +642,9 @@ impl QueryTerm {
     pub fn is_prefix(&self) -> bool {
         self.is_prefix
     }
+    pub fn restricted_fids(&self) -> Option<&[FieldId]> {
+        self.restricted_fids.as_deref()
+    }
     pub fn original_word(&self, ctx: &SearchContext<'_>) -> String {
         ctx.word_interner.get(self.original).clone()
     }
@@ -493,6 +661,7 @@ impl QueryTerm {
             &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
+        words.extend(self.stems.iter().copied());
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
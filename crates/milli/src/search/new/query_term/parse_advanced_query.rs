@@ -0,0 +1,469 @@
+use std::ops::Range;
+
+use charabia::{Token, TokenKind, Tokenizer};
+
+use super::compute_derivations::partially_initialized_term_from_word;
+use super::parse_query::{number_of_typos_allowed, PhraseBuilder};
+use super::phrase::parse_phrase_operator;
+use super::LocatedQueryTerm;
+use crate::search::new::Word;
+use crate::{FieldId, Result, SearchContext};
+
+/// A location of the query matched by any of its alternative terms.
+#[derive(Clone)]
+pub struct QueryClause {
+    pub alternatives: Vec<LocatedQueryTerm>,
+    /// `true` if the clause cannot be removed by the terms matching strategy.
+    pub required: bool,
+}
+
+impl QueryClause {
+    /// A clause made of a single term that can be removed by the terms matching strategy.
+    pub fn optional(term: LocatedQueryTerm) -> Self {
+        Self { alternatives: vec![term], required: false }
+    }
+}
+
+/// Extraction of the content of a query written with the advanced syntax.
+pub struct ExtractedClauses {
+    /// The clauses to search for in the database.
+    pub clauses: Vec<QueryClause>,
+    /// The words that must not appear in the results.
+    pub negative_words: Vec<Word>,
+    /// The phrases that must not appear in the results, possibly in some fields only.
+    pub negative_phrases: Vec<LocatedQueryTerm>,
+}
+
+/// Convert a query written with the advanced syntax into a list of located query clauses.
+///
+/// In this syntax:
+/// - `+word` is a required clause, never removed by the terms matching strategy,
+/// - `-word` is an excluded clause, the documents containing it are removed from the results,
+/// - `a OR b` and `(a OR b)` are alternatives matched at the same location of the query,
+/// - `attribute:word` only matches the word in the fields of the attribute,
+/// - `"a phrase"` is matched as a phrase, and the operators apply to quotes and parentheses,
+/// - `"a phrase"~N` is a fuzzy phrase, allowing up to `N` words between its words and typos.
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+pub fn located_query_clauses_from_advanced_query(
+    ctx: &mut SearchContext<'_>,
+    tokenizer: &Tokenizer<'_>,
+    query: &str,
+    words_limit: Option<usize>,
+) -> Result<ExtractedClauses> {
+    let nbr_typos = number_of_typos_allowed(ctx)?;
+    let allow_prefix_search = ctx.is_prefix_search_allowed();
+    let clauses_limit = words_limit.unwrap_or(usize::MAX);
+
+    let mut clauses = Vec::new();
+    let mut negative_words = Vec::new();
+    let mut negative_phrases = Vec::new();
+    let mut position = 0u16;
+
+    for clause in parse(query) {
+        if clauses.len() >= clauses_limit {
+            break;
+        }
+
+        let fids = match clause.attribute {
+            Some(attribute) => Some(ctx.fields_ids_of_attribute(attribute)?),
+            None => None,
+        };
+        let required = clause.occurrence == Occurrence::Required;
+        let excluded = clause.occurrence == Occurrence::Excluded;
+
+        // the words of an unquoted text are as many clauses, like in a simple query
+        if let [alternative @ Alternative { quoted: false, .. }] = clause.alternatives.as_slice() {
+            if !excluded {
+                let tokens = words(tokenizer, alternative.text);
+                for (i, token) in tokens.iter().enumerate() {
+                    let term_position = position;
+                    position = position.saturating_add(1);
+                    let is_prefix = allow_prefix_search
+                        && i + 1 == tokens.len()
+                        && alternative.end == query.len();
+                    if (matches!(token.kind, TokenKind::StopWord) && !is_prefix)
+                        || clauses.len() >= clauses_limit
+                    {
+                        continue;
+                    }
+                    let word = token.lemma();
+                    let term = partially_initialized_term_from_word(
+                        ctx,
+                        word,
+                        nbr_typos(word),
+                        is_prefix,
+                        false,
+                    )?;
+                    let term = LocatedQueryTerm {
+                        value: ctx.term_interner.push(term),
+                        positions: term_position..=term_position,
+                    };
+                    restrict_to_fields(ctx, &term, fids.as_deref());
+                    clauses.push(QueryClause { alternatives: vec![term], required });
+                }
+                continue;
+            }
+        }
+
+        // the alternatives of a clause all start at its position
+        let mut alternatives = Vec::new();
+        let mut width = 1;
+        for alternative in &clause.alternatives {
+            let tokens = words(tokenizer, alternative.text);
+            let term = match tokens.as_slice() {
+                [] => continue,
+                [token] if !alternative.quoted && excluded && fids.is_none() => {
+                    let word = ctx.word_interner.insert(token.lemma().to_string());
+                    negative_words.push(Word::Original(word));
+                    continue;
+                }
+                [token] if !alternative.quoted && !excluded => {
+                    if matches!(token.kind, TokenKind::StopWord) {
+                        continue;
+                    }
+                    let word = token.lemma();
+                    let term = partially_initialized_term_from_word(
+                        ctx,
+                        word,
+                        nbr_typos(word),
+                        false,
+                        false,
+                    )?;
+                    LocatedQueryTerm {
+                        value: ctx.term_interner.push(term),
+                        positions: position..=position,
+                    }
+                }
+                tokens => {
+                    let mut phrase = PhraseBuilder::empty();
+                    for (i, token) in tokens.iter().enumerate() {
+                        phrase.push_word(ctx, token, position.saturating_add(i as u16));
+                    }
+                    width = width.max(tokens.len() as u16);
+                    if let Some(slop) = alternative.slop {
+                        phrase.fuzzy(slop);
+                    }
+                    match phrase.build(ctx)? {
+                        Some(term) => term,
+                        None => continue,
+                    }
+                }
+            };
+            restrict_to_fields(ctx, &term, fids.as_deref());
+            alternatives.push(term);
+        }
+        position = position.saturating_add(width);
+
+        if alternatives.is_empty() {
+            continue;
+        }
+        if excluded {
+            negative_phrases.extend(alternatives);
+        } else {
+            clauses.push(QueryClause { alternatives, required });
+        }
+    }
+
+    Ok(ExtractedClauses { clauses, negative_words, negative_phrases })
+}
+
+/// Returns the word and stop word tokens of the text.
+fn words<'o>(tokenizer: &Tokenizer<'_>, text: &'o str) -> Vec<Token<'o>> {
+    tokenizer
+        .tokenize(text)
+        .filter(|token| {
+            matches!(token.kind, TokenKind::Word | TokenKind::StopWord) && !token.lemma().is_empty()
+        })
+        .take(super::limits::MAX_TOKEN_COUNT)
+        .collect()
+}
+
+fn restrict_to_fields(
+    ctx: &mut SearchContext<'_>,
+    term: &LocatedQueryTerm,
+    fids: Option<&[FieldId]>,
+) {
+    if let Some(fids) = fids {
+        ctx.term_interner.get_mut(term.value).restricted_fids = Some(fids.to_vec());
+    }
+}
+
+/// How a clause of an advanced query must be matched by the documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Occurrence {
+    /// The clause can be removed by the terms matching strategy.
+    Optional,
+    /// The clause is prefixed with `+`, it must be matched.
+    Required,
+    /// The clause is prefixed with `-`, it must not be matched.
+    Excluded,
+}
+
+/// A clause of an advanced query, before the tokenization of its text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Clause<'q> {
+    occurrence: Occurrence,
+    /// The attribute the clause is restricted to, e.g. `title` in `title:invoice`.
+    attribute: Option<&'q str>,
+    /// The alternatives separated by `OR`.
+    alternatives: Vec<Alternative<'q>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Alternative<'q> {
+    text: &'q str,
+    /// `true` if the text is quoted and must be matched as a phrase.
+    quoted: bool,
+    /// The slop of a fuzzy phrase, e.g. `2` in `"hello world"~2`.
+    slop: Option<u8>,
+    /// The byte offset of the end of the alternative in the query, the last word of a query
+    /// is a prefix if nothing follows it.
+    end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lexeme<'q> {
+    Open,
+    Close,
+    /// A quoted text and the slop of the fuzzy phrase it is, if followed by `~N`.
+    Quoted(&'q str, Option<u8>),
+    Word(&'q str),
+}
+
+/// Splits the query into parentheses, quoted texts and whitespace separated words,
+/// an unclosed quote extends to the end of the query.
+fn lex(query: &str) -> Vec<(Lexeme<'_>, Range<usize>)> {
+    let mut lexemes = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let (lexeme, end) = match c {
+            c if c.is_whitespace() => continue,
+            '(' => (Lexeme::Open, start + 1),
+            ')' => (Lexeme::Close, start + 1),
+            '"' => match query[start + 1..].find('"') {
+                Some(len) => {
+                    let text = &query[start + 1..start + 1 + len];
+                    let end = start + len + 2;
+                    match parse_phrase_operator(&query[end..]) {
+                        Some((slop, operator_len)) => {
+                            (Lexeme::Quoted(text, Some(slop)), end + operator_len)
+                        }
+                        None => (Lexeme::Quoted(text, None), end),
+                    }
+                }
+                None => (Lexeme::Quoted(&query[start + 1..], None), query.len()),
+            },
+            _ => {
+                let len = query[start..]
+                    .find(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '"'))
+                    .unwrap_or(query.len() - start);
+                (Lexeme::Word(&query[start..start + len]), start + len)
+            }
+        };
+        while chars.next_if(|&(i, _)| i < end).is_some() {}
+        lexemes.push((lexeme, start..end));
+    }
+    lexemes
+}
+
+/// Parses the clauses of the query, the operators are never rejected: a dangling
+/// operator is ignored and an unclosed group extends to the end of the query.
+fn parse(query: &str) -> Vec<Clause<'_>> {
+    let lexemes = lex(query);
+    let mut clauses: Vec<Clause<'_>> = Vec::new();
+    let mut after_or = false;
+    let mut i = 0;
+    while let Some((lexeme, range)) = lexemes.get(i) {
+        let (occurrence, attribute, alternatives) = match *lexeme {
+            Lexeme::Word("OR") if !clauses.is_empty() => {
+                i += 1;
+                after_or = true;
+                continue;
+            }
+            Lexeme::Close => {
+                i += 1;
+                continue;
+            }
+            Lexeme::Word(word) => {
+                i += 1;
+                let (occurrence, rest) = match word.as_bytes()[0] {
+                    b'+' => (Occurrence::Required, &word[1..]),
+                    b'-' => (Occurrence::Excluded, &word[1..]),
+                    _ => (Occurrence::Optional, word),
+                };
+                let (attribute, rest) = match rest.split_once(':') {
+                    Some((attribute, rest)) if !attribute.is_empty() => (Some(attribute), rest),
+                    _ => (None, rest),
+                };
+                let alternatives = if rest.is_empty() {
+                    // the operators apply to the quoted text or the group that follows them
+                    parse_group(query, &lexemes, &mut i)
+                } else {
+                    vec![Alternative { text: rest, quoted: false, slop: None, end: range.end }]
+                };
+                (occurrence, attribute, alternatives)
+            }
+            Lexeme::Open | Lexeme::Quoted(..) => {
+                (Occurrence::Optional, None, parse_group(query, &lexemes, &mut i))
+            }
+        };
+
+        if alternatives.is_empty() {
+            after_or = false;
+            continue;
+        }
+        match clauses.last_mut() {
+            Some(last)
+                if after_or
+                    && occurrence != Occurrence::Excluded
+                    && last.occurrence != Occurrence::Excluded
+                    && attribute == last.attribute =>
+            {
+                last.alternatives.extend(alternatives)
+            }
+            _ => clauses.push(Clause { occurrence, attribute, alternatives }),
+        }
+        after_or = false;
+    }
+    clauses
+}
+
+/// Parses the quoted text or the group of alternatives at `lexemes[*i]`, the alternatives
+/// of a group are separated by `OR` and the parentheses nested in a group are ignored.
+fn parse_group<'q>(
+    query: &'q str,
+    lexemes: &[(Lexeme<'q>, Range<usize>)],
+    i: &mut usize,
+) -> Vec<Alternative<'q>> {
+    match lexemes.get(*i) {
+        Some((Lexeme::Quoted(text, slop), range)) => {
+            *i += 1;
+            vec![Alternative { text, quoted: true, slop: *slop, end: range.end }]
+        }
+        Some((Lexeme::Open, _)) => {
+            *i += 1;
+            let mut alternatives = Vec::new();
+            let mut parts: Vec<&(Lexeme<'q>, Range<usize>)> = Vec::new();
+            while let Some(lexeme) = lexemes.get(*i) {
+                *i += 1;
+                match lexeme.0 {
+                    Lexeme::Close => break,
+                    Lexeme::Word("OR") => alternatives.extend(group_alternative(query, &parts)),
+                    Lexeme::Open => continue,
+                    Lexeme::Word(_) | Lexeme::Quoted(..) => {
+                        parts.push(lexeme);
+                        continue;
+                    }
+                }
+                parts.clear();
+            }
+            alternatives.extend(group_alternative(query, &parts));
+            alternatives
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the alternative made of the lexemes of a group between two `OR`s, several words
+/// are matched as a phrase.
+fn group_alternative<'q>(
+    query: &'q str,
+    parts: &[&(Lexeme<'q>, Range<usize>)],
+) -> Option<Alternative<'q>> {
+    let ((_, first), (_, last)) = (parts.first()?, parts.last()?);
+    match parts {
+        [(Lexeme::Quoted(text, slop), range)] => {
+            Some(Alternative { text, quoted: true, slop: *slop, end: range.end })
+        }
+        _ => Some(Alternative {
+            text: &query[first.start..last.end],
+            quoted: parts.len() > 1,
+            slop: None,
+            end: last.end,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders the parsed clauses of the query, the alternatives of a clause in parentheses.
+    fn render(query: &str) -> String {
+        let clauses: Vec<_> = parse(query)
+            .into_iter()
+            .map(|Clause { occurrence, attribute, alternatives }| {
+                let occurrence = match occurrence {
+                    Occurrence::Optional => "",
+                    Occurrence::Required => "+",
+                    Occurrence::Excluded => "-",
+                };
+                let attribute =
+                    attribute.map(|attribute| format!("{attribute}:")).unwrap_or_default();
+                let alternatives: Vec<_> = alternatives
+                    .into_iter()
+                    .map(|Alternative { text, quoted, slop, end: _ }| match (quoted, slop) {
+                        (true, Some(slop)) => format!("\"{text}\"~{slop}"),
+                        (true, None) => format!("\"{text}\""),
+                        (false, _) => text.to_string(),
+                    })
+                    .collect();
+                match alternatives.as_slice() {
+                    [alternative] => format!("{occurrence}{attribute}{alternative}"),
+                    _ => format!("{occurrence}{attribute}({})", alternatives.join(" OR ")),
+                }
+            })
+            .collect();
+        clauses.join(" ")
+    }
+
+    #[test]
+    fn parse_operators() {
+        assert_eq!(
+            render("title:invoice +paid -draft (urgent OR asap)"),
+            "title:invoice +paid -draft (urgent OR asap)"
+        );
+        assert_eq!(render("urgent OR asap OR now"), "(urgent OR asap OR now)");
+        assert_eq!(
+            render("+\"hello world\" -title:\"draft\""),
+            "+\"hello world\" -title:\"draft\""
+        );
+        assert_eq!(render("+title:(invoice OR bill)"), "+title:(invoice OR bill)");
+        assert_eq!(render("(\"a b\" OR c d)"), "(\"a b\" OR \"c d\")");
+    }
+
+    #[test]
+    fn parse_dangling_operators() {
+        // an `OR` without left operand is a word, a dangling operator is ignored
+        assert_eq!(render("OR paid + -"), "OR paid");
+        // the alternatives are only merged with a clause of the same attribute
+        assert_eq!(render("title:a OR b -c OR d"), "title:a b -c d");
+        // the unclosed groups and quotes extend to the end of the query
+        assert_eq!(render("(a OR b c"), "(a OR \"b c\")");
+        assert_eq!(render("\"hello world"), "\"hello world\"");
+        // an empty attribute is not an attribute
+        assert_eq!(render(":word"), ":word");
+    }
+
+    #[test]
+    fn parse_fuzzy_phrases() {
+        assert_eq!(render("\"hello world\"~2 today"), "\"hello world\"~2 today");
+        // a `~` without slop only tolerates typos, and the slop is capped
+        assert_eq!(
+            render("+\"hello world\"~ (\"a b\"~9 OR c)"),
+            "+\"hello world\"~0 (\"a b\"~2 OR c)"
+        );
+        // an unclosed quote is never followed by an operator
+        assert_eq!(render("\"hello world~2"), "\"hello world~2\"");
+    }
+
+    #[test]
+    fn parse_ends_of_alternatives() {
+        let ends = |query| -> Vec<_> {
+            parse(query).iter().flat_map(|c| c.alternatives.iter().map(|a| a.end)).collect()
+        };
+        assert_eq!(ends("+paid inv"), [5, 9]);
+        assert_eq!(ends("title:inv "), [9]);
+        assert_eq!(ends("(a OR b)"), [2, 7]);
+    }
+}
@@ -3,7 +3,10 @@ use std::collections::BTreeSet;
 use charabia::normalizer::NormalizedTokenIter;
 use charabia::{SeparatorKind, TokenKind};
 
-use super::compute_derivations::partially_initialized_term_from_word;
+use super::compute_derivations::{
+    partially_initialized_term_from_word, phrase_word_typo_derivations,
+};
+use super::phrase::parse_phrase_operator;
 use super::{LocatedQueryTerm, ZeroTypoTerm};
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
 use crate::search::new::Word;
@@ -117,7 +120,7 @@ pub fn located_query_terms_from_tokens(
                     // If we have a hard separator inside a phrase, we immediately start a new phrase
                     let phrase = if separator_kind == SeparatorKind::Hard {
                         if let Some(phrase) = phrase {
-                            if let Some(located_query_term) = phrase.build(ctx) {
+                            if let Some(located_query_term) = phrase.build(ctx)? {
                                 // as we are evaluating a negative operator we put the phrase
                                 // in the negative one *but* we don't reset the negative operator
                                 // as we are immediately starting a new negative phrase.
@@ -142,10 +145,38 @@ pub fn located_query_terms_from_tokens(
                     }
 
                     // Consume the closing quote and the phrase
-                    if let Some(phrase) = phrase {
+                    if let Some(mut phrase) = phrase {
                         // Per the check above, quote_count > 0
                         quote_count -= 1;
-                        if let Some(located_query_term) = phrase.build(ctx) {
+
+                        // A closing quote followed by `~N` makes the phrase fuzzy,
+                        // `~N` may be a word of its own, or the `~` a separator followed
+                        // by the slop as a word
+                        let lemma = token.lemma();
+                        let mut operator =
+                            lemma[lemma.find('"').map_or(lemma.len(), |i| i + 1)..].to_string();
+                        if operator.is_empty() {
+                            if let Some(token) = peekable.next_if(|token| {
+                                token
+                                    .lemma()
+                                    .strip_prefix('~')
+                                    .is_some_and(|slop| slop.bytes().all(|b| b.is_ascii_digit()))
+                            }) {
+                                operator.push_str(token.lemma());
+                            }
+                        }
+                        if operator == "~" {
+                            if let Some(slop) = peekable.next_if(|token| {
+                                token.is_word() && token.lemma().bytes().all(|b| b.is_ascii_digit())
+                            }) {
+                                operator.push_str(slop.lemma());
+                            }
+                        }
+                        if let Some((slop, _)) = parse_phrase_operator(&operator) {
+                            phrase.fuzzy(slop);
+                        }
+
+                        if let Some(located_query_term) = phrase.build(ctx)? {
                             // we were evaluating a negative operator so we
                             // put the phrase in the negative phrases
                             if negative_phrase {
@@ -178,7 +209,7 @@ pub fn located_query_terms_from_tokens(
 
     // If a quote is never closed, we consider all of the end of the query as a phrase.
     if let Some(phrase) = phrase.take() {
-        if let Some(located_query_term) = phrase.build(ctx) {
+        if let Some(located_query_term) = phrase.build(ctx)? {
             // put the phrase in the negative set if we are evaluating a negative operator.
             if negative_phrase {
                 negative_phrases.push(located_query_term);
@@ -199,12 +230,15 @@ pub fn number_of_typos_allowed<'ctx>(
     let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
 
     let exact_words = ctx.index.exact_words(ctx.txn)?;
+    // the fields may allow more typos than the global settings
+    let typo_budgets = ctx.typo_budgets.clone();
 
     Ok(Box::new(move |word: &str| {
-        if !authorize_typos
-            || word.len() < min_len_one_typo as usize
-            || exact_words.as_ref().is_some_and(|fst| fst.contains(word))
-        {
+        if !authorize_typos || exact_words.as_ref().is_some_and(|fst| fst.contains(word)) {
+            0
+        } else if let Some(typo_budgets) = &typo_budgets {
+            typo_budgets.max_typos(word)
+        } else if word.len() < min_len_one_typo as usize {
             0
         } else if word.len() < min_len_two_typos as usize {
             1
@@ -258,14 +292,12 @@ pub fn make_ngram(
         partially_initialized_term_from_word(ctx, &ngram_str, max_nbr_typos, is_prefix, true)?;
 
     // Now add the synonyms
-    let index_synonyms = ctx.index.synonyms(ctx.txn)?;
+    let synonyms = ctx.synonyms(&words)?;
 
-    term.zero_typo.synonyms.extend(
-        index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
-            let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
-        }),
-    );
+    term.zero_typo.synonyms.extend(synonyms.into_iter().map(|words| {
+        let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
+        ctx.phrase_interner.insert(Phrase { words, ..Default::default() })
+    }));
 
     let term = QueryTerm {
         original: ngram_str_interned,
@@ -275,6 +307,10 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        stems: BTreeSet::new(),
+        phonetic: BTreeSet::new(),
+        infix_of: BTreeSet::new(),
+        restricted_fids: None,
     };
 
     let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
@@ -282,15 +318,23 @@ pub fn make_ngram(
     Ok(Some(term))
 }
 
-struct PhraseBuilder {
+pub(super) struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
     start: u16,
     end: u16,
+    /// The slop of a fuzzy phrase, `None` if the phrase must be matched exactly.
+    slop: Option<u8>,
 }
 
 impl PhraseBuilder {
-    fn empty() -> Self {
-        Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
+    pub(super) fn empty() -> Self {
+        Self { words: Default::default(), start: u16::MAX, end: u16::MAX, slop: None }
+    }
+
+    /// Makes the phrase fuzzy, allowing up to `slop` words between its consecutive words
+    /// and typos on them.
+    pub(super) fn fuzzy(&mut self, slop: u8) {
+        self.slop = Some(slop);
     }
 
     fn is_empty(&self) -> bool {
@@ -298,7 +342,7 @@ impl PhraseBuilder {
     }
 
     // precondition: token has kind Word or StopWord
-    fn push_word(
+    pub(super) fn push_word(
         &mut self,
         ctx: &mut SearchContext<'_>,
         token: &charabia::Token<'_>,
@@ -317,13 +361,28 @@ impl PhraseBuilder {
         }
     }
 
-    fn build(self, ctx: &mut SearchContext<'_>) -> Option<LocatedQueryTerm> {
+    pub(super) fn build(self, ctx: &mut SearchContext<'_>) -> Result<Option<LocatedQueryTerm>> {
         if self.is_empty() {
-            return None;
+            return Ok(None);
+        }
+        let mut typos = Vec::new();
+        if self.slop.is_some() {
+            let nbr_typos = number_of_typos_allowed(ctx)?;
+            for word in self.words.iter().copied() {
+                let derivations = match word {
+                    Some(word) => {
+                        let max_typo = nbr_typos(ctx.word_interner.get(word));
+                        phrase_word_typo_derivations(ctx, word, max_typo)?
+                    }
+                    None => Vec::new(),
+                };
+                typos.push(derivations);
+            }
         }
-        Some(LocatedQueryTerm {
+        let slop = self.slop.unwrap_or_default();
+        Ok(Some(LocatedQueryTerm {
             value: ctx.term_interner.push({
-                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words });
+                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words, slop, typos });
                 let phrase_desc = phrase.description(ctx);
                 QueryTerm {
                     original: ctx.word_interner.insert(phrase_desc),
@@ -339,10 +398,14 @@ impl PhraseBuilder {
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    stems: BTreeSet::default(),
+                    phonetic: BTreeSet::default(),
+                    infix_of: BTreeSet::default(),
+                    restricted_fids: None,
                 }
             }),
             positions: self.start..=self.end,
-        })
+        }))
     }
 }
 
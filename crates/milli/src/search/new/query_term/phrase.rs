@@ -1,21 +1,65 @@
 use itertools::Itertools;
 
 use crate::search::new::interner::Interned;
+use crate::search::new::limits;
 use crate::SearchContext;
 
 /// A phrase in the user's search query, consisting of several words
 /// that must appear side-by-side in the search results.
+///
+/// A fuzzy phrase, written `"a phrase"~N`, allows up to `N` words between two consecutive
+/// words of the phrase and tolerates typos on them.
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 pub struct Phrase {
     pub words: Vec<Option<Interned<String>>>,
+    /// The maximum number of words between two consecutive words of the phrase.
+    pub slop: u8,
+    /// The words derived with typos from each word of the phrase, empty if the phrase
+    /// isn't fuzzy.
+    pub typos: Vec<Vec<Interned<String>>>,
 }
+
+impl Phrase {
+    /// Returns `true` if the words of the phrase must be matched side-by-side and without typos.
+    pub fn is_exact(&self) -> bool {
+        self.slop == 0 && self.typos.iter().all(Vec::is_empty)
+    }
+
+    /// Returns the words matching each word of the phrase, the original word first.
+    pub fn derivations(&self) -> Vec<Option<Vec<Interned<String>>>> {
+        self.words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                word.map(|word| {
+                    let typos = self.typos.get(i).into_iter().flatten().copied();
+                    std::iter::once(word).chain(typos).collect()
+                })
+            })
+            .collect()
+    }
+}
+
 impl Interned<Phrase> {
     pub fn description(self, ctx: &SearchContext<'_>) -> String {
         let p = ctx.phrase_interner.get(self);
         p.words.iter().flatten().map(|w| ctx.word_interner.get(*w)).join(" ")
     }
-    pub fn words(self, ctx: &SearchContext<'_>) -> Vec<Option<Interned<String>>> {
+    /// The words matching each word of the phrase, see [`Phrase::derivations`].
+    pub fn derivations(self, ctx: &SearchContext<'_>) -> Vec<Option<Vec<Interned<String>>>> {
         let p = ctx.phrase_interner.get(self);
-        p.words.clone()
+        p.derivations()
     }
 }
+
+/// Parses the operator following the closing quote of a phrase, `~` optionally followed by
+/// the slop of the phrase, and returns the slop and the length of the operator.
+///
+/// The slop is capped to [`limits::MAX_PHRASE_SLOP`], the farthest proximity between two
+/// words stored in the index.
+pub(super) fn parse_phrase_operator(text: &str) -> Option<(u8, usize)> {
+    let digits = text.strip_prefix('~')?;
+    let len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+    let slop = digits[..len].parse::<u32>().map_or(0, |slop| slop.min(u8::MAX as u32) as u8);
+    Some((slop.min(limits::MAX_PHRASE_SLOP), len + 1))
+}
@@ -64,7 +64,8 @@ impl RankingRuleGraphTrait for FidGraph {
         }
 
         for phrase in term.term_subset.all_phrases(ctx)? {
-            for &word in phrase.words(ctx).iter().flatten() {
+            // the words of a fuzzy phrase may be misspelled
+            for word in phrase.derivations(ctx).into_iter().flatten().flatten() {
                 let fields = ctx.get_db_word_fids(word)?;
                 all_fields.extend(fields);
             }
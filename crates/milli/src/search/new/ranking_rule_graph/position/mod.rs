@@ -67,8 +67,9 @@ impl RankingRuleGraphTrait for PositionGraph {
             // it is difficult/impossible to know the expected position
             // of a word in a phrase.
             // There is probably a more correct way to do it though.
-            if let Some(word) = phrase.words(ctx).iter().flatten().next() {
-                let positions = ctx.get_db_word_positions(*word)?;
+            let first_words = phrase.derivations(ctx).into_iter().flatten().next();
+            for word in first_words.into_iter().flatten() {
+                let positions = ctx.get_db_word_positions(word)?;
                 all_positions.extend(positions);
             }
         }
@@ -8,18 +8,38 @@ use crate::search::new::SearchContext;
 use crate::Result;
 
 pub fn build_edges(
-    _ctx: &mut SearchContext<'_>,
+    ctx: &mut SearchContext<'_>,
     conditions_interner: &mut DedupInterner<ProximityCondition>,
     left_term: Option<&LocatedQueryTermSubset>,
     right_term: &LocatedQueryTermSubset,
 ) -> Result<Vec<(u32, Interned<ProximityCondition>)>> {
+    // A fuzzy phrase is closer when matched with a smaller slop, each word allowed
+    // between its words adds to the cost of the edges leading to it.
+    let mut edges = vec![];
+    let subsets = right_term.term_subset.phrase_slop_subsets(ctx);
+    for (slop, term_subset) in subsets.into_iter().enumerate() {
+        let right_term = LocatedQueryTermSubset { term_subset, ..right_term.clone() };
+        edges.extend(
+            build_edges_to_term(conditions_interner, left_term, &right_term)
+                .into_iter()
+                .map(|(cost, condition)| (cost + slop as u32, condition)),
+        );
+    }
+    Ok(edges)
+}
+
+fn build_edges_to_term(
+    conditions_interner: &mut DedupInterner<ProximityCondition>,
+    left_term: Option<&LocatedQueryTermSubset>,
+    right_term: &LocatedQueryTermSubset,
+) -> Vec<(u32, Interned<ProximityCondition>)> {
     let right_ngram_max = right_term.term_ids.len().saturating_sub(1);
 
     let Some(left_term) = left_term else {
-        return Ok(vec![(
+        return vec![(
             right_ngram_max as u32,
             conditions_interner.insert(ProximityCondition::Term { term: right_term.clone() }),
-        )]);
+        )];
     };
 
     if left_term.positions.end() + 1 != *right_term.positions.start() {
@@ -29,10 +49,10 @@ pub fn build_edges(
         // `flowers` is removed by the `words` ranking rule.
         // The remaining query graph represents `the sun .. are beautiful`
         // but `sun` and `are` have no proximity condition between them
-        return Ok(vec![(
+        return vec![(
             right_ngram_max as u32,
             conditions_interner.insert(ProximityCondition::Term { term: right_term.clone() }),
-        )]);
+        )];
     }
 
     let mut conditions = vec![];
@@ -52,5 +72,5 @@ pub fn build_edges(
         conditions_interner.insert(ProximityCondition::Term { term: right_term.clone() }),
     ));
 
-    Ok(conditions)
+    conditions
 }
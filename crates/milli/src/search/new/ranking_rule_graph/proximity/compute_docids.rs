@@ -222,9 +222,10 @@ fn last_words_of_term_derivations(
     }
     for p in t.all_phrases(ctx)? {
         let phrase = ctx.phrase_interner.get(p);
-        let last_term_of_phrase = phrase.words.last().unwrap();
-        if let Some(last_word) = last_term_of_phrase {
-            result.insert((Some(p), Word::Original(*last_word)));
+        // the last word of a fuzzy phrase may be misspelled
+        let last_term_of_phrase = phrase.derivations().pop().unwrap();
+        for last_word in last_term_of_phrase.into_iter().flatten() {
+            result.insert((Some(p), Word::Original(last_word)));
         }
     }
 
@@ -241,9 +242,10 @@ fn first_word_of_term_iter(
     }
     for p in t.all_phrases(ctx)? {
         let phrase = ctx.phrase_interner.get(p);
-        let first_term_of_phrase = phrase.words.first().unwrap();
-        if let Some(first_word) = first_term_of_phrase {
-            result.insert((*first_word, Some(p)));
+        // the first word of a fuzzy phrase may be misspelled
+        let first_term_of_phrase = phrase.derivations().into_iter().next().unwrap();
+        for first_word in first_term_of_phrase.into_iter().flatten() {
+            result.insert((first_word, Some(p)));
         }
     }
 
@@ -14,6 +14,18 @@ pub struct TypoCondition {
     nbr_typos: u8,
 }
 
+/// The cost of matching a word through its stem, the same as a single typo as the word
+/// only differs by its inflection.
+const STEM_COST: u8 = 1;
+
+/// The cost of matching a word through its phonetic code, above the cost of the two typos
+/// derivations as sounding alike is a looser match than a typo.
+const PHONETIC_COST: u8 = 3;
+
+/// The cost of matching a word of an infix attribute containing the query word, above the
+/// cost of the phonetic codes as it is the loosest match.
+const INFIX_COST: u8 = 4;
+
 pub enum TypoGraph {}
 
 impl RankingRuleGraphTrait for TypoGraph {
@@ -26,6 +38,9 @@ impl RankingRuleGraphTrait for TypoGraph {
         universe: &RoaringBitmap,
     ) -> Result<ComputedCondition> {
         let TypoCondition { term, .. } = condition;
+        // The derivations with typos are only searched in the fields whose typo budget
+        // allows their number of typos, so the cost of a document depends on the fields
+        // in which its words are found.
         // maybe compute_query_term_subset_docids should accept a universe as argument
         let docids = compute_query_term_subset_docids(ctx, Some(universe), &term.term_subset)?;
 
@@ -69,12 +84,57 @@ impl RankingRuleGraphTrait for TypoGraph {
                 }
                 _ => panic!(),
             };
+            term.term_subset.clear_stem_subset();
+            term.term_subset.clear_phonetic_subset();
+            term.term_subset.clear_infix_subset();
 
             edges.push((
                 nbr_typos as u32 + base_cost,
                 conditions_interner.insert(TypoCondition { term, nbr_typos }),
             ));
         }
+
+        if term.term_subset.has_stems(ctx) {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+            term.term_subset.clear_phonetic_subset();
+            term.term_subset.clear_infix_subset();
+
+            edges.push((
+                STEM_COST as u32 + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos: STEM_COST }),
+            ));
+        }
+
+        if term.term_subset.has_phonetic_codes(ctx) {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+            term.term_subset.clear_stem_subset();
+            term.term_subset.clear_infix_subset();
+
+            edges.push((
+                PHONETIC_COST as u32 + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos: PHONETIC_COST }),
+            ));
+        }
+
+        if !term.term_subset.infix_words(ctx).is_empty() {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+            term.term_subset.clear_stem_subset();
+            term.term_subset.clear_phonetic_subset();
+
+            edges.push((
+                INFIX_COST as u32 + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos: INFIX_COST }),
+            ));
+        }
         Ok(edges)
     }
 
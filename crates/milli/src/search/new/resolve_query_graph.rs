@@ -1,8 +1,9 @@
 #![allow(clippy::too_many_arguments)]
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 use fxhash::FxHashMap;
+use itertools::Itertools;
 use roaring::{MultiOps, RoaringBitmap};
 
 use super::interner::Interned;
@@ -10,8 +11,9 @@ use super::query_graph::QueryNodeData;
 use super::query_term::{Phrase, QueryTermSubset};
 use super::small_bitmap::SmallBitmap;
 use super::{QueryGraph, SearchContext, Word};
+use crate::proximity::MAX_DISTANCE;
 use crate::search::new::query_term::LocatedQueryTermSubset;
-use crate::Result;
+use crate::{FieldId, Result};
 
 #[derive(Default)]
 pub struct PhraseDocIdsCache {
@@ -30,15 +32,78 @@ impl SearchContext<'_> {
         Ok(docids)
     }
 }
+
+/// Returns the derivations of the term only matching in some fields along with these fields:
+/// - the typo derivations in the fields allowing their number of typos, if some fields
+///   override the global typo tolerance settings,
+/// - the words containing the original word in the infix attributes.
+///
+/// The other derivations are searched in every field.
+fn restricted_derivations_fields(
+    ctx: &mut SearchContext<'_>,
+    term: &QueryTermSubset,
+) -> Result<BTreeMap<Interned<String>, Vec<FieldId>>> {
+    let infix_words = term.infix_words(ctx);
+    if ctx.typo_budgets.is_none() && infix_words.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    let derivations = term.typo_derivations(ctx)?;
+    let mut restricted = BTreeMap::new();
+    if let Some(typo_budgets) = &ctx.typo_budgets {
+        let original = term.description(ctx);
+        let fields = [
+            typo_budgets.fields_allowing(&original, 1),
+            typo_budgets.fields_allowing(&original, 2),
+        ];
+        restricted.extend(
+            derivations
+                .iter()
+                .map(|(word, nbr_typos)| (*word, fields[*nbr_typos as usize - 1].clone())),
+        );
+    }
+
+    // an infix word also derived with typos matches in the fields of both derivations
+    for word in infix_words {
+        match restricted.get_mut(&word) {
+            Some(fids) => {
+                fids.extend(ctx.infix_fids.iter().copied());
+                fids.sort_unstable();
+                fids.dedup();
+            }
+            None if derivations.contains_key(&word) => (),
+            None => {
+                restricted.insert(word, ctx.infix_fids.clone());
+            }
+        }
+    }
+
+    Ok(restricted)
+}
+
 pub fn compute_query_term_subset_docids(
     ctx: &mut SearchContext<'_>,
     universe: Option<&RoaringBitmap>,
     term: &QueryTermSubset,
 ) -> Result<RoaringBitmap> {
+    // a term scoped to some attributes only matches in their fields
+    if let Some(fids) = term.restricted_fids(ctx) {
+        let mut docids = RoaringBitmap::new();
+        for fid in fids {
+            docids |= compute_query_term_subset_docids_within_field_id(ctx, universe, term, fid)?;
+        }
+        return Ok(docids);
+    }
+
     let mut docids = RoaringBitmap::new();
+    let restricted_derivations_fields = restricted_derivations_fields(ctx, term)?;
     // TODO use the MultiOps trait to do large intersections
     for word in term.all_single_words_except_prefix_db(ctx)? {
-        if let Some(word_docids) = ctx.word_docids(universe, word)? {
+        let word_docids = match restricted_derivations_fields.get(&word.interned()) {
+            Some(fids) => ctx.word_docids_within_field_ids(universe, word.interned(), fids)?,
+            None => ctx.word_docids(universe, word)?,
+        };
+        if let Some(word_docids) = word_docids {
             docids |= word_docids;
         }
     }
@@ -64,8 +129,20 @@ pub fn compute_query_term_subset_docids_within_field_id(
     term: &QueryTermSubset,
     fid: u16,
 ) -> Result<RoaringBitmap> {
+    if term.restricted_fids(ctx).is_some_and(|fids| !fids.contains(&fid)) {
+        return Ok(RoaringBitmap::new());
+    }
+
     let mut docids = RoaringBitmap::new();
+    let restricted_derivations_fields = restricted_derivations_fields(ctx, term)?;
     for word in term.all_single_words_except_prefix_db(ctx)? {
+        // the restricted derivations only match in their fields
+        if restricted_derivations_fields
+            .get(&word.interned())
+            .is_some_and(|fids| !fids.contains(&fid))
+        {
+            continue;
+        }
         if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(universe, word.interned(), fid)? {
             docids |= word_fid_docids;
         }
@@ -74,8 +151,10 @@ pub fn compute_query_term_subset_docids_within_field_id(
     for phrase in term.all_phrases(ctx)? {
         // There may be false positives when resolving a phrase, so we're not
         // guaranteed that all of its words are within a single fid.
-        if let Some(word) = phrase.words(ctx).iter().flatten().next() {
-            if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(universe, *word, fid)? {
+        // The first word of a fuzzy phrase may be misspelled.
+        let first_words = phrase.derivations(ctx).into_iter().flatten().next();
+        for word in first_words.into_iter().flatten() {
+            if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(universe, word, fid)? {
                 docids |= ctx.get_phrase_docids(phrase)? & word_fid_docids;
             }
         }
@@ -99,20 +178,33 @@ pub fn compute_query_term_subset_docids_within_position(
     position: u16,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    let restricted_derivations_fields = restricted_derivations_fields(ctx, term)?;
     for word in term.all_single_words_except_prefix_db(ctx)? {
         if let Some(word_position_docids) =
             ctx.get_db_word_position_docids(universe, word.interned(), position)?
         {
-            docids |= word_position_docids;
+            // the positions don't tell the fields, the restricted derivations are only kept
+            // in the documents containing them in one of their fields
+            match restricted_derivations_fields.get(&word.interned()) {
+                Some(fids) => {
+                    if let Some(allowed) =
+                        ctx.word_docids_within_field_ids(universe, word.interned(), fids)?
+                    {
+                        docids |= word_position_docids & allowed;
+                    }
+                }
+                None => docids |= word_position_docids,
+            }
         }
     }
 
     for phrase in term.all_phrases(ctx)? {
         // It's difficult to know the expected position of the words in the phrase,
         // so instead we just check the first one.
-        if let Some(word) = phrase.words(ctx).iter().flatten().next() {
+        let first_words = phrase.derivations(ctx).into_iter().flatten().next();
+        for word in first_words.into_iter().flatten() {
             if let Some(word_position_docids) =
-                ctx.get_db_word_position_docids(universe, *word, position)?
+                ctx.get_db_word_position_docids(universe, word, position)?
             {
                 docids |= ctx.get_phrase_docids(phrase)? & word_position_docids;
             }
@@ -126,6 +218,12 @@ pub fn compute_query_term_subset_docids_within_position(
             docids |= word_position_docids;
         }
     }
+
+    // the positions don't tell the fields, a term scoped to some attributes is only kept in
+    // the documents containing it in one of their fields
+    if term.restricted_fids(ctx).is_some() {
+        docids &= compute_query_term_subset_docids(ctx, universe, term)?;
+    }
     Ok(docids)
 }
 
@@ -188,22 +286,30 @@ pub fn compute_phrase_docids(
     ctx: &mut SearchContext<'_>,
     phrase: Interned<Phrase>,
 ) -> Result<RoaringBitmap> {
-    let Phrase { words } = ctx.phrase_interner.get(phrase).clone();
+    let phrase = ctx.phrase_interner.get(phrase).clone();
+    let slop = phrase.slop;
+    // the words of a fuzzy phrase also match their typo derivations
+    let words = phrase.derivations();
 
     if words.is_empty() {
         return Ok(RoaringBitmap::new());
     }
     let mut candidates = None;
-    for word in words.iter().flatten().copied() {
-        if let Some(word_docids) = ctx.word_docids(None, Word::Original(word))? {
-            if let Some(candidates) = candidates.as_mut() {
-                *candidates &= word_docids;
-            } else {
-                candidates = Some(word_docids);
+    for derivations in words.iter().flatten() {
+        let mut word_docids = RoaringBitmap::new();
+        for word in derivations.iter().copied() {
+            if let Some(docids) = ctx.word_docids(None, Word::Original(word))? {
+                word_docids |= docids;
             }
-        } else {
+        }
+        if word_docids.is_empty() {
             return Ok(RoaringBitmap::new());
         }
+        if let Some(candidates) = candidates.as_mut() {
+            *candidates &= word_docids;
+        } else {
+            candidates = Some(word_docids);
+        }
     }
 
     let Some(mut candidates) = candidates else {
@@ -215,38 +321,54 @@ pub fn compute_phrase_docids(
     for win in words.windows(winsize) {
         // Get all the documents with the matching distance for each word pairs.
         let mut bitmaps = Vec::with_capacity(winsize.pow(2));
-        for (offset, &s1) in win
+        for (offset, s1) in win
             .iter()
             .enumerate()
             .filter_map(|(index, word)| word.as_ref().map(|word| (index, word)))
         {
-            for (dist, &s2) in win
+            for (dist, s2) in win
                 .iter()
                 .skip(offset + 1)
                 .enumerate()
                 .filter_map(|(index, word)| word.as_ref().map(|word| (index, word)))
             {
-                if dist == 0 {
-                    match ctx.get_db_word_pair_proximity_docids(None, s1, s2, 1)? {
-                        Some(m) => bitmaps.push(m),
-                        // If there are no documents for this pair, there will be no
-                        // results for the phrase query.
-                        None => return Ok(RoaringBitmap::new()),
-                    }
-                } else {
-                    let mut bitmap = RoaringBitmap::new();
-                    for dist in 0..=dist {
+                // The words can be separated by the `dist` words between them in the phrase,
+                // and by up to `slop` other words before each of them.
+                let max_proximity = (dist + 1) * (slop as usize + 1);
+                if max_proximity >= MAX_DISTANCE as usize {
+                    // The index doesn't store the proximities that far, the pairs of
+                    // consecutive words are enough to check the phrase.
+                    continue;
+                }
+
+                let mut bitmap = RoaringBitmap::new();
+                for (&left, &right) in s1.iter().cartesian_product(s2) {
+                    for proximity in 1..=max_proximity as u8 {
                         if let Some(m) =
-                            ctx.get_db_word_pair_proximity_docids(None, s1, s2, dist as u8 + 1)?
+                            ctx.get_db_word_pair_proximity_docids(None, left, right, proximity)?
                         {
                             bitmap |= m;
                         }
+                        // The words of a fuzzy phrase may also be swapped, the swapped pairs
+                        // are stored one proximity closer like in the proximity ranking rule.
+                        if slop > 0 && proximity > 1 {
+                            if let Some(m) = ctx.get_db_word_pair_proximity_docids(
+                                None,
+                                right,
+                                left,
+                                proximity - 1,
+                            )? {
+                                bitmap |= m;
+                            }
+                        }
                     }
-                    if bitmap.is_empty() {
-                        return Ok(bitmap);
-                    } else {
-                        bitmaps.push(bitmap);
-                    }
+                }
+                // If there are no documents for this pair, there will be no
+                // results for the phrase query.
+                if bitmap.is_empty() {
+                    return Ok(bitmap);
+                } else {
+                    bitmaps.push(bitmap);
                 }
             }
         }
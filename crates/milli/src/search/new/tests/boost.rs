@@ -0,0 +1,115 @@
+/*!
+This module tests the boost ranking rule:
+
+1. documents are sorted by the decreasing sum of the weights of the boosts they match
+2. documents matching a boost with a negative weight are returned after the other documents
+3. the boosts are applied after the words ranking rule
+4. the total weight of a document is visible in its score details
+5. a boost whose filter can't be evaluated returns an error mentioning its index
+*/
+
+use big_s::S;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoreDetails;
+use crate::{
+    Boost, Error, Filter, FilterableAttributesRule, Search, SearchResult, TermsMatchingStrategy,
+    UserError,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(vec![
+                FilterableAttributesRule::Field(S("brand")),
+                FilterableAttributesRule::Field(S("color")),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello", "brand": "acme", "color": "red" },
+            { "id": 1, "text": "hello", "brand": "other", "color": "red" },
+            { "id": 2, "text": "hello", "brand": "acme", "color": "blue" },
+            { "id": 3, "text": "hello", "brand": "other", "color": "blue" },
+            { "id": 4, "text": "hello world", "brand": "other", "color": "green" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn boost(filter: &str, weight: f64) -> Boost<'_> {
+    Boost { filter: Filter::from_str(filter).unwrap().unwrap(), weight }
+}
+
+#[test]
+fn test_boost_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+    s.boosts(vec![boost("brand = acme", 2.0), boost("color = blue", -1.0)]);
+
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 1, 4, 3]");
+
+    let json = ScoreDetails::to_json_map(document_scores[0].iter());
+    insta::assert_json_snapshot!(json, @r###"
+    {
+      "boost": {
+        "order": 0,
+        "weight": 2.0,
+        "score": 1.0
+      }
+    }
+    "###);
+
+    let json = ScoreDetails::to_json_map(document_scores[4].iter());
+    insta::assert_json_snapshot!(json, @r###"
+    {
+      "boost": {
+        "order": 0,
+        "weight": -1.0,
+        "score": 0.25
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_boost_with_query() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello world");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.boosts(vec![boost("brand = acme", 2.0), boost("color = blue", -1.0)]);
+
+    // the document matching all the words comes first, the others are sorted by boost
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 0, 2, 1, 3]");
+}
+
+#[test]
+fn test_boost_invalid_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.boosts(vec![boost("brand = acme", 2.0), boost("id = 1", 1.0)]);
+
+    let error = s.execute().unwrap_err();
+    assert!(
+        matches!(error, Error::UserError(UserError::InvalidBoost { index: 1, .. })),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,163 @@
+/*!
+This module tests the ranking expression ranking rule:
+
+1. documents are sorted by the decreasing value of the expression
+2. documents for which the expression can't be computed appear at the end
+3. when an attribute contains an array, its smallest value is used
+4. the value is normalized between the smallest and biggest values to compute the score
+5. the expression is redacted when it uses attributes that are not displayed
+6. the evaluation of the documents stops when the time budget is exceeded
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails};
+use crate::{Criterion, Search, SearchResult, TimeBudget};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Expression("popularity * 2 - price".parse().unwrap())]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello", "popularity": 10, "price": 5 },
+            { "id": 1, "text": "hello", "popularity": 3, "price": 1 },
+            { "id": 2, "text": "hello", "popularity": 20, "price": 30 },
+            { "id": 3, "text": "hello", "popularity": 8 },
+            { "id": 4, "text": "hello", "popularity": 10, "price": 5 },
+            { "id": 5, "text": "hello", "popularity": [40, 4], "price": 0 },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn expression_details(document_scores: &[Vec<ScoreDetails>]) -> Vec<(Option<f64>, u32)> {
+    document_scores
+        .iter()
+        .map(|scores| match scores.as_slice() {
+            [ScoreDetails::Expression(score_details::Expression { value, rank, .. })] => {
+                (*value, rank.rank)
+            }
+            scores => panic!("unexpected score details {scores:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_expression_sort() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 4, 2, 5, 1, 3]");
+    assert_eq!(
+        expression_details(&document_scores),
+        [
+            (Some(15.), 20),
+            (Some(15.), 20),
+            (Some(10.), 11),
+            (Some(8.), 7),
+            (Some(5.), 2),
+            (None, 1)
+        ]
+    );
+
+    let global_scores: Vec<_> =
+        document_scores.iter().map(|scores| ScoreDetails::global_score(scores.iter())).collect();
+    assert_eq!(global_scores, [1.0, 1.0, 11. / 20., 7. / 20., 2. / 20., 1. / 20.]);
+
+    let json = ScoreDetails::to_json_map(document_scores[2].iter());
+    insta::assert_json_snapshot!(json, @r###"
+    {
+      "score: popularity * 2 - price": {
+        "order": 0,
+        "value": 10.0,
+        "score": 0.55
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_expression_sort_with_query() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Expression("coalesce(price, 100)".parse().unwrap()),
+            ]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 6, "text": "hello world", "popularity": 1, "price": 1 },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello world");
+    s.terms_matching_strategy(crate::TermsMatchingStrategy::Last);
+
+    // the document matching all the words comes first, the others are sorted by price
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[6, 3, 2, 0, 4, 1, 5]");
+}
+
+#[test]
+fn test_redacted() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_displayed_fields(vec!["text".to_owned(), "popularity".to_owned()]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    let SearchResult { document_scores, .. } = s.execute().unwrap();
+    let json = ScoreDetails::to_json_map(document_scores[0].iter());
+    insta::assert_json_snapshot!(json, @r###"
+    {
+      "<hidden-rule-0>": {
+        "order": 0,
+        "value": "<hidden>",
+        "score": 1.0
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_time_budget() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+    // one check in the bucket sort, then only the documents 0, 1 and 2 are evaluated
+    s.time_budget(TimeBudget::max().with_stop_after(4));
+
+    let SearchResult { documents_ids, document_scores, degraded, .. } = s.execute().unwrap();
+    assert!(degraded);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+    assert_eq!(expression_details(&document_scores[..1]), [(Some(15.), 20)]);
+    assert!(document_scores[1..].iter().all(|scores| scores == &[ScoreDetails::Skipped]));
+}
@@ -0,0 +1,64 @@
+/*!
+This module tests the fuzzy phrases, written `"a phrase"~N`:
+- up to `N` words are allowed between two consecutive words of the phrase
+- the words of the phrase tolerate typos
+- the slop is capped to the farthest proximity stored in the index
+- the proximity ranking rule ranks the phrases matched with a smaller slop first
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, QuerySyntax, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Proximity]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox" },
+            { "id": 1, "text": "the quick red brown fox" },
+            { "id": 2, "text": "the quick big red brown fox" },
+            { "id": 3, "text": "the quick big fat red brown fox" },
+            { "id": 4, "text": "the quicc brown fox" },
+            { "id": 5, "text": "brown quick fox" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_fuzzy_phrase() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let search = |query: &str, syntax: QuerySyntax| {
+        let mut s = Search::new(&txn, &index);
+        s.query(query);
+        s.query_syntax(syntax);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        format!("{documents_ids:?}")
+    };
+
+    for syntax in [QuerySyntax::Simple, QuerySyntax::Advanced] {
+        insta::allow_duplicates! {
+            // the phrases are matched exactly
+            insta::assert_snapshot!(search("\"quick brown\"", syntax), @"[0]");
+            // `quicc` is a typo of `quick`
+            insta::assert_snapshot!(search("\"quick brown\"~", syntax), @"[0, 4]");
+            // the swapped words are one word apart, like in the proximity ranking rule
+            insta::assert_snapshot!(search("\"quick brown\"~1", syntax), @"[0, 4, 1, 5]");
+            insta::assert_snapshot!(search("\"quick brown\"~2", syntax), @"[0, 4, 1, 5, 2]");
+            // the words are never more than two words apart
+            insta::assert_snapshot!(search("\"quick brown\"~5", syntax), @"[0, 4, 1, 5, 2]");
+            insta::assert_snapshot!(search("\"quick brown\"~1 fox", syntax), @"[0, 4, 1, 5]");
+        }
+    }
+}
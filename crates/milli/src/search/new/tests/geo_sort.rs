@@ -279,6 +279,51 @@ fn test_geo_sort_around_the_edge_of_the_flat_earth() {
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
 
+#[test]
+fn geo_sort_multiple_points() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 0 } },
+            { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 1 } },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 12 } },
+            { "id": 3, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 3 } },
+            { "id": 4, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 6 } },
+            { "id": 5, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 25 } },
+            { "id": 6 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    let target_points = |scores: &[Vec<ScoreDetails>]| -> Vec<[f64; 2]> {
+        scores
+            .iter()
+            .filter_map(|scores| {
+                scores.iter().find_map(|score| match score {
+                    ScoreDetails::GeoSort(geo_sort) => Some(geo_sort.target_point),
+                    _ => None,
+                })
+            })
+            .collect()
+    };
+
+    // the documents are sorted by their distance to the closest point
+    s.sort_criteria(vec![AscDesc::Asc(Member::GeoPoints(vec![[0., 0.], [0., 10.]]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3, 4, 5, 6]");
+    insta::assert_snapshot!(format!("{:?}", target_points(&scores)), @"[[0.0, 0.0], [0.0, 0.0], [0.0, 10.0], [0.0, 0.0], [0.0, 10.0], [0.0, 10.0], [0.0, 0.0]]");
+
+    // the rtree can't be used to sort by descending distance to multiple points
+    s.sort_criteria(vec![AscDesc::Desc(Member::GeoPoints(vec![[0., 0.], [0., 10.]]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[5, 4, 3, 2, 1, 0, 6]");
+}
+
 #[test]
 fn geo_sort_mixed_with_words() {
     let index = create_index();
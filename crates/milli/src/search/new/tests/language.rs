@@ -1,5 +1,8 @@
+use big_s::S;
+use charabia::Language;
+
 use crate::index::tests::TempIndex;
-use crate::{Search, SearchResult};
+use crate::{LocalizedAttributesRule, Search, SearchResult, SynonymSet, SynonymSetKind};
 
 #[test]
 fn test_kanji_language_detection() {
@@ -21,3 +24,95 @@ fn test_kanji_language_detection() {
 
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
 }
+
+#[test]
+fn test_localized_synonym_sets() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            let set = |kind, input: &[&str], synonyms: &[&str], locales| SynonymSet {
+                kind,
+                input: input.iter().map(|word| S(word)).collect(),
+                synonyms: synonyms.iter().map(|word| S(word)).collect(),
+                locales,
+            };
+            s.set_synonym_sets(vec![
+                set(SynonymSetKind::Equivalent, &[], &["tv", "television"], vec![]),
+                set(SynonymSetKind::OneWay, &["smartphone"], &["iphone", "android phone"], vec![]),
+                set(SynonymSetKind::Equivalent, &[], &["chips", "fries"], vec![Language::Eng]),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "television" },
+            { "id": 1, "title": "iphone" },
+            { "id": 2, "title": "android phone" },
+            { "id": 3, "title": "smartphone" },
+            { "id": 4, "title": "fries" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let search = |query: &str, locales: Option<Vec<Language>>| {
+        let mut search = Search::new(&txn, &index);
+        search.query(query);
+        if let Some(locales) = locales {
+            search.locales(locales);
+        }
+        let SearchResult { mut documents_ids, .. } = search.execute().unwrap();
+        documents_ids.sort_unstable();
+        format!("{documents_ids:?}")
+    };
+
+    insta::assert_snapshot!(search("tv", None), @"[0]");
+    insta::assert_snapshot!(search("smartphone", None), @"[1, 2, 3]");
+    // the synonyms of a one-way set are not synonyms of its input
+    insta::assert_snapshot!(search("iphone", None), @"[1]");
+    // the set restricted to english is only applied to the searches in english
+    insta::assert_snapshot!(search("chips", None), @"[]");
+    insta::assert_snapshot!(search("chips", Some(vec![Language::Eng])), @"[4]");
+}
+
+#[test]
+fn test_stemming() {
+    let index = TempIndex::new();
+
+    let rules = |stemming| {
+        let mut rule = LocalizedAttributesRule::new(vec![S("title")], vec![Language::Eng]);
+        rule.stemming = stemming;
+        vec![rule]
+    };
+    index.update_settings(|s| s.set_localized_attributes_rules(rules(true))).unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "she runs" },
+            { "id": 1, "title": "new running shoes" },
+            { "id": 2, "title": "a run" },
+            { "id": 3, "note": "runs" },
+        ]))
+        .unwrap();
+
+    let search = |query: &str| {
+        let txn = index.read_txn().unwrap();
+        let mut search = Search::new(&txn, &index);
+        search.query(query);
+        let SearchResult { documents_ids, .. } = search.execute().unwrap();
+        format!("{documents_ids:?}")
+    };
+
+    // the words sharing the stem of the query word cost one typo, so they are ranked
+    // after its exact matches, including the ones in the attributes which aren't stemmed
+    insta::assert_snapshot!(search("running"), @"[1, 0, 2]");
+    insta::assert_snapshot!(search("runs"), @"[0, 3, 1, 2]");
+
+    index.update_settings(|s| s.set_localized_attributes_rules(rules(false))).unwrap();
+    insta::assert_snapshot!(search("running"), @"[1]");
+
+    // the stems are indexed again when stemming is enabled on an existing index
+    index.update_settings(|s| s.set_localized_attributes_rules(rules(true))).unwrap();
+    insta::assert_snapshot!(search("running"), @"[1, 0, 2]");
+}
@@ -1,8 +1,11 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod boost;
 pub mod cutoff;
 pub mod distinct;
 pub mod exactness;
+pub mod expression_sort;
+pub mod fuzzy_phrase;
 pub mod geo_sort;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
@@ -11,8 +14,11 @@ pub mod language;
 pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod query_suggestions;
+pub mod query_syntax;
 pub mod sort;
 pub mod stop_words;
+pub mod suggest;
 pub mod typo;
 pub mod typo_proximity;
 pub mod words_tms;
@@ -0,0 +1,64 @@
+/*!
+This module tests the suggested corrections of the query:
+- the words that are not found in the documents are replaced by their typo derivations
+- the corrections found in the most documents come first, the other ones are alternatives
+- the operators of the query are kept
+- nothing is suggested when the words of the query are found without typos
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the sunflower garden" },
+            { "id": 1, "text": "a sunflower field" },
+            { "id": 2, "text": "sunflower seeds store" },
+            { "id": 3, "text": "a sunflowers garden store" },
+            { "id": 4, "text": "steer the boat" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_query_suggestions() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let search = |query: &str| {
+        let mut s = Search::new(&txn, &index);
+        s.query(query);
+        s.suggest_query(true);
+        let SearchResult { query_suggestions, .. } = s.execute().unwrap();
+        format!("{query_suggestions:?}")
+    };
+
+    insta::assert_snapshot!(search("sunflwer garden"), @r###"[QuerySuggestion { query: "sunflower garden", estimated_hits: 1 }]"###);
+    insta::assert_snapshot!(search("stoer"), @r###"[QuerySuggestion { query: "store", estimated_hits: 2 }, QuerySuggestion { query: "steer", estimated_hits: 1 }]"###);
+    insta::assert_snapshot!(search("sunflwer -boat"), @r###"[QuerySuggestion { query: "sunflower -boat", estimated_hits: 3 }]"###);
+    // `sunflowers` is found in fewer documents than `sunflower`
+    insta::assert_snapshot!(search("sunflower garden"), @"[]");
+}
+
+#[test]
+fn test_query_suggestions_not_requested() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("sunflwer garden");
+    let SearchResult { query_suggestions, .. } = s.execute().unwrap();
+    assert!(query_suggestions.is_empty());
+}
@@ -0,0 +1,83 @@
+/*!
+This module tests the advanced query syntax:
+- the `+` required clauses are never removed by the terms matching strategy
+- the documents containing the `-` excluded clauses are removed from the results
+- the `OR` alternatives match at the same location of the query
+- the `attribute:` clauses only match in the fields of the attribute
+- an unknown attribute is rejected when the searchable attributes are user-defined
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, QuerySyntax, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "body".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "invoice march", "body": "paid urgent" },
+            { "id": 1, "title": "invoice april", "body": "draft asap" },
+            { "id": 2, "title": "receipt", "body": "invoice paid asap" },
+            { "id": 3, "title": "invoice may", "body": "paid" },
+            { "id": 4, "title": "quote", "body": "paid urgent" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_advanced_query_syntax() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let search = |query: &str, strategy: TermsMatchingStrategy| {
+        let mut s = Search::new(&txn, &index);
+        s.query(query);
+        s.query_syntax(QuerySyntax::Advanced);
+        s.terms_matching_strategy(strategy);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        format!("{documents_ids:?}")
+    };
+
+    // `paid` is never removed, `invoice` is only matched in the titles
+    insta::assert_snapshot!(
+        search("title:invoice +paid -draft (urgent OR asap)", TermsMatchingStrategy::Last),
+        @"[0, 3, 2, 4]"
+    );
+    insta::assert_snapshot!(
+        search("title:invoice +paid -draft (urgent OR asap)", TermsMatchingStrategy::All),
+        @"[0]"
+    );
+    insta::assert_snapshot!(search("invoice -title:invoice", TermsMatchingStrategy::All), @"[2]");
+    insta::assert_snapshot!(search("(march OR may OR asap)", TermsMatchingStrategy::All), @"[0, 1, 2, 3]");
+
+    // the attributes are words of a simple query
+    let mut s = Search::new(&txn, &index);
+    s.query("title:invoice +paid");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn test_advanced_query_syntax_unknown_attribute() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("author:paid");
+    s.query_syntax(QuerySyntax::Advanced);
+    let error = s.execute().unwrap_err();
+    assert!(matches!(
+        error,
+        crate::Error::UserError(crate::UserError::InvalidSearchableAttribute { .. })
+    ));
+}
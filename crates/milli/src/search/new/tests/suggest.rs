@@ -0,0 +1,65 @@
+/*!
+This module tests the completion of the queries being typed:
+- the last word is completed with the words of the index starting with it
+- the phrases starting with these words are suggested too
+- a query ending with a separator is completed with the words following its last word
+- the completions must match the words already typed and the filter
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Filter, FilterableAttributesRule, Suggest};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(vec![FilterableAttributesRule::Field("tag".to_owned())]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "sunflower garden", "tag": "flower" },
+            { "id": 1, "text": "sunflower seeds", "tag": "flower" },
+            { "id": 2, "text": "sunflower garden party", "tag": "flower" },
+            { "id": 3, "text": "sunset boulevard", "tag": "road" },
+            { "id": 4, "text": "a garden of sunflowers", "tag": "flower" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_suggest() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let suggest = |query: &str| {
+        let suggestions = Suggest::new(query.to_owned(), 5, &index, &txn).execute().unwrap();
+        let suggestions: Vec<_> =
+            suggestions.iter().map(|s| format!("{} ({})", s.query, s.estimated_hits)).collect();
+        format!("{suggestions:?}")
+    };
+
+    insta::assert_snapshot!(suggest("sun"), @r###"["sunflower (3)", "sunflower garden (2)", "sunflower seeds (1)", "sunflowers (1)", "sunset (1)"]"###);
+    insta::assert_snapshot!(suggest("sunflower g"), @r###"["sunflower garden (2)", "sunflower garden party (1)"]"###);
+    insta::assert_snapshot!(suggest("sunflower "), @r###"["sunflower garden (2)", "sunflower seeds (1)"]"###);
+    insta::assert_snapshot!(suggest("moon"), @"[]");
+}
+
+#[test]
+fn test_suggest_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut suggest = Suggest::new("sun".to_owned(), 5, &index, &txn);
+    suggest.filter(Filter::from_str("tag = road").unwrap().unwrap());
+    let suggestions = suggest.execute().unwrap();
+    let suggestions: Vec<_> =
+        suggestions.iter().map(|s| format!("{} ({})", s.query, s.estimated_hits)).collect();
+
+    insta::assert_snapshot!(format!("{suggestions:?}"), @r###"["sunset (1)", "sunset boulevard (1)"]"###);
+}
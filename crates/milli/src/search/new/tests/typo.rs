@@ -16,13 +16,21 @@ This module tests the following properties:
 12. Prefix tolerance costs nothing according to the typo ranking rule
 13. Split words cost 1 typo according to the typo ranking rule
 14. Synonyms cost nothing according to the typo ranking rule
+15. Phonetic codes cost more than two typos according to the typo ranking rule
+16. The typo budgets of the attributes and locales restrict the typos allowed in their words
+17. The words of the infix attributes containing a query word cost more than its phonetic codes,
+    and the trigrams of these words are updated by each batch
 */
 
 use std::collections::BTreeMap;
 
 use crate::index::tests::TempIndex;
 use crate::search::new::tests::collect_field_values;
-use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+use crate::trigrams::values_containing;
+use crate::{
+    AttributeTypoBudget, Criterion, LocaleTypoBudget, LocalizedAttributesRule, PhoneticAlgorithm,
+    PhoneticSettings, Search, SearchResult, TermsMatchingStrategy, TypoBudget,
+};
 
 fn create_index() -> TempIndex {
     let index = TempIndex::new();
@@ -635,3 +643,173 @@ fn test_typo_synonyms() {
     ]
     "###);
 }
+
+#[test]
+fn test_typo_phonetic() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["name".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+            s.set_phonetic(PhoneticSettings {
+                attribute_patterns: vec!["name".to_owned()].into(),
+                algorithm: PhoneticAlgorithm::DoubleMetaphone,
+            });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "Smith" },
+            { "id": 1, "name": "Schmidt" },
+            { "id": 2, "name": "Smyth" },
+            { "id": 3, "name": "Johnson" },
+            { "id": 4, "name": "Schmitt" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("schmidt");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // the words sounding alike are ranked after the words with a typo
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 0, 2]");
+    drop(txn);
+
+    index.update_settings(|s| s.reset_phonetic()).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("schmidt");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4]");
+}
+
+#[test]
+fn test_typo_infix() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["brand".to_owned(), "title".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+            s.set_infix_attributes(["brand".to_owned()].into());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "brand": "Walmart" },
+            { "id": 1, "brand": "Mart" },
+            { "id": 2, "brand": "Martial" },
+            { "id": 3, "title": "Walmart" },
+            { "id": 4, "brand": "Kmart" },
+        ]))
+        .unwrap();
+
+    let search = |query: &str| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, &index);
+        s.query(query);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        format!("{documents_ids:?}")
+    };
+
+    // the words containing the query word are ranked after the exact and prefix matches,
+    // and only in the infix attributes
+    insta::assert_snapshot!(search("mart"), @"[1, 2, 0, 4]");
+    insta::assert_snapshot!(search("almar"), @"[0]");
+    // the query words shorter than a trigram only match by prefix
+    insta::assert_snapshot!(search("ma"), @"[1, 2]");
+
+    // the trigrams of the words added to and deleted from the infix attributes by the
+    // later batches are updated
+    index.delete_document("4");
+    index
+        .add_documents(documents!([
+            { "id": 5, "brand": "Smart" },
+            { "id": 6, "title": "Kmart" },
+        ]))
+        .unwrap();
+    // `Smart` reuses the internal id of the deleted `Kmart`
+    insta::assert_snapshot!(search("mart"), @"[1, 2, 0, 4]");
+    {
+        let txn = index.read_txn().unwrap();
+        let fst = index.words_trigrams_fst(&txn).unwrap().unwrap();
+        let words = values_containing(&fst, &[], "mart", 10).unwrap();
+        insta::assert_snapshot!(format!("{words:?}"), @r###"["mart", "martial", "smart", "walmart"]"###);
+    }
+
+    index.update_settings(|s| s.reset_infix_attributes()).unwrap();
+    insta::assert_snapshot!(search("mart"), @"[1, 2]");
+}
+
+#[test]
+fn test_typo_budgets() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["sku".to_owned(), "description".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "sku": "keyboard", "description": "wireless" },
+            { "id": 1, "sku": "keyboarx", "description": "wireless" },
+            { "id": 2, "sku": "mouse", "description": "keyboarx" },
+            { "id": 3, "sku": "mouse", "description": "wireless" },
+        ]))
+        .unwrap();
+
+    let search = |query: &str| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, &index);
+        s.query(query);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        format!("{documents_ids:?}")
+    };
+
+    insta::assert_snapshot!(search("keyboard"), @"[0, 1, 2]");
+
+    // no typo is allowed in the SKUs
+    index
+        .update_settings(|s| {
+            s.set_attribute_typo_budgets(vec![AttributeTypoBudget {
+                attribute_patterns: vec!["sku".to_owned()].into(),
+                budget: TypoBudget { max_typos: Some(0), ..Default::default() },
+            }])
+        })
+        .unwrap();
+    insta::assert_snapshot!(search("keyboard"), @"[0, 2]");
+
+    // nor in the french attributes
+    index
+        .update_settings(|s| {
+            s.set_localized_attributes_rules(vec![LocalizedAttributesRule::new(
+                vec!["description".to_owned()],
+                vec![charabia::Language::Fra],
+            )]);
+            s.set_locale_typo_budgets(vec![LocaleTypoBudget {
+                locales: vec![charabia::Language::Fra],
+                budget: TypoBudget { max_typos: Some(0), ..Default::default() },
+            }]);
+        })
+        .unwrap();
+    insta::assert_snapshot!(search("keyboard"), @"[0]");
+
+    // the SKUs accept typos in short words again
+    index
+        .update_settings(|s| {
+            s.set_attribute_typo_budgets(vec![AttributeTypoBudget {
+                attribute_patterns: vec!["sku".to_owned()].into(),
+                budget: TypoBudget { min_word_len_one_typo: Some(4), ..Default::default() },
+            }])
+        })
+        .unwrap();
+    insta::assert_snapshot!(search("mose"), @"[2, 3]");
+    insta::assert_snapshot!(search("wirelesz"), @"[]");
+}
@@ -127,6 +127,9 @@ impl<'a> Similar<'a> {
             document_scores,
             degraded: false,
             used_negative_operator: false,
+            disjunctive_candidates: Default::default(),
+            applied_rules: Vec::new(),
+            query_suggestions: Vec::new(),
         })
     }
 }
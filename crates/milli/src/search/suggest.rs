@@ -0,0 +1,268 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use charabia::TokenizerBuilder;
+use fst::automaton::{Automaton, Str};
+use fst::{IntoStreamer, Streamer};
+use roaring::RoaringBitmap;
+
+use crate::phonetic::is_phonetic_code;
+use crate::proximity::ProximityPrecision;
+use crate::search::new::Word;
+use crate::stemming::is_stem;
+use crate::{
+    filtered_universe, CboRoaringBitmapLenCodec, Filter, Index, QuerySuggestion, Result,
+    SearchContext,
+};
+
+/// Maximum number of words of the index starting with the last word of the query
+/// considered to complete it, the ones contained in the most documents are kept.
+const MAX_COMPLETED_WORD_COUNT: usize = 1_000;
+/// Maximum number of words following a word considered to complete the query with a phrase,
+/// the ones following it in the most documents are kept.
+const MAX_NEXT_WORD_COUNT: usize = 100;
+
+/// Keeps the `max` most frequent words pushed into it, the smallest one first on equal counts.
+struct MostFrequentWords {
+    max: usize,
+    heap: BinaryHeap<Reverse<(u64, Reverse<String>)>>,
+}
+
+impl MostFrequentWords {
+    fn new(max: usize) -> Self {
+        Self { max, heap: BinaryHeap::new() }
+    }
+
+    fn push(&mut self, count: u64, word: &str) {
+        if self.heap.len() < self.max {
+            self.heap.push(Reverse((count, Reverse(word.to_owned()))));
+        } else if let Some(mut least_frequent) = self.heap.peek_mut() {
+            let Reverse((least_count, Reverse(least_word))) = &*least_frequent;
+            if (count, Reverse(word)) > (*least_count, Reverse(least_word.as_str())) {
+                *least_frequent = Reverse((count, Reverse(word.to_owned())));
+            }
+        }
+    }
+
+    /// Returns the words, the most frequent first.
+    fn into_words(self) -> Vec<String> {
+        self.heap.into_sorted_vec().into_iter().map(|Reverse((_, Reverse(word)))| word).collect()
+    }
+}
+
+/// Completes a query being typed with the words of the index, the most frequent first.
+///
+/// The last word of the query is completed with the words of the index starting with it,
+/// and with the phrases formed by these words and the words following them in the documents.
+/// When the query ends with a separator, it is completed with the words following its last word.
+pub struct Suggest<'a> {
+    query: String,
+    // this should be linked to the String in the query
+    filter: Option<Filter<'a>>,
+    searchable_attributes: Option<&'a [String]>,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Suggest<'a> {
+    pub fn new(query: String, limit: usize, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self { query, filter: None, searchable_attributes: None, limit, rtxn, index }
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Restricts the words completing the query to the ones of these attributes.
+    pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Self {
+        self.searchable_attributes = Some(searchable);
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<QuerySuggestion>> {
+        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            ctx.attributes_to_search_on(searchable_attributes)?;
+        }
+
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        let separators = self.index.allowed_separators(self.rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        let dictionary = self.index.dictionary(self.rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+
+        let mut tokenizer_builder = TokenizerBuilder::new();
+        if let Some(ref stop_words) = stop_words {
+            tokenizer_builder.stop_words(stop_words);
+        }
+        if let Some(ref separators) = separators {
+            tokenizer_builder.separators(separators);
+        }
+        if let Some(ref dictionary) = dictionary {
+            tokenizer_builder.words_dict(dictionary);
+        }
+        let tokenizer = tokenizer_builder.build();
+
+        let mut words: Vec<_> = tokenizer
+            .tokenize(&self.query)
+            .filter(|token| token.is_word() || token.is_stopword())
+            .collect();
+
+        // the last word is being typed unless the query ends with a separator
+        let prefix = match words.last() {
+            Some(token) if token.byte_end == self.query.len() => words.pop(),
+            _ => None,
+        };
+
+        // the completions must match all the words already typed, the stop words are not indexed
+        let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        for token in words.iter().filter(|token| token.is_word()) {
+            let word = ctx.word_interner.insert(token.lemma().to_owned());
+            universe = ctx.word_docids(Some(&universe), Word::Original(word))?.unwrap_or_default();
+        }
+        if universe.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (typed_query, completions) = match prefix {
+            Some(prefix) => {
+                let completions = self.complete_prefix(&mut ctx, prefix.lemma(), &universe)?;
+                (&self.query[..prefix.byte_start], completions)
+            }
+            None => match words.last().filter(|token| token.is_word()) {
+                Some(token) => {
+                    (self.query.as_str(), next_words(&mut ctx, token.lemma(), &universe)?)
+                }
+                None => return Ok(Vec::new()),
+            },
+        };
+
+        let mut suggestions: Vec<_> = completions
+            .into_iter()
+            .map(|(completion, docids)| QuerySuggestion {
+                query: format!("{typed_query}{completion}"),
+                estimated_hits: docids.len(),
+            })
+            .collect();
+        suggestions.sort_by(|left, right| {
+            (Reverse(left.estimated_hits), &left.query)
+                .cmp(&(Reverse(right.estimated_hits), &right.query))
+        });
+        suggestions.truncate(self.limit);
+
+        Ok(suggestions)
+    }
+
+    /// Returns the words starting with the prefix and the phrases they start,
+    /// along with the documents of the universe containing them.
+    fn complete_prefix(
+        &self,
+        ctx: &mut SearchContext<'_>,
+        prefix: &str,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<(String, RoaringBitmap)>> {
+        // the prefix databases quickly tell whether there is anything to complete
+        let interned_prefix = ctx.word_interner.insert(prefix.to_owned());
+        if let Some(docids) =
+            ctx.word_prefix_docids(Some(universe), Word::Original(interned_prefix))?
+        {
+            if docids.is_empty() {
+                return Ok(Vec::new());
+            }
+        }
+
+        // the words are ranked by the number of documents containing them before being truncated,
+        // the lengths of the bitmaps are read without decoding them
+        let word_docids_len = self.index.word_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+        let fst = ctx.get_words_fst()?;
+        let mut stream = fst.search(Str::new(prefix).starts_with()).into_stream();
+        let mut most_frequent = MostFrequentWords::new(MAX_COMPLETED_WORD_COUNT);
+        while let Some(word) = stream.next() {
+            let word = std::str::from_utf8(word)?;
+            if is_stem(word) || is_phonetic_code(word) {
+                continue;
+            }
+            if let Some(count) = word_docids_len.get(self.rtxn, word)? {
+                most_frequent.push(count, word);
+            }
+        }
+
+        let mut words = Vec::new();
+        for word in most_frequent.into_words() {
+            let word = ctx.word_interner.insert(word);
+            if let Some(docids) = ctx.word_docids(Some(universe), Word::Original(word))? {
+                if !docids.is_empty() {
+                    words.push((word, docids));
+                }
+            }
+        }
+        words.sort_by_key(|(_, docids)| Reverse(docids.len()));
+        words.truncate(self.limit);
+
+        let mut completions = Vec::new();
+        for (word, docids) in words {
+            let word = ctx.word_interner.get(word).to_owned();
+            for (next_word, phrase_docids) in next_words(ctx, &word, &docids)? {
+                completions.push((format!("{word} {next_word}"), phrase_docids));
+            }
+            completions.push((word, docids));
+        }
+
+        Ok(completions)
+    }
+}
+
+/// Returns the words directly following the word in the documents of the universe,
+/// along with these documents.
+fn next_words(
+    ctx: &mut SearchContext<'_>,
+    word: &str,
+    universe: &RoaringBitmap,
+) -> Result<Vec<(String, RoaringBitmap)>> {
+    // the positions of the words are not known when the proximity is computed by attribute
+    if ctx.index.proximity_precision(ctx.txn)?.unwrap_or_default() != ProximityPrecision::ByWord {
+        return Ok(Vec::new());
+    }
+
+    // the following words are ranked by the number of documents containing the pair
+    // before being truncated, the lengths of the bitmaps are read without decoding them
+    let mut most_frequent = MostFrequentWords::new(MAX_NEXT_WORD_COUNT);
+    let key = (1, word, "");
+    let iter = ctx
+        .index
+        .word_pair_proximity_docids
+        .remap_data_type::<CboRoaringBitmapLenCodec>()
+        .prefix_iter(ctx.txn, &key)?;
+    for result in iter {
+        let ((_, _, next_word), count) = result?;
+        if !is_stem(next_word) && !is_phonetic_code(next_word) {
+            most_frequent.push(count, next_word);
+        }
+    }
+    let next_words = most_frequent.into_words();
+
+    let word = ctx.word_interner.insert(word.to_owned());
+    let mut phrases = Vec::new();
+    for next_word in next_words {
+        let interned = ctx.word_interner.insert(next_word.clone());
+        // the pairs of words are not restricted to the searchable attributes, the words are
+        let Some(next_word_docids) = ctx.word_docids(Some(universe), Word::Original(interned))?
+        else {
+            continue;
+        };
+        let Some(pair_docids) =
+            ctx.get_db_word_pair_proximity_docids(Some(&next_word_docids), word, interned, 1)?
+        else {
+            continue;
+        };
+        if !pair_docids.is_empty() {
+            phrases.push((next_word, pair_docids));
+        }
+    }
+
+    Ok(phrases)
+}
@@ -0,0 +1,658 @@
+//! Merchandising rules pin, hide or boost documents for the searches matching their condition,
+//! query rules rewrite the query of the searches before they are executed.
+//!
+//! The rules are stored in the index settings, associated with an identifier that is returned
+//! in the search results when the rule is applied.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+use deserr::Deserr;
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::score_details::{Pinned, ScoreDetails};
+use crate::search::new::Boost;
+use crate::{normalize_facet, DocumentId, Filter, Index, Result, SearchResult, UserError};
+
+/// A rule applied to the searches matching its condition.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchRule {
+    #[serde(default)]
+    #[deserr(default)]
+    pub condition: SearchRuleCondition,
+    #[serde(default)]
+    #[deserr(default)]
+    pub actions: SearchRuleActions,
+}
+
+/// The condition a search must fulfill for the rule to be applied, a rule without
+/// condition is applied to every search.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchRuleCondition {
+    /// The words the query must match, compared case-insensitively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub query: Option<String>,
+    /// How the words of the query must match the words of the condition.
+    #[serde(default)]
+    #[deserr(default)]
+    pub anchoring: QueryAnchoring,
+    /// A filter whose top-level conditions must all be part of the filter of the search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum QueryAnchoring {
+    /// The query is made of the words of the condition.
+    #[default]
+    Is,
+    /// The query starts with the words of the condition.
+    StartsWith,
+    /// The query ends with the words of the condition.
+    EndsWith,
+    /// The words of the condition appear consecutively in the query.
+    Contains,
+}
+
+impl QueryAnchoring {
+    /// Returns the range of the words of the query matching the words of the pattern.
+    fn find(self, words: &[String], pattern: &[String]) -> Option<Range<usize>> {
+        match self {
+            QueryAnchoring::Is => (words == pattern).then_some(0..words.len()),
+            QueryAnchoring::StartsWith => words.starts_with(pattern).then_some(0..pattern.len()),
+            QueryAnchoring::EndsWith => {
+                words.ends_with(pattern).then_some(words.len() - pattern.len()..words.len())
+            }
+            QueryAnchoring::Contains if pattern.is_empty() => Some(0..0),
+            QueryAnchoring::Contains => words
+                .windows(pattern.len())
+                .position(|window| window == pattern)
+                .map(|start| start..start + pattern.len()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchRuleActions {
+    /// The documents to return at a fixed position, as long as they match the filter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub pin: Vec<PinnedDocument>,
+    /// The ids of the documents to remove from the results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub hide: Vec<String>,
+    /// The filters whose matching documents are ranked higher or lower.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub boost: Vec<SearchRuleBoost>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocument {
+    pub id: String,
+    /// The position of the document in the results, starting at `0`.
+    pub position: usize,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchRuleBoost {
+    pub filter: String,
+    pub weight: BoostWeight,
+}
+
+/// The weight of a boost, it can only be built from a finite number.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Deserr, ToSchema)]
+#[serde(try_from = "f64", into = "f64")]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidBoostWeight)]
+#[schema(value_type = f64)]
+pub struct BoostWeight(f64);
+
+impl Eq for BoostWeight {}
+
+impl BoostWeight {
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for BoostWeight {
+    type Error = InvalidBoostWeight;
+
+    fn try_from(weight: f64) -> std::result::Result<Self, Self::Error> {
+        if weight.is_finite() {
+            Ok(BoostWeight(weight))
+        } else {
+            Err(InvalidBoostWeight(weight))
+        }
+    }
+}
+
+impl From<BoostWeight> for f64 {
+    fn from(weight: BoostWeight) -> Self {
+        weight.0
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidBoostWeight(f64);
+
+impl fmt::Display for InvalidBoostWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The weight of a boost must be a finite number, found `{}`.", self.0)
+    }
+}
+
+impl std::error::Error for InvalidBoostWeight {}
+
+impl SearchRule {
+    /// Checks that the filters of the rule are valid.
+    pub fn validate(&self, id: &str) -> Result<()> {
+        self.condition_filter(id)?;
+        self.boosts(id)?;
+        Ok(())
+    }
+
+    /// Parses the filter of the condition of the rule.
+    pub fn condition_filter(&self, id: &str) -> Result<Option<Filter<'_>>> {
+        match &self.condition.filter {
+            Some(filter) => {
+                Filter::from_str(filter).map_err(|error| invalid_search_rule(id, error.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Parses the filters of the boosts of the rule, an empty filter boosts nothing.
+    pub fn boosts(&self, id: &str) -> Result<Vec<Boost<'_>>> {
+        let mut boosts = Vec::with_capacity(self.actions.boost.len());
+        for (index, SearchRuleBoost { filter, weight }) in self.actions.boost.iter().enumerate() {
+            let filter = Filter::from_str(filter).map_err(|error| {
+                invalid_search_rule(id, format!("Invalid boost at index {index}: {error}"))
+            })?;
+            if let Some(filter) = filter {
+                boosts.push(Boost { filter, weight: weight.get() });
+            }
+        }
+        Ok(boosts)
+    }
+
+    /// Whether the rule applies to a search with the given query and filter,
+    /// `condition` is the filter of its condition, see [`Self::condition_filter`].
+    pub fn matches(
+        &self,
+        query: &str,
+        condition: Option<&Filter<'_>>,
+        filter: Option<&Filter<'_>>,
+    ) -> bool {
+        let SearchRuleCondition { query: pattern, anchoring, filter: _ } = &self.condition;
+
+        if let Some(pattern) = pattern {
+            let words = normalized_words(query);
+            if anchoring.find(&words, &normalized_words(pattern)).is_none() {
+                return false;
+            }
+        }
+
+        match condition {
+            Some(condition) => filter.is_some_and(|filter| filter.includes(condition)),
+            None => true,
+        }
+    }
+}
+
+fn invalid_search_rule(id: &str, error: String) -> crate::Error {
+    UserError::InvalidSearchRule { id: id.to_string(), error }.into()
+}
+
+fn normalized_words(text: &str) -> Vec<String> {
+    normalize_facet(text).split_whitespace().map(String::from).collect()
+}
+
+/// A rule rewriting the queries matching its condition.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRule {
+    pub condition: QueryRuleCondition,
+    #[serde(default)]
+    #[deserr(default)]
+    pub consequence: QueryRuleConsequence,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleCondition {
+    /// The words the query must match, compared case-insensitively.
+    pub query: String,
+    /// How the words of the query must match the words of the condition.
+    #[serde(default)]
+    #[deserr(default)]
+    pub anchoring: QueryAnchoring,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleConsequence {
+    /// The words replacing the words of the query matching the condition,
+    /// an empty string removes them from the query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub replace_with: Option<String>,
+    /// A filter added to the filter of the search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<String>,
+    /// A URL returned in the search results, to which the user should be redirected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub redirect: Option<String>,
+}
+
+impl QueryRule {
+    /// Checks that the filter of the rule is valid.
+    pub fn validate(&self, id: &str) -> Result<()> {
+        if let Some(filter) = &self.consequence.filter {
+            Filter::from_str(filter).map_err(|error| UserError::InvalidQueryRule {
+                id: id.to_string(),
+                error: error.to_string(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the query rewritten by the rule, or `None` when the query doesn't match
+    /// the condition of the rule.
+    ///
+    /// The words of the query that are not replaced are kept as they were written.
+    pub fn rewrite(&self, query: &str) -> Option<String> {
+        let QueryRuleCondition { query: pattern, anchoring } = &self.condition;
+        let words: Vec<&str> = query.split_whitespace().collect();
+        let normalized: Vec<_> = words.iter().map(|word| normalize_facet(word)).collect();
+        let range = anchoring.find(&normalized, &normalized_words(pattern))?;
+
+        let rewritten = match &self.consequence.replace_with {
+            Some(replacement) => {
+                let replacement = replacement.split_whitespace();
+                let (before, after) = (&words[..range.start], &words[range.end..]);
+                before.iter().copied().chain(replacement).chain(after.iter().copied()).collect()
+            }
+            None => words,
+        };
+        Some(rewritten.join(" "))
+    }
+}
+
+/// The actions of the rules matching a search, borrowing the filters of their boosts
+/// from the rules of the index.
+#[derive(Debug, Default)]
+pub(crate) struct MatchedRules<'r> {
+    /// The ids of the matching rules, in lexicographic order.
+    pub ids: Vec<String>,
+    pub hidden: RoaringBitmap,
+    /// The pinned documents sorted by position.
+    pub pinned: Vec<(usize, DocumentId)>,
+    boosts: Vec<Boost<'r>>,
+}
+
+impl<'r> MatchedRules<'r> {
+    /// Parses the filters of the rules once and keeps the actions of the matching ones.
+    pub fn new(
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        rules: &'r BTreeMap<String, SearchRule>,
+        query: Option<&str>,
+        filter: Option<&Filter<'_>>,
+    ) -> Result<Self> {
+        let external_documents_ids = index.external_documents_ids();
+        let mut matched = Self::default();
+        let mut pinned_docids = HashSet::new();
+        for (id, rule) in rules {
+            let condition = rule.condition_filter(id)?;
+            if !rule.matches(query.unwrap_or_default(), condition.as_ref(), filter) {
+                continue;
+            }
+
+            let SearchRuleActions { pin, hide, boost: _ } = &rule.actions;
+            for PinnedDocument { id, position } in pin {
+                if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+                    if pinned_docids.insert(docid) {
+                        matched.pinned.push((*position, docid));
+                    }
+                }
+            }
+            for id in hide {
+                if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+                    matched.hidden.insert(docid);
+                }
+            }
+            matched.boosts.extend(rule.boosts(id)?);
+            matched.ids.push(id.clone());
+        }
+
+        // a hidden document cannot be pinned
+        matched.pinned.retain(|(_, docid)| !matched.hidden.contains(*docid));
+        matched.pinned.sort_by_key(|(position, _)| *position);
+
+        Ok(matched)
+    }
+
+    /// The boosts of the matching rules.
+    pub fn boosts(&self) -> &[Boost<'r>] {
+        &self.boosts
+    }
+
+    pub fn pinned_docids(&self) -> RoaringBitmap {
+        self.pinned.iter().map(|(_, docid)| *docid).collect()
+    }
+
+    /// Inserts the pinned documents at their position in the results, which must have been
+    /// retrieved from the first one, and only keeps the requested page.
+    ///
+    /// Only the pinned documents that are part of the candidates are inserted, a pinned document
+    /// whose position is after the last result is appended when there are no more results.
+    ///
+    /// The pinned documents are not ranked, so they are not subject to the ranking score
+    /// threshold and are given a perfect score through their [`ScoreDetails::Pinned`] details.
+    pub fn paginate(&self, mut result: SearchResult, offset: usize, limit: usize) -> SearchResult {
+        let length = offset + limit;
+        let exhausted = result.documents_ids.len() < length;
+        for &(position, docid) in &self.pinned {
+            if !result.candidates.contains(docid) {
+                continue;
+            }
+            let position = if position <= result.documents_ids.len() {
+                position
+            } else if exhausted {
+                result.documents_ids.len()
+            } else {
+                break;
+            };
+            result.documents_ids.insert(position, docid);
+            result
+                .document_scores
+                .insert(position, vec![ScoreDetails::Pinned(Pinned { position })]);
+        }
+
+        result.documents_ids.truncate(length);
+        result.document_scores.truncate(length);
+        let offset = offset.min(result.documents_ids.len());
+        result.documents_ids.drain(..offset);
+        result.document_scores.drain(..offset);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+    use crate::score_details::ScoringStrategy;
+    use crate::{FilterableAttributesRule, Search};
+
+    fn rule(query: &str, anchoring: QueryAnchoring, filter: Option<&str>) -> SearchRule {
+        SearchRule {
+            condition: SearchRuleCondition {
+                query: Some(query.to_string()),
+                anchoring,
+                filter: filter.map(String::from),
+            },
+            actions: SearchRuleActions::default(),
+        }
+    }
+
+    #[test]
+    fn rule_matches_query() {
+        let is = rule("Blue  Shoes", QueryAnchoring::Is, None);
+        assert!(is.matches("blue shoes", None, None));
+        assert!(!is.matches("blue shoes for kids", None, None));
+
+        let starts_with = rule("blue shoes", QueryAnchoring::StartsWith, None);
+        assert!(starts_with.matches("BLUE shoes for kids", None, None));
+        assert!(!starts_with.matches("dark blue shoes", None, None));
+
+        let ends_with = rule("blue shoes", QueryAnchoring::EndsWith, None);
+        assert!(ends_with.matches("dark blue shoes", None, None));
+        assert!(!ends_with.matches("blue shoes for kids", None, None));
+
+        let contains = rule("blue shoes", QueryAnchoring::Contains, None);
+        assert!(contains.matches("dark blue shoes for kids", None, None));
+        assert!(!contains.matches("blue dark shoes", None, None));
+
+        // an empty query condition only matches the placeholder searches
+        let placeholder = rule("", QueryAnchoring::Is, None);
+        assert!(placeholder.matches("", None, None));
+        assert!(!placeholder.matches("shoes", None, None));
+
+        // without a query condition every query matches
+        assert!(SearchRule::default().matches("shoes", None, None));
+    }
+
+    #[test]
+    fn query_rule_rewrite() {
+        let query_rule = |query: &str, anchoring, replace_with: Option<&str>| QueryRule {
+            condition: QueryRuleCondition { query: query.to_string(), anchoring },
+            consequence: QueryRuleConsequence {
+                replace_with: replace_with.map(String::from),
+                filter: None,
+                redirect: None,
+            },
+        };
+
+        let strip = query_rule("cheap", QueryAnchoring::Contains, Some(""));
+        assert_eq!(strip.rewrite("Cheap  Red shoes").as_deref(), Some("Red shoes"));
+        assert_eq!(strip.rewrite("red cheap").as_deref(), Some("red"));
+        assert_eq!(strip.rewrite("cheaper shoes"), None);
+
+        let synonym = query_rule("tee shirt", QueryAnchoring::EndsWith, Some("t-shirt"));
+        assert_eq!(synonym.rewrite("Blue Tee shirt").as_deref(), Some("Blue t-shirt"));
+        assert_eq!(synonym.rewrite("tee shirt blue"), None);
+
+        // without a replacement the query is kept as is
+        let redirect = query_rule("returns", QueryAnchoring::Is, None);
+        assert_eq!(redirect.rewrite("Returns").as_deref(), Some("Returns"));
+        assert_eq!(redirect.rewrite("returns policy"), None);
+    }
+
+    #[test]
+    fn rule_matches_filter() {
+        let rule = rule("shoes", QueryAnchoring::Is, Some("brand = nike AND size > 40"));
+        let filter = Filter::from_str("size > 40 AND brand = nike AND color = blue").unwrap();
+        let condition = rule.condition_filter("rule").unwrap();
+        assert!(rule.matches("shoes", condition.as_ref(), filter.as_ref()));
+
+        let filter = Filter::from_str("brand = nike").unwrap();
+        assert!(!rule.matches("shoes", condition.as_ref(), filter.as_ref()));
+        assert!(!rule.matches("shoes", condition.as_ref(), None));
+    }
+
+    #[test]
+    fn boost_weight_is_finite() {
+        assert_eq!(BoostWeight::try_from(-1.5).unwrap().get(), -1.5);
+        assert!(BoostWeight::try_from(f64::NAN).is_err());
+        assert!(BoostWeight::try_from(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn invalid_boost_filter() {
+        let mut rule = SearchRule::default();
+        rule.actions.boost.push(SearchRuleBoost {
+            filter: S("brand ="),
+            weight: BoostWeight::try_from(2.0).unwrap(),
+        });
+        let error = rule.boosts("promo").unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UserError(UserError::InvalidSearchRule { ref id, ref error })
+                if id == "promo" && error.starts_with("Invalid boost at index 0:")
+        ));
+    }
+
+    #[test]
+    fn pinned_and_hidden_documents() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| {
+                s.set_primary_key(S("id"));
+                s.set_filterable_fields(vec![FilterableAttributesRule::Field(S("color"))]);
+                let pin = |id: &str, position| PinnedDocument { id: id.to_string(), position };
+                s.set_search_rules(BTreeMap::from([
+                    (
+                        S("promo"),
+                        SearchRule {
+                            condition: SearchRuleCondition::default(),
+                            actions: SearchRuleActions {
+                                pin: vec![pin("4", 0), pin("5", 2), pin("missing", 1)],
+                                hide: vec![S("1")],
+                                boost: Vec::new(),
+                            },
+                        },
+                    ),
+                    (
+                        S("blue"),
+                        SearchRule {
+                            condition: SearchRuleCondition {
+                                filter: Some(S("color = blue")),
+                                ..Default::default()
+                            },
+                            actions: SearchRuleActions {
+                                pin: vec![pin("3", 10)],
+                                ..Default::default()
+                            },
+                        },
+                    ),
+                ]));
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "color": "red" },
+                { "id": 1, "color": "red" },
+                { "id": 2, "color": "blue" },
+                { "id": 3, "color": "blue" },
+                { "id": 4, "color": "green" },
+                { "id": 5, "color": "blue" },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let result = Search::new(&txn, &index).execute().unwrap();
+        assert_eq!(result.documents_ids, [4, 0, 5, 2, 3]);
+        assert_eq!(result.candidates.len(), 5);
+        assert_eq!(result.applied_rules, [S("promo")]);
+
+        // the pinned documents are at their position in every page
+        let result = Search::new(&txn, &index).offset(1).limit(2).execute().unwrap();
+        assert_eq!(result.documents_ids, [0, 5]);
+
+        // the pinned documents must match the filter and are appended to the last page
+        let filter = Filter::from_str("color = blue").unwrap().unwrap();
+        let result = Search::new(&txn, &index).filter(filter).execute().unwrap();
+        assert_eq!(result.documents_ids, [2, 5, 3]);
+        assert_eq!(result.applied_rules, [S("blue"), S("promo")]);
+    }
+
+    #[test]
+    fn pinned_documents_within_max_total_hits() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| {
+                s.set_primary_key(S("id"));
+                s.set_pagination_max_total_hits(3);
+                let pin = |id: &str, position| PinnedDocument { id: id.to_string(), position };
+                s.set_search_rules(BTreeMap::from([(
+                    S("promo"),
+                    SearchRule {
+                        condition: SearchRuleCondition::default(),
+                        actions: SearchRuleActions {
+                            pin: vec![pin("4", 1), pin("5", 3)],
+                            ..Default::default()
+                        },
+                    },
+                )]));
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0 }, { "id": 1 }, { "id": 2 }, { "id": 3 }, { "id": 4 }, { "id": 5 },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        // the document pinned after the max total hits is not returned
+        let result = Search::new(&txn, &index).limit(usize::MAX).execute().unwrap();
+        assert_eq!(result.documents_ids, [0, 4, 1]);
+
+        let result = Search::new(&txn, &index).offset(2).limit(usize::MAX).execute().unwrap();
+        assert_eq!(result.documents_ids, [1]);
+
+        let result = Search::new(&txn, &index).offset(usize::MAX).execute().unwrap();
+        assert!(result.documents_ids.is_empty());
+    }
+
+    #[test]
+    fn pinned_documents_with_distinct() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| {
+                s.set_primary_key(S("id"));
+                s.set_filterable_fields(vec![FilterableAttributesRule::Field(S("group"))]);
+                let pin = |id: &str, position| PinnedDocument { id: id.to_string(), position };
+                s.set_search_rules(BTreeMap::from([(
+                    S("promo"),
+                    SearchRule {
+                        condition: SearchRuleCondition::default(),
+                        actions: SearchRuleActions {
+                            pin: vec![pin("3", 0), pin("1", 2), pin("0", 5)],
+                            ..Default::default()
+                        },
+                    },
+                )]));
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "group": "a" },
+                { "id": 1, "group": "a" },
+                { "id": 2, "group": "b" },
+                { "id": 3, "group": "c" },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        // the document 0 is dropped because the document 1 of its group is pinned before it
+        let mut search = Search::new(&txn, &index);
+        search.distinct(S("group")).scoring_strategy(ScoringStrategy::Detailed);
+        let result = search.execute().unwrap();
+        assert_eq!(result.documents_ids, [3, 2, 1]);
+        assert_eq!(result.candidates.len(), 3);
+        assert_eq!(result.document_scores[0], [ScoreDetails::Pinned(Pinned { position: 0 })]);
+        assert_eq!(result.document_scores[2], [ScoreDetails::Pinned(Pinned { position: 2 })]);
+    }
+}
@@ -0,0 +1,75 @@
+use charabia::Language;
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// The character prefixing the stems in the word databases.
+///
+/// It can't be part of a token, so that the stems are never mistaken for the words of the
+/// documents, nor derived from them by typos, and can be ranked on their own.
+pub const STEM_PREFIX: char = '\u{2}';
+
+/// Returns the stemmer of the language, if the language is supported.
+fn stemmer(language: Language) -> Option<Stemmer> {
+    let algorithm = match language {
+        Language::Ara => Algorithm::Arabic,
+        Language::Dan => Algorithm::Danish,
+        Language::Deu => Algorithm::German,
+        Language::Ell => Algorithm::Greek,
+        Language::Eng => Algorithm::English,
+        Language::Fin => Algorithm::Finnish,
+        Language::Fra => Algorithm::French,
+        Language::Hun => Algorithm::Hungarian,
+        Language::Ita => Algorithm::Italian,
+        Language::Nld => Algorithm::Dutch,
+        Language::Nob => Algorithm::Norwegian,
+        Language::Por => Algorithm::Portuguese,
+        Language::Ron => Algorithm::Romanian,
+        Language::Rus => Algorithm::Russian,
+        Language::Spa => Algorithm::Spanish,
+        Language::Swe => Algorithm::Swedish,
+        Language::Tam => Algorithm::Tamil,
+        Language::Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// Returns the distinct stems of the word in the supported languages, prefixed by [`STEM_PREFIX`].
+///
+/// The stems are indexed along the words of the stemmed attributes and searched as
+/// derivations of the query words, so that `running` matches `runs`. A word being its own
+/// stem is stemmed too, so that `run` matches `running`.
+pub fn stems(languages: &[Language], word: &str) -> Vec<String> {
+    let mut stems: Vec<String> = Vec::new();
+    for stemmer in languages.iter().filter_map(|language| stemmer(*language)) {
+        let stem = stemmer.stem(word);
+        if stem.is_empty() {
+            continue;
+        }
+        let stem = format!("{STEM_PREFIX}{stem}");
+        if !stems.contains(&stem) {
+            stems.push(stem);
+        }
+    }
+    stems
+}
+
+/// Returns `true` if the word of the word databases is a stem.
+pub fn is_stem(word: &str) -> bool {
+    word.starts_with(STEM_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_in_languages() {
+        assert_eq!(stems(&[Language::Eng], "running"), vec!["\u{2}run".to_string()]);
+        assert_eq!(stems(&[Language::Eng], "run"), vec!["\u{2}run".to_string()]);
+        assert!(stems(&[Language::Eng], "running").iter().all(|stem| is_stem(stem)));
+        // the languages without stemmer are ignored
+        assert!(stems(&[Language::Jpn], "running").is_empty());
+        // the stems are deduplicated
+        assert_eq!(stems(&[Language::Eng, Language::Fra], "run"), vec!["\u{2}run".to_string()]);
+    }
+}
@@ -0,0 +1,53 @@
+use charabia::Language;
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{Result, UserError};
+
+/// A set of synonyms, optionally restricted to the searches in some locales.
+///
+/// The words of an equivalent set are all synonyms of each other, while the words of a one-way
+/// set are only synonyms of its input: searching for the input also matches the synonyms
+/// but searching for a synonym doesn't match the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SynonymSet {
+    #[serde(default)]
+    pub kind: SynonymSetKind,
+    /// The words whose synonyms are the words of the set, only used by one-way sets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input: Vec<String>,
+    pub synonyms: Vec<String>,
+    /// The locales of the searches to which the set is applied, the set is applied
+    /// to every search when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<String>)]
+    pub locales: Vec<Language>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum SynonymSetKind {
+    #[default]
+    Equivalent,
+    OneWay,
+}
+
+impl SynonymSet {
+    /// Checks that only the one-way sets define an input.
+    pub fn validate(&self, index: usize) -> Result<()> {
+        let error = match self.kind {
+            SynonymSetKind::Equivalent if !self.input.is_empty() => {
+                "An equivalent set of synonyms cannot define an `input`."
+            }
+            SynonymSetKind::OneWay if self.input.is_empty() => {
+                "A one-way set of synonyms must define an `input`."
+            }
+            _ => return Ok(()),
+        };
+
+        Err(UserError::InvalidSynonymSet { index, error: error.to_string() }.into())
+    }
+}
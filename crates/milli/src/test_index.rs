@@ -8,7 +8,7 @@ use maplit::btreemap;
 use memmap2::Mmap;
 use tempfile::TempDir;
 
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::{Error, InternalError};
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
 use crate::progress::Progress;
@@ -1118,6 +1118,9 @@ fn bug_3021_fourth() {
         mut documents_ids,
         degraded: _,
         used_negative_operator: _,
+        disjunctive_candidates: _,
+        applied_rules: _,
+        query_suggestions: _,
     } = search.execute().unwrap();
     let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
     documents_ids.sort_unstable();
@@ -1205,6 +1208,35 @@ fn unexpected_extra_fields_in_geo_field() {
     db_snap!(index, geo_faceted_documents_ids); // ensure that no documents were inserted
 }
 
+#[test]
+fn bad_geojson_field() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_primary_key("id".to_string());
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                RESERVED_GEOJSON_FIELD_NAME.to_string(),
+            )]);
+        })
+        .unwrap();
+
+    let err = index
+        .add_documents(
+            documents!({ "id" : "doggo", RESERVED_GEOJSON_FIELD_NAME: { "lat": 1, "lng": 2 }}),
+        )
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"doggo"` is not a valid GeoJSON geometry: the `type` field is missing."###);
+
+    let err = index
+        .add_documents(documents!({ "id" : "doggo", RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1]]] }}))
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"doggo"` is not a valid GeoJSON geometry: a polygon ring must contain at least four positions and its first and last positions must be the same."###);
+
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.geojson_rtree(&rtxn).unwrap().is_none()); // ensure that no documents were inserted
+}
+
 #[test]
 fn swapping_searchable_attributes() {
     // See https://github.com/meilisearch/meilisearch/issues/4484
@@ -0,0 +1,223 @@
+use std::collections::BTreeSet;
+
+use fst::{IntoStreamer, Streamer};
+use heed::types::{Bytes, SerdeBincode, Str};
+use heed::{RoTxn, RwTxn};
+
+use crate::attribute_patterns::PatternMatch;
+use crate::index::main_key;
+use crate::{AttributePatterns, FieldId, Index, Result};
+
+/// The number of characters of a trigram.
+const TRIGRAM_LEN: usize = 3;
+
+/// The byte separating the trigram from the value in the keys of the trigrams FSTs.
+const SEPARATOR: u8 = 0;
+
+/// Returns the distinct trigrams of the text, none if the text is shorter than a trigram.
+pub fn trigrams(text: &str) -> BTreeSet<&str> {
+    let bounds: Vec<_> = text.char_indices().map(|(i, _)| i).chain(Some(text.len())).collect();
+    bounds.windows(TRIGRAM_LEN + 1).map(|w| &text[w[0]..w[TRIGRAM_LEN]]).collect()
+}
+
+/// Returns the keys indexing the value by its trigrams, made of the prefix, the trigram
+/// and the value.
+pub(crate) fn trigram_keys<'a>(
+    prefix: &'a [u8],
+    value: &'a str,
+) -> impl Iterator<Item = Vec<u8>> + 'a {
+    trigrams(value).into_iter().map(move |trigram| {
+        let mut key = Vec::with_capacity(prefix.len() + trigram.len() + 1 + value.len());
+        key.extend_from_slice(prefix);
+        key.extend_from_slice(trigram.as_bytes());
+        key.push(SEPARATOR);
+        key.extend_from_slice(value.as_bytes());
+        key
+    })
+}
+
+/// Returns at most `limit` values indexed under the prefix that contain the infix, by
+/// intersecting the values of its trigrams, or `None` if the infix is shorter than a trigram.
+pub(crate) fn values_containing<D: AsRef<[u8]>>(
+    fst: &fst::Set<D>,
+    prefix: &[u8],
+    infix: &str,
+    limit: usize,
+) -> Option<Vec<String>> {
+    let trigrams = trigrams(infix);
+    if trigrams.is_empty() {
+        return None;
+    }
+
+    let mut candidates: Option<BTreeSet<Vec<u8>>> = None;
+    for trigram in trigrams {
+        let mut start = prefix.to_vec();
+        start.extend_from_slice(trigram.as_bytes());
+        start.push(SEPARATOR);
+        let mut end = start.clone();
+        *end.last_mut().unwrap() = SEPARATOR + 1;
+
+        let mut values = BTreeSet::new();
+        let mut stream = fst.range().ge(&start).lt(&end).into_stream();
+        while let Some(key) = stream.next() {
+            let value = &key[start.len()..];
+            if candidates.as_ref().is_none_or(|candidates| candidates.contains(value)) {
+                values.insert(value.to_vec());
+            }
+        }
+
+        let is_empty = values.is_empty();
+        candidates = Some(values);
+        if is_empty {
+            break;
+        }
+    }
+
+    // the trigrams of a value may all be in the infix without the value containing it
+    let values = candidates
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|value| String::from_utf8(value).ok())
+        .filter(|value| value.contains(infix))
+        .take(limit)
+        .collect();
+    Some(values)
+}
+
+impl Index {
+    /* infix attributes */
+
+    pub(crate) fn put_infix_attributes(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        attributes: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(
+            wtxn,
+            main_key::INFIX_ATTRIBUTES_KEY,
+            attributes,
+        )
+    }
+
+    pub(crate) fn delete_infix_attributes(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::INFIX_ATTRIBUTES_KEY)
+    }
+
+    /// The patterns of the attributes whose words and facet values are indexed by their
+    /// trigrams, so that they can be matched by a part of them.
+    pub fn infix_attributes(&self, rtxn: &RoTxn<'_>) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<BTreeSet<String>>>()
+            .get(rtxn, main_key::INFIX_ATTRIBUTES_KEY)?)
+    }
+
+    /// Returns the ids of the fields matching the infix attributes.
+    pub fn infix_fields_ids(&self, rtxn: &RoTxn<'_>) -> Result<Vec<FieldId>> {
+        let Some(attributes) = self.infix_attributes(rtxn)? else {
+            return Ok(Vec::new());
+        };
+        let patterns = AttributePatterns::from(attributes.into_iter().collect::<Vec<_>>());
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(fields_ids_map
+            .iter()
+            .filter(|(_, name)| patterns.match_str(name) == PatternMatch::Match)
+            .map(|(fid, _)| fid)
+            .collect())
+    }
+
+    /* trigrams fsts */
+
+    pub(crate) fn put_words_trigrams_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, Bytes>().put(
+            wtxn,
+            main_key::WORDS_TRIGRAMS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_words_trigrams_fst(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::WORDS_TRIGRAMS_FST_KEY)
+    }
+
+    /// Returns the FST indexing the words of the infix attributes by their trigrams.
+    pub fn words_trigrams_fst<'t>(
+        &self,
+        rtxn: &'t RoTxn<'t>,
+    ) -> Result<Option<fst::Set<&'t [u8]>>> {
+        match self.main.remap_types::<Str, Bytes>().get(rtxn, main_key::WORDS_TRIGRAMS_FST_KEY)? {
+            Some(bytes) => Ok(Some(fst::Set::new(bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn put_facet_strings_trigrams_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, Bytes>().put(
+            wtxn,
+            main_key::FACET_STRINGS_TRIGRAMS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_facet_strings_trigrams_fst(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::FACET_STRINGS_TRIGRAMS_FST_KEY)
+    }
+
+    /// Returns the FST indexing the normalized facet strings of the infix attributes by
+    /// their trigrams, the keys being prefixed by the field id.
+    pub fn facet_strings_trigrams_fst<'t>(
+        &self,
+        rtxn: &'t RoTxn<'t>,
+    ) -> Result<Option<fst::Set<&'t [u8]>>> {
+        match self
+            .main
+            .remap_types::<Str, Bytes>()
+            .get(rtxn, main_key::FACET_STRINGS_TRIGRAMS_FST_KEY)?
+        {
+            Some(bytes) => Ok(Some(fst::Set::new(bytes)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigrams_of_text() {
+        assert_eq!(
+            trigrams("walmart").into_iter().collect::<Vec<_>>(),
+            ["alm", "art", "lma", "mar", "wal"]
+        );
+        assert_eq!(trigrams("été").into_iter().collect::<Vec<_>>(), ["été"]);
+        assert!(trigrams("ab").is_empty());
+    }
+
+    #[test]
+    fn values_containing_an_infix() {
+        let mut keys: Vec<_> = ["walmart", "marty", "smart", "martial", "art", "tram"]
+            .iter()
+            .flat_map(|word| trigram_keys(&[], word))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let fst = fst::Set::from_iter(keys).unwrap();
+
+        let values = values_containing(&fst, &[], "mart", 10).unwrap();
+        assert_eq!(values, ["martial", "marty", "smart", "walmart"]);
+        assert_eq!(values_containing(&fst, &[], "artm", 10).unwrap(), Vec::<String>::new());
+        assert_eq!(values_containing(&fst, &[], "ma", 10), None);
+    }
+}
@@ -0,0 +1,221 @@
+use charabia::Language;
+use heed::types::{SerdeJson, Str};
+use heed::{RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+
+use crate::attribute_patterns::PatternMatch;
+use crate::index::main_key;
+use crate::{AttributePatterns, FieldId, Index, LocalizedAttributesRule, Result};
+
+/// The number of typos allowed in the words of some attributes, overriding the global
+/// typo tolerance settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypoBudget {
+    /// The maximum number of typos allowed in a word, at most two.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_typos: Option<u8>,
+    /// The minimum length of a word to accept one typo, the global one if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_word_len_one_typo: Option<u8>,
+    /// The minimum length of a word to accept two typos, the global one if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_word_len_two_typos: Option<u8>,
+}
+
+impl TypoBudget {
+    /// Returns the number of typos allowed in the word, given the global minimum lengths
+    /// of the words accepting one and two typos.
+    pub fn typos_allowed(&self, word: &str, min_len_one_typo: u8, min_len_two_typos: u8) -> u8 {
+        let min_len_one_typo = self.min_word_len_one_typo.unwrap_or(min_len_one_typo);
+        let min_len_two_typos = self.min_word_len_two_typos.unwrap_or(min_len_two_typos);
+        let allowed = if word.len() < min_len_one_typo as usize {
+            0
+        } else if word.len() < min_len_two_typos as usize {
+            1
+        } else {
+            2
+        };
+        allowed.min(self.max_typos.unwrap_or(2))
+    }
+}
+
+/// The typo budget of the attributes matching some patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeTypoBudget {
+    pub attribute_patterns: AttributePatterns,
+    pub budget: TypoBudget,
+}
+
+/// The typo budget of the attributes localized in some locales by the localized attributes rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleTypoBudget {
+    pub locales: Vec<Language>,
+    pub budget: TypoBudget,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypoBudgets {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeTypoBudget>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<LocaleTypoBudget>,
+}
+
+impl TypoBudgets {
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.locales.is_empty()
+    }
+
+    /// Returns the budget of the first attribute rule matching the field or else, the budget
+    /// of the first locale rule sharing a locale with the field.
+    pub fn field_budget(
+        &self,
+        field_name: &str,
+        field_locales: Option<&[Language]>,
+    ) -> Option<&TypoBudget> {
+        let attribute = self
+            .attributes
+            .iter()
+            .find(|rule| rule.attribute_patterns.match_str(field_name) == PatternMatch::Match);
+        if let Some(rule) = attribute {
+            return Some(&rule.budget);
+        }
+
+        let field_locales = field_locales?;
+        self.locales
+            .iter()
+            .find(|rule| rule.locales.iter().any(|locale| field_locales.contains(locale)))
+            .map(|rule| &rule.budget)
+    }
+}
+
+/// The typo budgets of the typo tolerant searchable fields, resolved once per search.
+#[derive(Debug, Clone)]
+pub(crate) struct FieldsTypoBudgets {
+    min_len_one_typo: u8,
+    min_len_two_typos: u8,
+    fields: Vec<(FieldId, TypoBudget)>,
+}
+
+impl FieldsTypoBudgets {
+    /// Resolves the budgets of the fields, returns `None` if no typo budget is defined
+    /// so that the global typo tolerance settings apply to every field.
+    pub fn new<'a>(
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        fields: impl IntoIterator<Item = (&'a str, FieldId)>,
+    ) -> Result<Option<Self>> {
+        let budgets = index.typo_budgets(rtxn)?;
+        if budgets.is_empty() {
+            return Ok(None);
+        }
+
+        let localized_rules = index.localized_attributes_rules(rtxn)?.unwrap_or_default();
+        let fields = fields
+            .into_iter()
+            .map(|(name, fid)| {
+                let locales = localized_rules
+                    .iter()
+                    .find(|rule| rule.match_str(name) == PatternMatch::Match)
+                    .map(LocalizedAttributesRule::locales);
+                (fid, budgets.field_budget(name, locales).copied().unwrap_or_default())
+            })
+            .collect();
+
+        Ok(Some(Self {
+            min_len_one_typo: index.min_word_len_one_typo(rtxn)?,
+            min_len_two_typos: index.min_word_len_two_typos(rtxn)?,
+            fields,
+        }))
+    }
+
+    /// Returns the highest number of typos allowed in the word among the fields.
+    pub fn max_typos(&self, word: &str) -> u8 {
+        self.fields
+            .iter()
+            .map(|(_, budget)| {
+                budget.typos_allowed(word, self.min_len_one_typo, self.min_len_two_typos)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the fields in which the word can contain the number of typos.
+    pub fn fields_allowing(&self, word: &str, nbr_typos: u8) -> Vec<FieldId> {
+        self.fields
+            .iter()
+            .filter(|(_, budget)| {
+                budget.typos_allowed(word, self.min_len_one_typo, self.min_len_two_typos)
+                    >= nbr_typos
+            })
+            .map(|(fid, _)| *fid)
+            .collect()
+    }
+}
+
+impl Index {
+    pub fn typo_budgets(&self, txn: &RoTxn<'_>) -> heed::Result<TypoBudgets> {
+        self.main
+            .remap_types::<Str, SerdeJson<TypoBudgets>>()
+            .get(txn, main_key::TYPO_BUDGETS)
+            .map(Option::unwrap_or_default)
+    }
+
+    pub(crate) fn put_typo_budgets(
+        &self,
+        txn: &mut RwTxn<'_>,
+        budgets: &TypoBudgets,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<TypoBudgets>>().put(
+            txn,
+            main_key::TYPO_BUDGETS,
+            budgets,
+        )
+    }
+
+    pub(crate) fn delete_typo_budgets(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::TYPO_BUDGETS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_budgets() {
+        let sku = TypoBudget { max_typos: Some(0), ..Default::default() };
+        let japanese = TypoBudget { max_typos: Some(1), ..Default::default() };
+        let budgets = TypoBudgets {
+            attributes: vec![AttributeTypoBudget {
+                attribute_patterns: vec!["sku*".to_string()].into(),
+                budget: sku,
+            }],
+            locales: vec![LocaleTypoBudget { locales: vec![Language::Jpn], budget: japanese }],
+        };
+
+        assert_eq!(budgets.field_budget("sku_code", None), Some(&sku));
+        assert_eq!(budgets.field_budget("sku_code", Some(&[Language::Jpn])), Some(&sku));
+        assert_eq!(budgets.field_budget("title", Some(&[Language::Jpn])), Some(&japanese));
+        assert_eq!(budgets.field_budget("title", Some(&[Language::Eng])), None);
+        assert_eq!(budgets.field_budget("title", None), None);
+    }
+
+    #[test]
+    fn typos_allowed() {
+        let default = TypoBudget::default();
+        assert_eq!(default.typos_allowed("cat", 5, 9), 0);
+        assert_eq!(default.typos_allowed("kitten", 5, 9), 1);
+        assert_eq!(default.typos_allowed("kittenish", 5, 9), 2);
+
+        let lenient = TypoBudget { min_word_len_one_typo: Some(3), ..Default::default() };
+        assert_eq!(lenient.typos_allowed("cat", 5, 9), 1);
+
+        let capped = TypoBudget { max_typos: Some(1), ..Default::default() };
+        assert_eq!(capped.typos_allowed("kittenish", 5, 9), 1);
+    }
+}
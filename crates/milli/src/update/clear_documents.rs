@@ -48,6 +48,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             vector_arroy,
             embedder_category_id: _,
             documents,
+            geojson_geometries,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
@@ -58,9 +59,12 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         // We clean some of the main engine datastructures.
         self.index.put_words_fst(self.wtxn, &fst::Set::default())?;
         self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
+        self.index.delete_words_trigrams_fst(self.wtxn)?;
+        self.index.delete_facet_strings_trigrams_fst(self.wtxn)?;
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
@@ -95,6 +99,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         vector_arroy.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
+        geojson_geometries.clear(self.wtxn)?;
 
         Ok(number_of_documents)
     }
@@ -135,6 +140,8 @@ mod tests {
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geojson_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geojson_geometries.is_empty(&rtxn).unwrap());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
@@ -165,10 +165,22 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         .new
                         .geo_fields_ids
                         .is_some_and(|(lat, lng)| field_id == lat || field_id == lng);
-                    let del_filterable_values =
-                        del_value.map(|value| extract_facet_values(&value, del_geo_support));
-                    let add_filterable_values =
-                        add_value.map(|value| extract_facet_values(&value, add_geo_support));
+                    let del_date_support = settings_diff
+                        .old
+                        .fields_ids_map
+                        .metadata(field_id)
+                        .is_some_and(|metadata| metadata.is_date());
+                    let add_date_support = settings_diff
+                        .new
+                        .fields_ids_map
+                        .metadata(field_id)
+                        .is_some_and(|metadata| metadata.is_date());
+                    let del_filterable_values = del_value.map(|value| {
+                        extract_facet_values(&value, del_geo_support, del_date_support)
+                    });
+                    let add_filterable_values = add_value.map(|value| {
+                        extract_facet_values(&value, add_geo_support, add_date_support)
+                    });
 
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
@@ -513,13 +525,14 @@ enum FilterableValues {
 }
 
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+fn extract_facet_values(value: &Value, geo_field: bool, date_field: bool) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
         geo_field: bool,
+        date_field: bool,
     ) {
         match value {
             Value::Null => (),
@@ -541,7 +554,11 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                         )
                     }
                 }
-                let normalized = crate::normalize_facet(original);
+                let normalized = if date_field {
+                    crate::normalize_date_facet(original)
+                } else {
+                    crate::normalize_facet(original)
+                };
                 output_strings.push((normalized, original.clone()));
             }
             Value::Array(values) => {
@@ -553,6 +570,7 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             output_numbers,
                             output_strings,
                             geo_field,
+                            date_field,
                         );
                     }
                 }
@@ -569,7 +587,14 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                geo_field,
+                date_field,
+            );
             FilterableValues::Values { numbers, strings }
         }
     }
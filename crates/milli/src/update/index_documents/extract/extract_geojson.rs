@@ -0,0 +1,81 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::str::from_utf8;
+
+use serde_json::Value;
+
+use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::error::GeoError;
+use crate::geojson::Geometry;
+use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
+use crate::{try_split_array_at, DocumentId, FieldId, InternalError, Result};
+
+/// Extracts the GeoJSON geometries contained in each document under the `_geojson` field.
+///
+/// Returns the generated grenad reader containing the docid as key associated to the
+/// bincode serialized geometries.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_geojson<R: io::Read + io::Seek>(
+    obkv_documents: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    old_geojson_fid: Option<FieldId>,
+    new_geojson_fid: Option<FieldId>,
+) -> Result<grenad::Reader<BufReader<File>>> {
+    let mut writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+
+    let mut cursor = obkv_documents.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        // this must always be serialized as (docid, external_docid);
+        const SIZE_OF_DOCUMENTID: usize = std::mem::size_of::<DocumentId>();
+        let (docid_bytes, external_id_bytes) =
+            try_split_array_at::<u8, SIZE_OF_DOCUMENTID>(key).unwrap();
+        let obkv = obkv::KvReader::from_slice(value);
+        // since we only need the primary key when we throw an error
+        // we create this getter to lazily get it when needed
+        let document_id = || -> Value { from_utf8(external_id_bytes).unwrap().into() };
+
+        let del_geometry = extract_geometry(obkv, old_geojson_fid, DelAdd::Deletion, document_id)?;
+        let add_geometry = extract_geometry(obkv, new_geojson_fid, DelAdd::Addition, document_id)?;
+
+        if del_geometry != add_geometry {
+            let mut obkv = KvWriterDelAdd::memory();
+            if let Some(geometry) = del_geometry {
+                let bytes = bincode::serialize(&geometry).map_err(InternalError::BincodeError)?;
+                obkv.insert(DelAdd::Deletion, bytes)?;
+            }
+            if let Some(geometry) = add_geometry {
+                let bytes = bincode::serialize(&geometry).map_err(InternalError::BincodeError)?;
+                obkv.insert(DelAdd::Addition, bytes)?;
+            }
+            let bytes = obkv.into_inner()?;
+            writer.insert(docid_bytes, bytes)?;
+        }
+    }
+
+    writer_into_reader(writer)
+}
+
+/// Extract and validate the geometry of one side of the document.
+fn extract_geometry(
+    document: &obkv::KvReader<FieldId>,
+    geojson_fid: Option<FieldId>,
+    deladd: DelAdd,
+    document_id: impl Fn() -> Value,
+) -> Result<Option<Geometry>> {
+    let Some(geojson_fid) = geojson_fid else { return Ok(None) };
+    let geojson =
+        document.get(geojson_fid).map(KvReaderDelAdd::from_slice).and_then(|r| r.get(deladd));
+    match geojson {
+        Some(geojson) => {
+            let value = serde_json::from_slice(geojson).map_err(InternalError::SerdeJson)?;
+            Geometry::from_json(value)
+                .map_err(|error| GeoError::BadGeoJson { document_id: document_id(), error })
+                .map_err(|error| Box::new(error).into())
+        }
+        None => Ok(None),
+    }
+}
@@ -63,6 +63,18 @@ pub fn extract_word_docids<R: io::Read + io::Seek>(
         if let Some(deletion) = del_add_reader.get(DelAdd::Deletion) {
             for (_pos, word) in KvReaderU16::from_slice(deletion).iter() {
                 del_words.insert(word.to_vec());
+                // the stems and phonetic codes of the word are indexed along it
+                if let Ok(word) = std::str::from_utf8(word) {
+                    let derived_words = settings_diff.old.derived_words(fid, word);
+                    del_words.extend(derived_words.into_iter().map(String::into_bytes));
+                }
+            }
+            // and the identifier variants of the words along them
+            let words = KvReaderU16::from_slice(deletion).iter();
+            let words =
+                words.filter_map(|(pos, word)| Some((pos, std::str::from_utf8(word).ok()?)));
+            for (_pos, variant) in settings_diff.old.identifier_variants(fid, words) {
+                del_words.insert(variant.into_bytes());
             }
         }
 
@@ -70,6 +82,16 @@ pub fn extract_word_docids<R: io::Read + io::Seek>(
         if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
             for (_pos, word) in KvReaderU16::from_slice(addition).iter() {
                 add_words.insert(word.to_vec());
+                if let Ok(word) = std::str::from_utf8(word) {
+                    let derived_words = settings_diff.new.derived_words(fid, word);
+                    add_words.extend(derived_words.into_iter().map(String::into_bytes));
+                }
+            }
+            let words = KvReaderU16::from_slice(addition).iter();
+            let words =
+                words.filter_map(|(pos, word)| Some((pos, std::str::from_utf8(word).ok()?)));
+            for (_pos, variant) in settings_diff.new.identifier_variants(fid, words) {
+                add_words.insert(variant.into_bytes());
             }
         }
 
@@ -22,7 +22,7 @@ use crate::{bucketed_position, DocumentId, Result};
 pub fn extract_word_position_docids<R: io::Read + io::Seek>(
     docid_word_positions: grenad::Reader<R>,
     indexer: GrenadParameters,
-    _settings_diff: &InnerIndexSettingsDiff,
+    settings_diff: &InnerIndexSettingsDiff,
 ) -> Result<grenad::Reader<BufReader<File>>> {
     let max_memory = indexer.max_memory_by_thread();
 
@@ -42,9 +42,12 @@ pub fn extract_word_position_docids<R: io::Read + io::Seek>(
     let mut key_buffer = Vec::new();
     let mut cursor = docid_word_positions.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
-        let (document_id_bytes, _fid_bytes) = try_split_array_at(key)
+        let (document_id_bytes, fid_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let (fid_bytes, _) = try_split_array_at(fid_bytes)
             .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
         let document_id = DocumentId::from_be_bytes(document_id_bytes);
+        let fid = u16::from_be_bytes(fid_bytes);
 
         if current_document_id.is_some_and(|id| document_id != id) {
             words_position_into_sorter(
@@ -66,6 +69,19 @@ pub fn extract_word_position_docids<R: io::Read + io::Seek>(
             for (position, word_bytes) in KvReaderU16::from_slice(deletion).iter() {
                 let position = bucketed_position(position);
                 del_word_positions.insert((position, word_bytes.to_vec()));
+                // the stems and phonetic codes of the word are indexed at its position
+                if let Ok(word) = std::str::from_utf8(word_bytes) {
+                    for derived in settings_diff.old.derived_words(fid, word) {
+                        del_word_positions.insert((position, derived.into_bytes()));
+                    }
+                }
+            }
+            // and the identifier variants of the words at the position of their first word
+            let words = KvReaderU16::from_slice(deletion).iter();
+            let words =
+                words.filter_map(|(pos, word)| Some((pos, std::str::from_utf8(word).ok()?)));
+            for (position, variant) in settings_diff.old.identifier_variants(fid, words) {
+                del_word_positions.insert((bucketed_position(position), variant.into_bytes()));
             }
         }
 
@@ -74,6 +90,17 @@ pub fn extract_word_position_docids<R: io::Read + io::Seek>(
             for (position, word_bytes) in KvReaderU16::from_slice(addition).iter() {
                 let position = bucketed_position(position);
                 add_word_positions.insert((position, word_bytes.to_vec()));
+                if let Ok(word) = std::str::from_utf8(word_bytes) {
+                    for derived in settings_diff.new.derived_words(fid, word) {
+                        add_word_positions.insert((position, derived.into_bytes()));
+                    }
+                }
+            }
+            let words = KvReaderU16::from_slice(addition).iter();
+            let words =
+                words.filter_map(|(pos, word)| Some((pos, std::str::from_utf8(word).ok()?)));
+            for (position, variant) in settings_diff.new.identifier_variants(fid, words) {
+                add_word_positions.insert((bucketed_position(position), variant.into_bytes()));
             }
         }
     }
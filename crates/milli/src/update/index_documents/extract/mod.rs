@@ -4,6 +4,7 @@ mod extract_facet_string_docids;
 mod extract_fid_docid_facet_values;
 mod extract_fid_word_count_docids;
 mod extract_geo_points;
+mod extract_geojson;
 mod extract_vector_points;
 mod extract_word_docids;
 mod extract_word_pair_proximity_docids;
@@ -22,6 +23,7 @@ use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
+use self::extract_geojson::extract_geojson;
 use self::extract_vector_points::{
     extract_embeddings, extract_vector_points, ExtractedVectorPoints,
 };
@@ -239,6 +241,21 @@ fn send_original_documents_data(
         // no point in indexing vectors without embedders
         && (!settings_diff.new.embedding_configs.inner_as_ref().is_empty());
 
+    if settings_diff.run_geojson_indexing() {
+        let documents_chunk_cloned = original_documents_chunk.clone();
+        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        let (old_geojson_fid, new_geojson_fid) =
+            (settings_diff.old.geojson_fid, settings_diff.new.geojson_fid);
+        rayon::spawn(move || {
+            let result =
+                extract_geojson(documents_chunk_cloned, indexer, old_geojson_fid, new_geojson_fid);
+            let _ = match result {
+                Ok(geojson) => lmdb_writer_sx_cloned.send(Ok(TypedChunk::GeoJson(geojson))),
+                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
+            };
+        });
+    }
+
     if index_vectors {
         let settings_diff = settings_diff.clone();
         let embedders_configs = embedders_configs.clone();
@@ -36,7 +36,8 @@ use crate::progress::Progress;
 use crate::thread_pool_no_abort::ThreadPoolNoAbortBuilder;
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
-    IndexerConfig, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
+    IndexerConfig, TrigramsFst, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids,
+    WordsPrefixesFst,
 };
 use crate::vector::{ArroyWrapper, EmbeddingConfigs};
 use crate::{CboRoaringBitmapCodec, Index, Result, UserError};
@@ -579,6 +580,9 @@ where
             return Err(Error::InternalError(InternalError::AbortedIndexation));
         }
 
+        // Run the trigrams update operation of the infix attributes.
+        TrigramsFst::new(self.wtxn, self.index).execute()?;
+
         let previous_words_prefixes_fst =
             self.index.words_prefixes_fst(self.wtxn)?.map_data(|cow| cow.into_owned())?;
 
@@ -668,11 +668,12 @@ impl<'a, 'i> Transform<'a, 'i> {
         // Always keep the primary key.
         let is_primary_key = |id: FieldId| -> bool { settings_diff.primary_key_id == Some(id) };
 
-        // If only a faceted field has been added, keep only this field.
-        let facet_fids_changed = settings_diff.facet_fids_changed();
+        // If only a faceted field has been added, keep only this field,
+        // but keep all of them when a global facet setting, e.g. the date attributes, changed.
+        let reindex_facets = settings_diff.reindex_facets();
 
         let necessary_faceted_field = |id: FieldId| -> Option<DelAddOperation> {
-            if facet_fids_changed {
+            if reindex_facets {
                 let field_name = settings_diff.new.fields_ids_map.name(id).unwrap();
                 // if the faceted fields changed, we need to keep all the field that are
                 // faceted in the old or new settings.
@@ -820,19 +821,21 @@ impl<'a, 'i> Transform<'a, 'i> {
         let documents_count = documents_ids.len() as usize;
 
         // We initialize the sorter with the user indexing settings.
-        let mut original_sorter = if settings_diff.reindex_vectors() {
-            Some(create_sorter(
-                grenad::SortAlgorithm::Stable,
-                KeepFirst,
-                self.indexer_settings.chunk_compression_type,
-                self.indexer_settings.chunk_compression_level,
-                self.indexer_settings.max_nb_chunks,
-                self.indexer_settings.max_memory.map(|mem| mem / 2),
-                true,
-            ))
-        } else {
-            None
-        };
+        // The GeoJSON geometries are extracted from the original documents.
+        let mut original_sorter =
+            if settings_diff.reindex_vectors() || settings_diff.run_geojson_indexing() {
+                Some(create_sorter(
+                    grenad::SortAlgorithm::Stable,
+                    KeepFirst,
+                    self.indexer_settings.chunk_compression_type,
+                    self.indexer_settings.chunk_compression_level,
+                    self.indexer_settings.max_nb_chunks,
+                    self.indexer_settings.max_memory.map(|mem| mem / 2),
+                    true,
+                ))
+            } else {
+                None
+            };
 
         let readers: BTreeMap<&str, (ArroyWrapper, &RoaringBitmap)> = settings_diff
             .embedding_config_updates
@@ -28,8 +28,8 @@ use crate::update::index_documents::helpers::{
 use crate::update::settings::InnerIndexSettingsDiff;
 use crate::vector::ArroyWrapper;
 use crate::{
-    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Index, InternalError,
-    Result, SerializationError, U8StrStrCodec,
+    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Geometry, Index,
+    InternalError, Result, SerializationError, U8StrStrCodec,
 };
 
 /// This struct accumulates and group the TypedChunks
@@ -84,6 +84,7 @@ pub(crate) enum TypedChunk {
     FieldIdFacetIsNullDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetIsEmptyDocids(grenad::Reader<BufReader<File>>),
     GeoPoints(grenad::Reader<BufReader<File>>),
+    GeoJson(grenad::Reader<BufReader<File>>),
     VectorPoints {
         remove_vectors: grenad::Reader<BufReader<File>>,
         embeddings: Option<grenad::Reader<BufReader<File>>>,
@@ -111,7 +112,8 @@ impl TypedChunk {
             | (FieldIdFacetExistsDocids(_), FieldIdFacetExistsDocids(_))
             | (FieldIdFacetIsNullDocids(_), FieldIdFacetIsNullDocids(_))
             | (FieldIdFacetIsEmptyDocids(_), FieldIdFacetIsEmptyDocids(_))
-            | (GeoPoints(_), GeoPoints(_)) => true,
+            | (GeoPoints(_), GeoPoints(_))
+            | (GeoJson(_), GeoJson(_)) => true,
             (
                 VectorPoints { embedder_name: left, expected_dimension: left_dim, .. },
                 VectorPoints { embedder_name: right, expected_dimension: right_dim, .. },
@@ -610,6 +612,45 @@ pub(crate) fn write_typed_chunk_into_index(
             index.put_geo_rtree(wtxn, &rtree)?;
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
+        TypedChunk::GeoJson(_) => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "geojson");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(KeepFirst);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::GeoJson(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            let mut rtree = index.geojson_rtree(wtxn)?.unwrap_or_default();
+            let geometries = index.geojson_geometries.remap_data_type::<Bytes>();
+
+            let mut iter = merger.into_stream_merger_iter()?;
+            while let Some((key, value)) = iter.next()? {
+                // convert the key back to a u32 (4 bytes)
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+
+                let deladd_obkv = KvReaderDelAdd::from_slice(value);
+                if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
+                    let geometry: Geometry =
+                        bincode::deserialize(value).map_err(InternalError::BincodeError)?;
+                    rtree.remove(&geometry.to_shape(docid));
+                    geometries.delete(wtxn, &docid)?;
+                }
+                if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
+                    let geometry: Geometry =
+                        bincode::deserialize(value).map_err(InternalError::BincodeError)?;
+                    rtree.insert(geometry.to_shape(docid));
+                    // the geometry is already serialized with bincode
+                    geometries.put(wtxn, &docid, value)?;
+                }
+            }
+            index.put_geojson_rtree(wtxn, &rtree)?;
+        }
         TypedChunk::VectorPoints { .. } => {
             let span = tracing::trace_span!(target: "indexing::write_db", "vector_points");
             let _entered = span.enter();
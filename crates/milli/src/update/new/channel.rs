@@ -22,9 +22,10 @@ use super::extract::FacetKind;
 use super::ref_cell_ext::RefCellExt;
 use super::thread_local::{FullySend, ThreadLocal};
 use super::StdResult;
+use crate::geojson::Geometry;
 use crate::heed_codec::facet::{FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec};
 use crate::index::db_name;
-use crate::index::main_key::{GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
+use crate::index::main_key::{GEOJSON_RTREE_KEY, GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
 use crate::update::new::KvReaderFieldId;
 use crate::vector::Embedding;
 use crate::{CboRoaringBitmapCodec, DocumentId, Error, Index, InternalError};
@@ -398,6 +399,7 @@ pub enum Database {
     FacetIdStringDocids,
     FieldIdDocidFacetStrings,
     FieldIdDocidFacetF64s,
+    GeoJsonGeometries,
 }
 
 impl Database {
@@ -419,6 +421,7 @@ impl Database {
             Database::FacetIdStringDocids => index.facet_id_string_docids.remap_types(),
             Database::FieldIdDocidFacetStrings => index.field_id_docid_facet_strings.remap_types(),
             Database::FieldIdDocidFacetF64s => index.field_id_docid_facet_f64s.remap_types(),
+            Database::GeoJsonGeometries => index.geojson_geometries.remap_types(),
         }
     }
 
@@ -440,6 +443,7 @@ impl Database {
             Database::FacetIdStringDocids => db_name::FACET_ID_STRING_DOCIDS,
             Database::FieldIdDocidFacetStrings => db_name::FIELD_ID_DOCID_FACET_STRINGS,
             Database::FieldIdDocidFacetF64s => db_name::FIELD_ID_DOCID_FACET_F64S,
+            Database::GeoJsonGeometries => db_name::GEOJSON_GEOMETRIES,
         }
     }
 }
@@ -963,6 +967,30 @@ impl GeoSender<'_, '_> {
             .map_err(|_| SendError(()))
     }
 
+    pub fn set_geojson_rtree(&self, value: Mmap) -> StdResult<(), SendError<()>> {
+        self.0
+            .sender
+            .send(ReceiverAction::LargeEntry(LargeEntry {
+                database: Database::Main,
+                key: GEOJSON_RTREE_KEY.to_string().into_bytes().into_boxed_slice(),
+                value,
+            }))
+            .map_err(|_| SendError(()))
+    }
+
+    pub fn set_geojson_geometry(
+        &self,
+        docid: DocumentId,
+        geometry: &Geometry,
+    ) -> crate::Result<()> {
+        let value = bincode::serialize(geometry).map_err(InternalError::BincodeError)?;
+        self.0.write_key_value(Database::GeoJsonGeometries, &docid.to_be_bytes(), &value)
+    }
+
+    pub fn delete_geojson_geometry(&self, docid: DocumentId) -> crate::Result<()> {
+        self.0.delete_entry(Database::GeoJsonGeometries, &docid.to_be_bytes())
+    }
+
     pub fn set_geo_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
         let database = Database::Main;
         let value_length = bitmap.serialized_size();
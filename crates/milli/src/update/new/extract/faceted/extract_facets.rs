@@ -244,7 +244,7 @@ impl FacetedDocidsExtractor {
         cached_sorter: &mut BalancedCaches<'extractor>,
         cache_fn: impl Fn(&mut BalancedCaches<'extractor>, &[u8], u32) -> Result<()>,
         del_add_facet_value: &mut DelAddFacetValue<'doc>,
-        facet_fn: impl Fn(&mut DelAddFacetValue<'doc>, FieldId, BVec<'doc, u8>, FacetKind),
+        facet_fn: impl Fn(&mut DelAddFacetValue<'doc>, FieldId, Metadata, BVec<'doc, u8>, FacetKind),
         docid: DocumentId,
         fid: FieldId,
         meta: Metadata,
@@ -278,7 +278,7 @@ impl FacetedDocidsExtractor {
                 {
                     let mut number = BVec::with_capacity_in(16, doc_alloc);
                     number.extend_from_slice(&ordered);
-                    facet_fn(del_add_facet_value, fid, number, FacetKind::Number);
+                    facet_fn(del_add_facet_value, fid, meta, number, FacetKind::Number);
 
                     buffer.clear();
                     buffer.push(FacetKind::Number as u8);
@@ -295,9 +295,9 @@ impl FacetedDocidsExtractor {
             Value::String(s) if !s.is_empty() => {
                 let mut string = BVec::new_in(doc_alloc);
                 string.extend_from_slice(s.as_bytes());
-                facet_fn(del_add_facet_value, fid, string, FacetKind::String);
+                facet_fn(del_add_facet_value, fid, meta, string, FacetKind::String);
 
-                let normalized = crate::normalize_facet(s);
+                let normalized = meta.normalize_facet(s);
                 let truncated = truncate_str(&normalized);
                 buffer.clear();
                 buffer.push(FacetKind::String as u8);
@@ -311,7 +311,7 @@ impl FacetedDocidsExtractor {
                 let b = if *b { "true" } else { "false" };
                 let mut string = BVec::new_in(doc_alloc);
                 string.extend_from_slice(b.as_bytes());
-                facet_fn(del_add_facet_value, fid, string, FacetKind::String);
+                facet_fn(del_add_facet_value, fid, meta, string, FacetKind::String);
 
                 buffer.clear();
                 buffer.push(FacetKind::String as u8);
@@ -382,7 +382,7 @@ impl<'doc> DelAddFacetValue<'doc> {
         Self { strings: HashMap::new_in(doc_alloc), f64s: HashMap::new_in(doc_alloc), doc_alloc }
     }
 
-    fn insert_add(&mut self, fid: FieldId, value: BVec<'doc, u8>, kind: FacetKind) {
+    fn insert_add(&mut self, fid: FieldId, meta: Metadata, value: BVec<'doc, u8>, kind: FacetKind) {
         match kind {
             FacetKind::Number => {
                 let key = (fid, value);
@@ -394,7 +394,7 @@ impl<'doc> DelAddFacetValue<'doc> {
             }
             FacetKind::String => {
                 if let Ok(s) = std::str::from_utf8(&value) {
-                    let normalized = crate::normalize_facet(s);
+                    let normalized = meta.normalize_facet(s);
                     let truncated = self.doc_alloc.alloc_str(truncate_str(&normalized));
                     self.strings.insert((fid, truncated), Some(value));
                 }
@@ -403,7 +403,7 @@ impl<'doc> DelAddFacetValue<'doc> {
         }
     }
 
-    fn insert_del(&mut self, fid: FieldId, value: BVec<'doc, u8>, kind: FacetKind) {
+    fn insert_del(&mut self, fid: FieldId, meta: Metadata, value: BVec<'doc, u8>, kind: FacetKind) {
         match kind {
             FacetKind::Number => {
                 let key = (fid, value);
@@ -415,7 +415,7 @@ impl<'doc> DelAddFacetValue<'doc> {
             }
             FacetKind::String => {
                 if let Ok(s) = std::str::from_utf8(&value) {
-                    let normalized = crate::normalize_facet(s);
+                    let normalized = meta.normalize_facet(s);
                     let truncated = self.doc_alloc.alloc_str(truncate_str(&normalized));
                     self.strings.insert((fid, truncated), None);
                 }
@@ -0,0 +1,207 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Seek as _};
+use std::iter;
+
+use bumpalo::Bump;
+use heed::RoTxn;
+use serde_json::value::RawValue;
+use serde_json::Value;
+
+use crate::constants::RESERVED_GEOJSON_FIELD_NAME;
+use crate::error::GeoError;
+use crate::geojson::Geometry;
+use crate::update::new::document::Document;
+use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
+use crate::update::new::ref_cell_ext::RefCellExt as _;
+use crate::update::new::thread_local::MostlySend;
+use crate::update::new::DocumentChange;
+use crate::{DocumentId, Index, InternalError, Result};
+
+pub struct GeoJsonExtractor;
+
+impl GeoJsonExtractor {
+    pub fn new(rtxn: &RoTxn, index: &Index) -> Result<Option<Self>> {
+        if index.is_geojson_filtering_enabled(rtxn)? {
+            Ok(Some(GeoJsonExtractor))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The geometries can't be packed like the geo points, we directly
+/// serialize them with bincode in temporary files instead.
+#[derive(Default)]
+pub struct GeoJsonExtractorData {
+    /// The geometries of the documents that were removed. If a document sees its
+    /// geometry being updated, we first put it in the removed and then in the inserted.
+    removed: Option<BufWriter<File>>,
+    inserted: Option<BufWriter<File>>,
+}
+
+impl GeoJsonExtractorData {
+    pub fn freeze(self) -> Result<FrozenGeoJsonExtractorData> {
+        let GeoJsonExtractorData { removed, inserted } = self;
+
+        Ok(FrozenGeoJsonExtractorData {
+            removed: removed
+                .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
+                .transpose()?,
+            inserted: inserted
+                .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
+                .transpose()?,
+        })
+    }
+
+    fn push_removed(&mut self, docid: DocumentId, geometry: &Geometry) -> Result<()> {
+        push_geometry(&mut self.removed, docid, geometry)
+    }
+
+    fn push_inserted(&mut self, docid: DocumentId, geometry: &Geometry) -> Result<()> {
+        push_geometry(&mut self.inserted, docid, geometry)
+    }
+}
+
+fn push_geometry(
+    file: &mut Option<BufWriter<File>>,
+    docid: DocumentId,
+    geometry: &Geometry,
+) -> Result<()> {
+    let file = match file {
+        Some(file) => file,
+        None => file.insert(tempfile::tempfile().map(BufWriter::new)?),
+    };
+    bincode::serialize_into(file, &(docid, geometry)).map_err(InternalError::BincodeError)?;
+    Ok(())
+}
+
+unsafe impl MostlySend for GeoJsonExtractorData {}
+
+pub struct FrozenGeoJsonExtractorData {
+    pub removed: Option<BufReader<File>>,
+    pub inserted: Option<BufReader<File>>,
+}
+
+impl FrozenGeoJsonExtractorData {
+    pub fn iter_and_clear_removed(
+        &mut self,
+    ) -> io::Result<impl IntoIterator<Item = Result<(DocumentId, Geometry)>> + '_> {
+        iterator_over_geometries(&mut self.removed)
+    }
+
+    pub fn iter_and_clear_inserted(
+        &mut self,
+    ) -> io::Result<impl IntoIterator<Item = Result<(DocumentId, Geometry)>> + '_> {
+        iterator_over_geometries(&mut self.inserted)
+    }
+}
+
+fn iterator_over_geometries(
+    file: &mut Option<BufReader<File>>,
+) -> io::Result<impl IntoIterator<Item = Result<(DocumentId, Geometry)>> + '_> {
+    let mut file = file.take();
+    if let Some(file) = &mut file {
+        file.rewind()?;
+    }
+
+    Ok(iter::from_fn(move || match &mut file {
+        Some(reader) => match bincode::deserialize_from(&mut *reader) {
+            Ok(entry) => Some(Ok(entry)),
+            Err(e) => match *e {
+                bincode::ErrorKind::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+                e => Some(Err(InternalError::BincodeError(Box::new(e)).into())),
+            },
+        },
+        None => None,
+    }))
+}
+
+impl<'extractor> Extractor<'extractor> for GeoJsonExtractor {
+    type Data = RefCell<GeoJsonExtractorData>;
+
+    fn init_data<'doc>(&'doc self, _extractor_alloc: &'extractor Bump) -> Result<Self::Data> {
+        Ok(RefCell::new(GeoJsonExtractorData::default()))
+    }
+
+    fn process<'doc>(
+        &'doc self,
+        changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
+        context: &'doc DocumentChangeContext<Self::Data>,
+    ) -> Result<()> {
+        let rtxn = &context.rtxn;
+        let index = context.index;
+        let db_fields_ids_map = context.db_fields_ids_map;
+        let mut data_ref = context.data.borrow_mut_or_yield();
+
+        for change in changes {
+            match change? {
+                DocumentChange::Deletion(deletion) => {
+                    let docid = deletion.docid();
+                    let external_id = deletion.external_document_id();
+                    let current = deletion.current(rtxn, index, db_fields_ids_map)?;
+                    let current_geometry = current
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .map(|geojson| extract_geometry(external_id, geojson))
+                        .transpose()?;
+
+                    if let Some(geometry) = current_geometry.flatten() {
+                        data_ref.push_removed(docid, &geometry)?;
+                    }
+                }
+                DocumentChange::Update(update) => {
+                    let current = update.current(rtxn, index, db_fields_ids_map)?;
+                    let external_id = update.external_document_id();
+                    let docid = update.docid();
+
+                    let current_geometry = current
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .map(|geojson| extract_geometry(external_id, geojson))
+                        .transpose()?;
+
+                    let updated_geometry = update
+                        .merged(rtxn, index, db_fields_ids_map)?
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .map(|geojson| extract_geometry(external_id, geojson))
+                        .transpose()?;
+
+                    if current_geometry != updated_geometry {
+                        // If the current and new geometries are different it means that
+                        // we need to replace the current shape by the new one in the RTree.
+                        if let Some(geometry) = current_geometry.flatten() {
+                            data_ref.push_removed(docid, &geometry)?;
+                        }
+
+                        if let Some(geometry) = updated_geometry.flatten() {
+                            data_ref.push_inserted(docid, &geometry)?;
+                        }
+                    }
+                }
+                DocumentChange::Insertion(insertion) => {
+                    let external_id = insertion.external_document_id();
+                    let docid = insertion.docid();
+
+                    let inserted_geometry = insertion
+                        .inserted()
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .map(|geojson| extract_geometry(external_id, geojson))
+                        .transpose()?;
+
+                    if let Some(geometry) = inserted_geometry.flatten() {
+                        data_ref.push_inserted(docid, &geometry)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Extracts and validates the GeoJSON geometry of a document `_geojson` field.
+pub fn extract_geometry(external_id: &str, raw_value: &RawValue) -> Result<Option<Geometry>> {
+    let value = serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)?;
+    Geometry::from_json(value).map_err(|error| {
+        Box::new(GeoError::BadGeoJson { document_id: Value::from(external_id), error }).into()
+    })
+}
@@ -1,3 +1,5 @@
+mod geojson;
+
 use std::cell::RefCell;
 use std::fs::File;
 use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Seek as _, Write as _};
@@ -18,6 +20,8 @@ use crate::update::new::DocumentChange;
 use crate::update::GrenadParameters;
 use crate::{lat_lng_to_xyz, DocumentId, GeoPoint, Index, InternalError, Result};
 
+pub use self::geojson::{GeoJsonExtractor, GeoJsonExtractorData};
+
 pub struct GeoExtractor {
     grenad_parameters: GrenadParameters,
 }
@@ -17,7 +17,9 @@ use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, MostlySend, ThreadLocal};
 use crate::update::new::DocumentChange;
-use crate::{bucketed_position, DocumentId, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE};
+use crate::{
+    bucketed_position, AttributePatterns, DocumentId, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE,
+};
 
 const MAX_COUNTED_WORDS: usize = 30;
 
@@ -46,11 +48,15 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
         }
     }
 
+    /// The derived words, stems and phonetic codes, are indexed along the words, but they
+    /// don't count in the number of words of the field.
+    #[allow(clippy::too_many_arguments)]
     fn insert_add_u32(
         &mut self,
         field_id: FieldId,
         position: u16,
         word: &str,
+        is_derived: bool,
         exact: bool,
         docid: u32,
         bump: &Bump,
@@ -78,6 +84,10 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
         buffer.extend_from_slice(&position.to_be_bytes());
         self.word_position_docids.insert_add_u32(&buffer, docid)?;
 
+        if is_derived {
+            return Ok(());
+        }
+
         if self.current_docid.is_some_and(|id| docid != id) {
             self.flush_fid_word_count(&mut buffer)?;
         }
@@ -91,11 +101,13 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn insert_del_u32(
         &mut self,
         field_id: FieldId,
         position: u16,
         word: &str,
+        is_derived: bool,
         exact: bool,
         docid: u32,
         bump: &Bump,
@@ -123,6 +135,10 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
         buffer.extend_from_slice(&position.to_be_bytes());
         self.word_position_docids.insert_del_u32(&buffer, docid)?;
 
+        if is_derived {
+            return Ok(());
+        }
+
         if self.current_docid.is_some_and(|id| docid != id) {
             self.flush_fid_word_count(&mut buffer)?;
         }
@@ -270,9 +286,16 @@ impl WordDocidsExtractors {
         let tokenizer = builder.build();
         let localized_attributes_rules =
             indexing_context.index.localized_attributes_rules(&rtxn)?.unwrap_or_default();
+        let phonetic = indexing_context.index.phonetic_settings(&rtxn)?;
+        let identifier_attributes = indexing_context
+            .index
+            .identifier_attributes(&rtxn)?
+            .map(|attributes| AttributePatterns::from(attributes.into_iter().collect::<Vec<_>>()));
         let document_tokenizer = DocumentTokenizer {
             tokenizer: &tokenizer,
             localized_attributes_rules: &localized_attributes_rules,
+            phonetic: phonetic.as_ref(),
+            identifier_attributes: identifier_attributes.as_ref(),
             max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
         };
         let extractor_data = WordDocidsExtractorData {
@@ -326,17 +349,18 @@ impl WordDocidsExtractors {
         };
         match document_change {
             DocumentChange::Deletion(inner) => {
-                let mut token_fn = |fname: &str, fid, pos, word: &str| {
+                let mut token_fn = |fname: &str, fid, pos, word: &str, is_derived| {
                     cached_sorter.insert_del_u32(
                         fid,
                         pos,
                         word,
+                        is_derived,
                         is_exact(fname, word),
                         inner.docid(),
                         doc_alloc,
                     )
                 };
-                document_tokenizer.tokenize_document(
+                document_tokenizer.tokenize_document_with_derived_words(
                     inner.current(rtxn, index, context.db_fields_ids_map)?,
                     new_fields_ids_map,
                     &mut token_fn,
@@ -354,50 +378,53 @@ impl WordDocidsExtractors {
                     return Ok(());
                 }
 
-                let mut token_fn = |fname: &str, fid, pos, word: &str| {
+                let mut token_fn = |fname: &str, fid, pos, word: &str, is_derived| {
                     cached_sorter.insert_del_u32(
                         fid,
                         pos,
                         word,
+                        is_derived,
                         is_exact(fname, word),
                         inner.docid(),
                         doc_alloc,
                     )
                 };
-                document_tokenizer.tokenize_document(
+                document_tokenizer.tokenize_document_with_derived_words(
                     inner.current(rtxn, index, context.db_fields_ids_map)?,
                     new_fields_ids_map,
                     &mut token_fn,
                 )?;
 
-                let mut token_fn = |fname: &str, fid, pos, word: &str| {
+                let mut token_fn = |fname: &str, fid, pos, word: &str, is_derived| {
                     cached_sorter.insert_add_u32(
                         fid,
                         pos,
                         word,
+                        is_derived,
                         is_exact(fname, word),
                         inner.docid(),
                         doc_alloc,
                     )
                 };
-                document_tokenizer.tokenize_document(
+                document_tokenizer.tokenize_document_with_derived_words(
                     inner.merged(rtxn, index, context.db_fields_ids_map)?,
                     new_fields_ids_map,
                     &mut token_fn,
                 )?;
             }
             DocumentChange::Insertion(inner) => {
-                let mut token_fn = |fname: &str, fid, pos, word: &str| {
+                let mut token_fn = |fname: &str, fid, pos, word: &str, is_derived| {
                     cached_sorter.insert_add_u32(
                         fid,
                         pos,
                         word,
+                        is_derived,
                         is_exact(fname, word),
                         inner.docid(),
                         doc_alloc,
                     )
                 };
-                document_tokenizer.tokenize_document(
+                document_tokenizer.tokenize_document_with_derived_words(
                     inner.inserted(),
                     new_fields_ids_map,
                     &mut token_fn,
@@ -86,6 +86,8 @@ impl WordPairProximityDocidsExtractor {
         let document_tokenizer = DocumentTokenizer {
             tokenizer: &tokenizer,
             localized_attributes_rules: &localized_attributes_rules,
+            phonetic: None,
+            identifier_attributes: None,
             max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
         };
         let extractor_data = WordPairProximityDocidsExtractorData {
@@ -4,13 +4,14 @@ use charabia::{SeparatorKind, Token, TokenKind, Tokenizer, TokenizerBuilder};
 use serde_json::Value;
 
 use crate::attribute_patterns::PatternMatch;
+use crate::identifier_attributes::identifier_variants;
 use crate::update::new::document::Document;
 use crate::update::new::extract::perm_json_p::{
     seek_leaf_values_in_array, seek_leaf_values_in_object, Depth,
 };
 use crate::{
-    FieldId, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result, UserError,
-    MAX_WORD_LENGTH,
+    AttributePatterns, FieldId, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule,
+    PhoneticSettings, Result, UserError, MAX_WORD_LENGTH,
 };
 
 // todo: should be crate::proximity::MAX_DISTANCE but it has been forgotten
@@ -19,6 +20,8 @@ const MAX_DISTANCE: u32 = 8;
 pub struct DocumentTokenizer<'a> {
     pub tokenizer: &'a Tokenizer<'a>,
     pub localized_attributes_rules: &'a [LocalizedAttributesRule],
+    pub phonetic: Option<&'a PhoneticSettings>,
+    pub identifier_attributes: Option<&'a AttributePatterns>,
     pub max_positions_per_attributes: u32,
 }
 
@@ -28,6 +31,37 @@ impl DocumentTokenizer<'_> {
         document: impl Document<'doc>,
         field_id_map: &mut GlobalFieldsIdsMap,
         token_fn: &mut impl FnMut(&str, FieldId, u16, &str) -> Result<()>,
+    ) -> Result<()> {
+        self.tokenize(
+            document,
+            field_id_map,
+            false,
+            &mut |field_name: &str, field_id, position, word: &str, _| {
+                token_fn(field_name, field_id, position, word)
+            },
+        )
+    }
+
+    /// Tokenizes the document like [`Self::tokenize_document`] but also calls `token_fn` with the
+    /// stems of the words of the stemmed attributes and the phonetic codes of the words of the
+    /// phonetic attributes, at the position of their word, and with the identifier variants of
+    /// the words of the identifier attributes, the last argument of `token_fn` being `true`
+    /// for these derived words.
+    pub fn tokenize_document_with_derived_words<'doc>(
+        &self,
+        document: impl Document<'doc>,
+        field_id_map: &mut GlobalFieldsIdsMap,
+        token_fn: &mut impl FnMut(&str, FieldId, u16, &str, bool) -> Result<()>,
+    ) -> Result<()> {
+        self.tokenize(document, field_id_map, true, token_fn)
+    }
+
+    fn tokenize<'doc>(
+        &self,
+        document: impl Document<'doc>,
+        field_id_map: &mut GlobalFieldsIdsMap,
+        with_derived_words: bool,
+        token_fn: &mut impl FnMut(&str, FieldId, u16, &str, bool) -> Result<()>,
     ) -> Result<()> {
         let mut field_position = HashMap::new();
         let mut tokenize_field = |field_name: &str, _depth, value: &Value| {
@@ -36,7 +70,14 @@ impl DocumentTokenizer<'_> {
             };
 
             if meta.is_searchable() {
-                self.tokenize_field(field_id, field_name, value, token_fn, &mut field_position)?;
+                self.tokenize_field(
+                    field_id,
+                    field_name,
+                    value,
+                    with_derived_words,
+                    token_fn,
+                    &mut field_position,
+                )?;
             }
 
             // todo: should be a match on the field_name using `match_field_legacy` function,
@@ -74,7 +115,8 @@ impl DocumentTokenizer<'_> {
         field_id: FieldId,
         field_name: &str,
         value: &Value,
-        token_fn: &mut impl FnMut(&str, u16, u16, &str) -> std::result::Result<(), crate::Error>,
+        with_derived_words: bool,
+        token_fn: &mut impl FnMut(&str, u16, u16, &str, bool) -> std::result::Result<(), crate::Error>,
         field_position: &mut HashMap<u16, u32>,
     ) -> Result<()> {
         let position = field_position
@@ -86,6 +128,13 @@ impl DocumentTokenizer<'_> {
         }
 
         let text;
+        let mut stemming_rule = None;
+        let mut phonetic = None;
+        let is_identifier = with_derived_words
+            && self
+                .identifier_attributes
+                .is_some_and(|patterns| patterns.match_str(field_name) == PatternMatch::Match);
+        let mut identifier_words = Vec::new();
         let tokens = match value {
             Value::Number(n) => {
                 text = n.to_string();
@@ -96,11 +145,15 @@ impl DocumentTokenizer<'_> {
                 self.tokenizer.tokenize(text.as_str())
             }
             Value::String(text) => {
-                let locales = self
+                let rule = self
                     .localized_attributes_rules
                     .iter()
-                    .find(|rule| rule.match_str(field_name) == PatternMatch::Match)
-                    .map(|rule| rule.locales());
+                    .find(|rule| rule.match_str(field_name) == PatternMatch::Match);
+                stemming_rule = rule.filter(|rule| with_derived_words && rule.stemming);
+                phonetic = self.phonetic.filter(|phonetic| {
+                    with_derived_words && phonetic.match_str(field_name) == PatternMatch::Match
+                });
+                let locales = rule.map(|rule| rule.locales());
                 self.tokenizer.tokenize_with_allow_list(text.as_str(), locales)
             }
             _ => return Ok(()),
@@ -116,11 +169,29 @@ impl DocumentTokenizer<'_> {
             if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
                 *position = index;
                 if let Ok(position) = (*position).try_into() {
-                    token_fn(field_name, field_id, position, token)?;
+                    token_fn(field_name, field_id, position, token, false)?;
+                    if let Some(rule) = stemming_rule {
+                        for stem in rule.stems(token) {
+                            token_fn(field_name, field_id, position, &stem, true)?;
+                        }
+                    }
+                    if let Some(phonetic) = phonetic {
+                        for code in phonetic.codes(token) {
+                            token_fn(field_name, field_id, position, &code, true)?;
+                        }
+                    }
+                    if is_identifier {
+                        identifier_words.push((position, token.to_string()));
+                    }
                 }
             }
         }
 
+        let words = identifier_words.iter().map(|(position, word)| (*position, word.as_str()));
+        for (position, variant) in identifier_variants(words) {
+            token_fn(field_name, field_id, position, &variant, true)?;
+        }
+
         Ok(())
     }
 }
@@ -222,6 +293,8 @@ mod test {
         let document_tokenizer = DocumentTokenizer {
             tokenizer: &tb.build(),
             localized_attributes_rules: &[],
+            phonetic: None,
+            identifier_attributes: None,
             max_positions_per_attributes: 1000,
         };
 
@@ -234,6 +307,7 @@ mod test {
                 None,
                 None,
                 Default::default(),
+                None,
             ),
         );
 
@@ -16,7 +16,7 @@ use crate::index::IndexEmbeddingConfig;
 use crate::progress::MergingWordCache;
 use crate::proximity::ProximityPrecision;
 use crate::update::new::extract::EmbeddingExtractor;
-use crate::update::new::merger::merge_and_send_rtree;
+use crate::update::new::merger::{merge_and_send_geojson_rtree, merge_and_send_rtree};
 use crate::update::new::{merge_and_send_docids, merge_and_send_facet_docids, FacetDatabases};
 use crate::vector::EmbeddingConfigs;
 use crate::{Result, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
@@ -306,6 +306,35 @@ where
             &indexing_context.must_stop_processing,
         )?;
     }
+
+    'geojson: {
+        let Some(extractor) = GeoJsonExtractor::new(&rtxn, index)? else {
+            break 'geojson;
+        };
+        let datastore = ThreadLocal::with_capacity(rayon::current_num_threads());
+
+        {
+            let span = tracing::trace_span!(target: "indexing::documents::extract", "geojson");
+            let _entered = span.enter();
+
+            extract(
+                document_changes,
+                &extractor,
+                indexing_context,
+                extractor_allocs,
+                &datastore,
+                IndexingStep::WritingGeoJsonShapes,
+            )?;
+        }
+
+        merge_and_send_geojson_rtree(
+            datastore,
+            &rtxn,
+            index,
+            extractor_sender.geo(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
     indexing_context.progress.update_progress(IndexingStep::WaitingForDatabaseWrites);
     finished_extraction.store(true, std::sync::atomic::Ordering::Relaxed);
 
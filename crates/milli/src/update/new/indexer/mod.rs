@@ -20,7 +20,7 @@ use super::thread_local::ThreadLocal;
 use crate::documents::PrimaryKey;
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::progress::Progress;
-use crate::update::GrenadParameters;
+use crate::update::{GrenadParameters, TrigramsFst};
 use crate::vector::{ArroyWrapper, EmbeddingConfigs};
 use crate::{FieldsIdsMap, GlobalFieldsIdsMap, Index, InternalError, Result, ThreadPoolNoAbort};
 
@@ -237,5 +237,8 @@ where
         document_ids,
     )?;
 
+    // the infix attributes are matched against the fields ids map written above
+    TrigramsFst::new(wtxn, index).execute()?;
+
     Ok(congestion)
 }
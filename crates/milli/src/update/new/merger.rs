@@ -10,7 +10,7 @@ use roaring::RoaringBitmap;
 use super::channel::*;
 use super::extract::{
     merge_caches_sorted, transpose_and_freeze_caches, BalancedCaches, DelAddRoaringBitmap,
-    FacetKind, GeoExtractorData,
+    FacetKind, GeoExtractorData, GeoJsonExtractorData,
 };
 use crate::update::facet::new_incremental::FacetFieldIdChange;
 use crate::{CboRoaringBitmapCodec, FieldId, GeoPoint, Index, InternalError, Result};
@@ -62,6 +62,49 @@ where
     Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::merge")]
+pub fn merge_and_send_geojson_rtree<MSP>(
+    datastore: impl IntoIterator<Item = RefCell<GeoJsonExtractorData>>,
+    rtxn: &RoTxn,
+    index: &Index,
+    geo_sender: GeoSender<'_, '_>,
+    must_stop_processing: &MSP,
+) -> Result<()>
+where
+    MSP: Fn() -> bool + Sync,
+{
+    let mut rtree = index.geojson_rtree(rtxn)?.unwrap_or_default();
+
+    for data in datastore {
+        if must_stop_processing() {
+            return Err(InternalError::AbortedIndexation.into());
+        }
+
+        let mut frozen = data.into_inner().freeze()?;
+        for result in frozen.iter_and_clear_removed()? {
+            let (docid, geometry) = result?;
+            let removed = rtree.remove(&geometry.to_shape(docid));
+            debug_assert!(removed.is_some());
+            geo_sender.delete_geojson_geometry(docid)?;
+        }
+
+        for result in frozen.iter_and_clear_inserted()? {
+            let (docid, geometry) = result?;
+            rtree.insert(geometry.to_shape(docid));
+            geo_sender.set_geojson_geometry(docid, &geometry)?;
+        }
+    }
+
+    let mut file = tempfile::tempfile()?;
+    bincode::serialize_into(&mut file, &rtree).map_err(InternalError::BincodeError)?;
+    file.sync_all()?;
+
+    let rtree_mmap = unsafe { Mmap::map(&file)? };
+    geo_sender.set_geojson_rtree(rtree_mmap).map_err(|_| InternalError::AbortedIndexation)?;
+
+    Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::merge")]
 pub fn merge_and_send_docids<'extractor, MSP, D>(
     mut caches: Vec<BalancedCaches<'extractor>>,
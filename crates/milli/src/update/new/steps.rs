@@ -12,6 +12,7 @@ make_enum_progress! {
         MergingWordCaches,
         MergingWordProximity,
         WritingGeoPoints,
+        WritingGeoJsonShapes,
         WaitingForDatabaseWrites,
         WaitingForExtractors,
         WritingEmbeddingsToDatabase,
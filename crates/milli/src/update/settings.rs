@@ -15,12 +15,13 @@ use super::del_add::{DelAdd, DelAddOperation};
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::attribute_patterns::PatternMatch;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::criterion::Criterion;
 use crate::disabled_typos_terms::DisabledTyposTerms;
 use crate::error::UserError;
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::filterable_attributes_rules::match_faceted_field;
+use crate::identifier_attributes::identifier_variants;
 use crate::index::{
     IndexEmbeddingConfig, PrefixSearch, DEFAULT_MIN_WORD_LEN_ONE_TYPO,
     DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
@@ -29,13 +30,17 @@ use crate::order_by_map::OrderByMap;
 use crate::prompt::default_max_bytes;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
-use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::update::{IndexDocuments, TrigramsFst, UpdateIndexingStep};
 use crate::vector::settings::{
     EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
     SubEmbeddingSettings, WriteBackToDocuments,
 };
 use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
+use crate::{
+    AttributePatterns, AttributeTypoBudget, FieldId, FilterableAttributesRule, Index,
+    LocaleTypoBudget, LocalizedAttributesRule, PhoneticSettings, QueryRule, Result, SearchRule,
+    SynonymSet, SynonymSetKind,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -151,6 +156,125 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Setting<T> {
     }
 }
 
+/// The words added to and removed from a list setting that is not replaced by the update.
+///
+/// The removals are applied before the additions, so a word removed and then added again
+/// is kept in the list.
+#[derive(Debug, Default)]
+struct WordsDelta {
+    added: BTreeSet<String>,
+    removed: BTreeSet<String>,
+}
+
+impl WordsDelta {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Adds the words to the setting if it is replaced, or records them to add them to
+    /// the current value of the setting otherwise.
+    fn add(&mut self, setting: &mut Setting<BTreeSet<String>>, words: BTreeSet<String>) {
+        match setting {
+            Setting::Set(current) => current.extend(words),
+            Setting::Reset if !words.is_empty() => *setting = Setting::Set(words),
+            Setting::Reset => (),
+            Setting::NotSet => self.added.extend(words),
+        }
+    }
+
+    fn remove(&mut self, setting: &mut Setting<BTreeSet<String>>, words: BTreeSet<String>) {
+        match setting {
+            Setting::Set(current) => {
+                current.retain(|word| !words.contains(word));
+                if current.is_empty() {
+                    *setting = Setting::Reset;
+                }
+            }
+            Setting::Reset => (),
+            Setting::NotSet => {
+                self.added.retain(|word| !words.contains(word));
+                self.removed.extend(words);
+            }
+        }
+    }
+
+    fn apply(self, mut current: BTreeSet<String>) -> BTreeSet<String> {
+        current.retain(|word| !self.removed.contains(word));
+        current.extend(self.added);
+        current
+    }
+}
+
+/// The synonyms added to and removed from the ones of the index when they are not replaced.
+///
+/// The removals are applied before the additions, so the synonyms of a word that is removed
+/// and then added again are only the added ones.
+#[derive(Debug, Default)]
+struct SynonymsDelta {
+    added: BTreeMap<String, Vec<String>>,
+    removed: BTreeSet<String>,
+}
+
+impl SynonymsDelta {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Appends the synonyms to the ones of each word, skipping the ones it already has.
+    fn extend(
+        current: &mut BTreeMap<String, Vec<String>>,
+        synonyms: BTreeMap<String, Vec<String>>,
+    ) {
+        for (word, synonyms) in synonyms {
+            let current = current.entry(word).or_default();
+            for synonym in synonyms {
+                if !current.contains(&synonym) {
+                    current.push(synonym);
+                }
+            }
+        }
+    }
+
+    fn add(
+        &mut self,
+        setting: &mut Setting<BTreeMap<String, Vec<String>>>,
+        synonyms: BTreeMap<String, Vec<String>>,
+    ) {
+        match setting {
+            Setting::Set(current) => Self::extend(current, synonyms),
+            Setting::Reset if !synonyms.is_empty() => *setting = Setting::Set(synonyms),
+            Setting::Reset => (),
+            Setting::NotSet => Self::extend(&mut self.added, synonyms),
+        }
+    }
+
+    fn remove(
+        &mut self,
+        setting: &mut Setting<BTreeMap<String, Vec<String>>>,
+        words: BTreeSet<String>,
+    ) {
+        match setting {
+            Setting::Set(current) => {
+                current.retain(|word, _| !words.contains(word));
+                if current.is_empty() {
+                    *setting = Setting::Reset;
+                }
+            }
+            Setting::Reset => (),
+            Setting::NotSet => {
+                self.added.retain(|word, _| !words.contains(word));
+                self.removed.extend(words);
+            }
+        }
+    }
+
+    fn apply(self, mut current: BTreeMap<String, Vec<String>>) -> BTreeMap<String, Vec<String>> {
+        current.retain(|word, _| !self.removed.contains(word));
+        Self::extend(&mut current, self.added);
+        current
+    }
+}
+
 pub struct Settings<'a, 't, 'i> {
     wtxn: &'t mut heed::RwTxn<'i>,
     index: &'i Index,
@@ -163,11 +287,26 @@ pub struct Settings<'a, 't, 'i> {
     sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
+    /// The stop words added to and removed from the ones of the index.
+    stop_words_delta: WordsDelta,
     non_separator_tokens: Setting<BTreeSet<String>>,
+    /// Patterns of the attributes whose words are also indexed as identifier variants.
+    identifier_attributes: Setting<BTreeSet<String>>,
+    /// Patterns of the attributes whose words and facet strings are indexed by their trigrams.
+    infix_attributes: Setting<BTreeSet<String>>,
+    /// Patterns of the attributes whose facet values are normalized as dates.
+    date_attributes: Setting<BTreeSet<String>>,
     separator_tokens: Setting<BTreeSet<String>>,
     dictionary: Setting<BTreeSet<String>>,
+    /// The words added to and removed from the dictionary of the index.
+    dictionary_delta: WordsDelta,
     distinct_field: Setting<String>,
     synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    /// The synonyms added to and removed from the ones of the index.
+    synonyms_delta: SynonymsDelta,
+    synonym_sets: Setting<Vec<SynonymSet>>,
+    /// The synonym sets to append to the ones of the index.
+    synonym_sets_to_add: Vec<SynonymSet>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     disable_on_numbers: Setting<bool>,
@@ -176,8 +315,17 @@ pub struct Settings<'a, 't, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    /// Attributes whose words are also indexed by their phonetic codes.
+    phonetic: Setting<PhoneticSettings>,
+    /// Typo budgets of the attributes overriding the global typo tolerance settings.
+    attribute_typo_budgets: Setting<Vec<AttributeTypoBudget>>,
+    /// Typo budgets of the localized attributes overriding the global typo tolerance settings.
+    locale_typo_budgets: Setting<Vec<LocaleTypoBudget>>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<OrderByMap>,
+    facet_hierarchies: Setting<BTreeMap<String, Vec<String>>>,
+    search_rules: Setting<BTreeMap<String, SearchRule>>,
+    query_rules: Setting<BTreeMap<String, QueryRule>>,
     pagination_max_total_hits: Setting<usize>,
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
@@ -202,11 +350,19 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
+            stop_words_delta: WordsDelta::default(),
             non_separator_tokens: Setting::NotSet,
+            identifier_attributes: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            date_attributes: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            dictionary_delta: WordsDelta::default(),
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonyms_delta: SynonymsDelta::default(),
+            synonym_sets: Setting::NotSet,
+            synonym_sets_to_add: Vec::new(),
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
             disable_on_numbers: Setting::NotSet,
@@ -214,8 +370,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            phonetic: Setting::NotSet,
+            attribute_typo_budgets: Setting::NotSet,
+            locale_typo_budgets: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
+            facet_hierarchies: Setting::NotSet,
+            search_rules: Setting::NotSet,
+            query_rules: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
@@ -269,11 +431,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
 
     pub fn reset_stop_words(&mut self) {
         self.stop_words = Setting::Reset;
+        self.stop_words_delta = WordsDelta::default();
     }
 
     pub fn set_stop_words(&mut self, stop_words: BTreeSet<String>) {
         self.stop_words =
-            if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
+            if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) };
+        self.stop_words_delta = WordsDelta::default();
+    }
+
+    /// Adds the stop words to the current ones, without having to send them all again.
+    pub fn add_stop_words(&mut self, stop_words: BTreeSet<String>) {
+        self.stop_words_delta.add(&mut self.stop_words, stop_words);
+    }
+
+    pub fn remove_stop_words(&mut self, stop_words: BTreeSet<String>) {
+        self.stop_words_delta.remove(&mut self.stop_words, stop_words);
     }
 
     pub fn reset_non_separator_tokens(&mut self) {
@@ -288,6 +461,39 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    pub fn reset_identifier_attributes(&mut self) {
+        self.identifier_attributes = Setting::Reset;
+    }
+
+    pub fn set_identifier_attributes(&mut self, identifier_attributes: BTreeSet<String>) {
+        self.identifier_attributes = if identifier_attributes.is_empty() {
+            Setting::Reset
+        } else {
+            Setting::Set(identifier_attributes)
+        }
+    }
+
+    pub fn reset_infix_attributes(&mut self) {
+        self.infix_attributes = Setting::Reset;
+    }
+
+    pub fn set_infix_attributes(&mut self, infix_attributes: BTreeSet<String>) {
+        self.infix_attributes = if infix_attributes.is_empty() {
+            Setting::Reset
+        } else {
+            Setting::Set(infix_attributes)
+        }
+    }
+
+    pub fn reset_date_attributes(&mut self) {
+        self.date_attributes = Setting::Reset;
+    }
+
+    pub fn set_date_attributes(&mut self, date_attributes: BTreeSet<String>) {
+        self.date_attributes =
+            if date_attributes.is_empty() { Setting::Reset } else { Setting::Set(date_attributes) }
+    }
+
     pub fn reset_separator_tokens(&mut self) {
         self.separator_tokens = Setting::Reset;
     }
@@ -302,11 +508,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
 
     pub fn reset_dictionary(&mut self) {
         self.dictionary = Setting::Reset;
+        self.dictionary_delta = WordsDelta::default();
     }
 
     pub fn set_dictionary(&mut self, dictionary: BTreeSet<String>) {
         self.dictionary =
-            if dictionary.is_empty() { Setting::Reset } else { Setting::Set(dictionary) }
+            if dictionary.is_empty() { Setting::Reset } else { Setting::Set(dictionary) };
+        self.dictionary_delta = WordsDelta::default();
+    }
+
+    /// Adds the words to the current dictionary, without having to send it all again.
+    pub fn add_dictionary(&mut self, dictionary: BTreeSet<String>) {
+        self.dictionary_delta.add(&mut self.dictionary, dictionary);
+    }
+
+    pub fn remove_dictionary(&mut self, dictionary: BTreeSet<String>) {
+        self.dictionary_delta.remove(&mut self.dictionary, dictionary);
     }
 
     pub fn reset_distinct_field(&mut self) {
@@ -319,10 +536,46 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
 
     pub fn reset_synonyms(&mut self) {
         self.synonyms = Setting::Reset;
+        self.synonyms_delta = SynonymsDelta::default();
     }
 
     pub fn set_synonyms(&mut self, synonyms: BTreeMap<String, Vec<String>>) {
-        self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) }
+        self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) };
+        self.synonyms_delta = SynonymsDelta::default();
+    }
+
+    /// Appends the synonyms to the ones already associated with each word,
+    /// without having to send all the synonyms again.
+    pub fn add_synonyms(&mut self, synonyms: BTreeMap<String, Vec<String>>) {
+        self.synonyms_delta.add(&mut self.synonyms, synonyms);
+    }
+
+    /// Removes all the synonyms of the words.
+    pub fn remove_synonyms(&mut self, words: BTreeSet<String>) {
+        self.synonyms_delta.remove(&mut self.synonyms, words);
+    }
+
+    pub fn reset_synonym_sets(&mut self) {
+        self.synonym_sets = Setting::Reset;
+        self.synonym_sets_to_add.clear();
+    }
+
+    pub fn set_synonym_sets(&mut self, synonym_sets: Vec<SynonymSet>) {
+        self.synonym_sets =
+            if synonym_sets.is_empty() { Setting::Reset } else { Setting::Set(synonym_sets) };
+        self.synonym_sets_to_add.clear();
+    }
+
+    /// Appends the synonym sets to the current ones, without having to send them all again.
+    pub fn add_synonym_sets(&mut self, synonym_sets: Vec<SynonymSet>) {
+        match &mut self.synonym_sets {
+            Setting::Set(current) => current.extend(synonym_sets),
+            Setting::Reset if !synonym_sets.is_empty() => {
+                self.synonym_sets = Setting::Set(synonym_sets)
+            }
+            Setting::Reset => (),
+            Setting::NotSet => self.synonym_sets_to_add.extend(synonym_sets),
+        }
     }
 
     pub fn reset_primary_key(&mut self) {
@@ -381,6 +634,30 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_phonetic(&mut self, phonetic: PhoneticSettings) {
+        self.phonetic = Setting::Set(phonetic);
+    }
+
+    pub fn reset_phonetic(&mut self) {
+        self.phonetic = Setting::Reset;
+    }
+
+    pub fn set_attribute_typo_budgets(&mut self, budgets: Vec<AttributeTypoBudget>) {
+        self.attribute_typo_budgets = Setting::Set(budgets);
+    }
+
+    pub fn reset_attribute_typo_budgets(&mut self) {
+        self.attribute_typo_budgets = Setting::Reset;
+    }
+
+    pub fn set_locale_typo_budgets(&mut self, budgets: Vec<LocaleTypoBudget>) {
+        self.locale_typo_budgets = Setting::Set(budgets);
+    }
+
+    pub fn reset_locale_typo_budgets(&mut self) {
+        self.locale_typo_budgets = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -397,6 +674,30 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.sort_facet_values_by = Setting::Reset;
     }
 
+    pub fn set_facet_hierarchies(&mut self, value: BTreeMap<String, Vec<String>>) {
+        self.facet_hierarchies = Setting::Set(value);
+    }
+
+    pub fn reset_facet_hierarchies(&mut self) {
+        self.facet_hierarchies = Setting::Reset;
+    }
+
+    pub fn set_search_rules(&mut self, value: BTreeMap<String, SearchRule>) {
+        self.search_rules = Setting::Set(value);
+    }
+
+    pub fn reset_search_rules(&mut self) {
+        self.search_rules = Setting::Reset;
+    }
+
+    pub fn set_query_rules(&mut self, value: BTreeMap<String, QueryRule>) {
+        self.query_rules = Setting::Set(value);
+    }
+
+    pub fn reset_query_rules(&mut self) {
+        self.query_rules = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -554,6 +855,33 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    /// Turns the words added to and removed from the lists that are not replaced by this update
+    /// into new values for these lists, based on their current values.
+    fn apply_list_deltas(&mut self) -> Result<()> {
+        if !self.stop_words_delta.is_empty() {
+            let current = match self.index.stop_words(self.wtxn)? {
+                Some(stop_words) => stop_words.stream().into_strs()?.into_iter().collect(),
+                None => BTreeSet::new(),
+            };
+            let stop_words = std::mem::take(&mut self.stop_words_delta).apply(current);
+            self.set_stop_words(stop_words);
+        }
+
+        if !self.dictionary_delta.is_empty() {
+            let current = self.index.dictionary(self.wtxn)?.unwrap_or_default();
+            let dictionary = std::mem::take(&mut self.dictionary_delta).apply(current);
+            self.set_dictionary(dictionary);
+        }
+
+        if !self.synonyms_delta.is_empty() {
+            let current = self.index.user_defined_synonyms(self.wtxn)?;
+            let synonyms = std::mem::take(&mut self.synonyms_delta).apply(current);
+            self.set_synonyms(synonyms);
+        }
+
+        Ok(())
+    }
+
     fn update_stop_words(&mut self) -> Result<bool> {
         match self.stop_words {
             Setting::Set(ref stop_words) => {
@@ -610,6 +938,50 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changes)
     }
 
+    fn update_identifier_attributes(&mut self) -> Result<()> {
+        match self.identifier_attributes {
+            Setting::Set(ref identifier_attributes) => {
+                self.index.put_identifier_attributes(self.wtxn, identifier_attributes)?;
+            }
+            Setting::Reset => {
+                self.index.delete_identifier_attributes(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_date_attributes(&mut self) -> Result<()> {
+        match self.date_attributes {
+            Setting::Set(ref date_attributes) => {
+                self.index.put_date_attributes(self.wtxn, date_attributes)?;
+            }
+            Setting::Reset => {
+                self.index.delete_date_attributes(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_infix_attributes(&mut self) -> Result<bool> {
+        match self.infix_attributes {
+            Setting::Set(ref infix_attributes) => {
+                let current = self.index.infix_attributes(self.wtxn)?;
+                if current.is_none_or(|current| &current != infix_attributes) {
+                    self.index.put_infix_attributes(self.wtxn, infix_attributes)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_infix_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_separator_tokens(&mut self) -> Result<bool> {
         let changes = match self.separator_tokens {
             Setting::Set(ref separator_tokens) => {
@@ -661,79 +1033,148 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
     }
 
     fn update_synonyms(&mut self) -> Result<bool> {
-        match self.synonyms {
-            Setting::Set(ref user_synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<'_>, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
-                        .filter_map(|token| {
-                            if token.is_word() && !token.lemma().is_empty() {
-                                Some(token.lemma().to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                }
+        if self.synonyms == Setting::NotSet
+            && self.synonym_sets == Setting::NotSet
+            && self.synonym_sets_to_add.is_empty()
+        {
+            return Ok(false);
+        }
 
-                let mut builder = TokenizerBuilder::new();
-                let stop_words = self.index.stop_words(self.wtxn)?;
-                if let Some(ref stop_words) = stop_words {
-                    builder.stop_words(stop_words);
-                }
+        fn normalize(tokenizer: &Tokenizer<'_>, text: &str) -> Vec<String> {
+            tokenizer
+                .tokenize(text)
+                .filter_map(|token| {
+                    if token.is_word() && !token.lemma().is_empty() {
+                        Some(token.lemma().to_string())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
 
-                let separators = self.index.allowed_separators(self.wtxn)?;
-                let separators: Option<Vec<_>> =
-                    separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref separators) = separators {
-                    builder.separators(separators);
-                }
+        /// Associates each key with the synonyms, except itself.
+        fn insert_synonyms(
+            map: &mut HashMap<Vec<String>, Vec<Vec<String>>>,
+            keys: &[Vec<String>],
+            synonyms: &[Vec<String>],
+        ) {
+            for key in keys {
+                let others = synonyms.iter().filter(|synonym| *synonym != key).cloned();
+                map.entry(key.clone()).or_default().extend(others);
+            }
+        }
 
-                let dictionary = self.index.dictionary(self.wtxn)?;
-                let dictionary: Option<Vec<_>> =
-                    dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref dictionary) = dictionary {
-                    builder.words_dict(dictionary);
-                }
+        fn dedup_synonyms(map: &mut HashMap<Vec<String>, Vec<Vec<String>>>) {
+            map.retain(|_, synonyms| !synonyms.is_empty());
+            map.values_mut().for_each(|synonyms| {
+                synonyms.sort_unstable();
+                synonyms.dedup();
+            });
+        }
 
-                let tokenizer = builder.build();
+        let mut builder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.wtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            builder.stop_words(stop_words);
+        }
 
-                let mut new_synonyms = HashMap::new();
-                for (word, synonyms) in user_synonyms {
-                    // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms: Vec<_> = synonyms
-                        .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
-                        .filter(|synonym| !synonym.is_empty())
-                        .collect();
-
-                    // Store the normalized synonyms under the normalized word,
-                    // merging the possible duplicate words.
-                    if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
-                        let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
-                        entry.extend(normalized_synonyms.into_iter());
-                    }
-                }
+        let separators = self.index.allowed_separators(self.wtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            builder.separators(separators);
+        }
 
-                // Make sure that we don't have duplicate synonyms.
-                new_synonyms.iter_mut().for_each(|(_, synonyms)| {
-                    synonyms.sort_unstable();
-                    synonyms.dedup();
-                });
+        let dictionary = self.index.dictionary(self.wtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            builder.words_dict(dictionary);
+        }
 
-                let old_synonyms = self.index.synonyms(self.wtxn)?;
+        let tokenizer = builder.build();
 
-                if new_synonyms != old_synonyms {
-                    self.index.put_synonyms(self.wtxn, &new_synonyms, user_synonyms)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
+        // the synonyms and the synonym sets that are not updated are normalized again
+        // as the tokenizer may have changed
+        let user_synonyms = match &self.synonyms {
+            Setting::Set(synonyms) => synonyms.clone(),
+            Setting::Reset => BTreeMap::new(),
+            Setting::NotSet => self.index.user_defined_synonyms(self.wtxn)?,
+        };
+        let mut synonym_sets = match &self.synonym_sets {
+            Setting::Set(synonym_sets) => synonym_sets.clone(),
+            Setting::Reset => Vec::new(),
+            Setting::NotSet => self.index.synonym_sets(self.wtxn)?,
+        };
+        synonym_sets.append(&mut self.synonym_sets_to_add);
+
+        let mut new_synonyms = HashMap::new();
+        for (word, synonyms) in &user_synonyms {
+            // Normalize both the word and associated synonyms.
+            let normalized_word = normalize(&tokenizer, word);
+            let normalized_synonyms: Vec<_> = synonyms
+                .iter()
+                .map(|synonym| normalize(&tokenizer, synonym))
+                .filter(|synonym| !synonym.is_empty())
+                .collect();
+
+            // Store the normalized synonyms under the normalized word,
+            // merging the possible duplicate words.
+            if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
+                let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
+                entry.extend(normalized_synonyms.into_iter());
             }
-            Setting::Reset => Ok(self.index.delete_synonyms(self.wtxn)?),
-            Setting::NotSet => Ok(false),
         }
+
+        // The synonym sets restricted to some locales are stored apart from the others.
+        let mut localized_synonyms = BTreeMap::new();
+        for (index, synonym_set) in synonym_sets.iter().enumerate() {
+            synonym_set.validate(index)?;
+
+            let normalize_all = |words: &[String]| -> Vec<_> {
+                words
+                    .iter()
+                    .map(|word| normalize(&tokenizer, word))
+                    .filter(|word| !word.is_empty())
+                    .collect()
+            };
+            let synonyms = normalize_all(&synonym_set.synonyms);
+            let keys = match synonym_set.kind {
+                SynonymSetKind::Equivalent => synonyms.clone(),
+                SynonymSetKind::OneWay => normalize_all(&synonym_set.input),
+            };
+
+            if synonym_set.locales.is_empty() {
+                insert_synonyms(&mut new_synonyms, &keys, &synonyms);
+            }
+            for locale in &synonym_set.locales {
+                let map = localized_synonyms.entry(*locale).or_default();
+                insert_synonyms(map, &keys, &synonyms);
+            }
+        }
+
+        // Make sure that we don't have duplicate synonyms.
+        dedup_synonyms(&mut new_synonyms);
+        localized_synonyms.values_mut().for_each(dedup_synonyms);
+
+        let old_synonyms = self.index.synonyms(self.wtxn)?;
+        let old_localized_synonyms = self.index.localized_synonyms(self.wtxn)?;
+        let changed = new_synonyms != old_synonyms || localized_synonyms != old_localized_synonyms;
+
+        if user_synonyms.is_empty() && new_synonyms.is_empty() {
+            self.index.delete_synonyms(self.wtxn)?;
+        } else {
+            self.index.put_synonyms(self.wtxn, &new_synonyms, &user_synonyms)?;
+        }
+
+        if synonym_sets.is_empty() {
+            self.index.delete_synonym_sets(self.wtxn)?;
+        } else {
+            self.index.put_synonym_sets(self.wtxn, &synonym_sets, &localized_synonyms)?;
+        }
+
+        Ok(changed)
     }
 
     fn update_exact_attributes(&mut self) -> Result<bool> {
@@ -755,6 +1196,46 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    fn update_typo_budgets(&mut self) -> Result<()> {
+        if self.attribute_typo_budgets.is_not_set() && self.locale_typo_budgets.is_not_set() {
+            return Ok(());
+        }
+
+        let mut budgets = self.index.typo_budgets(self.wtxn)?;
+        match self.attribute_typo_budgets {
+            Setting::Set(ref attributes) => budgets.attributes = attributes.clone(),
+            Setting::Reset => budgets.attributes = Vec::new(),
+            Setting::NotSet => (),
+        }
+        match self.locale_typo_budgets {
+            Setting::Set(ref locales) => budgets.locales = locales.clone(),
+            Setting::Reset => budgets.locales = Vec::new(),
+            Setting::NotSet => (),
+        }
+
+        if budgets.is_empty() {
+            self.index.delete_typo_budgets(self.wtxn)?;
+        } else {
+            self.index.put_typo_budgets(self.wtxn, &budgets)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_phonetic(&mut self) -> Result<()> {
+        match self.phonetic {
+            Setting::Set(ref phonetic) => {
+                self.index.put_phonetic_settings(self.wtxn, phonetic)?;
+            }
+            Setting::Reset => {
+                self.index.delete_phonetic_settings(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_filterable(&mut self) -> Result<()> {
         match self.filterable_fields {
             Setting::Set(ref fields) => {
@@ -955,6 +1436,54 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_facet_hierarchies(&mut self) -> Result<()> {
+        match self.facet_hierarchies.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_facet_hierarchies(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_facet_hierarchies(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_search_rules(&mut self) -> Result<()> {
+        match self.search_rules.as_ref() {
+            Setting::Set(rules) => {
+                for (id, rule) in rules {
+                    rule.validate(id)?;
+                }
+                self.index.put_search_rules(self.wtxn, rules)?;
+            }
+            Setting::Reset => {
+                self.index.delete_search_rules(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_query_rules(&mut self) -> Result<()> {
+        match self.query_rules.as_ref() {
+            Setting::Set(rules) => {
+                for (id, rule) in rules {
+                    rule.validate(id)?;
+                }
+                self.index.put_query_rules(self.wtxn, rules)?;
+            }
+            Setting::Reset => {
+                self.index.delete_query_rules(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -1255,22 +1784,31 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
+        self.update_typo_budgets()?;
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
+        self.update_facet_hierarchies()?;
+        self.update_search_rules()?;
+        self.update_query_rules()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        let infix_attributes_changed = self.update_infix_attributes()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
         self.update_sortable()?;
+        self.update_date_attributes()?;
+        self.apply_list_deltas()?;
         self.update_stop_words()?;
         self.update_non_separator_tokens()?;
+        self.update_identifier_attributes()?;
         self.update_separator_tokens()?;
         self.update_dictionary()?;
         self.update_synonyms()?;
         self.update_user_defined_searchable_attributes()?;
         self.update_exact_attributes()?;
+        self.update_phonetic()?;
         self.update_proximity_precision()?;
         self.update_prefix_search()?;
         self.update_facet_search()?;
@@ -1299,6 +1837,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
         }
 
+        // the trigrams are built from the indexed words and facet strings
+        if infix_attributes_changed {
+            TrigramsFst::new(self.wtxn, self.index).rebuild()?;
+        }
+
         Ok(())
     }
 }
@@ -1358,6 +1901,8 @@ impl InnerIndexSettingsDiff {
                 || old_settings.localized_attributes_rules
                     != new_settings.localized_attributes_rules
                 || old_settings.disabled_typos_terms != new_settings.disabled_typos_terms
+                || old_settings.phonetic != new_settings.phonetic
+                || old_settings.identifier_attributes != new_settings.identifier_attributes
         };
 
         let cache_exact_attributes = old_settings.exact_attributes != new_settings.exact_attributes;
@@ -1524,6 +2069,8 @@ impl InnerIndexSettingsDiff {
     pub fn global_facet_settings_changed(&self) -> bool {
         self.old.localized_attributes_rules != self.new.localized_attributes_rules
             || self.old.facet_search != self.new.facet_search
+            || self.old.fields_ids_map.metadata_builder().date_attributes()
+                != self.new.fields_ids_map.metadata_builder().date_attributes()
     }
 
     pub fn reindex_facets(&self) -> bool {
@@ -1542,6 +2089,11 @@ impl InnerIndexSettingsDiff {
         self.old.geo_fields_ids != self.new.geo_fields_ids
             || (!self.settings_update_only && self.new.geo_fields_ids.is_some())
     }
+
+    pub fn run_geojson_indexing(&self) -> bool {
+        self.old.geojson_fid != self.new.geojson_fid
+            || (!self.settings_update_only && self.new.geojson_fid.is_some())
+    }
 }
 
 #[derive(Clone)]
@@ -1558,9 +2110,12 @@ pub(crate) struct InnerIndexSettings {
     pub sortable_fields: HashSet<String>,
     pub exact_attributes: HashSet<FieldId>,
     pub disabled_typos_terms: DisabledTyposTerms,
+    pub phonetic: Option<PhoneticSettings>,
+    pub identifier_attributes: Option<AttributePatterns>,
     pub proximity_precision: ProximityPrecision,
     pub embedding_configs: EmbeddingConfigs,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
+    pub geojson_fid: Option<FieldId>,
     pub prefix_search: PrefixSearch,
     pub facet_search: bool,
 }
@@ -1595,6 +2150,10 @@ impl InnerIndexSettings {
             }
             _ => None,
         };
+        let geojson_fid = match fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) {
+            Some(fid) if index.is_geojson_filtering_enabled(rtxn)? => Some(fid),
+            _ => None,
+        };
         let localized_attributes_rules =
             index.localized_attributes_rules(rtxn)?.unwrap_or_default();
         let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
@@ -1607,6 +2166,10 @@ impl InnerIndexSettings {
         let builder = MetadataBuilder::from_index(index, rtxn)?;
         let fields_ids_map = FieldIdMapWithMetadata::new(fields_ids_map, builder);
         let disabled_typos_terms = index.disabled_typos_terms(rtxn)?;
+        let phonetic = index.phonetic_settings(rtxn)?;
+        let identifier_attributes = index
+            .identifier_attributes(rtxn)?
+            .map(|attributes| AttributePatterns::from(attributes.into_iter().collect::<Vec<_>>()));
         Ok(Self {
             stop_words,
             allowed_separators,
@@ -1622,12 +2185,58 @@ impl InnerIndexSettings {
             proximity_precision,
             embedding_configs,
             geo_fields_ids,
+            geojson_fid,
             prefix_search,
             facet_search,
             disabled_typos_terms,
+            phonetic,
+            identifier_attributes,
         })
     }
 
+    /// Returns the words derived from the word to index along it in the field: its stems if
+    /// the localized attributes rule of the field enables stemming, and its phonetic codes if
+    /// the field is a phonetic attribute.
+    pub fn derived_words(&self, field_id: FieldId, word: &str) -> Vec<String> {
+        let mut derived_words = self
+            .fields_ids_map
+            .metadata(field_id)
+            .and_then(|metadata| {
+                metadata.localized_attributes_rule(&self.localized_attributes_rules)
+            })
+            .map_or_else(Vec::new, |rule| rule.stems(word));
+
+        let phonetic = self.phonetic.as_ref().filter(|phonetic| {
+            self.fields_ids_map
+                .name(field_id)
+                .is_some_and(|name| phonetic.match_str(name) == PatternMatch::Match)
+        });
+        if let Some(phonetic) = phonetic {
+            derived_words.extend(phonetic.codes(word));
+        }
+
+        derived_words
+    }
+
+    /// Returns the identifier variants of the words of the field, with their position,
+    /// if the field is an identifier attribute.
+    pub fn identifier_variants<'a>(
+        &self,
+        field_id: FieldId,
+        words: impl IntoIterator<Item = (u16, &'a str)>,
+    ) -> Vec<(u16, String)> {
+        let is_identifier = self.identifier_attributes.as_ref().is_some_and(|patterns| {
+            self.fields_ids_map
+                .name(field_id)
+                .is_some_and(|name| patterns.match_str(name) == PatternMatch::Match)
+        });
+        if is_identifier {
+            identifier_variants(words)
+        } else {
+            Vec::new()
+        }
+    }
+
     pub fn match_faceted_field(&self, field: &str) -> PatternMatch {
         match_faceted_field(
             field,
@@ -584,6 +584,73 @@ fn set_and_reset_synonyms() {
     assert!(result.documents_ids.is_empty());
 }
 
+#[test]
+fn add_and_remove_synonyms_and_stop_words() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "kevin", "age": 23, "maxim": "I love dogs"},
+            { "id": 1, "name": "kevina", "age": 21, "maxim": "Doggos are the best"},
+            { "id": 2, "name": "benoit", "age": 34, "maxim": "The crepes are really good"},
+        ]))
+        .unwrap();
+
+    index
+        .update_settings(|settings| {
+            settings.set_stop_words(btreeset! { "the".to_string() });
+            settings.set_synonyms(btreemap! {
+                "blini".to_string() => vec!["crepes".to_string()],
+            });
+        })
+        .unwrap();
+
+    // the additions and removals are applied on top of the current settings
+    index
+        .update_settings(|settings| {
+            settings.add_stop_words(btreeset! { "are".to_string() });
+            settings.add_synonyms(btreemap! {
+                "puppies".to_string() => vec!["dogs".to_string()],
+            });
+            settings.remove_synonyms(btreeset! { "blini".to_string() });
+            // a word removed and then added again is kept
+            settings.remove_stop_words(btreeset! { "the".to_string() });
+            settings.add_stop_words(btreeset! { "the".to_string() });
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let stop_words = index.stop_words(&rtxn).unwrap().unwrap();
+    let stop_words: Vec<_> = stop_words.stream().into_strs().unwrap();
+    assert_eq!(stop_words, ["are", "the"]);
+    let synonyms = index.user_defined_synonyms(&rtxn).unwrap();
+    assert_eq!(synonyms, btreemap! { "puppies".to_string() => vec!["dogs".to_string()] });
+
+    let result = index.search(&rtxn).query("blini").execute().unwrap();
+    assert!(result.documents_ids.is_empty());
+    let result = index.search(&rtxn).query("puppies").execute().unwrap();
+    assert_eq!(result.documents_ids.len(), 1);
+    drop(rtxn);
+
+    // the synonyms of a word are appended to its current ones
+    index
+        .update_settings(|settings| {
+            settings.add_synonyms(btreemap! {
+                "puppies".to_string() => vec!["dogs".to_string(), "doggos".to_string()],
+            });
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let synonyms = index.user_defined_synonyms(&rtxn).unwrap();
+    assert_eq!(
+        synonyms,
+        btreemap! { "puppies".to_string() => vec!["dogs".to_string(), "doggos".to_string()] }
+    );
+    let result = index.search(&rtxn).query("puppies").execute().unwrap();
+    assert_eq!(result.documents_ids.len(), 2);
+}
+
 #[test]
 fn thai_synonyms() {
     let index = TempIndex::new();
@@ -876,19 +943,33 @@ fn test_correct_settings_init() {
                 sortable_fields,
                 criteria,
                 stop_words,
+                stop_words_delta,
                 non_separator_tokens,
+                identifier_attributes,
+                infix_attributes,
+                date_attributes,
                 separator_tokens,
                 dictionary,
+                dictionary_delta,
                 distinct_field,
                 synonyms,
+                synonyms_delta,
+                synonym_sets,
+                synonym_sets_to_add,
                 primary_key,
                 authorize_typos,
                 min_word_len_two_typos,
                 min_word_len_one_typo,
                 exact_words,
                 exact_attributes,
+                phonetic,
+                attribute_typo_budgets,
+                locale_typo_budgets,
                 max_values_per_facet,
                 sort_facet_values_by,
+                facet_hierarchies,
+                search_rules,
+                query_rules,
                 pagination_max_total_hits,
                 proximity_precision,
                 embedder_settings,
@@ -904,19 +985,33 @@ fn test_correct_settings_init() {
             assert!(matches!(sortable_fields, Setting::NotSet));
             assert!(matches!(criteria, Setting::NotSet));
             assert!(matches!(stop_words, Setting::NotSet));
+            assert!(stop_words_delta.is_empty());
             assert!(matches!(non_separator_tokens, Setting::NotSet));
+            assert!(matches!(identifier_attributes, Setting::NotSet));
+            assert!(matches!(infix_attributes, Setting::NotSet));
+            assert!(matches!(date_attributes, Setting::NotSet));
             assert!(matches!(separator_tokens, Setting::NotSet));
             assert!(matches!(dictionary, Setting::NotSet));
+            assert!(dictionary_delta.is_empty());
             assert!(matches!(distinct_field, Setting::NotSet));
             assert!(matches!(synonyms, Setting::NotSet));
+            assert!(synonyms_delta.is_empty());
+            assert!(matches!(synonym_sets, Setting::NotSet));
+            assert!(synonym_sets_to_add.is_empty());
             assert!(matches!(primary_key, Setting::NotSet));
             assert!(matches!(authorize_typos, Setting::NotSet));
             assert!(matches!(min_word_len_two_typos, Setting::NotSet));
             assert!(matches!(min_word_len_one_typo, Setting::NotSet));
             assert!(matches!(exact_words, Setting::NotSet));
             assert!(matches!(exact_attributes, Setting::NotSet));
+            assert!(matches!(phonetic, Setting::NotSet));
+            assert!(matches!(attribute_typo_budgets, Setting::NotSet));
+            assert!(matches!(locale_typo_budgets, Setting::NotSet));
             assert!(matches!(max_values_per_facet, Setting::NotSet));
             assert!(matches!(sort_facet_values_by, Setting::NotSet));
+            assert!(matches!(facet_hierarchies, Setting::NotSet));
+            assert!(matches!(search_rules, Setting::NotSet));
+            assert!(matches!(query_rules, Setting::NotSet));
             assert!(matches!(pagination_max_total_hits, Setting::NotSet));
             assert!(matches!(proximity_precision, Setting::NotSet));
             assert!(matches!(embedder_settings, Setting::NotSet));
@@ -0,0 +1,216 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{BytesDecode, RwTxn};
+use itertools::{merge_join_by, EitherOrBoth};
+
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupKeyCodec};
+use crate::heed_codec::{StrBEU16Codec, StrRefCodec};
+use crate::phonetic::is_phonetic_code;
+use crate::stemming::is_stem;
+use crate::trigrams::trigram_keys;
+use crate::{Index, Result};
+
+/// Updates the FSTs indexing the words and the facet strings of the infix attributes by
+/// their trigrams, or deletes them when no infix attribute is defined.
+pub struct TrigramsFst<'t, 'i> {
+    wtxn: &'t mut RwTxn<'i>,
+    index: &'t Index,
+}
+
+impl<'t, 'i> TrigramsFst<'t, 'i> {
+    pub fn new(wtxn: &'t mut RwTxn<'i>, index: &'t Index) -> TrigramsFst<'t, 'i> {
+        TrigramsFst { wtxn, index }
+    }
+
+    /// Updates the FSTs with the words and facet strings of the infix attributes added or
+    /// deleted since the last committed version of the index, like the word prefix databases,
+    /// or builds them if they don't exist yet.
+    #[tracing::instrument(
+        level = "trace",
+        skip_all,
+        target = "indexing::prefix",
+        name = "trigrams_fst"
+    )]
+    pub fn execute(self) -> Result<()> {
+        let infix_fids = self.index.infix_fields_ids(self.wtxn)?;
+        if infix_fids.is_empty() {
+            self.index.delete_words_trigrams_fst(self.wtxn)?;
+            self.index.delete_facet_strings_trigrams_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        let words_fst = self.index.words_trigrams_fst(self.wtxn)?;
+        let facet_strings_fst = self.index.facet_strings_trigrams_fst(self.wtxn)?;
+        let (Some(words_fst), Some(facet_strings_fst)) = (words_fst, facet_strings_fst) else {
+            return self.rebuild();
+        };
+        let words_fst = words_fst.map_data(|bytes| bytes.to_vec())?;
+        let facet_strings_fst = facet_strings_fst.map_data(|bytes| bytes.to_vec())?;
+
+        let rtxn = self.index.read_txn()?;
+
+        // The words whose fields changed, their trigrams are kept if they are still in an
+        // infix attribute and removed otherwise.
+        let mut words = BTreeSet::new();
+        let db = self.index.word_fid_docids.remap_types::<Bytes, DecodeIgnore>();
+        let previous_keys = db.iter(&rtxn)?;
+        let current_keys = db.iter(self.wtxn)?;
+        for eob in merge_join_by(previous_keys, current_keys, |lhs, rhs| match (lhs, rhs) {
+            (Ok((l, _)), Ok((r, _))) => l.cmp(r),
+            (Err(_), _) | (_, Err(_)) => Ordering::Equal,
+        }) {
+            match eob {
+                EitherOrBoth::Both(lhs, rhs) => {
+                    let (_, _) = lhs?;
+                    let (_, _) = rhs?;
+                }
+                EitherOrBoth::Left(result) | EitherOrBoth::Right(result) => {
+                    let (key, ()) = result?;
+                    let (word, fid) =
+                        StrBEU16Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+                    if infix_fids.contains(&fid) && !is_stem(word) && !is_phonetic_code(word) {
+                        words.insert(word.to_string());
+                    }
+                }
+            }
+        }
+
+        let mut added = Vec::new();
+        let mut deleted = Vec::new();
+        let db = self.index.word_fid_docids.remap_data_type::<DecodeIgnore>();
+        for word in &words {
+            let mut is_indexed = false;
+            for &fid in &infix_fids {
+                if db.get(self.wtxn, &(word.as_str(), fid))?.is_some() {
+                    is_indexed = true;
+                    break;
+                }
+            }
+            let keys = if is_indexed { &mut added } else { &mut deleted };
+            keys.extend(trigram_keys(&[], word));
+        }
+        if !added.is_empty() || !deleted.is_empty() {
+            let words_fst = apply_delta(&words_fst, added, deleted)?;
+            self.index.put_words_trigrams_fst(self.wtxn, &words_fst)?;
+        }
+
+        // The facet strings are indexed once per field, so their keys give the delta.
+        let mut added = Vec::new();
+        let mut deleted = Vec::new();
+        let db = self
+            .index
+            .facet_id_string_docids
+            .remap_types::<FacetGroupKeyCodec<StrRefCodec>, DecodeIgnore>();
+        for &field_id in &infix_fids {
+            let prefix = field_id.to_be_bytes();
+            let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
+            let previous_keys = db.prefix_iter(&rtxn, &base)?;
+            let current_keys = db.prefix_iter(self.wtxn, &base)?;
+            for eob in merge_join_by(previous_keys, current_keys, |lhs, rhs| match (lhs, rhs) {
+                (Ok((l, _)), Ok((r, _))) => l.left_bound.cmp(r.left_bound),
+                (Err(_), _) | (_, Err(_)) => Ordering::Equal,
+            }) {
+                match eob {
+                    EitherOrBoth::Both(lhs, rhs) => {
+                        let (_, _) = lhs?;
+                        let (_, _) = rhs?;
+                    }
+                    EitherOrBoth::Left(result) => {
+                        let (FacetGroupKey { left_bound, .. }, ()) = result?;
+                        deleted.extend(trigram_keys(&prefix, left_bound));
+                    }
+                    EitherOrBoth::Right(result) => {
+                        let (FacetGroupKey { left_bound, .. }, ()) = result?;
+                        added.extend(trigram_keys(&prefix, left_bound));
+                    }
+                }
+            }
+        }
+        if !added.is_empty() || !deleted.is_empty() {
+            let facet_strings_fst = apply_delta(&facet_strings_fst, added, deleted)?;
+            self.index.put_facet_strings_trigrams_fst(self.wtxn, &facet_strings_fst)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds the FSTs from all the words and facet strings of the infix attributes, when
+    /// the infix attributes changed.
+    #[tracing::instrument(
+        level = "trace",
+        skip_all,
+        target = "indexing::prefix",
+        name = "trigrams_fst_rebuild"
+    )]
+    pub fn rebuild(self) -> Result<()> {
+        let infix_fids = self.index.infix_fields_ids(self.wtxn)?;
+        if infix_fids.is_empty() {
+            self.index.delete_words_trigrams_fst(self.wtxn)?;
+            self.index.delete_facet_strings_trigrams_fst(self.wtxn)?;
+            return Ok(());
+        }
+
+        // The words are sorted but a word appears once per field.
+        let mut keys = Vec::new();
+        let mut previous_word = None;
+        let iter = self.index.word_fid_docids.remap_data_type::<DecodeIgnore>().iter(self.wtxn)?;
+        for result in iter {
+            let ((word, fid), ()) = result?;
+            if !infix_fids.contains(&fid)
+                || is_stem(word)
+                || is_phonetic_code(word)
+                || previous_word.as_deref() == Some(word)
+            {
+                continue;
+            }
+            keys.extend(trigram_keys(&[], word));
+            previous_word = Some(word.to_string());
+        }
+        let words_fst = build_fst(keys)?;
+        self.index.put_words_trigrams_fst(self.wtxn, &words_fst)?;
+
+        let strings_db = self
+            .index
+            .facet_id_string_docids
+            .remap_types::<FacetGroupKeyCodec<StrRefCodec>, DecodeIgnore>();
+        let mut keys = Vec::new();
+        for &field_id in &infix_fids {
+            let prefix = field_id.to_be_bytes();
+            let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
+            for result in strings_db.prefix_iter(self.wtxn, &base)? {
+                let (FacetGroupKey { left_bound, .. }, ()) = result?;
+                keys.extend(trigram_keys(&prefix, left_bound));
+            }
+        }
+        let facet_strings_fst = build_fst(keys)?;
+        self.index.put_facet_strings_trigrams_fst(self.wtxn, &facet_strings_fst)?;
+
+        Ok(())
+    }
+}
+
+fn build_fst(mut keys: Vec<Vec<u8>>) -> Result<fst::Set<Vec<u8>>> {
+    keys.sort_unstable();
+    keys.dedup();
+    Ok(fst::Set::from_iter(keys)?)
+}
+
+/// Returns the FST without the deleted keys and with the added ones.
+fn apply_delta(
+    fst: &fst::Set<Vec<u8>>,
+    added: Vec<Vec<u8>>,
+    deleted: Vec<Vec<u8>>,
+) -> Result<fst::Set<Vec<u8>>> {
+    let added = build_fst(added)?;
+    let deleted = build_fst(deleted)?;
+
+    let mut builder = fst::SetBuilder::memory();
+    builder.extend_stream(fst.op().add(&deleted).difference())?;
+    let kept = builder.into_set();
+
+    let mut builder = fst::SetBuilder::memory();
+    builder.extend_stream(kept.op().add(&added).r#union())?;
+    Ok(builder.into_set())
+}